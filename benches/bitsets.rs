@@ -0,0 +1,103 @@
+// Micro-benchmarks for the bitset backends, to pick the default for a
+// platform. Backends behind features are only measured when enabled:
+// cargo bench --bench bitsets --features fixedbitset
+// Each operation runs on random sets, half their bits set, and on
+// clustered ones, a single run of 64 bits like a clique or neighborhood
+// after relabeling for locality (see locality.rs).
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use vcc::bitset::{BitSet, Blocked, Words};
+use vcc::rng::{default_rng, DefaultRng};
+
+const SIZES: [usize; 3] = [256, 4096, 65536];
+
+type Pattern<B> = fn(usize, &mut DefaultRng) -> B;
+
+// Half the bits set, at random.
+fn random<B: BitSet>(n: usize, rng: &mut DefaultRng) -> B {
+  use rand_core::RngCore;
+  let mut bits = B::zeros(n);
+  for i in 0..n {
+    bits.set(i, rng.next_u32() & 1 == 1);
+  }
+  bits
+}
+
+fn clustered<B: BitSet>(n: usize, rng: &mut DefaultRng) -> B {
+  use rand_core::RngCore;
+  let start = rng.next_u64() as usize % (n - 64);
+  let mut bits = B::zeros(n);
+  for i in start..start + 64 {
+    bits.set(i, true);
+  }
+  bits
+}
+
+// The mix a transfer does: intersect, test, count, and scan bits.
+fn backend<B: BitSet>(c: &mut Criterion, name: &str) {
+  let mut group = c.benchmark_group(name);
+  let patterns: [(&str, Pattern<B>); 2] = [("random", random), ("clustered", clustered)];
+  for &n in &SIZES {
+    for (pattern, make) in patterns {
+      let mut rng = default_rng(0);
+      let a = make(n, &mut rng);
+      let b = make(n, &mut rng);
+      let id = |op: &str| BenchmarkId::new(format!("{}/{}", op, pattern), n);
+      group.bench_function(id("and_inplace"), |bench| {
+        let mut x = a.clone();
+        bench.iter(|| x.and_inplace(&b));
+      });
+      group.bench_function(id("or_xor_inplace"), |bench| {
+        let mut x = a.clone();
+        bench.iter(|| {
+          x.or_inplace(&b);
+          x.xor_inplace(&b);
+        });
+      });
+      group.bench_function(id("and_cloned_any"), |bench| {
+        bench.iter(|| a.and_cloned(&b).any());
+      });
+      group.bench_function(id("count_ones"), |bench| {
+        bench.iter(|| a.count_ones());
+      });
+      group.bench_function(id("get_unchecked"), |bench| {
+        bench.iter(|| (0..n).filter(|&i| a.get_unchecked(i)).count());
+      });
+      group.bench_function(id("set_all_false"), |bench| {
+        let mut x = a.clone();
+        bench.iter(|| {
+          x.or_inplace(&b);
+          x.set_all_false();
+        });
+      });
+    }
+  }
+  group.finish();
+}
+
+fn words(c: &mut Criterion) {
+  backend::<Words>(c, "words");
+}
+
+fn blocked(c: &mut Criterion) {
+  backend::<Blocked>(c, "blocked");
+}
+
+#[cfg(feature = "simd")]
+fn simd(c: &mut Criterion) {
+  backend::<vcc::bitset::SimdBitVec>(c, "simd");
+}
+
+#[cfg(not(feature = "simd"))]
+fn simd(_: &mut Criterion) {}
+
+#[cfg(feature = "fixedbitset")]
+fn fixedbitset(c: &mut Criterion) {
+  backend::<vcc::bitset::FixedBitSet>(c, "fixedbitset");
+}
+
+#[cfg(not(feature = "fixedbitset"))]
+fn fixedbitset(_: &mut Criterion) {}
+
+criterion_group!(benches, words, blocked, simd, fixedbitset);
+criterion_main!(benches);
@@ -0,0 +1,80 @@
+// Micro-benchmarks for the bitset-heavy kernels, across sizes and densities.
+// Run with: cargo bench --bench kernels
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use vcc::rng::default_rng;
+use vcc::{get_random_graph_with_k_cliques, Graph};
+
+const SIZES: [usize; 3] = [64, 256, 1024];
+const DENSITIES: [f64; 2] = [0.25, 0.75];
+
+fn instance(n: usize, p: f64) -> Graph {
+  get_random_graph_with_k_cliques(n, n / 8, p, &mut default_rng(0)).unwrap()
+}
+
+fn points() -> impl Iterator<Item = (usize, f64, String)> {
+  SIZES.iter().flat_map(|&n| {
+    DENSITIES
+      .iter()
+      .map(move |&p| (n, p, format!("n{}_p{}", n, p)))
+  })
+}
+
+fn generation(c: &mut Criterion) {
+  let mut group = c.benchmark_group("generation");
+  for (n, p, id) in points() {
+    group.bench_with_input(BenchmarkId::from_parameter(id), &(n, p), |b, &(n, p)| {
+      let mut rng = default_rng(0);
+      b.iter(|| get_random_graph_with_k_cliques(n, n / 8, p, &mut rng).unwrap());
+    });
+  }
+  group.finish();
+}
+
+fn vcc_greedy(c: &mut Criterion) {
+  let mut group = c.benchmark_group("vcc_greedy");
+  for (n, p, id) in points() {
+    let g = instance(n, p);
+    group.bench_function(BenchmarkId::from_parameter(id), |b| {
+      b.iter_batched_ref(
+        || {
+          let mut g = g.clone();
+          g.shuffle_active_cliques();
+          g
+        },
+        |g| g.vcc_greedy(),
+        BatchSize::LargeInput,
+      );
+    });
+  }
+  group.finish();
+}
+
+// Starting from singletons, grows the first clique by offering it every
+// other vertex in turn.
+fn transfer_compatible_vertices(c: &mut Criterion) {
+  let mut group = c.benchmark_group("transfer_compatible_vertices");
+  for (n, p, id) in points() {
+    let g = instance(n, p);
+    group.bench_function(BenchmarkId::from_parameter(id), |b| {
+      b.iter_batched_ref(
+        || g.clone(),
+        |g| {
+          for j in 1..g.cliques_ct() {
+            g.transfer_compatible_vertices_between(0, j);
+          }
+        },
+        BatchSize::LargeInput,
+      );
+    });
+  }
+  group.finish();
+}
+
+criterion_group!(
+  benches,
+  generation,
+  vcc_greedy,
+  transfer_compatible_vertices
+);
+criterion_main!(benches);
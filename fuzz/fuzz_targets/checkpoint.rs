@@ -0,0 +1,23 @@
+// A checkpoint file must either fail to load or restore to a graph whose
+// search state is consistent.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use vcc::checkpoint::Checkpoint;
+
+fuzz_target!(|data: &[u8]| {
+  let Ok(json) = std::str::from_utf8(data) else {
+    return;
+  };
+  let Ok(checkpoint) = Checkpoint::from_json_str(json) else {
+    return;
+  };
+  // The vertex count is trusted for allocation; keep it small enough that
+  // the fuzzer looks for bad indices rather than running out of memory.
+  if checkpoint.num_vertices > 1_000 {
+    return;
+  }
+  if let Ok((g, _)) = checkpoint.restore() {
+    g.check_invariants().unwrap();
+  }
+});
@@ -0,0 +1,22 @@
+// Covers read from JSON go through validate_cover before load_cover
+// touches the search state; anything it accepts must load cleanly.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use vcc::{CliqueCover, Graph};
+
+fuzz_target!(|data: &[u8]| {
+  let Ok(cover) = serde_json::from_slice::<CliqueCover>(data) else {
+    return;
+  };
+  // A 6-cycle plus the chord 0-3.
+  let mut g = Graph::new(6);
+  for (u, v) in [(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 0), (0, 3)] {
+    g.add_edge(u, v).unwrap();
+  }
+  g.conform_cliques_to_vertices();
+  if g.load_cover(&cover).is_ok() {
+    g.check_invariants().unwrap();
+    assert_eq!(g.cover().len(), cover.len());
+  }
+});
@@ -0,0 +1,26 @@
+// DIMACS text, weighted or directed, must be rejected with an error or
+// parse to a consistent graph that reads back the same once written out.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use vcc::formats::{GraphFormat, Symmetrize};
+use vcc::Graph;
+
+fuzz_target!(|data: &[u8]| {
+  let Ok(text) = std::str::from_utf8(data) else {
+    return;
+  };
+  for symmetrize in [
+    Symmetrize::Union,
+    Symmetrize::Intersection,
+    Symmetrize::Error,
+  ] {
+    let Ok((g, _)) = Graph::parse_weighted(text, GraphFormat::Dimacs, symmetrize) else {
+      continue;
+    };
+    g.check_invariants().unwrap();
+    let reread = Graph::parse(&g.to_format(GraphFormat::Dimacs), GraphFormat::Dimacs).unwrap();
+    assert_eq!(reread.size(), g.size());
+    assert!((0..g.size()).all(|v| reread.neighbors(v).eq(g.neighbors(v))));
+  }
+});
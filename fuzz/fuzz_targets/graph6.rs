@@ -0,0 +1,20 @@
+// A graph6 line must be rejected with an error or parse to a consistent
+// graph that writes back as the same line.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use vcc::formats::GraphFormat;
+use vcc::Graph;
+
+fuzz_target!(|data: &[u8]| {
+  let Ok(text) = std::str::from_utf8(data) else {
+    return;
+  };
+  let Ok(g) = Graph::parse(text, GraphFormat::Graph6) else {
+    return;
+  };
+  g.check_invariants().unwrap();
+  let reread = Graph::parse(&g.to_format(GraphFormat::Graph6), GraphFormat::Graph6).unwrap();
+  assert_eq!(reread.size(), g.size());
+  assert!((0..g.size()).all(|v| reread.neighbors(v).eq(g.neighbors(v))));
+});
@@ -0,0 +1,11 @@
+// Manifest parsing must reject malformed TOML with an error, not a panic.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use vcc::manifest::Manifest;
+
+fuzz_target!(|data: &[u8]| {
+  if let Ok(toml_str) = std::str::from_utf8(data) {
+    let _ = Manifest::from_toml_str(toml_str);
+  }
+});
@@ -0,0 +1,175 @@
+// When iterated greedy stalls, the search anneals: it opens a new clique,
+// seeds it with up to seed_size mutually adjacent vertices, each taken from
+// a different clique, and carries on. The first annealing comes after
+// initial_iterations iterations without improvement; after every
+// annealings_per_slowdown annealings, that wait grows by a factor of
+// 1 + growth, so the search perturbs less and less often.
+//
+// The donor policy orders the cliques the seed is taken from, using the
+// sizes and neighbor counts the cliques keep cached (see lib.rs):
+// - random: uniformly shuffled,
+// - largest: largest first, which breaks up the cliques with the most to
+//   spare,
+// - most-conflicted: fewest neighbors first, the cliques no outside
+//   vertex could join, where iterated greedy is most likely stuck,
+// - size-weighted: random, each next donor drawn with probability
+//   proportional to its size.
+// Ties go in random order.
+
+use crate::error::VccError;
+use crate::rng::{below, shuffle};
+use crate::Graph;
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+use core::str::FromStr;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DonorPolicy {
+  #[default]
+  Random,
+  Largest,
+  MostConflicted,
+  SizeWeighted,
+}
+
+// "random", "largest", "most-conflicted" or "size-weighted"
+impl FromStr for DonorPolicy {
+  type Err = VccError;
+
+  fn from_str(donor_str: &str) -> Result<DonorPolicy, VccError> {
+    match donor_str {
+      "random" => Ok(DonorPolicy::Random),
+      "largest" => Ok(DonorPolicy::Largest),
+      "most-conflicted" => Ok(DonorPolicy::MostConflicted),
+      "size-weighted" => Ok(DonorPolicy::SizeWeighted),
+      _ => Err(VccError::Parse(format!(
+        "bad donor policy: {:?}",
+        donor_str
+      ))),
+    }
+  }
+}
+
+// Fields missing from a serialized schedule take their default values.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AnnealingSchedule {
+  pub initial_iterations: usize,
+  pub growth: f64,
+  pub annealings_per_slowdown: usize,
+  pub seed_size: usize,
+  pub donor: DonorPolicy,
+}
+
+impl Default for AnnealingSchedule {
+  fn default() -> AnnealingSchedule {
+    AnnealingSchedule {
+      initial_iterations: 1_000_000,
+      growth: 0.02,
+      annealings_per_slowdown: 1,
+      seed_size: 1,
+      donor: DonorPolicy::Random,
+    }
+  }
+}
+
+impl AnnealingSchedule {
+  pub fn validate(&self) -> Result<(), VccError> {
+    if self.initial_iterations == 0 || self.annealings_per_slowdown == 0 || self.seed_size == 0 {
+      return Err(VccError::InvalidParameter(
+        "annealing needs at least one iteration, one annealing per slowdown and one seed vertex"
+          .to_string(),
+      ));
+    }
+    if !(self.growth >= 0.0 && self.growth.is_finite()) {
+      return Err(VccError::InvalidParameter(format!(
+        "annealing growth {} is not a finite non-negative number",
+        self.growth
+      )));
+    }
+    Ok(())
+  }
+
+  pub(crate) fn grow(&self, iterations: usize) -> usize {
+    iterations.saturating_add((iterations as f64 * self.growth) as usize)
+  }
+}
+
+impl Graph {
+  // Opens a new clique and seeds it as described above. Source cliques are
+  // visited in the donor policy's order and give up a random member
+  // adjacent to the seed so far; frozen cliques and cliques with no such
+  // member are skipped. Returns false if every clique was already in use.
+  pub(crate) fn seed_new_clique(&mut self, seed_size: usize, donor: DonorPolicy) -> bool {
+    if !self.activate_inactive_clique() {
+      return false;
+    }
+    let new = self.cliques_ct - 1;
+    let mut sources: Vec<usize> = (0..new).collect();
+    shuffle(&mut self.rng, &mut sources);
+    match donor {
+      DonorPolicy::Random | DonorPolicy::SizeWeighted => {}
+      DonorPolicy::Largest => sources.sort_by_key(|&c| Reverse(self.cliques[c].members_ct)),
+      DonorPolicy::MostConflicted => sources.sort_by_key(|&c| self.cliques[c].neighbors_ct),
+    }
+    // Weighted draws are made lazily, as seeding usually stops early.
+    let mut weight_left: usize = match donor {
+      DonorPolicy::SizeWeighted => sources.iter().map(|&c| self.cliques[c].members_ct).sum(),
+      _ => 0,
+    };
+    let mut utility_bv = self.scratch.take();
+    let mut seeded = 0;
+    for i in 0..sources.len() {
+      if seeded == seed_size {
+        break;
+      }
+      if weight_left > 0 {
+        let mut r = below(&mut self.rng, weight_left);
+        let j = (i..sources.len())
+          .find(|&j| {
+            let size = self.cliques[sources[j]].members_ct;
+            r < size || {
+              r -= size;
+              false
+            }
+          })
+          .unwrap();
+        sources.swap(i, j);
+        weight_left -= self.cliques[sources[i]].members_ct;
+      }
+      let c = sources[i];
+      let (cliques_before_new, cliques_from_new) = self.cliques.split_at_mut(new);
+      let clique_from = &mut cliques_before_new[c];
+      let clique_into = &mut cliques_from_new[0];
+      if clique_from.is_frozen {
+        continue;
+      }
+      let candidates: Vec<usize> = clique_from
+        .members
+        .iter()
+        .copied()
+        .filter(|&v| clique_into.neighbors_bv.get_unchecked(v))
+        .collect();
+      if candidates.is_empty() {
+        continue;
+      }
+      let v = candidates[below(&mut self.rng, candidates.len())];
+      Self::transfer_vertex_into_clique(
+        clique_into,
+        clique_from,
+        &mut utility_bv,
+        &self.topology.vertices,
+        &mut self.vertex_clique,
+        &self.forbidden,
+        v,
+      );
+      seeded += 1;
+    }
+    self.scratch.give(utility_bv);
+    true
+  }
+}
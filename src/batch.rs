@@ -0,0 +1,38 @@
+// Solving many small, independent instances at once, spread over the
+// rayon pool one instance per task. Each instance gets its own params,
+// budget and target included, so a batch mixing sizes can give the large
+// instances more iterations; it runs on its own copy of the graph and its
+// generator, and stops early once its cover matches its independent set
+// bound, which on small graphs is most of the time. The covers come back
+// in input order.
+
+use crate::cover::CliqueCover;
+use crate::error::VccError;
+use crate::shared::SharedBounds;
+use crate::solve::SolveParams;
+use crate::Graph;
+use rayon::prelude::*;
+
+// params[i] is graphs[i]'s.
+pub fn solve_batch(graphs: &[Graph], params: &[SolveParams]) -> Result<Vec<CliqueCover>, VccError> {
+  if params.len() != graphs.len() {
+    return Err(VccError::InvalidParameter(format!(
+      "{} params for {} graphs",
+      params.len(),
+      graphs.len()
+    )));
+  }
+  for instance_params in params {
+    instance_params.validate()?;
+  }
+  graphs
+    .par_iter()
+    .zip(params)
+    .map(|(graph, params)| {
+      let mut g = graph.clone();
+      let bounds = SharedBounds::new();
+      bounds.offer_lower(g.greedy_independent_set_bound());
+      Ok(g.solve_with_bounds(params, Some(&bounds))?.best_cover)
+    })
+    .collect()
+}
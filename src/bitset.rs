@@ -0,0 +1,553 @@
+// The bit vectors of the search state. BitSet is the handful of operations
+// the solver needs, implemented by four backends: a blocked vector that
+// skips all-zero blocks (blocked feature), bitvec_simd's SIMD vectors (simd
+// feature), fixedbitset's (fixedbitset feature) and a plain vector of u64
+// words that needs no dependency. The solver's BitVec wraps whichever is
+// enabled, in that order of preference; run benches/bitsets.rs to see which
+// is fastest on a given machine. Bits past the length are always clear, so
+// counts and emptiness tests see only real vertices.
+//
+// "Unchecked" reads skip the bounds check, not safety: no backend uses
+// unsafe code, but an index past the length may read a clear padding bit
+// and answer false instead of failing. BitVec is the one layer the solver
+// reads bits through, and debug builds, or the paranoid feature, check
+// every index there, so a vertex id from malformed input that slipped
+// past the parsers fails loudly rather than reading as a non-edge.
+
+pub use blocked::Blocked;
+pub use words::Words;
+
+#[cfg(feature = "simd")]
+pub use bitvec_simd::BitVec as SimdBitVec;
+
+#[cfg(feature = "fixedbitset")]
+pub use fixedbitset::FixedBitSet;
+
+#[cfg(feature = "blocked")]
+type Backend = Blocked;
+
+#[cfg(all(not(feature = "blocked"), feature = "simd"))]
+type Backend = SimdBitVec;
+
+#[cfg(all(
+  not(any(feature = "blocked", feature = "simd")),
+  feature = "fixedbitset"
+))]
+type Backend = FixedBitSet;
+
+#[cfg(not(any(feature = "blocked", feature = "simd", feature = "fixedbitset")))]
+type Backend = Words;
+
+// Binary operations and equality expect both sides to have the same length.
+pub trait BitSet: Clone + PartialEq {
+  fn zeros(len: usize) -> Self;
+  fn len(&self) -> usize;
+  // i must be below the length.
+  fn get_unchecked(&self, i: usize) -> bool;
+  fn set(&mut self, i: usize, value: bool);
+  fn set_all_false(&mut self);
+  fn set_all_true(&mut self);
+  fn count_ones(&self) -> usize;
+  fn any(&self) -> bool;
+  fn and_inplace(&mut self, other: &Self);
+  fn or_inplace(&mut self, other: &Self);
+  fn xor_inplace(&mut self, other: &Self);
+
+  fn ones(len: usize) -> Self {
+    let mut bits = Self::zeros(len);
+    bits.set_all_true();
+    bits
+  }
+
+  // Whether the length is zero, not whether any bit is set.
+  fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  fn get(&self, i: usize) -> Option<bool> {
+    (i < self.len()).then(|| self.get_unchecked(i))
+  }
+
+  fn none(&self) -> bool {
+    !self.any()
+  }
+
+  fn and_cloned(&self, other: &Self) -> Self {
+    let mut bits = self.clone();
+    bits.and_inplace(other);
+    bits
+  }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct BitVec(Backend);
+
+impl BitVec {
+  #[inline]
+  pub fn zeros(len: usize) -> BitVec {
+    BitVec(BitSet::zeros(len))
+  }
+
+  #[inline]
+  pub fn ones(len: usize) -> BitVec {
+    BitVec(BitSet::ones(len))
+  }
+
+  #[inline]
+  pub fn len(&self) -> usize {
+    BitSet::len(&self.0)
+  }
+
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    BitSet::is_empty(&self.0)
+  }
+
+  #[inline]
+  pub fn get(&self, i: usize) -> Option<bool> {
+    BitSet::get(&self.0, i)
+  }
+
+  #[inline]
+  pub fn get_unchecked(&self, i: usize) -> bool {
+    #[cfg(any(debug_assertions, feature = "paranoid"))]
+    assert!(i < self.len(), "bit {} of {}", i, self.len());
+    BitSet::get_unchecked(&self.0, i)
+  }
+
+  #[inline]
+  pub fn set(&mut self, i: usize, value: bool) {
+    BitSet::set(&mut self.0, i, value)
+  }
+
+  #[inline]
+  pub fn set_all_false(&mut self) {
+    BitSet::set_all_false(&mut self.0)
+  }
+
+  #[inline]
+  pub fn set_all_true(&mut self) {
+    BitSet::set_all_true(&mut self.0)
+  }
+
+  #[inline]
+  pub fn count_ones(&self) -> usize {
+    BitSet::count_ones(&self.0)
+  }
+
+  #[inline]
+  pub fn any(&self) -> bool {
+    BitSet::any(&self.0)
+  }
+
+  #[inline]
+  pub fn none(&self) -> bool {
+    BitSet::none(&self.0)
+  }
+
+  #[inline]
+  pub fn and_cloned(&self, other: &BitVec) -> BitVec {
+    BitVec(BitSet::and_cloned(&self.0, &other.0))
+  }
+
+  #[inline]
+  pub fn and_inplace(&mut self, other: &BitVec) {
+    BitSet::and_inplace(&mut self.0, &other.0)
+  }
+
+  #[inline]
+  pub fn or_inplace(&mut self, other: &BitVec) {
+    BitSet::or_inplace(&mut self.0, &other.0)
+  }
+
+  #[inline]
+  pub fn xor_inplace(&mut self, other: &BitVec) {
+    BitSet::xor_inplace(&mut self.0, &other.0)
+  }
+}
+
+#[cfg(feature = "simd")]
+impl BitSet for SimdBitVec {
+  #[inline]
+  fn zeros(len: usize) -> Self {
+    SimdBitVec::zeros(len)
+  }
+
+  #[inline]
+  fn ones(len: usize) -> Self {
+    SimdBitVec::ones(len)
+  }
+
+  #[inline]
+  fn len(&self) -> usize {
+    SimdBitVec::len(self)
+  }
+
+  #[inline]
+  fn get_unchecked(&self, i: usize) -> bool {
+    SimdBitVec::get_unchecked(self, i)
+  }
+
+  #[inline]
+  fn set(&mut self, i: usize, value: bool) {
+    SimdBitVec::set(self, i, value)
+  }
+
+  #[inline]
+  fn set_all_false(&mut self) {
+    SimdBitVec::set_all_false(self)
+  }
+
+  #[inline]
+  fn set_all_true(&mut self) {
+    SimdBitVec::set_all_true(self)
+  }
+
+  #[inline]
+  fn count_ones(&self) -> usize {
+    SimdBitVec::count_ones(self)
+  }
+
+  #[inline]
+  fn any(&self) -> bool {
+    SimdBitVec::any(self)
+  }
+
+  #[inline]
+  fn and_cloned(&self, other: &Self) -> Self {
+    SimdBitVec::and_cloned(self, other)
+  }
+
+  #[inline]
+  fn and_inplace(&mut self, other: &Self) {
+    SimdBitVec::and_inplace(self, other)
+  }
+
+  #[inline]
+  fn or_inplace(&mut self, other: &Self) {
+    SimdBitVec::or_inplace(self, other)
+  }
+
+  #[inline]
+  fn xor_inplace(&mut self, other: &Self) {
+    SimdBitVec::xor_inplace(self, other)
+  }
+}
+
+#[cfg(feature = "fixedbitset")]
+impl BitSet for FixedBitSet {
+  #[inline]
+  fn zeros(len: usize) -> Self {
+    FixedBitSet::with_capacity(len)
+  }
+
+  #[inline]
+  fn len(&self) -> usize {
+    FixedBitSet::len(self)
+  }
+
+  #[inline]
+  fn get_unchecked(&self, i: usize) -> bool {
+    self.contains(i)
+  }
+
+  #[inline]
+  fn set(&mut self, i: usize, value: bool) {
+    FixedBitSet::set(self, i, value)
+  }
+
+  #[inline]
+  fn set_all_false(&mut self) {
+    self.clear()
+  }
+
+  #[inline]
+  fn set_all_true(&mut self) {
+    self.insert_range(..)
+  }
+
+  #[inline]
+  fn count_ones(&self) -> usize {
+    FixedBitSet::count_ones(self, ..)
+  }
+
+  #[inline]
+  fn any(&self) -> bool {
+    !self.is_clear()
+  }
+
+  #[inline]
+  fn and_inplace(&mut self, other: &Self) {
+    self.intersect_with(other)
+  }
+
+  #[inline]
+  fn or_inplace(&mut self, other: &Self) {
+    self.union_with(other)
+  }
+
+  #[inline]
+  fn xor_inplace(&mut self, other: &Self) {
+    self.symmetric_difference_with(other)
+  }
+}
+
+mod words {
+  use super::BitSet;
+  use alloc::vec;
+  use alloc::vec::Vec;
+
+  #[derive(Clone, Debug, PartialEq, Eq)]
+  pub struct Words {
+    words: Vec<u64>,
+    len: usize,
+  }
+
+  impl BitSet for Words {
+    fn zeros(len: usize) -> Words {
+      Words {
+        words: vec![0; len.div_ceil(64)],
+        len,
+      }
+    }
+
+    fn len(&self) -> usize {
+      self.len
+    }
+
+    fn get_unchecked(&self, i: usize) -> bool {
+      self.words[i / 64] >> (i % 64) & 1 == 1
+    }
+
+    fn set(&mut self, i: usize, value: bool) {
+      assert!(i < self.len, "bit {} of {}", i, self.len);
+      if value {
+        self.words[i / 64] |= 1 << (i % 64);
+      } else {
+        self.words[i / 64] &= !(1 << (i % 64));
+      }
+    }
+
+    fn set_all_false(&mut self) {
+      self.words.fill(0);
+    }
+
+    fn set_all_true(&mut self) {
+      self.words.fill(u64::MAX);
+      if !self.len.is_multiple_of(64) {
+        if let Some(last) = self.words.last_mut() {
+          *last = (1 << (self.len % 64)) - 1;
+        }
+      }
+    }
+
+    fn count_ones(&self) -> usize {
+      self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    fn any(&self) -> bool {
+      self.words.iter().any(|&w| w != 0)
+    }
+
+    fn and_inplace(&mut self, other: &Words) {
+      for (w, o) in self.words.iter_mut().zip(&other.words) {
+        *w &= o;
+      }
+    }
+
+    fn or_inplace(&mut self, other: &Words) {
+      for (w, o) in self.words.iter_mut().zip(&other.words) {
+        *w |= o;
+      }
+    }
+
+    fn xor_inplace(&mut self, other: &Words) {
+      for (w, o) in self.words.iter_mut().zip(&other.words) {
+        *w ^= o;
+      }
+    }
+  }
+}
+
+// Words grouped into blocks of 512 bits, with a summary bit per block that
+// is set exactly when the block has a bit set. Operations walk the summary
+// and touch only the blocks it marks, so their cost follows how many blocks
+// the sets occupy rather than the vertex count. On large sparse graphs
+// relabeled so that neighborhoods and cliques are local (see locality.rs),
+// most sets occupy a few blocks.
+mod blocked {
+  use super::BitSet;
+  use alloc::vec;
+  use alloc::vec::Vec;
+  use core::ops::Range;
+
+  const BLOCK_WORDS: usize = 8;
+
+  #[derive(Clone, Debug, PartialEq, Eq)]
+  pub struct Blocked {
+    words: Vec<u64>,
+    summary: Vec<u64>,
+    len: usize,
+  }
+
+  impl Blocked {
+    fn block(&self, b: usize) -> Range<usize> {
+      b * BLOCK_WORDS..((b + 1) * BLOCK_WORDS).min(self.words.len())
+    }
+
+    fn has_block(&self, b: usize) -> bool {
+      self.summary[b / 64] >> (b % 64) & 1 == 1
+    }
+
+    fn mark(&mut self, b: usize) {
+      self.summary[b / 64] |= 1 << (b % 64);
+    }
+
+    fn unmark(&mut self, b: usize) {
+      self.summary[b / 64] &= !(1 << (b % 64));
+    }
+
+    // Clears block b's summary bit if the block is now all zero.
+    fn refresh(&mut self, b: usize) {
+      let range = self.block(b);
+      if self.words[range].iter().all(|&w| w == 0) {
+        self.unmark(b);
+      }
+    }
+
+    // The blocks the summary marks, in order.
+    fn blocks(summary: &[u64]) -> impl Iterator<Item = usize> + '_ {
+      summary.iter().enumerate().flat_map(|(i, &s)| {
+        let mut s = s;
+        core::iter::from_fn(move || {
+          (s != 0).then(|| {
+            let b = i * 64 + s.trailing_zeros() as usize;
+            s &= s - 1;
+            b
+          })
+        })
+      })
+    }
+  }
+
+  impl BitSet for Blocked {
+    fn zeros(len: usize) -> Blocked {
+      let words = len.div_ceil(64);
+      Blocked {
+        words: vec![0; words],
+        summary: vec![0; words.div_ceil(BLOCK_WORDS).div_ceil(64)],
+        len,
+      }
+    }
+
+    fn len(&self) -> usize {
+      self.len
+    }
+
+    fn get_unchecked(&self, i: usize) -> bool {
+      self.words[i / 64] >> (i % 64) & 1 == 1
+    }
+
+    fn set(&mut self, i: usize, value: bool) {
+      assert!(i < self.len, "bit {} of {}", i, self.len);
+      let b = i / 64 / BLOCK_WORDS;
+      if value {
+        self.words[i / 64] |= 1 << (i % 64);
+        self.mark(b);
+      } else {
+        self.words[i / 64] &= !(1 << (i % 64));
+        self.refresh(b);
+      }
+    }
+
+    fn set_all_false(&mut self) {
+      for i in 0..self.summary.len() {
+        let mut s = self.summary[i];
+        while s != 0 {
+          let b = i * 64 + s.trailing_zeros() as usize;
+          s &= s - 1;
+          let range = self.block(b);
+          self.words[range].fill(0);
+        }
+        self.summary[i] = 0;
+      }
+    }
+
+    fn set_all_true(&mut self) {
+      self.words.fill(u64::MAX);
+      if !self.len.is_multiple_of(64) {
+        if let Some(last) = self.words.last_mut() {
+          *last = (1 << (self.len % 64)) - 1;
+        }
+      }
+      for b in 0..self.words.len().div_ceil(BLOCK_WORDS) {
+        self.mark(b);
+      }
+    }
+
+    fn count_ones(&self) -> usize {
+      Self::blocks(&self.summary)
+        .map(|b| {
+          self.words[self.block(b)]
+            .iter()
+            .map(|w| w.count_ones() as usize)
+            .sum::<usize>()
+        })
+        .sum()
+    }
+
+    fn any(&self) -> bool {
+      self.summary.iter().any(|&s| s != 0)
+    }
+
+    fn and_inplace(&mut self, other: &Blocked) {
+      for i in 0..self.summary.len() {
+        let mut s = self.summary[i];
+        while s != 0 {
+          let b = i * 64 + s.trailing_zeros() as usize;
+          s &= s - 1;
+          let range = self.block(b);
+          if other.has_block(b) {
+            for (w, o) in self.words[range]
+              .iter_mut()
+              .zip(&other.words[b * BLOCK_WORDS..])
+            {
+              *w &= o;
+            }
+            self.refresh(b);
+          } else {
+            self.words[range].fill(0);
+            self.unmark(b);
+          }
+        }
+      }
+    }
+
+    fn or_inplace(&mut self, other: &Blocked) {
+      for b in Self::blocks(&other.summary) {
+        let range = self.block(b);
+        for (w, o) in self.words[range]
+          .iter_mut()
+          .zip(&other.words[b * BLOCK_WORDS..])
+        {
+          *w |= o;
+        }
+        self.mark(b);
+      }
+    }
+
+    fn xor_inplace(&mut self, other: &Blocked) {
+      for b in Self::blocks(&other.summary) {
+        let range = self.block(b);
+        for (w, o) in self.words[range]
+          .iter_mut()
+          .zip(&other.words[b * BLOCK_WORDS..])
+        {
+          *w ^= o;
+        }
+        self.mark(b);
+        self.refresh(b);
+      }
+    }
+  }
+}
@@ -0,0 +1,161 @@
+// Bounds on the clique cover number. No clique holds two vertices of an
+// independent set, so any independent set's size is a lower bound. Any
+// cover is an upper bound; DSATUR on the complement gives a good one fast.
+// Covers of one or two cliques can be decided exactly in O(n^2).
+
+use crate::bitset::BitVec;
+use crate::cover::CliqueCover;
+use crate::rng::shuffle;
+use crate::Graph;
+use alloc::vec;
+use alloc::vec::Vec;
+
+impl Graph {
+  // Size of an independent set built greedily over a random vertex order.
+  pub fn greedy_independent_set_bound(&mut self) -> usize {
+    let mut order: Vec<usize> = (0..self.size).collect();
+    shuffle(&mut self.rng, &mut order);
+    let mut chosen: Vec<usize> = vec![];
+    for v in order {
+      if chosen
+        .iter()
+        .all(|&u| !self.topology.vertices[v].neighbors_bv.get_unchecked(u))
+      {
+        chosen.push(v);
+      }
+    }
+    chosen.len()
+  }
+
+  // DSATUR coloring of the complement, whose color classes are cliques
+  // here: repeatedly take the unplaced vertex barred from the most
+  // cliques (ties: fewest neighbors) and put it into the first clique of
+  // its neighbors, or a new one. O(n^2) time.
+  pub fn dsatur_cover(&self) -> CliqueCover {
+    let n = self.size;
+    let degrees: Vec<usize> = (0..n).map(|v| self.degree(v)).collect();
+    let mut placed = vec![false; n];
+    // barred[v][c]: clique c holds a non-neighbor of v.
+    let mut barred: Vec<Vec<bool>> = vec![vec![]; n];
+    let mut saturation = vec![0; n];
+    let mut cliques: Vec<Vec<usize>> = vec![];
+    for _ in 0..n {
+      let u = (0..n)
+        .filter(|&v| !placed[v])
+        .max_by_key(|&v| (saturation[v], n - degrees[v]))
+        .unwrap();
+      placed[u] = true;
+      let c = match (0..cliques.len()).find(|&c| !barred[u].get(c).copied().unwrap_or(false)) {
+        Some(c) => c,
+        None => {
+          cliques.push(vec![]);
+          cliques.len() - 1
+        }
+      };
+      cliques[c].push(u);
+      for v in 0..n {
+        if placed[v] || v == u || self.topology.vertices[u].neighbors_bv.get_unchecked(v) {
+          continue;
+        }
+        if barred[v].len() <= c {
+          barred[v].resize(c + 1, false);
+        }
+        if !barred[v][c] {
+          barred[v][c] = true;
+          saturation[v] += 1;
+        }
+      }
+    }
+    CliqueCover { cliques }
+  }
+
+  // Degeneracy of the complement: the largest minimum complement degree
+  // met while repeatedly removing a vertex of minimum complement degree.
+  // Smallest-last coloring of the complement needs at most one more
+  // color, so this plus one bounds the cover number.
+  pub fn complement_degeneracy(&self) -> usize {
+    let n = self.size;
+    let mut alive = vec![true; n];
+    let mut degrees: Vec<usize> = (0..n).map(|v| n - 1 - self.degree(v)).collect();
+    let mut degeneracy = 0;
+    for _ in 0..n {
+      let u = (0..n)
+        .filter(|&v| alive[v])
+        .min_by_key(|&v| degrees[v])
+        .unwrap();
+      degeneracy = degeneracy.max(degrees[u]);
+      alive[u] = false;
+      for v in 0..n {
+        if alive[v] && v != u && !self.topology.vertices[u].neighbors_bv.get_unchecked(v) {
+          degrees[v] -= 1;
+        }
+      }
+    }
+    degeneracy
+  }
+
+  // The optimal cover if it has at most two cliques. Two cliques cover the
+  // graph exactly when the complement is bipartite, which a breadth-first
+  // 2-coloring of the complement decides; a complete graph needs one.
+  pub fn small_cover(&self) -> Option<CliqueCover> {
+    let n = self.size;
+    let mut side: Vec<Option<bool>> = vec![None; n];
+    let mut queue = alloc::collections::VecDeque::new();
+    for root in 0..n {
+      if side[root].is_some() {
+        continue;
+      }
+      side[root] = Some(false);
+      queue.push_back(root);
+      while let Some(u) = queue.pop_front() {
+        let u_side = side[u].unwrap();
+        for (v, v_side) in side.iter_mut().enumerate() {
+          if v == u || self.topology.vertices[u].neighbors_bv.get_unchecked(v) {
+            continue;
+          }
+          match *v_side {
+            None => {
+              *v_side = Some(!u_side);
+              queue.push_back(v);
+            }
+            Some(s) if s == u_side => return None,
+            Some(_) => {}
+          }
+        }
+      }
+    }
+    let mut cliques = vec![vec![], vec![]];
+    for (v, s) in side.iter().enumerate() {
+      cliques[s.unwrap() as usize].push(v);
+    }
+    // Every root starts on the same side, so only that side can be empty
+    // when the other isn't; a complete graph puts everything there.
+    cliques.retain(|members| !members.is_empty());
+    Some(CliqueCover { cliques })
+  }
+
+  // Greedy sequential coloring of the complement: over a random vertex
+  // order, each vertex joins the first clique of its neighbors, or starts
+  // a new one. A valid cover in O(n * cliques) word operations.
+  pub fn sequential_cover(&mut self) -> CliqueCover {
+    let mut order: Vec<usize> = (0..self.size).collect();
+    shuffle(&mut self.rng, &mut order);
+    let mut cliques: Vec<Vec<usize>> = vec![];
+    // joinable[c]: the common neighbors of clique c's members.
+    let mut joinable: Vec<BitVec> = vec![];
+    for v in order {
+      let neighbors_bv = &self.topology.vertices[v].neighbors_bv;
+      match (0..cliques.len()).find(|&c| joinable[c].get_unchecked(v)) {
+        Some(c) => {
+          cliques[c].push(v);
+          joinable[c].and_inplace(neighbors_bv);
+        }
+        None => {
+          cliques.push(vec![v]);
+          joinable.push(neighbors_bv.clone());
+        }
+      }
+    }
+    CliqueCover { cliques }
+  }
+}
@@ -0,0 +1,105 @@
+// How often a restart checks its cover against the target, and publishes
+// it to bounds shared with other workers, between the checks made whenever
+// the cover shrinks: every so many iterations, by default a million, and
+// optionally also whenever an interval has passed. On a huge instance a
+// million iterations can take hours, so a target met without the cover
+// shrinking, say because another worker raised the lower bound to it, is
+// noticed late; an interval bounds that delay in time rather than work.
+//
+// Iteration checks also restart the stall count that triggers annealing,
+// so they are part of the search's trajectory, which must not depend on
+// timing; checks on the interval only compare and publish, leaving the
+// trajectory, and so seeded runs, reproducible. Without std there is no
+// clock (see clock.rs) and the interval never passes.
+
+use crate::clock::{parse_duration, Instant};
+use crate::error::VccError;
+use alloc::format;
+use alloc::string::ToString;
+use core::str::FromStr;
+use core::time::Duration;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CheckCadence {
+  pub iterations: usize,
+  pub interval: Option<Duration>,
+}
+
+impl Default for CheckCadence {
+  fn default() -> CheckCadence {
+    CheckCadence {
+      iterations: 1_000_000,
+      interval: None,
+    }
+  }
+}
+
+// "250_000" iterations, or an interval such as "30s", "500ms" or "2m"
+// (with the default iterations), or both, "250_000,30s"
+impl FromStr for CheckCadence {
+  type Err = VccError;
+
+  fn from_str(cadence_str: &str) -> Result<CheckCadence, VccError> {
+    let bad = || VccError::Parse(format!("bad check cadence: {:?}", cadence_str));
+    let mut cadence = CheckCadence::default();
+    for part in cadence_str.split(',') {
+      if part.ends_with(|c: char| c.is_ascii_alphabetic()) {
+        cadence.interval = Some(parse_duration(part).map_err(|_| bad())?);
+      } else {
+        cadence.iterations = part.replace('_', "").parse().map_err(|_| bad())?;
+      }
+    }
+    Ok(cadence)
+  }
+}
+
+impl CheckCadence {
+  pub fn validate(&self) -> Result<(), VccError> {
+    if self.iterations == 0 {
+      return Err(VccError::InvalidParameter(
+        "checks need at least one iteration between them".to_string(),
+      ));
+    }
+    if self.interval == Some(Duration::ZERO) {
+      return Err(VccError::InvalidParameter(
+        "check interval must be positive".to_string(),
+      ));
+    }
+    Ok(())
+  }
+}
+
+// A restart's clock for the interval, restarted by every check.
+pub(crate) struct CheckClock {
+  interval: Option<Duration>,
+  last: Instant,
+}
+
+impl CheckClock {
+  pub(crate) fn new(cadence: &CheckCadence) -> CheckClock {
+    CheckClock {
+      interval: cadence.interval,
+      last: Instant::now(),
+    }
+  }
+
+  pub(crate) fn restart(&mut self) {
+    if self.interval.is_some() {
+      self.last = Instant::now();
+    }
+  }
+
+  // Whether the interval has passed since the last check, restarting the
+  // clock if so. Reads no clock without an interval.
+  pub(crate) fn is_due(&mut self) -> bool {
+    let Some(interval) = self.interval else {
+      return false;
+    };
+    if self.last.elapsed() < interval {
+      return false;
+    }
+    self.last = Instant::now();
+    true
+  }
+}
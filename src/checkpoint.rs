@@ -0,0 +1,79 @@
+// Checkpoints of a single-instance solve, taken between restarts. Besides
+// the instance, parameters and best cover so far, a checkpoint holds the
+// state of the search's generator, so a resumed run makes exactly the
+// choices the uninterrupted run would have made.
+
+use crate::cover::CliqueCover;
+use crate::durable::write_atomic;
+use crate::error::VccError;
+use crate::rng::DefaultRng;
+use crate::solve::{SolveParams, SolveProgress};
+use crate::Graph;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+#[derive(Serialize, Deserialize)]
+pub struct Checkpoint {
+  pub num_vertices: usize,
+  pub edges: Vec<(usize, usize)>,
+  pub params: SolveParams,
+  pub restarts: usize,
+  pub best_cover: CliqueCover,
+  pub rng: DefaultRng,
+  #[serde(default)]
+  pub restart_seed_base: u64,
+}
+
+impl Checkpoint {
+  // None if g's generator isn't the default one, whose state can't be saved.
+  pub fn capture(g: &Graph, params: &SolveParams, progress: &SolveProgress) -> Option<Checkpoint> {
+    let mut edges = vec![];
+    for u in 0..g.size {
+      for v in (u + 1)..g.size {
+        if g.topology.vertices[u].neighbors_bv.get_unchecked(v) {
+          edges.push((u, v));
+        }
+      }
+    }
+    Some(Checkpoint {
+      num_vertices: g.size,
+      edges,
+      params: *params,
+      restarts: progress.restarts,
+      best_cover: progress.best_cover.clone(),
+      rng: g.default_rng_state()?,
+      restart_seed_base: progress.restart_seed_base,
+    })
+  }
+
+  pub fn save(&self, path: &str) -> Result<(), VccError> {
+    self.best_cover.check_consistency()?;
+    write_atomic(path, serde_json::to_string(self)?)
+  }
+
+  pub fn load(path: &str) -> Result<Checkpoint, VccError> {
+    Checkpoint::from_json_str(&fs::read_to_string(path)?)
+  }
+
+  pub fn from_json_str(json: &str) -> Result<Checkpoint, VccError> {
+    Ok(serde_json::from_str(json)?)
+  }
+
+  // The graph and progress to hand to Graph::resume_solve. Fails if the
+  // edges or best cover don't fit the checkpoint's vertex count.
+  pub fn restore(&self) -> Result<(Graph, SolveProgress), VccError> {
+    let mut g = Graph::new(self.num_vertices);
+    for &(u, v) in &self.edges {
+      g.add_edge(u, v)?;
+    }
+    g.conform_cliques_to_vertices();
+    g.validate_cover(&self.best_cover)?;
+    g.set_rng(self.rng.clone());
+    let progress = SolveProgress {
+      restarts: self.restarts,
+      best_cover: self.best_cover.clone(),
+      restart_seed_base: self.restart_seed_base,
+    };
+    Ok((g, progress))
+  }
+}
@@ -0,0 +1,58 @@
+// The clock behind the solver's timings and deadlines. With std it is the
+// system's monotonic clock. Without std there is none to read: time never
+// passes, so elapsed times read zero and deadlines never come, leaving
+// node and iteration budgets to stop the search.
+
+use crate::error::VccError;
+use alloc::format;
+use core::time::Duration;
+
+#[cfg(feature = "std")]
+pub use std::time::Instant;
+
+#[cfg(not(feature = "std"))]
+mod frozen {
+  use core::ops::Add;
+  use core::time::Duration;
+
+  #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+  pub struct Instant(Duration);
+
+  impl Instant {
+    pub fn now() -> Instant {
+      Instant(Duration::ZERO)
+    }
+
+    pub fn elapsed(&self) -> Duration {
+      Duration::ZERO
+    }
+  }
+
+  impl Add<Duration> for Instant {
+    type Output = Instant;
+
+    fn add(self, duration: Duration) -> Instant {
+      Instant(self.0 + duration)
+    }
+  }
+}
+
+#[cfg(not(feature = "std"))]
+pub use frozen::Instant;
+
+// "60s", "500ms" or "2m"; a bare number is seconds. Underscores may
+// separate digits.
+pub fn parse_duration(duration_str: &str) -> Result<Duration, VccError> {
+  let bad = || VccError::Parse(format!("bad duration: {:?}", duration_str));
+  let (number, unit) = match duration_str.find(|c: char| c.is_ascii_alphabetic()) {
+    Some(i) => duration_str.split_at(i),
+    None => (duration_str, "s"),
+  };
+  let number: u64 = number.replace('_', "").parse().map_err(|_| bad())?;
+  match unit {
+    "ms" => Ok(Duration::from_millis(number)),
+    "s" => Ok(Duration::from_secs(number)),
+    "m" => Ok(Duration::from_secs(number * 60)),
+    _ => Err(bad()),
+  }
+}
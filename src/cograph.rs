@@ -0,0 +1,114 @@
+// Cographs, the graphs without an induced path on four vertices, fall
+// apart completely: every induced subgraph on two or more vertices is
+// either disconnected or has a disconnected complement. Splitting that way
+// down to single vertices gives the cotree, which solves clique cover
+// exactly:
+// - a disjoint union needs the sum of its parts' cliques,
+// - a join needs only the most any part needs, as the i-th cliques of all
+//   the parts together are still a clique.
+// Cographs are perfect, so the cover is as small as the largest
+// independent set and certifies itself. Recognition splits off components
+// level by level, quadratic in the vertices per level; linear-time
+// recognizers exist but are much more involved.
+
+use crate::cover::CliqueCover;
+use crate::Graph;
+use alloc::collections::VecDeque;
+use alloc::vec;
+use alloc::vec::Vec;
+
+pub enum Cotree {
+  Leaf(usize),
+  Union(Vec<Cotree>),
+  Join(Vec<Cotree>),
+}
+
+impl Cotree {
+  pub fn cover(&self) -> CliqueCover {
+    let cliques = match self {
+      Cotree::Leaf(v) => vec![vec![*v]],
+      Cotree::Union(parts) => parts.iter().flat_map(|part| part.cover().cliques).collect(),
+      Cotree::Join(parts) => {
+        let mut cliques: Vec<Vec<usize>> = vec![];
+        for part in parts {
+          for (i, members) in part.cover().cliques.into_iter().enumerate() {
+            match cliques.get_mut(i) {
+              Some(clique) => clique.extend(members),
+              None => cliques.push(members),
+            }
+          }
+        }
+        cliques
+      }
+    };
+    CliqueCover { cliques }
+  }
+}
+
+impl Graph {
+  // None if the graph isn't a cograph.
+  pub fn cotree(&self) -> Option<Cotree> {
+    let vertices: Vec<usize> = (0..self.size).collect();
+    if vertices.is_empty() {
+      return Some(Cotree::Union(vec![]));
+    }
+    cotree_of(self, &vertices)
+  }
+
+  pub fn cograph_cover(&self) -> Option<CliqueCover> {
+    self.cotree().map(|tree| tree.cover())
+  }
+}
+
+fn cotree_of(g: &Graph, vertices: &[usize]) -> Option<Cotree> {
+  if let [v] = vertices {
+    return Some(Cotree::Leaf(*v));
+  }
+  let parts = components(g, vertices, true);
+  if parts.len() > 1 {
+    let trees = parts
+      .iter()
+      .map(|part| cotree_of(g, part))
+      .collect::<Option<_>>()?;
+    return Some(Cotree::Union(trees));
+  }
+  let parts = components(g, vertices, false);
+  if parts.len() > 1 {
+    let trees = parts
+      .iter()
+      .map(|part| cotree_of(g, part))
+      .collect::<Option<_>>()?;
+    return Some(Cotree::Join(trees));
+  }
+  None
+}
+
+// The components of the subgraph induced by vertices, or of its complement
+// if linked is false.
+fn components(g: &Graph, vertices: &[usize], linked: bool) -> Vec<Vec<usize>> {
+  let mut unseen = vec![false; g.size];
+  for &v in vertices {
+    unseen[v] = true;
+  }
+  let mut parts = vec![];
+  let mut queue = VecDeque::new();
+  for &root in vertices {
+    if !unseen[root] {
+      continue;
+    }
+    unseen[root] = false;
+    queue.push_back(root);
+    let mut part = vec![];
+    while let Some(u) = queue.pop_front() {
+      part.push(u);
+      for &v in vertices {
+        if unseen[v] && v != u && g.topology.vertices[u].neighbors_bv.get_unchecked(v) == linked {
+          unseen[v] = false;
+          queue.push_back(v);
+        }
+      }
+    }
+    parts.push(part);
+  }
+  parts
+}
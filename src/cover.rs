@@ -0,0 +1,279 @@
+use crate::error::VccError;
+use crate::{Clique, Graph};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use serde::{Deserialize, Serialize};
+
+// A vertex clique cover detached from the search state: one list of
+// vertex ids per clique. Cheap to keep around and hand to exporters.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CliqueCover {
+  pub cliques: Vec<Vec<usize>>,
+}
+
+impl CliqueCover {
+  pub(crate) fn from_cliques(cliques: &[Clique]) -> CliqueCover {
+    CliqueCover {
+      cliques: cliques
+        .iter()
+        .filter(|c| c.is_active && c.members_ct > 0)
+        .map(|c| c.members.to_vec())
+        .collect(),
+    }
+  }
+
+  pub fn len(&self) -> usize {
+    self.cliques.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.cliques.is_empty()
+  }
+
+  // The cover with vertex v renamed map[v], e.g. by the inverse
+  // permutation Graph::permute returns.
+  pub fn relabel(&self, map: &[usize]) -> CliqueCover {
+    CliqueCover {
+      cliques: self
+        .cliques
+        .iter()
+        .map(|members| members.iter().map(|&v| map[v]).collect())
+        .collect(),
+    }
+  }
+
+  // Sorts each clique's members, then the cliques by smallest member and
+  // size, so that equal covers print and export identically whatever
+  // order the search left them in.
+  pub fn canonicalize(&mut self) {
+    for members in &mut self.cliques {
+      members.sort_unstable();
+    }
+    self
+      .cliques
+      .sort_unstable_by_key(|members| (members.first().copied(), members.len()));
+  }
+
+  // Checks what a cover must satisfy whatever the graph: no empty clique,
+  // no clique twice, and no vertex in two cliques. A cover the search
+  // produced failing this is a bug, so exporters check it first; see
+  // Graph::validate_cover for the checks against a graph.
+  pub fn check_consistency(&self) -> Result<(), VccError> {
+    let mut sorted: Vec<(Vec<usize>, usize)> = vec![];
+    for (c, members) in self.cliques.iter().enumerate() {
+      if members.is_empty() {
+        return Err(VccError::InvalidCover(format!("clique {} is empty", c)));
+      }
+      let mut members = members.clone();
+      members.sort_unstable();
+      sorted.push((members, c));
+    }
+    sorted.sort_unstable();
+    for pair in sorted.windows(2) {
+      if pair[0].0 == pair[1].0 {
+        return Err(VccError::InvalidCover(format!(
+          "cliques {} and {} are the same",
+          pair[0].1, pair[1].1
+        )));
+      }
+    }
+    let mut owner = vec![None; self.cliques.iter().flatten().max().map_or(0, |&v| v + 1)];
+    for (c, members) in self.cliques.iter().enumerate() {
+      for &v in members {
+        if let Some(first) = owner[v].replace(c) {
+          if first == c {
+            return Err(VccError::InvalidCover(format!(
+              "vertex {} is in clique {} twice",
+              v, c
+            )));
+          }
+          return Err(VccError::InvalidCover(format!(
+            "vertex {} is in cliques {} and {}",
+            v, first, c
+          )));
+        }
+      }
+    }
+    Ok(())
+  }
+
+  // Partition distance to another cover of the same vertices: how many
+  // vertices must move to turn one into the other. Cliques are matched
+  // greedily by overlap rather than optimally, so this can overestimate
+  // the exact distance, but it is 0 exactly when the covers agree.
+  pub fn distance(&self, other: &CliqueCover) -> usize {
+    let num_vertices: usize = self.cliques.iter().map(|c| c.len()).sum();
+    num_vertices - self.matching(other).1
+  }
+
+  // The greedy matching distance uses: each of self's cliques' partner in
+  // other, if any, and the vertices the partners share.
+  pub(crate) fn matching(&self, other: &CliqueCover) -> (Vec<Option<usize>>, usize) {
+    let size = self
+      .cliques
+      .iter()
+      .chain(&other.cliques)
+      .flatten()
+      .max()
+      .map_or(0, |&v| v + 1);
+    let mut other_label = vec![usize::MAX; size];
+    for (j, members) in other.cliques.iter().enumerate() {
+      for &v in members {
+        other_label[v] = j;
+      }
+    }
+    let mut overlaps = vec![];
+    for (i, members) in self.cliques.iter().enumerate() {
+      let mut labels: Vec<usize> = members.iter().map(|&v| other_label[v]).collect();
+      labels.sort_unstable();
+      for run in labels.chunk_by(|a, b| a == b) {
+        if run[0] != usize::MAX {
+          overlaps.push((run.len(), i, run[0]));
+        }
+      }
+    }
+    overlaps.sort_unstable_by(|a, b| b.cmp(a));
+    let mut partner = vec![None; self.len()];
+    let mut used_other = vec![false; other.len()];
+    let mut kept = 0;
+    for (overlap, i, j) in overlaps {
+      if partner[i].is_none() && !used_other[j] {
+        partner[i] = Some(j);
+        used_other[j] = true;
+        kept += overlap;
+      }
+    }
+    (partner, kept)
+  }
+
+  // The cover as a clustering: each vertex's clique index, by vertex, as
+  // scikit-learn's labels_ arrays have it. Gaps in the vertex ids get
+  // usize::MAX.
+  pub fn labels(&self) -> Vec<usize> {
+    let size = self.cliques.iter().flatten().max().map_or(0, |&v| v + 1);
+    let mut labels = vec![usize::MAX; size];
+    for (c, members) in self.cliques.iter().enumerate() {
+      for &v in members {
+        labels[v] = c;
+      }
+    }
+    labels
+  }
+
+  // The labels as a "vertex,cluster" CSV, with a header row.
+  pub fn to_cluster_csv(&self) -> String {
+    let mut csv = "vertex,cluster\n".to_string();
+    for (v, label) in self.labels().into_iter().enumerate() {
+      csv.push_str(&format!("{},{}\n", v, label));
+    }
+    csv
+  }
+
+  // The cover as a DIMACS coloring solution of the complement graph, whose
+  // color classes are the cover's cliques: "s col <colors>", then
+  // "l <vertex> <color>" per covered vertex, both counted from 1.
+  pub fn to_dimacs_coloring(&self) -> String {
+    let mut solution = format!(
+      "c clique cover as a coloring of the complement\ns col {}\n",
+      self.len()
+    );
+    for (v, label) in self.labels().into_iter().enumerate() {
+      if label != usize::MAX {
+        solution.push_str(&format!("l {} {}\n", v + 1, label + 1));
+      }
+    }
+    solution
+  }
+}
+
+impl Graph {
+  // Checks that cover partitions this graph's vertices into cliques, none
+  // of which breaks a forbidden set.
+  pub fn validate_cover(&self, cover: &CliqueCover) -> Result<(), VccError> {
+    let mut seen = vec![false; self.size];
+    for (c, members) in cover.cliques.iter().enumerate() {
+      if members.is_empty() {
+        return Err(VccError::InvalidCover(format!("clique {} is empty", c)));
+      }
+      for (i, &v) in members.iter().enumerate() {
+        if v >= self.size {
+          return Err(VccError::InvalidCover(format!(
+            "vertex {} in a graph with {} vertices",
+            v, self.size
+          )));
+        }
+        if seen[v] {
+          return Err(VccError::InvalidCover(format!(
+            "vertex {} is in more than one clique",
+            v
+          )));
+        }
+        seen[v] = true;
+        for &u in &members[..i] {
+          if !self.topology.vertices[v].neighbors_bv.get_unchecked(u) {
+            return Err(VccError::InvalidCover(format!(
+              "clique {} holds non-adjacent vertices {} and {}",
+              c, u, v
+            )));
+          }
+        }
+      }
+    }
+    if let Some(v) = seen.iter().position(|&s| !s) {
+      return Err(VccError::InvalidCover(format!(
+        "vertex {} is not covered",
+        v
+      )));
+    }
+    for (c, members) in cover.cliques.iter().enumerate() {
+      if let Some(set) = self.broken_limit(members) {
+        return Err(VccError::InvalidCover(format!(
+          "clique {} holds more than {} of forbidden set {:?}",
+          c, set.max_together, set.members
+        )));
+      }
+    }
+    Ok(())
+  }
+
+  // Replaces the search state with the given cover, after checking it.
+  pub fn load_cover(&mut self, cover: &CliqueCover) -> Result<(), VccError> {
+    self.validate_cover(cover)?;
+    self.load_cover_unchecked(cover);
+    Ok(())
+  }
+
+  // Cliques beyond the cover are left empty and inactive, ready for
+  // activate_inactive_clique.
+  pub(crate) fn load_cover_unchecked(&mut self, cover: &CliqueCover) {
+    for (i, members) in cover.cliques.iter().enumerate() {
+      let clique = &mut self.cliques[i];
+      clique.members_bv.set_all_false();
+      clique.members.clear();
+      clique.neighbors_bv.set_all_true();
+      for &v in members {
+        self.vertex_clique[v] = clique.id;
+        clique.members_bv.set(v, true);
+        clique.members.push(v);
+        clique
+          .neighbors_bv
+          .and_inplace(&self.topology.vertices[v].neighbors_bv);
+      }
+      clique.members_ct = members.len();
+      clique.neighbors_ct = clique.neighbors_bv.count_ones();
+      clique.is_active = true;
+      clique.is_frozen = false;
+    }
+    for clique in &mut self.cliques[cover.len()..] {
+      clique.members_bv.set_all_false();
+      clique.members.clear();
+      clique.members_ct = 0;
+      clique.neighbors_bv.set_all_true();
+      clique.neighbors_ct = clique.length;
+      clique.is_active = false;
+      clique.is_frozen = false;
+    }
+    self.cliques_ct = cover.len();
+  }
+}
@@ -0,0 +1,150 @@
+// The decision form of the problem: is there a cover with at most k
+// cliques? Checks run from cheap to expensive, and the first that settles
+// the question answers it:
+// - lower bounds (independent sets, the cograph optimum, the fractional
+//   cover on small graphs, the Lagrangian bound), any above k saying no,
+// - greedy covers (DSatur, sequential), any within k saying yes,
+// - the kernel (see kernel.rs): theta = theta(kernel) + its fixed cliques,
+//   so bounds on the kernel carry over with the fixed cliques added,
+// - a search on the kernel with k less the fixed cliques as its target,
+// - the exact solver on kernels of at most exact_size vertices, which
+//   answers either way unless it runs out of nodes.
+// A yes comes with its cover as a certificate, a no with the lower bound
+// that rules k out; anything else is unknown, with the bounds found.
+
+use crate::cover::CliqueCover;
+use crate::error::VccError;
+use crate::exact::min_degree_independent_set_size;
+use crate::fractional::DEFAULT_MAX_CLIQUES;
+use crate::solve::SolveParams;
+use crate::Graph;
+use std::fmt;
+
+const INDEPENDENT_SET_TRIES: usize = 10;
+// The fractional cover's LP is dense, so only small graphs get it.
+const MAX_FRACTIONAL_SIZE: usize = 200;
+const LAGRANGIAN_ITERATIONS: usize = 200;
+
+#[derive(Clone, Copy)]
+pub struct Decide {
+  pub k: usize,
+  // Kernels up to this many vertices go to the exact solver...
+  pub exact_size: usize,
+  // ...with this many branch nodes.
+  pub max_nodes: usize,
+}
+
+pub enum Decision {
+  Yes(CliqueCover),
+  No {
+    lower_bound: usize,
+  },
+  Unknown {
+    lower_bound: usize,
+    upper_bound: usize,
+  },
+}
+
+impl fmt::Display for Decision {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Decision::Yes(cover) => write!(f, "yes: a cover with {} cliques", cover.len()),
+      Decision::No { lower_bound } => write!(f, "no: at least {} cliques needed", lower_bound),
+      Decision::Unknown {
+        lower_bound,
+        upper_bound,
+      } => write!(
+        f,
+        "unknown: between {} and {} cliques needed",
+        lower_bound, upper_bound
+      ),
+    }
+  }
+}
+
+impl Graph {
+  // The search runs with params, its target replaced. Works on copies, so
+  // the graph's search state is left as it was.
+  pub fn decide(&self, decide: &Decide, params: &SolveParams) -> Result<Decision, VccError> {
+    params.validate()?;
+    if !self.forbidden.is_empty() {
+      return Err(VccError::InvalidParameter(
+        "deciding doesn't support forbidden sets".to_string(),
+      ));
+    }
+    let k = decide.k;
+    let mut lower_bound = self.quick_lower_bound();
+    if lower_bound > k {
+      return Ok(Decision::No { lower_bound });
+    }
+    let mut best = self.dsatur_cover();
+    let sequential = self.clone().sequential_cover();
+    if sequential.len() < best.len() {
+      best = sequential;
+    }
+    if best.len() <= k {
+      return Ok(Decision::Yes(best));
+    }
+
+    let kernel = self.kernelize();
+    let fixed = kernel.fixed_cliques;
+    let mut g = kernel.graph.clone();
+    let kernel_lower_bound = g.quick_lower_bound();
+    lower_bound = lower_bound.max(fixed + kernel_lower_bound);
+    if g.size <= MAX_FRACTIONAL_SIZE {
+      let bound = g.fractional_cover(DEFAULT_MAX_CLIQUES).lower_bound();
+      lower_bound = lower_bound.max(fixed + bound.unwrap_or(0));
+    }
+    if lower_bound <= k {
+      let bound = g.lagrangian_bound(LAGRANGIAN_ITERATIONS).bound;
+      lower_bound = lower_bound.max(fixed + bound);
+    }
+    if lower_bound > k {
+      return Ok(Decision::No { lower_bound });
+    }
+    // k >= lower_bound >= fixed here.
+    let target = k - fixed;
+
+    let result = g.solve(&SolveParams { target, ..*params })?;
+    let lifted = kernel.lift(self, &result.best_cover);
+    if lifted.len() < best.len() {
+      best = lifted;
+    }
+    if best.len() <= k {
+      return Ok(Decision::Yes(best));
+    }
+
+    if g.size <= decide.exact_size {
+      let exact = g.exact_cover(decide.max_nodes);
+      let lifted = kernel.lift(self, &exact.cover);
+      if lifted.len() <= k {
+        return Ok(Decision::Yes(lifted));
+      }
+      if exact.optimal {
+        return Ok(Decision::No {
+          lower_bound: lifted.len(),
+        });
+      }
+      if lifted.len() < best.len() {
+        best = lifted;
+      }
+    }
+    Ok(Decision::Unknown {
+      lower_bound,
+      upper_bound: best.len(),
+    })
+  }
+
+  // The best of a few independent sets, or the optimum of a cograph.
+  fn quick_lower_bound(&self) -> usize {
+    if let Some(cover) = self.cograph_cover() {
+      return cover.len();
+    }
+    let mut g = self.clone();
+    let mut bound = min_degree_independent_set_size(self);
+    for _ in 0..INDEPENDENT_SET_TRIES {
+      bound = bound.max(g.greedy_independent_set_bound());
+    }
+    bound
+  }
+}
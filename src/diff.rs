@@ -0,0 +1,88 @@
+// Compact diffs between two covers of the same vertices, such as
+// successive best covers of a search: which vertices moved from which
+// clique to which. Cliques are matched by overlap the way
+// CliqueCover::distance matches them, and a vertex moved if its new
+// clique isn't its old clique's partner, so a diff moves exactly
+// distance vertices. Moves are run-length encoded: consecutive vertices
+// moving between the same two cliques make one run. Clique numbers index
+// each cover's own list, so canonicalize both covers first for diffs that
+// read the same however the search ordered its cliques.
+
+use crate::cover::CliqueCover;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+// Vertices first..=last moved from clique `from` of the old cover to
+// clique `to` of the new one.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct VertexRun {
+  pub first: usize,
+  pub last: usize,
+  pub from: usize,
+  pub to: usize,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CoverDiff {
+  pub before: usize,
+  pub after: usize,
+  // By increasing vertex.
+  pub runs: Vec<VertexRun>,
+}
+
+impl CoverDiff {
+  pub fn moved(&self) -> usize {
+    self.runs.iter().map(|run| run.last - run.first + 1).sum()
+  }
+}
+
+// "12 -> 11 cliques, 4 moved: 3-5:2>7 9:4>1"
+impl fmt::Display for CoverDiff {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "{} -> {} cliques, {} moved:",
+      self.before,
+      self.after,
+      self.moved()
+    )?;
+    for run in &self.runs {
+      if run.first == run.last {
+        write!(f, " {}:{}>{}", run.first, run.from, run.to)?;
+      } else {
+        write!(f, " {}-{}:{}>{}", run.first, run.last, run.from, run.to)?;
+      }
+    }
+    Ok(())
+  }
+}
+
+impl CliqueCover {
+  // Vertices in only one of the covers are left out.
+  pub fn diff(&self, next: &CliqueCover) -> CoverDiff {
+    let (partner, _) = self.matching(next);
+    let old = self.labels();
+    let new = next.labels();
+    let mut runs: Vec<VertexRun> = vec![];
+    for (v, (&from, &to)) in old.iter().zip(&new).enumerate() {
+      if from == usize::MAX || to == usize::MAX || partner[from] == Some(to) {
+        continue;
+      }
+      match runs.last_mut() {
+        Some(run) if run.last + 1 == v && run.from == from && run.to == to => run.last = v,
+        _ => runs.push(VertexRun {
+          first: v,
+          last: v,
+          from,
+          to,
+        }),
+      }
+    }
+    CoverDiff {
+      before: self.len(),
+      after: next.len(),
+      runs,
+    }
+  }
+}
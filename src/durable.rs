@@ -0,0 +1,107 @@
+// Crash-safe file writes, so that a crash, a kill or a full disk partway
+// through writing never leaves a truncated cover, checkpoint or report in
+// place of the last good one, which after a week-long run may be all there
+// is. Contents are written to a temporary file beside the target, then
+// renamed over it: renaming within a directory is atomic, so the target
+// holds either its old contents or the new, never part of them. If the
+// write fails the temporary file is removed and the target is untouched.
+//
+// Rename alone protects against the process dying, not the machine: until
+// the data reaches the disk a power loss can still leave an empty file.
+// How much is synced is a process-wide setting (the CLI's global --fsync,
+// given before the subcommand), since every write in a run wants the same
+// trade-off:
+//   none  No syncing; the operating system writes back when it likes.
+//   file  The temporary file's data is synced before the rename, so the
+//         target is never renamed to unwritten data. The default.
+//   full  The directory is synced after the rename as well, so the rename
+//         itself survives a power loss. Unix only; elsewhere as file.
+
+use crate::error::VccError;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+use std::process;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Fsync {
+  None,
+  #[default]
+  File,
+  Full,
+}
+
+// "none", "file" or "full"
+impl FromStr for Fsync {
+  type Err = VccError;
+
+  fn from_str(fsync_str: &str) -> Result<Fsync, VccError> {
+    match fsync_str {
+      "none" => Ok(Fsync::None),
+      "file" => Ok(Fsync::File),
+      "full" => Ok(Fsync::Full),
+      _ => Err(VccError::Parse(format!("bad fsync mode: {:?}", fsync_str))),
+    }
+  }
+}
+
+static FSYNC: AtomicU8 = AtomicU8::new(Fsync::File as u8);
+
+pub fn set_fsync(fsync: Fsync) {
+  FSYNC.store(fsync as u8, Ordering::Relaxed);
+}
+
+pub fn fsync() -> Fsync {
+  match FSYNC.load(Ordering::Relaxed) {
+    0 => Fsync::None,
+    1 => Fsync::File,
+    _ => Fsync::Full,
+  }
+}
+
+// Replaces path's contents with contents, atomically, syncing as set.
+pub fn write_atomic(path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> Result<(), VccError> {
+  let path = path.as_ref();
+  let name = path
+    .file_name()
+    .ok_or_else(|| VccError::InvalidParameter(format!("can't write {}", path.display())))?;
+  // Hidden, and unique to this process so concurrent runs don't collide.
+  let temp = path.with_file_name(format!(".{}.{}.tmp", name.to_string_lossy(), process::id()));
+  let fsync = fsync();
+  let written = write_synced(&temp, contents.as_ref(), fsync).and_then(|_| fs::rename(&temp, path));
+  if let Err(e) = written {
+    let _ = fs::remove_file(&temp);
+    return Err(e.into());
+  }
+  if fsync == Fsync::Full {
+    sync_dir(path)?;
+  }
+  Ok(())
+}
+
+fn write_synced(path: &Path, contents: &[u8], fsync: Fsync) -> std::io::Result<()> {
+  let mut file = File::create(path)?;
+  file.write_all(contents)?;
+  if fsync != Fsync::None {
+    file.sync_all()?;
+  }
+  Ok(())
+}
+
+// Syncs the directory holding path, making a rename into it durable.
+#[cfg(unix)]
+fn sync_dir(path: &Path) -> Result<(), VccError> {
+  let dir = match path.parent() {
+    Some(dir) if !dir.as_os_str().is_empty() => dir,
+    _ => Path::new("."),
+  };
+  File::open(dir)?.sync_all()?;
+  Ok(())
+}
+
+#[cfg(not(unix))]
+fn sync_dir(_path: &Path) -> Result<(), VccError> {
+  Ok(())
+}
@@ -0,0 +1,84 @@
+// Ejection: a directed attempt to remove the smallest clique. Each of its
+// members moves into another clique, either one that accepts it outright
+// or one where it conflicts with a single member, which is then ejected
+// into a third clique that accepts it outright. If any member can't be
+// placed the cover is left as it was.
+
+use crate::cover::CliqueCover;
+use crate::rng::{below, shuffle};
+use crate::strategy::SearchStrategy;
+use crate::Graph;
+
+impl Graph {
+  pub(crate) fn accepts(&self, members: &[usize], v: usize) -> bool {
+    members
+      .iter()
+      .all(|&u| self.topology.vertices[v].neighbors_bv.get_unchecked(u))
+  }
+
+  // The only member of `members` not adjacent to v, if there is exactly one.
+  pub(crate) fn sole_conflict(&self, members: &[usize], v: usize) -> Option<usize> {
+    let mut conflicts = members
+      .iter()
+      .enumerate()
+      .filter(|&(_, &u)| !self.topology.vertices[v].neighbors_bv.get_unchecked(u));
+    let first = conflicts.next()?;
+    match conflicts.next() {
+      Some(_) => None,
+      None => Some(first.0),
+    }
+  }
+
+  fn place_with_ejection(&self, cliques: &mut [Vec<usize>], v: usize) -> bool {
+    if let Some(c) = (0..cliques.len()).find(|&c| self.accepts(&cliques[c], v)) {
+      cliques[c].push(v);
+      return true;
+    }
+    for c in 0..cliques.len() {
+      let Some(i) = self.sole_conflict(&cliques[c], v) else {
+        continue;
+      };
+      let u = cliques[c][i];
+      if let Some(d) = (0..cliques.len()).find(|&d| d != c && self.accepts(&cliques[d], u)) {
+        cliques[c][i] = v;
+        cliques[d].push(u);
+        return true;
+      }
+    }
+    false
+  }
+
+  // Tries to dissolve a smallest clique (ties broken at random) into the
+  // others; returns whether it succeeded.
+  pub fn eject_smallest_clique(&mut self) -> bool {
+    let mut cliques = self.cover().cliques;
+    if cliques.len() < 2 {
+      return false;
+    }
+    shuffle(&mut self.rng, &mut cliques);
+    let smallest_len = cliques.iter().map(|c| c.len()).min().unwrap();
+    let smallest: Vec<usize> = (0..cliques.len())
+      .filter(|&c| cliques[c].len() == smallest_len)
+      .collect();
+    let dissolved = cliques.swap_remove(smallest[below(&mut self.rng, smallest.len())]);
+    for v in dissolved {
+      if !self.place_with_ejection(&mut cliques, v) {
+        return false;
+      }
+    }
+    self.load_cover_unchecked(&CliqueCover { cliques });
+    true
+  }
+}
+
+pub struct Ejection;
+
+impl SearchStrategy for Ejection {
+  fn name(&self) -> &'static str {
+    "ejection"
+  }
+
+  fn step(&mut self, g: &mut Graph) {
+    g.eject_smallest_clique();
+  }
+}
@@ -0,0 +1,69 @@
+use alloc::string::String;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io;
+
+#[derive(Debug)]
+pub enum VccError {
+  #[cfg(feature = "std")]
+  Io(io::Error),
+  // Malformed input: command-line values, manifests, checkpoints.
+  Parse(String),
+  // Vertex ids out of range, self-loops, and the like.
+  InvalidGraph(String),
+  // A cover that isn't a partition of the vertices into cliques.
+  InvalidCover(String),
+  // Solver or generator parameters that can't be run.
+  InvalidParameter(String),
+}
+
+impl fmt::Display for VccError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      #[cfg(feature = "std")]
+      VccError::Io(e) => write!(f, "io error: {}", e),
+      VccError::Parse(msg) => write!(f, "parse error: {}", msg),
+      VccError::InvalidGraph(msg) => write!(f, "invalid graph: {}", msg),
+      VccError::InvalidCover(msg) => write!(f, "invalid cover: {}", msg),
+      VccError::InvalidParameter(msg) => write!(f, "invalid parameter: {}", msg),
+    }
+  }
+}
+
+impl core::error::Error for VccError {
+  fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+    match self {
+      #[cfg(feature = "std")]
+      VccError::Io(e) => Some(e),
+      _ => None,
+    }
+  }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for VccError {
+  fn from(e: io::Error) -> VccError {
+    VccError::Io(e)
+  }
+}
+
+#[cfg(feature = "std")]
+impl From<toml::de::Error> for VccError {
+  fn from(e: toml::de::Error) -> VccError {
+    VccError::Parse(e.to_string())
+  }
+}
+
+#[cfg(feature = "std")]
+impl From<serde_json::Error> for VccError {
+  fn from(e: serde_json::Error) -> VccError {
+    VccError::Parse(e.to_string())
+  }
+}
+
+#[cfg(feature = "std")]
+impl From<rayon::ThreadPoolBuildError> for VccError {
+  fn from(e: rayon::ThreadPoolBuildError) -> VccError {
+    VccError::InvalidParameter(e.to_string())
+  }
+}
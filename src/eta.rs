@@ -0,0 +1,149 @@
+// How long a run of restarts will likely take to reach its target, from
+// the cover sizes its restarts have ended with so far, for deciding
+// whether a long run is worth keeping alive. Restarts are independent,
+// each seeded on its own, so each reaches the target with the same chance
+// p, and the chance that one of the next n does is 1 - (1 - p)^n.
+//
+// Once some restarts have reached the target, p is the fraction that did.
+// Before then it is extrapolated from the lower tail of the restarts'
+// results, assuming it is geometric: that each clique fewer is reached by
+// a constant fraction of the restarts reaching the size above, as fitted
+// by least squares to the logs of the fractions ending at or below the
+// (up to three) smallest sizes seen. That is a rough model; read an
+// extrapolated estimate as an order of magnitude. If every restart ended
+// at the same size there is no tail to fit and no estimate.
+
+use std::fmt;
+use std::time::Duration;
+
+pub struct Estimate {
+  pub target: usize,
+  pub restarts: usize,
+  // Restarts that reached the target.
+  pub reached: usize,
+  // The chance that one restart reaches the target, if it can be told.
+  pub per_restart: Option<f64>,
+  pub extrapolated: bool,
+  pub elapsed: Duration,
+}
+
+impl Estimate {
+  // From the cover size each restart ended with, in elapsed time.
+  pub fn new(cliques: &[usize], target: usize, elapsed: Duration) -> Estimate {
+    let restarts = cliques.len();
+    let reached = cliques.iter().filter(|&&c| c <= target).count();
+    let extrapolated = reached == 0;
+    let per_restart = if reached > 0 {
+      Some(reached as f64 / restarts as f64)
+    } else {
+      geometric_tail(cliques, target)
+    };
+    Estimate {
+      target,
+      restarts,
+      reached,
+      per_restart,
+      extrapolated,
+      elapsed,
+    }
+  }
+
+  // The mean time a restart took.
+  pub fn restart_time(&self) -> Duration {
+    self.elapsed.div_f64(self.restarts.max(1) as f64)
+  }
+
+  // Restarts per second.
+  pub fn throughput(&self) -> f64 {
+    self.restarts as f64 / self.elapsed.as_secs_f64()
+  }
+
+  // The chance that one of the next n restarts reaches the target.
+  pub fn chance_within(&self, n: usize) -> Option<f64> {
+    let p = self.per_restart?;
+    Some(1.0 - (1.0 - p).powi(n.min(i32::MAX as usize) as i32))
+  }
+
+  // The fewest restarts that reach the target with the given chance.
+  pub fn restarts_for(&self, chance: f64) -> Option<usize> {
+    let p = self.per_restart.filter(|&p| p > 0.0)?;
+    if p >= 1.0 {
+      return Some(1);
+    }
+    Some(((1.0 - chance).ln() / (1.0 - p).ln()).ceil().max(1.0) as usize)
+  }
+
+  // How long those restarts take, at the mean time per restart so far.
+  pub fn time_for(&self, chance: f64) -> Option<Duration> {
+    let n = self.restarts_for(chance)?;
+    Some(self.restart_time().mul_f64(n as f64))
+  }
+}
+
+// The fitted chance of reaching target, below every size in cliques.
+fn geometric_tail(cliques: &[usize], target: usize) -> Option<f64> {
+  let mut sizes = cliques.to_vec();
+  sizes.sort_unstable();
+  sizes.dedup();
+  if sizes.len() < 2 {
+    return None;
+  }
+  // (size, ln of the fraction ending at or below it)
+  let points: Vec<(f64, f64)> = sizes
+    .iter()
+    .take(3)
+    .map(|&size| {
+      let at_most = cliques.iter().filter(|&&c| c <= size).count();
+      (size as f64, (at_most as f64 / cliques.len() as f64).ln())
+    })
+    .collect();
+  let n = points.len() as f64;
+  let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+  let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+  let sxy: f64 = points.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum();
+  let sxx: f64 = points.iter().map(|p| (p.0 - mean_x) * (p.0 - mean_x)).sum();
+  let slope = sxy / sxx;
+  Some((mean_y + slope * (target as f64 - mean_x)).exp().min(1.0))
+}
+
+// "40 restarts in 12.5s (3.20/s), none reached 10; 1 in 125 restarts
+// (extrapolated), 50% within 87 restarts (~27.2s), 95% within 373
+// (~116.6s)"
+impl fmt::Display for Estimate {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "{} restarts in {:.1?} ({:.2}/s), ",
+      self.restarts,
+      self.elapsed,
+      self.throughput()
+    )?;
+    match self.reached {
+      0 => write!(f, "none reached {}; ", self.target)?,
+      reached => write!(f, "{} reached {}; ", reached, self.target)?,
+    }
+    let Some(p) = self.per_restart else {
+      return write!(f, "no estimate before two restart results differ");
+    };
+    // Small chances read better as odds.
+    if p >= 0.01 {
+      write!(f, "{:.1}% per restart", 100.0 * p)?;
+    } else {
+      write!(f, "1 in {:.0} restarts", 1.0 / p)?;
+    }
+    if self.extrapolated {
+      write!(f, " (extrapolated)")?;
+    }
+    if let (Some(half), Some(most)) = (self.restarts_for(0.5), self.restarts_for(0.95)) {
+      write!(
+        f,
+        ", 50% within {} restarts (~{:.1?}), 95% within {} (~{:.1?})",
+        half,
+        self.time_for(0.5).unwrap_or_default(),
+        most,
+        self.time_for(0.95).unwrap_or_default()
+      )?;
+    }
+    Ok(())
+  }
+}
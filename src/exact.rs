@@ -0,0 +1,159 @@
+// Exact clique covers of small graphs by branch and bound. Vertices are
+// placed one at a time, most constrained first (DSATUR on the complement):
+// each goes into every clique that can take it, then into a new clique if
+// that could still beat the best cover found. The search stops as soon as
+// the best cover matches a greedy independent set, which is a lower bound.
+
+use crate::bitset::BitVec;
+use crate::clock::Instant;
+use crate::cover::CliqueCover;
+use crate::Graph;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::time::Duration;
+
+pub struct ExactResult {
+  pub cover: CliqueCover,
+  // False if the node or time budget ran out before optimality was proven;
+  // the cover is then the best one found.
+  pub optimal: bool,
+  pub nodes: usize,
+}
+
+struct Search<'a> {
+  g: &'a Graph,
+  placed: Vec<bool>,
+  // Members of each open clique, and the vertices that could still join it.
+  members: Vec<Vec<usize>>,
+  joinable: Vec<BitVec>,
+  best: Vec<Vec<usize>>,
+  lower_bound: usize,
+  nodes: usize,
+  max_nodes: usize,
+  deadline: Option<Instant>,
+  exhausted: bool,
+}
+
+impl Search<'_> {
+  // The unplaced vertex that fits the fewest open cliques, preferring
+  // vertices with fewer neighbors.
+  fn most_constrained(&self) -> usize {
+    (0..self.g.size)
+      .filter(|&v| !self.placed[v])
+      .max_by_key(|&v| {
+        let blocked = self.joinable.iter().filter(|j| !j.get_unchecked(v)).count();
+        (blocked, self.g.size - self.g.degree(v))
+      })
+      .unwrap()
+  }
+
+  // Returns true once the search should stop.
+  fn branch(&mut self, placed_ct: usize) -> bool {
+    if self.members.len() >= self.best.len() {
+      return false;
+    }
+    if placed_ct == self.g.size {
+      self.best = self.members.clone();
+      return self.best.len() <= self.lower_bound;
+    }
+    self.nodes += 1;
+    if self.nodes > self.max_nodes || self.deadline.is_some_and(|d| Instant::now() >= d) {
+      self.exhausted = true;
+      return true;
+    }
+
+    let v = self.most_constrained();
+    self.placed[v] = true;
+    for c in 0..self.members.len() {
+      if !self.joinable[c].get_unchecked(v) {
+        continue;
+      }
+      let saved = self.joinable[c].clone();
+      self.joinable[c].and_inplace(&self.g.topology.vertices[v].neighbors_bv);
+      self.members[c].push(v);
+      let stop = self.branch(placed_ct + 1);
+      self.members[c].pop();
+      self.joinable[c] = saved;
+      if stop {
+        return true;
+      }
+    }
+    if self.members.len() + 1 < self.best.len() {
+      self.members.push(vec![v]);
+      self
+        .joinable
+        .push(self.g.topology.vertices[v].neighbors_bv.clone());
+      let stop = self.branch(placed_ct + 1);
+      self.members.pop();
+      self.joinable.pop();
+      if stop {
+        return true;
+      }
+    }
+    self.placed[v] = false;
+    false
+  }
+}
+
+// Repeatedly takes the vertex with the fewest remaining neighbors and
+// drops those neighbors.
+pub(crate) fn min_degree_independent_set(g: &Graph) -> Vec<usize> {
+  let mut alive = vec![true; g.size];
+  let mut chosen = vec![];
+  while let Some(v) = (0..g.size).filter(|&v| alive[v]).min_by_key(|&v| {
+    (0..g.size)
+      .filter(|&u| alive[u] && g.topology.vertices[v].neighbors_bv.get_unchecked(u))
+      .count()
+  }) {
+    chosen.push(v);
+    alive[v] = false;
+    for (u, is_alive) in alive.iter_mut().enumerate() {
+      if g.topology.vertices[v].neighbors_bv.get_unchecked(u) {
+        *is_alive = false;
+      }
+    }
+  }
+  chosen
+}
+
+pub(crate) fn min_degree_independent_set_size(g: &Graph) -> usize {
+  min_degree_independent_set(g).len()
+}
+
+impl Graph {
+  // Exponential in the worst case; meant for graphs of a few dozen vertices.
+  // Gives up after max_nodes branch nodes.
+  pub fn exact_cover(&self, max_nodes: usize) -> ExactResult {
+    self.exact_cover_until(max_nodes, None)
+  }
+
+  // Also gives up once time_limit has passed.
+  pub fn exact_cover_timed(&self, max_nodes: usize, time_limit: Duration) -> ExactResult {
+    self.exact_cover_until(max_nodes, Some(Instant::now() + time_limit))
+  }
+
+  fn exact_cover_until(&self, max_nodes: usize, deadline: Option<Instant>) -> ExactResult {
+    let mut search = Search {
+      g: self,
+      placed: vec![false; self.size],
+      members: vec![],
+      joinable: vec![],
+      // Singletons, plus one so that a cover of equal size is recorded.
+      best: (0..self.size).map(|v| vec![v]).collect(),
+      lower_bound: min_degree_independent_set_size(self),
+      nodes: 0,
+      max_nodes,
+      deadline,
+      exhausted: false,
+    };
+    search.best.push(vec![]);
+    search.branch(0);
+    let mut cliques = search.best;
+    cliques.retain(|members| !members.is_empty());
+    ExactResult {
+      cover: CliqueCover { cliques },
+      optimal: !search.exhausted,
+      nodes: search.nodes,
+    }
+  }
+}
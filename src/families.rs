@@ -0,0 +1,168 @@
+// Standard graph families with known clique cover numbers, for tests,
+// the selftest subcommand and experiments. The clique cover number of a
+// graph is the chromatic number of its complement, so complements of the
+// DIMACS coloring graphs (myciel, queen) have known answers too.
+//
+// The strongly regular families below are highly symmetric, with many
+// optimal covers equivalent under their automorphisms, which makes them
+// hard on a search that can't tell the equivalent covers apart.
+
+use crate::error::VccError;
+use crate::Graph;
+
+fn from_edges(n: usize, edges: impl IntoIterator<Item = (usize, usize)>) -> Graph {
+  let mut g = Graph::new(n);
+  for (u, v) in edges {
+    g.link(u, v);
+  }
+  g.conform_cliques_to_vertices();
+  g
+}
+
+// Clique cover number 1.
+pub fn complete(n: usize) -> Graph {
+  from_edges(n, (0..n).flat_map(|u| ((u + 1)..n).map(move |v| (u, v))))
+}
+
+// Clique cover number ceil(n / 2) for n >= 4.
+pub fn cycle(n: usize) -> Graph {
+  from_edges(n, (0..n).map(|v| (v, (v + 1) % n)))
+}
+
+// Clique cover number ceil(n / 2).
+pub fn path(n: usize) -> Graph {
+  from_edges(n, (1..n).map(|v| (v - 1, v)))
+}
+
+// Clique cover number max(a, b).
+pub fn complete_bipartite(a: usize, b: usize) -> Graph {
+  from_edges(
+    a + b,
+    (0..a).flat_map(|u| (a..(a + b)).map(move |v| (u, v))),
+  )
+}
+
+// Clique cover number 5; its complement's is 3.
+pub fn petersen() -> Graph {
+  from_edges(
+    10,
+    (0..5).flat_map(|i| [(i, (i + 1) % 5), (i, i + 5), (i + 5, (i + 2) % 5 + 5)]),
+  )
+}
+
+// Mycielski's construction, starting from K2: mycielski(2) is C5, and
+// mycielski(3) and mycielski(4) are the DIMACS graphs myciel3 and myciel4.
+// The complement of mycielski(k) has clique cover number k + 1.
+pub fn mycielski(k: usize) -> Graph {
+  let mut n = 2;
+  let mut edges = vec![(0, 1)];
+  for _ in 1..k {
+    let mut next = edges.clone();
+    for &(u, v) in &edges {
+      next.push((u, n + v));
+      next.push((n + u, v));
+    }
+    next.extend((0..n).map(|v| (n + v, 2 * n)));
+    edges = next;
+    n = 2 * n + 1;
+  }
+  from_edges(n, edges)
+}
+
+// The DIMACS queen graphs: cells of an n x n board, adjacent if a queen on
+// one attacks the other. For n = 5 and n = 7 the complement has clique
+// cover number n.
+pub fn queen(n: usize) -> Graph {
+  queen_board(n, n)
+}
+
+// The queen graph of a rows x cols board, such as queen8_12's.
+pub fn queen_board(rows: usize, cols: usize) -> Graph {
+  let cells = rows * cols;
+  let attacks = |a: usize, b: usize| {
+    let (ra, ca, rb, cb) = (a / cols, a % cols, b / cols, b % cols);
+    ra == rb || ca == cb || ra + cb == rb + ca || ra + ca == rb + cb
+  };
+  from_edges(
+    cells,
+    (0..cells).flat_map(move |a| {
+      ((a + 1)..cells)
+        .filter(move |&b| attacks(a, b))
+        .map(move |b| (a, b))
+    }),
+  )
+}
+
+// k disjoint copies of K_size; clique cover number k.
+pub fn disjoint_cliques(k: usize, size: usize) -> Graph {
+  from_edges(
+    k * size,
+    (0..(k * size)).flat_map(|u| ((u + 1)..((u / size + 1) * size)).map(move |v| (u, v))),
+  )
+}
+
+// The complement, with its cliques reset to singletons.
+pub fn complement(g: &Graph) -> Graph {
+  let mut g = g.complement();
+  g.conform_cliques_to_vertices();
+  g
+}
+
+// The Paley graph of a prime p = 1 mod 4: vertices 0..p, adjacent if their
+// difference is a nonzero square mod p. Strongly regular with parameters
+// (p, (p - 1) / 2, (p - 5) / 4, (p - 1) / 4) and self-complementary, so
+// its clique and independence numbers are equal; paley(5) is C5, and
+// paley(13) has clique cover number 5. Prime powers need field arithmetic
+// beyond integers mod p and aren't supported.
+pub fn paley(p: usize) -> Result<Graph, VccError> {
+  if p % 4 != 1
+    || !(2..p)
+      .take_while(|d| d * d <= p)
+      .all(|d| !p.is_multiple_of(d))
+  {
+    return Err(VccError::InvalidParameter(format!(
+      "{} is not a prime that is 1 mod 4",
+      p
+    )));
+  }
+  let mut square = vec![false; p];
+  for x in 1..p {
+    square[x * x % p] = true;
+  }
+  let square = &square;
+  Ok(from_edges(
+    p,
+    (0..p).flat_map(|u| {
+      ((u + 1)..p)
+        .filter(move |&v| square[v - u])
+        .map(move |v| (u, v))
+    }),
+  ))
+}
+
+// The triangular graph T(n), the line graph of K_n: the pairs of 0..n,
+// adjacent if they share an element. Strongly regular with parameters
+// (n(n - 1) / 2, 2(n - 2), n - 2, 4). Its complement is the Kneser graph
+// K(n, 2), of chromatic number n - 2, so for n >= 4 its clique cover
+// number is n - 2; T(5) is the complement of the Petersen graph.
+pub fn triangular(n: usize) -> Graph {
+  let pairs: Vec<(usize, usize)> = (0..n)
+    .flat_map(|a| ((a + 1)..n).map(move |b| (a, b)))
+    .collect();
+  let mut edges = vec![];
+  for (i, &(a, b)) in pairs.iter().enumerate() {
+    for (j, &(c, d)) in pairs.iter().enumerate().skip(i + 1) {
+      if a == c || a == d || b == c || b == d {
+        edges.push((i, j));
+      }
+    }
+  }
+  from_edges(pairs.len(), edges)
+}
+
+// The rook's graph on an n x n board, K_n x K_n: cells adjacent if in the
+// same row or column. Strongly regular with parameters
+// (n^2, 2(n - 1), n - 2, 2); clique cover number n, its rows.
+pub fn rook(n: usize) -> Graph {
+  complete(n).cartesian_product(&complete(n))
+}
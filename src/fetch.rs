@@ -0,0 +1,182 @@
+// A local cache of the DIMACS graph coloring benchmarks, whose complements
+// are the standard clique cover instances (see families.rs). Files are
+// downloaded with curl, which saves the crate an HTTP client, into an
+// instance directory, alongside a checksums.txt of "<checksum> <file>"
+// lines. The standard instances ship with what their graphs must be, their
+// entries in known.rs: vertex and edge counts, and the adjacency hash where
+// the graph can be rebuilt here. Those are of the graph rather than the
+// file, so mirrors whose comments or edge order differ all pass, and a
+// download that isn't the benchmark is rejected before it is cached.
+// Any file's checksum is then recorded, or may be listed beforehand, say
+// from a colleague's cache, and a later fetch is checked against it, so an
+// instance that changed on disk is caught rather than solved. Instances
+// outside the standard set are only checked against that record, which
+// their first download sets. The checksum is 64-bit FNV-1a: it catches
+// corruption, not tampering.
+
+use crate::durable::write_atomic;
+use crate::error::VccError;
+use crate::formats::GraphFormat;
+use crate::known::{KnownInstance, KNOWN_INSTANCES};
+use crate::Graph;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+pub const DEFAULT_BASE_URL: &str = "https://mat.tepper.cmu.edu/COLOR/instances";
+
+// The instances fetched when none are named, each with the benchmark its
+// file has to hold.
+pub const COLORING_INSTANCES: [(&str, &KnownInstance); 25] = [
+  ("myciel3.col", &KNOWN_INSTANCES[0]),
+  ("myciel4.col", &KNOWN_INSTANCES[1]),
+  ("myciel5.col", &KNOWN_INSTANCES[2]),
+  ("myciel6.col", &KNOWN_INSTANCES[3]),
+  ("myciel7.col", &KNOWN_INSTANCES[4]),
+  ("queen5_5.col", &KNOWN_INSTANCES[5]),
+  ("queen6_6.col", &KNOWN_INSTANCES[6]),
+  ("queen7_7.col", &KNOWN_INSTANCES[7]),
+  ("queen8_8.col", &KNOWN_INSTANCES[8]),
+  ("queen8_12.col", &KNOWN_INSTANCES[9]),
+  ("queen9_9.col", &KNOWN_INSTANCES[10]),
+  ("anna.col", &KNOWN_INSTANCES[11]),
+  ("david.col", &KNOWN_INSTANCES[12]),
+  ("homer.col", &KNOWN_INSTANCES[13]),
+  ("huck.col", &KNOWN_INSTANCES[14]),
+  ("jean.col", &KNOWN_INSTANCES[15]),
+  ("games120.col", &KNOWN_INSTANCES[16]),
+  ("miles250.col", &KNOWN_INSTANCES[17]),
+  ("miles500.col", &KNOWN_INSTANCES[18]),
+  ("DSJC125.1.col", &KNOWN_INSTANCES[19]),
+  ("DSJC125.5.col", &KNOWN_INSTANCES[20]),
+  ("DSJC125.9.col", &KNOWN_INSTANCES[21]),
+  ("le450_5a.col", &KNOWN_INSTANCES[22]),
+  ("le450_15a.col", &KNOWN_INSTANCES[23]),
+  ("le450_25a.col", &KNOWN_INSTANCES[24]),
+];
+
+const CHECKSUMS: &str = "checksums.txt";
+
+pub fn checksum(bytes: &[u8]) -> u64 {
+  bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| {
+    (hash ^ b as u64).wrapping_mul(0x0100_0000_01b3)
+  })
+}
+
+pub struct InstanceCache {
+  dir: PathBuf,
+  // By file name.
+  checksums: BTreeMap<String, u64>,
+}
+
+impl InstanceCache {
+  // Creates the directory if need be.
+  pub fn open(dir: impl AsRef<Path>) -> Result<InstanceCache, VccError> {
+    let dir = dir.as_ref().to_path_buf();
+    fs::create_dir_all(&dir)?;
+    let mut checksums = BTreeMap::new();
+    let path = dir.join(CHECKSUMS);
+    if path.exists() {
+      for line in fs::read_to_string(&path)?.lines() {
+        let bad = || VccError::Parse(format!("bad checksum line: {:?}", line));
+        let (sum, name) = line.split_once(' ').ok_or_else(bad)?;
+        let sum = u64::from_str_radix(sum, 16).map_err(|_| bad())?;
+        checksums.insert(name.to_string(), sum);
+      }
+    }
+    Ok(InstanceCache { dir, checksums })
+  }
+
+  // The path of the named instance, downloaded from base_url if it isn't
+  // cached yet, after checking its checksum.
+  pub fn fetch(&mut self, name: &str, base_url: &str) -> Result<PathBuf, VccError> {
+    if name.contains(['/', '\\']) || name.starts_with('.') || name == CHECKSUMS {
+      return Err(VccError::InvalidParameter(format!(
+        "bad instance name: {:?}",
+        name
+      )));
+    }
+    let path = self.dir.join(name);
+    if !path.exists() {
+      let partial = self.dir.join(format!(".{}.part", name));
+      let url = format!("{}/{}", base_url.trim_end_matches('/'), name);
+      let status = Command::new("curl")
+        .args(["-fsSL", "-o"])
+        .arg(&partial)
+        .arg(&url)
+        .status()?;
+      if !status.success() {
+        let _ = fs::remove_file(&partial);
+        return Err(VccError::InvalidParameter(format!(
+          "downloading {} failed: {}",
+          url, status
+        )));
+      }
+      if let Err(e) = check_expected(name, &partial).and_then(|_| self.check(name, &partial)) {
+        let _ = fs::remove_file(&partial);
+        return Err(e);
+      }
+      fs::rename(&partial, &path)?;
+      return Ok(path);
+    }
+    check_expected(name, &path)?;
+    self.check(name, &path)?;
+    Ok(path)
+  }
+
+  // Checks path's contents against name's checksum, recording it if there
+  // is none yet.
+  fn check(&mut self, name: &str, path: &Path) -> Result<(), VccError> {
+    let sum = checksum(&fs::read(path)?);
+    match self.checksums.get(name) {
+      Some(&expected) if expected != sum => Err(VccError::InvalidGraph(format!(
+        "{} has checksum {:016x}, not {:016x}",
+        path.display(),
+        sum,
+        expected
+      ))),
+      Some(_) => Ok(()),
+      None => {
+        self.checksums.insert(name.to_string(), sum);
+        self.save()
+      }
+    }
+  }
+
+  fn save(&self) -> Result<(), VccError> {
+    let mut lines = String::new();
+    for (name, sum) in &self.checksums {
+      writeln!(lines, "{:016x} {}", sum, name).unwrap();
+    }
+    write_atomic(self.dir.join(CHECKSUMS), lines)
+  }
+}
+
+// Checks a standard instance's file against the benchmark it has to hold.
+fn check_expected(name: &str, path: &Path) -> Result<(), VccError> {
+  let Some(&(_, expected)) = COLORING_INSTANCES.iter().find(|(file, _)| *file == name) else {
+    return Ok(());
+  };
+  let g = Graph::parse(&fs::read_to_string(path)?, GraphFormat::Dimacs)?;
+  if (g.size(), g.edge_count()) != (expected.vertices, expected.edges) {
+    return Err(VccError::InvalidGraph(format!(
+      "{} has {} vertices and {} edges, not {}'s {} and {}",
+      path.display(),
+      g.size(),
+      g.edge_count(),
+      expected.name,
+      expected.vertices,
+      expected.edges
+    )));
+  }
+  if expected.hash.is_some_and(|hash| g.adjacency_hash() != hash) {
+    return Err(VccError::InvalidGraph(format!(
+      "{} has {}'s counts but not its edges",
+      path.display(),
+      expected.name
+    )));
+  }
+  Ok(())
+}
@@ -0,0 +1,146 @@
+// Exact finishing: once a restart has stalled within a clique or two of
+// the lower bound, the last cliques are often stuck behind a handful of
+// vertices. The smallest cliques, together with the cliques most tightly
+// connected to them, are cut out as a residual subgraph of at most
+// max_vertices vertices and solved exactly under a node and time budget.
+// If the exact cover of the residual needs fewer cliques than were cut
+// out, it is spliced back in place of them.
+
+use crate::cover::CliqueCover;
+use crate::error::VccError;
+use crate::shared::SharedBounds;
+use crate::Graph;
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::str::FromStr;
+use core::time::Duration;
+use serde::{Deserialize, Serialize};
+
+// Fields missing from a serialized config take their default values.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExactFinish {
+  // Only tried when the cover is at most this many cliques above the
+  // lower bound.
+  pub gap: usize,
+  pub max_vertices: usize,
+  pub max_nodes: usize,
+  pub time_limit_ms: u64,
+}
+
+impl Default for ExactFinish {
+  fn default() -> ExactFinish {
+    ExactFinish {
+      gap: 2,
+      max_vertices: 40,
+      max_nodes: 100_000,
+      time_limit_ms: 100,
+    }
+  }
+}
+
+// "on" for the defaults, or "<gap>:<max_vertices>:<milliseconds>"
+impl FromStr for ExactFinish {
+  type Err = VccError;
+
+  fn from_str(finish_str: &str) -> Result<ExactFinish, VccError> {
+    if finish_str == "on" {
+      return Ok(ExactFinish::default());
+    }
+    let bad = || VccError::Parse(format!("bad exact finish: {:?}", finish_str));
+    let parts: Vec<&str> = finish_str.split(':').collect();
+    let [gap, max_vertices, time_limit_ms] = parts[..] else {
+      return Err(bad());
+    };
+    Ok(ExactFinish {
+      gap: gap.parse().map_err(|_| bad())?,
+      max_vertices: max_vertices.replace('_', "").parse().map_err(|_| bad())?,
+      time_limit_ms: time_limit_ms.replace('_', "").parse().map_err(|_| bad())?,
+      ..ExactFinish::default()
+    })
+  }
+}
+
+impl Graph {
+  // Tries one exact finish on the current cover; returns whether it found
+  // a smaller cover (which is then loaded).
+  pub fn exact_finish(&mut self, finish: &ExactFinish) -> bool {
+    let mut cliques = self.cover().cliques;
+    cliques.sort_by_key(|c| c.len());
+    // The two smallest cliques seed the residual ...
+    let mut residual: Vec<usize> = vec![];
+    let mut taken = 0;
+    while taken < cliques.len().min(2)
+      && residual.len() + cliques[taken].len() <= finish.max_vertices
+    {
+      residual.extend(&cliques[taken]);
+      taken += 1;
+    }
+    if taken < 2 {
+      return false;
+    }
+    // ... and the other cliques join it by their share of members adjacent
+    // to the seed, most connected first, while they fit.
+    let seed = residual.clone();
+    let connectivity = |members: &Vec<usize>| {
+      let adjacent = members
+        .iter()
+        .filter(|&&v| {
+          seed
+            .iter()
+            .any(|&u| self.topology.vertices[v].neighbors_bv.get_unchecked(u))
+        })
+        .count();
+      adjacent as f64 / members.len() as f64
+    };
+    let mut rest = cliques.split_off(taken);
+    rest.sort_by(|a, b| connectivity(b).total_cmp(&connectivity(a)));
+    let mut kept = vec![];
+    for members in rest {
+      if residual.len() + members.len() <= finish.max_vertices {
+        residual.extend(&members);
+        taken += 1;
+      } else {
+        kept.push(members);
+      }
+    }
+
+    let exact = self.induced_subgraph(&residual).exact_cover_timed(
+      finish.max_nodes,
+      Duration::from_millis(finish.time_limit_ms),
+    );
+    if exact.cover.len() >= taken {
+      return false;
+    }
+    let spliced: Vec<Vec<usize>> = exact
+      .cover
+      .cliques
+      .iter()
+      .map(|members| members.iter().map(|&i| residual[i]).collect())
+      .collect();
+    if !self.keeps_limits(&spliced) {
+      return false;
+    }
+    kept.extend(spliced);
+    self.load_cover_unchecked(&CliqueCover { cliques: kept });
+    true
+  }
+
+  // Called when a restart stalls: tries an exact finish if the cover is
+  // close enough to the lower bound, computing that bound on first use.
+  pub(crate) fn try_exact_finish(
+    &mut self,
+    finish: &ExactFinish,
+    bounds: Option<&SharedBounds>,
+    lower_bound: &mut Option<usize>,
+  ) -> bool {
+    let lower = *lower_bound.get_or_insert_with(|| {
+      (0..10)
+        .map(|_| self.greedy_independent_set_bound())
+        .max()
+        .unwrap_or(0)
+    });
+    let lower = lower.max(bounds.map_or(0, |b| b.lower()));
+    self.cliques_ct <= lower + finish.gap && self.exact_finish(finish)
+  }
+}
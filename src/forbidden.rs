@@ -0,0 +1,95 @@
+// Forbidden cliques: sets of vertices that may not all end up in one
+// clique, beyond what non-adjacency already rules out. Each set comes with
+// the most of its members one clique may hold; a plain forbidden set allows
+// all but one, and "at most two of these five" is the same set allowing
+// two. The transfer routines move only as many of a set's vertices into a
+// clique as it still has room for, so the restart search never breaks a
+// limit, and neither do the covers it starts from or splices in: an
+// initial cover or exact finish that would is passed over. Other
+// strategies don't know about the limits; validate_cover catches their
+// covers if they break one.
+
+use crate::bitset::BitVec;
+use crate::error::VccError;
+use crate::Graph;
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+#[derive(Clone)]
+pub struct ForbiddenSet {
+  pub members: Vec<usize>,
+  // The most members one clique may hold, at least one.
+  pub max_together: usize,
+}
+
+impl Graph {
+  // Replaces the graph's forbidden sets. Singleton cliques keep any limits,
+  // but the current cover may not: restart before searching.
+  pub fn set_forbidden(&mut self, sets: Vec<ForbiddenSet>) -> Result<(), VccError> {
+    for set in &sets {
+      if let Some(&v) = set.members.iter().find(|&&v| v >= self.size) {
+        return Err(VccError::InvalidParameter(format!(
+          "forbidden set with vertex {} in a graph with {} vertices",
+          v, self.size
+        )));
+      }
+      let mut members = set.members.clone();
+      members.sort_unstable();
+      if members.windows(2).any(|pair| pair[0] == pair[1]) {
+        return Err(VccError::InvalidParameter(format!(
+          "forbidden set {:?} repeats a vertex",
+          set.members
+        )));
+      }
+      if set.max_together == 0 {
+        return Err(VccError::InvalidParameter(
+          "a forbidden set must allow at least one member per clique".to_string(),
+        ));
+      }
+    }
+    self.forbidden = sets;
+    Ok(())
+  }
+
+  // The first forbidden set that members hold too many of.
+  pub(crate) fn broken_limit(&self, members: &[usize]) -> Option<&ForbiddenSet> {
+    self.forbidden.iter().find(|set| {
+      let together = set.members.iter().filter(|v| members.contains(v)).count();
+      together > set.max_together
+    })
+  }
+
+  pub(crate) fn keeps_limits(&self, cliques: &[Vec<usize>]) -> bool {
+    cliques
+      .iter()
+      .all(|members| self.broken_limit(members).is_none())
+  }
+}
+
+// Clears moving vertices (set in utility_bv) past each set's room in the
+// clique whose members are members_bv.
+pub(crate) fn trim_to_limits(
+  forbidden: &[ForbiddenSet],
+  members_bv: &BitVec,
+  utility_bv: &mut BitVec,
+) {
+  for set in forbidden {
+    let mut room = set.max_together.saturating_sub(
+      set
+        .members
+        .iter()
+        .filter(|&&v| members_bv.get_unchecked(v))
+        .count(),
+    );
+    for &v in &set.members {
+      if utility_bv.get_unchecked(v) {
+        if room == 0 {
+          utility_bv.set(v, false);
+        } else {
+          room -= 1;
+        }
+      }
+    }
+  }
+}
@@ -0,0 +1,438 @@
+// Graph file formats, picked by file extension:
+//   .col, .clq, .dimacs  DIMACS: a "p edge <vertices> <edges>" line, then
+//                        "e u v" per edge with vertices from 1; "c" lines
+//                        are comments.
+//   .g6                  graph6, as nauty writes it; only a file's first
+//                        graph is read.
+// Repeated edges collapse into one. The edge count of a DIMACS problem
+// line isn't checked, since plenty of published instances get it wrong.
+//
+// DIMACS files may also carry weights, as the weighted clique instances
+// do: "n v w" lines weigh vertices and "e u v w" lines weigh edges. The
+// search doesn't use them; parse_weighted hands them to callers that do.
+//
+// Directed DIMACS files list arcs, "a u v" with an optional weight, and
+// the caller picks how they become edges (see Symmetrize). An edge made
+// of two weighted arcs keeps the heavier weight.
+//
+// The path "-" reads stdin or writes stdout, for pipelines. It has no
+// extension, so its format has to be given: the _as functions take one.
+
+use crate::durable::write_atomic;
+use crate::error::VccError;
+use crate::Graph;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+pub const STDIO: &str = "-";
+
+// The most vertices a file may claim. The adjacency takes n^2 bits, so
+// this is 512 MiB of it; a header claiming more, mistaken or hostile, is
+// rejected rather than aborting the process in the allocator.
+pub const MAX_VERTICES: usize = 1 << 16;
+
+fn check_vertex_count(n: usize) -> Result<usize, VccError> {
+  if n > MAX_VERTICES {
+    return Err(VccError::Parse(format!(
+      "{} vertices, more than the {} a file may have",
+      n, MAX_VERTICES
+    )));
+  }
+  Ok(n)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GraphFormat {
+  Dimacs,
+  Graph6,
+}
+
+impl GraphFormat {
+  pub fn from_path(path: &str) -> Result<GraphFormat, VccError> {
+    let extension = Path::new(path)
+      .extension()
+      .and_then(|extension| extension.to_str())
+      .unwrap_or("")
+      .to_ascii_lowercase();
+    match extension.as_str() {
+      "col" | "clq" | "dimacs" => Ok(GraphFormat::Dimacs),
+      "g6" => Ok(GraphFormat::Graph6),
+      _ if path == STDIO => Err(VccError::Parse(
+        "no graph format for \"-\"; give one".to_string(),
+      )),
+      _ => Err(VccError::Parse(format!(
+        "no graph format for {:?} (expected .col, .clq, .dimacs or .g6)",
+        path
+      ))),
+    }
+  }
+}
+
+// "dimacs" or "graph6"
+impl FromStr for GraphFormat {
+  type Err = VccError;
+
+  fn from_str(format_str: &str) -> Result<GraphFormat, VccError> {
+    match format_str {
+      "dimacs" => Ok(GraphFormat::Dimacs),
+      "graph6" => Ok(GraphFormat::Graph6),
+      _ => Err(VccError::Parse(format!(
+        "bad graph format: {:?}",
+        format_str
+      ))),
+    }
+  }
+}
+
+// How arcs become edges.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Symmetrize {
+  // An edge wherever there is an arc either way.
+  Union,
+  // An edge only where there are arcs both ways.
+  Intersection,
+  // Arcs are rejected.
+  Error,
+}
+
+impl FromStr for Symmetrize {
+  type Err = VccError;
+
+  fn from_str(symmetrize_str: &str) -> Result<Symmetrize, VccError> {
+    match symmetrize_str {
+      "union" => Ok(Symmetrize::Union),
+      "intersection" => Ok(Symmetrize::Intersection),
+      "error" => Ok(Symmetrize::Error),
+      _ => Err(VccError::Parse(format!(
+        "bad symmetrization: {:?}",
+        symmetrize_str
+      ))),
+    }
+  }
+}
+
+// Vertex and edge weights; anything not given a weight weighs 1.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Weights {
+  pub vertex: Vec<u64>,
+  // Keyed by (u, v) with u < v.
+  pub edge: BTreeMap<(usize, usize), u64>,
+}
+
+impl Weights {
+  pub fn unit(size: usize) -> Weights {
+    Weights {
+      vertex: vec![1; size],
+      edge: BTreeMap::new(),
+    }
+  }
+
+  pub fn edge_weight(&self, u: usize, v: usize) -> u64 {
+    self.edge.get(&(u.min(v), u.max(v))).copied().unwrap_or(1)
+  }
+
+  pub fn is_unit(&self) -> bool {
+    self.vertex.iter().all(|&w| w == 1) && self.edge.values().all(|&w| w == 1)
+  }
+}
+
+// The file's contents, or stdin's for "-".
+pub fn read_text(path: &str) -> Result<String, VccError> {
+  if path != STDIO {
+    return Ok(fs::read_to_string(path)?);
+  }
+  let mut text = String::new();
+  io::stdin().read_to_string(&mut text)?;
+  Ok(text)
+}
+
+// Writes the file atomically (see durable.rs), or stdout for "-".
+pub fn write_text(path: &str, text: &str) -> Result<(), VccError> {
+  if path != STDIO {
+    return write_atomic(path, text);
+  }
+  let mut stdout = io::stdout().lock();
+  stdout.write_all(text.as_bytes())?;
+  stdout.flush()?;
+  Ok(())
+}
+
+pub fn read_graph(path: &str) -> Result<Graph, VccError> {
+  let format = GraphFormat::from_path(path)?;
+  Graph::parse(&read_text(path)?, format)
+}
+
+pub fn write_graph(g: &Graph, path: &str) -> Result<(), VccError> {
+  write_graph_as(g, path, GraphFormat::from_path(path)?)
+}
+
+pub fn write_graph_as(g: &Graph, path: &str, format: GraphFormat) -> Result<(), VccError> {
+  write_text(path, &g.to_format(format))
+}
+
+pub fn read_weighted_graph(
+  path: &str,
+  symmetrize: Symmetrize,
+) -> Result<(Graph, Weights), VccError> {
+  read_weighted_graph_as(path, GraphFormat::from_path(path)?, symmetrize)
+}
+
+pub fn read_weighted_graph_as(
+  path: &str,
+  format: GraphFormat,
+  symmetrize: Symmetrize,
+) -> Result<(Graph, Weights), VccError> {
+  Graph::parse_weighted(&read_text(path)?, format, symmetrize)
+}
+
+// graph6 has no room for weights, so they are dropped.
+pub fn write_weighted_graph(g: &Graph, weights: &Weights, path: &str) -> Result<(), VccError> {
+  match GraphFormat::from_path(path)? {
+    GraphFormat::Dimacs => write_text(path, &g.to_weighted_dimacs(weights)),
+    format => write_text(path, &g.to_format(format)),
+  }
+}
+
+impl Graph {
+  // The graph's cliques are singletons, ready to solve. Arcs are rejected.
+  pub fn parse(text: &str, format: GraphFormat) -> Result<Graph, VccError> {
+    Ok(Graph::parse_weighted(text, format, Symmetrize::Error)?.0)
+  }
+
+  // Formats without weights weigh everything 1.
+  pub fn parse_weighted(
+    text: &str,
+    format: GraphFormat,
+    symmetrize: Symmetrize,
+  ) -> Result<(Graph, Weights), VccError> {
+    let (mut g, weights) = match format {
+      GraphFormat::Dimacs => parse_dimacs(text, symmetrize)?,
+      GraphFormat::Graph6 => {
+        let g = parse_graph6(text)?;
+        let weights = Weights::unit(g.size);
+        (g, weights)
+      }
+    };
+    g.conform_cliques_to_vertices();
+    Ok((g, weights))
+  }
+
+  pub fn to_format(&self, format: GraphFormat) -> String {
+    match format {
+      GraphFormat::Dimacs => self.to_dimacs(),
+      GraphFormat::Graph6 => self.to_graph6(),
+    }
+  }
+
+  fn edges(&self) -> Vec<(usize, usize)> {
+    (0..self.size)
+      .flat_map(|u| {
+        ((u + 1)..self.size)
+          .filter(move |&v| self.topology.vertices[u].neighbors_bv.get_unchecked(v))
+          .map(move |v| (u, v))
+      })
+      .collect()
+  }
+
+  fn to_dimacs(&self) -> String {
+    self.to_weighted_dimacs(&Weights::unit(self.size))
+  }
+
+  // Weights of 1 are left implicit. Weights of non-edges are dropped.
+  pub fn to_weighted_dimacs(&self, weights: &Weights) -> String {
+    let edges = self.edges();
+    let mut text = format!("p edge {} {}\n", self.size, edges.len());
+    for (v, &w) in weights.vertex.iter().enumerate().take(self.size) {
+      if w != 1 {
+        text.push_str(&format!("n {} {}\n", v + 1, w));
+      }
+    }
+    for (u, v) in edges {
+      match weights.edge_weight(u, v) {
+        1 => text.push_str(&format!("e {} {}\n", u + 1, v + 1)),
+        w => text.push_str(&format!("e {} {} {}\n", u + 1, v + 1, w)),
+      }
+    }
+    text
+  }
+
+  fn to_graph6(&self) -> String {
+    let n = self.size;
+    let mut bytes = if n < 63 {
+      vec![n as u8]
+    } else if n < 1 << 18 {
+      let mut bytes = vec![63];
+      bytes.extend((0..3).rev().map(|i| ((n >> (6 * i)) & 63) as u8));
+      bytes
+    } else {
+      let mut bytes = vec![63, 63];
+      bytes.extend((0..6).rev().map(|i| ((n >> (6 * i)) & 63) as u8));
+      bytes
+    };
+    // The upper triangle column by column, six bits to a byte.
+    let mut bits = 0;
+    let mut bit_ct = 0;
+    for v in 1..n {
+      for u in 0..v {
+        bits = (bits << 1) | self.topology.vertices[u].neighbors_bv.get_unchecked(v) as u8;
+        bit_ct += 1;
+        if bit_ct == 6 {
+          bytes.push(bits);
+          bits = 0;
+          bit_ct = 0;
+        }
+      }
+    }
+    if bit_ct > 0 {
+      bytes.push(bits << (6 - bit_ct));
+    }
+    let mut text: String = bytes.into_iter().map(|b| (b + 63) as char).collect();
+    text.push('\n');
+    text
+  }
+}
+
+fn parse_dimacs(text: &str, symmetrize: Symmetrize) -> Result<(Graph, Weights), VccError> {
+  let mut parsed: Option<(Graph, Weights)> = None;
+  // Each arc's weight, by (tail, head).
+  let mut arcs = BTreeMap::new();
+  for (line_no, line) in text.lines().enumerate() {
+    let bad = || VccError::Parse(format!("line {}: bad DIMACS line {:?}", line_no + 1, line));
+    let vertex = |v: &str| match v.parse::<usize>() {
+      Ok(0) => Err(VccError::InvalidGraph(format!(
+        "line {}: DIMACS vertices count from 1",
+        line_no + 1
+      ))),
+      Ok(v) => Ok(v - 1),
+      Err(_) => Err(bad()),
+    };
+    let weight = |w: &str| w.parse::<u64>().map_err(|_| bad());
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    if let ["p", _, n, _] = fields.as_slice() {
+      if parsed.is_some() {
+        return Err(bad());
+      }
+      let n = check_vertex_count(n.parse().map_err(|_| bad())?)?;
+      parsed = Some((Graph::new(n), Weights::unit(n)));
+      continue;
+    }
+    if matches!(fields.as_slice(), [] | ["c", ..]) {
+      continue;
+    }
+    let (g, weights) = parsed.as_mut().ok_or_else(|| {
+      VccError::Parse(format!(
+        "line {}: {:?} before the problem line",
+        line_no + 1,
+        line
+      ))
+    })?;
+    match fields.as_slice() {
+      ["e", u, v] => g.add_edge(vertex(u)?, vertex(v)?)?,
+      ["e", u, v, w] => {
+        let (u, v, w) = (vertex(u)?, vertex(v)?, weight(w)?);
+        g.add_edge(u, v)?;
+        weights.edge.insert((u.min(v), u.max(v)), w);
+      }
+      ["a", u, v] | ["a", u, v, _] => {
+        if symmetrize == Symmetrize::Error {
+          return Err(VccError::InvalidGraph(format!(
+            "line {}: arc {:?} in a directed file; pick a symmetrization",
+            line_no + 1,
+            line
+          )));
+        }
+        let (u, v) = (vertex(u)?, vertex(v)?);
+        if u >= g.size || v >= g.size || u == v {
+          return Err(VccError::InvalidGraph(format!(
+            "line {}: arc ({}, {}) in a graph with {} vertices",
+            line_no + 1,
+            u + 1,
+            v + 1,
+            g.size
+          )));
+        }
+        let w = match fields.get(3) {
+          Some(w) => weight(w)?,
+          None => 1,
+        };
+        arcs.insert((u, v), w);
+      }
+      ["n", v, w] => {
+        let v = vertex(v)?;
+        if v >= g.size {
+          return Err(VccError::InvalidGraph(format!(
+            "line {}: weight for vertex {} in a graph with {} vertices",
+            line_no + 1,
+            v + 1,
+            g.size
+          )));
+        }
+        weights.vertex[v] = weight(w)?;
+      }
+      _ => return Err(bad()),
+    }
+  }
+  let (mut g, mut weights) =
+    parsed.ok_or_else(|| VccError::Parse("no DIMACS problem line".to_string()))?;
+  for (&(u, v), &w) in &arcs {
+    let w = match (symmetrize, arcs.get(&(v, u))) {
+      (Symmetrize::Intersection, None) => continue,
+      (_, Some(&back)) => w.max(back),
+      (_, None) => w,
+    };
+    g.link(u, v);
+    let edge_weight = weights.edge.entry((u.min(v), u.max(v))).or_insert(w);
+    *edge_weight = (*edge_weight).max(w);
+  }
+  Ok((g, weights))
+}
+
+fn parse_graph6(text: &str) -> Result<Graph, VccError> {
+  let line = text
+    .lines()
+    .find(|line| !line.trim().is_empty())
+    .unwrap_or("");
+  let line = line.trim();
+  let line = line.strip_prefix(">>graph6<<").unwrap_or(line);
+  let bad = || VccError::Parse(format!("bad graph6 line {:?}", line));
+  let bytes = line
+    .bytes()
+    .map(|b| {
+      if (63..=126).contains(&b) {
+        Ok(b - 63)
+      } else {
+        Err(bad())
+      }
+    })
+    .collect::<Result<Vec<u8>, VccError>>()?;
+  let (n, header) = match bytes.as_slice() {
+    [63, 63, rest @ ..] if rest.len() >= 6 => (number(&rest[..6]), 8),
+    [63, rest @ ..] if rest.len() >= 3 => (number(&rest[..3]), 4),
+    [n, ..] if *n < 63 => (*n as usize, 1),
+    _ => return Err(bad()),
+  };
+  let n = check_vertex_count(n)?;
+  let body = &bytes[header..];
+  if body.len() != (n * n.saturating_sub(1) / 2).div_ceil(6) {
+    return Err(bad());
+  }
+  let mut g = Graph::new(n);
+  let mut bit = 0;
+  for v in 1..n {
+    for u in 0..v {
+      if body[bit / 6] & (32 >> (bit % 6)) != 0 {
+        g.link(u, v);
+      }
+      bit += 1;
+    }
+  }
+  Ok(g)
+}
+
+// A graph6 size: six bits per byte, most significant first.
+fn number(bytes: &[u8]) -> usize {
+  bytes.iter().fold(0, |n, &b| (n << 6) | b as usize)
+}
@@ -0,0 +1,315 @@
+// The fractional clique cover: weights on cliques adding up to at least
+// one on every vertex, as little weight in total as possible. Its columns
+// are the maximal cliques, enumerated by Bron-Kerbosch with pivoting up to
+// a cap, plus a greedy maximal clique through any vertex the capped
+// enumeration missed.
+//
+// The LP is solved through its dual, a fractional independent set (vertex
+// weights adding up to at most one on every clique), by a dense simplex
+// that keeps the dual feasible throughout, so a run cut short by the pivot
+// cap still bounds. When the enumeration wasn't capped, every clique lies
+// in a column, and the dual's total rounded up is a lower bound on the
+// cover; it can beat the largest independent set on graphs like odd
+// antiholes. The clique weights guide construction: the cliques the LP
+// leans on are laid down first.
+
+use crate::cover::CliqueCover;
+use crate::rng::{mix64, shuffle};
+use crate::Graph;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use rand_core::RngCore;
+
+// Columns for the fractional initial cover of a restart.
+pub const DEFAULT_MAX_CLIQUES: usize = 500;
+const MAX_PIVOTS: usize = 50_000;
+// After this many pivots in a row that don't move the objective, the
+// simplex falls back to Bland's rule, which can't cycle.
+const DEGENERATE_PIVOTS: usize = 50;
+const EPS: f64 = 1e-9;
+const PERTURBATION: f64 = 1e-6;
+
+pub struct FractionalCover {
+  pub cliques: Vec<Vec<usize>>,
+  // The LP weight of each clique.
+  pub weights: Vec<f64>,
+  // The dual weight of each vertex.
+  pub vertex_weights: Vec<f64>,
+  // The dual's total; the fractional cover number if optimal and complete.
+  pub value: f64,
+  // Whether the enumeration found every maximal clique.
+  pub complete: bool,
+  // Whether the simplex finished within its pivot cap.
+  pub optimal: bool,
+}
+
+impl FractionalCover {
+  pub fn lower_bound(&self) -> Option<usize> {
+    self.complete.then(|| {
+      // Rounded up by hand, as f64::ceil needs std.
+      let value = (self.value - 1e-6).max(0.0);
+      let whole = value as usize;
+      whole + ((whole as f64) < value) as usize
+    })
+  }
+
+  // Takes the cliques by decreasing weight (ties in random order), each
+  // less the vertices already covered.
+  pub(crate) fn round<R: RngCore + ?Sized>(&self, rng: &mut R) -> CliqueCover {
+    let mut order: Vec<usize> = (0..self.cliques.len()).collect();
+    shuffle(rng, &mut order);
+    order.sort_by(|&a, &b| self.weights[b].total_cmp(&self.weights[a]));
+    let mut covered = vec![false; self.vertex_weights.len()];
+    let mut cliques = vec![];
+    for c in order {
+      let members: Vec<usize> = self.cliques[c]
+        .iter()
+        .copied()
+        .filter(|&v| !covered[v])
+        .collect();
+      if members.is_empty() {
+        continue;
+      }
+      for &v in &members {
+        covered[v] = true;
+      }
+      cliques.push(members);
+    }
+    CliqueCover { cliques }
+  }
+}
+
+impl fmt::Display for FractionalCover {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "fractional cover {:.3} over {} cliques",
+      self.value,
+      self.cliques.len()
+    )?;
+    if !self.complete {
+      write!(f, " (capped)")?;
+    }
+    if !self.optimal {
+      write!(f, " (pivot cap reached)")?;
+    }
+    match self.lower_bound() {
+      Some(bound) => write!(f, ", lower bound {}", bound),
+      None => write!(f, ", no bound"),
+    }
+  }
+}
+
+impl Graph {
+  pub fn fractional_cover(&self, max_cliques: usize) -> FractionalCover {
+    let n = self.size;
+    let (cliques, complete) = self.clique_columns(max_cliques);
+    let (vertex_weights, weights, optimal) = solve_dual(n, &cliques);
+    // Scale away any rounding drift, so the weights stay dual feasible.
+    let heaviest = cliques
+      .iter()
+      .map(|members| members.iter().map(|&v| vertex_weights[v]).sum::<f64>())
+      .fold(1.0, f64::max);
+    FractionalCover {
+      value: vertex_weights.iter().sum::<f64>() / heaviest,
+      cliques,
+      weights,
+      vertex_weights,
+      complete,
+      optimal,
+    }
+  }
+
+  // The maximal cliques up to the cap, then greedy ones through any vertex
+  // they miss; and whether every maximal clique was found.
+  pub(crate) fn clique_columns(&self, max_cliques: usize) -> (Vec<Vec<usize>>, bool) {
+    let n = self.size;
+    let mut cliques = vec![];
+    let complete = self.maximal_cliques(
+      &mut vec![],
+      (0..n).collect(),
+      vec![],
+      &mut cliques,
+      max_cliques,
+    );
+    let mut covered = vec![false; n];
+    for &v in cliques.iter().flatten() {
+      covered[v] = true;
+    }
+    for v in 0..n {
+      if covered[v] {
+        continue;
+      }
+      let mut members = vec![v];
+      for u in 0..n {
+        if u != v
+          && members
+            .iter()
+            .all(|&w| self.topology.vertices[u].neighbors_bv.get_unchecked(w))
+        {
+          members.push(u);
+        }
+      }
+      for &u in &members {
+        covered[u] = true;
+      }
+      cliques.push(members);
+    }
+    (cliques, complete)
+  }
+
+  // Bron-Kerbosch with pivoting; false once a clique past the cap turns up.
+  fn maximal_cliques(
+    &self,
+    clique: &mut Vec<usize>,
+    mut candidates: Vec<usize>,
+    mut excluded: Vec<usize>,
+    found: &mut Vec<Vec<usize>>,
+    max_cliques: usize,
+  ) -> bool {
+    let adjacent = |u: usize, v: usize| self.topology.vertices[u].neighbors_bv.get_unchecked(v);
+    if candidates.is_empty() {
+      if !excluded.is_empty() || clique.is_empty() {
+        return true;
+      }
+      if found.len() == max_cliques {
+        return false;
+      }
+      found.push(clique.clone());
+      return true;
+    }
+    let pivot = candidates
+      .iter()
+      .chain(&excluded)
+      .copied()
+      .max_by_key(|&u| candidates.iter().filter(|&&v| adjacent(u, v)).count())
+      .unwrap();
+    let branches: Vec<usize> = candidates
+      .iter()
+      .copied()
+      .filter(|&v| !adjacent(pivot, v))
+      .collect();
+    for v in branches {
+      clique.push(v);
+      let inner_candidates = candidates
+        .iter()
+        .copied()
+        .filter(|&u| adjacent(v, u))
+        .collect();
+      let inner_excluded = excluded
+        .iter()
+        .copied()
+        .filter(|&u| adjacent(v, u))
+        .collect();
+      let finished =
+        self.maximal_cliques(clique, inner_candidates, inner_excluded, found, max_cliques);
+      clique.pop();
+      if !finished {
+        return false;
+      }
+      candidates.retain(|&u| u != v);
+      excluded.push(v);
+    }
+    true
+  }
+}
+
+// Maximizes the total vertex weight with at most one on each clique, by
+// the simplex on a condensed tableau: one row per clique (its slack) and
+// one column per vertex to start with, the objective in the last row.
+// Returns the vertex weights, the clique weights (the slacks' reduced
+// costs) and whether the optimum was reached.
+fn solve_dual(n: usize, cliques: &[Vec<usize>]) -> (Vec<f64>, Vec<f64>, bool) {
+  let m = cliques.len();
+  let width = n + 1;
+  let mut t = vec![0.0; (m + 1) * width];
+  for (i, members) in cliques.iter().enumerate() {
+    for &v in members {
+      t[i * width + v] = 1.0;
+    }
+    // A slightly different bound on each clique breaks the ties that make
+    // this LP highly degenerate; the final rescaling undoes it.
+    t[i * width + n] = 1.0 + PERTURBATION * (mix64(i as u64) % 1024) as f64 / 1024.0;
+  }
+  for j in 0..n {
+    t[m * width + j] = -1.0;
+  }
+  // Variables 0..n are the vertex weights, n + i the slack of clique i.
+  let mut row_var: Vec<usize> = (n..(n + m)).collect();
+  let mut col_var: Vec<usize> = (0..n).collect();
+  let mut optimal = false;
+  let mut stalled = 0;
+  for _ in 0..MAX_PIVOTS {
+    let bland = stalled >= DEGENERATE_PIVOTS;
+    let entering = (0..n)
+      .filter(|&j| t[m * width + j] < -EPS)
+      .min_by(|&a, &b| {
+        if bland {
+          col_var[a].cmp(&col_var[b])
+        } else {
+          t[m * width + a].total_cmp(&t[m * width + b])
+        }
+      });
+    let Some(j) = entering else {
+      optimal = true;
+      break;
+    };
+    let leaving = (0..m).filter(|&i| t[i * width + j] > EPS).min_by(|&a, &b| {
+      let ratio_a = t[a * width + n] / t[a * width + j];
+      let ratio_b = t[b * width + n] / t[b * width + j];
+      ratio_a
+        .total_cmp(&ratio_b)
+        .then(row_var[a].cmp(&row_var[b]))
+    });
+    // Every vertex is in some clique, so the dual is bounded.
+    let Some(r) = leaving else {
+      break;
+    };
+    if t[r * width + n] < EPS {
+      stalled += 1;
+    } else {
+      stalled = 0;
+    }
+    pivot(&mut t, m, width, r, j);
+    core::mem::swap(&mut row_var[r], &mut col_var[j]);
+  }
+  let mut vertex_weights = vec![0.0; n];
+  for (i, &var) in row_var.iter().enumerate() {
+    if var < n {
+      vertex_weights[var] = t[i * width + n].max(0.0);
+    }
+  }
+  let mut weights = vec![0.0; m];
+  for (j, &var) in col_var.iter().enumerate() {
+    if var >= n {
+      weights[var - n] = t[m * width + j].max(0.0);
+    }
+  }
+  (vertex_weights, weights, optimal)
+}
+
+fn pivot(t: &mut [f64], m: usize, width: usize, r: usize, j: usize) {
+  let p = t[r * width + j];
+  for k in 0..width {
+    if k != j {
+      t[r * width + k] /= p;
+    }
+  }
+  for i in 0..=m {
+    if i == r {
+      continue;
+    }
+    let factor = t[i * width + j];
+    if factor == 0.0 {
+      continue;
+    }
+    for k in 0..width {
+      if k != j {
+        t[i * width + k] -= factor * t[r * width + k];
+      }
+    }
+    t[i * width + j] = -factor / p;
+  }
+  t[r * width + j] = 1.0 / p;
+}
@@ -0,0 +1,70 @@
+// Freezing: once a restart has grown some cliques large, they can be
+// locked so later merging passes and annealing can't take vertices out of
+// them (they may still gain vertices). The search effort then goes into
+// the fragmented remainder. Restarts and loaded covers start unfrozen.
+
+use crate::error::VccError;
+use crate::Graph;
+use alloc::format;
+use alloc::vec::Vec;
+use core::str::FromStr;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Freeze {
+  // Every clique with at least this many members.
+  MinSize(usize),
+  // The k largest cliques.
+  Largest(usize),
+}
+
+// "min-size:10" or "largest:3"
+impl FromStr for Freeze {
+  type Err = VccError;
+
+  fn from_str(freeze_str: &str) -> Result<Freeze, VccError> {
+    let bad = || VccError::Parse(format!("bad freeze rule: {:?}", freeze_str));
+    let (rule, n) = freeze_str.split_once(':').ok_or_else(bad)?;
+    let n = n.replace('_', "").parse().map_err(|_| bad())?;
+    match rule {
+      "min-size" => Ok(Freeze::MinSize(n)),
+      "largest" => Ok(Freeze::Largest(n)),
+      _ => Err(bad()),
+    }
+  }
+}
+
+impl Graph {
+  // Refreezes the active cliques by the rule.
+  pub fn freeze_cliques(&mut self, freeze: &Freeze) {
+    let active = &mut self.cliques[0..self.cliques_ct];
+    match *freeze {
+      Freeze::MinSize(min_size) => {
+        for clique in active {
+          clique.is_frozen = clique.members_ct >= min_size;
+        }
+      }
+      Freeze::Largest(k) => {
+        let mut by_size: Vec<usize> = (0..active.len()).collect();
+        by_size.sort_by_key(|&c| core::cmp::Reverse(active[c].members_ct));
+        for (rank, &c) in by_size.iter().enumerate() {
+          active[c].is_frozen = rank < k;
+        }
+      }
+    }
+  }
+
+  pub fn unfreeze_cliques(&mut self) {
+    for clique in &mut self.cliques {
+      clique.is_frozen = false;
+    }
+  }
+
+  pub fn frozen_ct(&self) -> usize {
+    self.cliques[0..self.cliques_ct]
+      .iter()
+      .filter(|c| c.is_frozen)
+      .count()
+  }
+}
@@ -0,0 +1,153 @@
+// Exact clique covers as an integer program, behind the `ilp` feature: one
+// 0/1 variable per maximal clique, at least one chosen clique on every
+// vertex, as few chosen as possible. Any cover extends to one of maximal
+// cliques of the same size, so once every maximal clique is a column the
+// program's optimum is the cover number. Its LP relaxation is the
+// fractional cover, which bounds far better than the independent set the
+// search of exact.rs prunes with, so it proves optima on medium instances
+// that search can't.
+//
+// good_lp solves the relaxations and the branching is done here, fixing
+// the most fractional clique in or out, so any of good_lp's backends will
+// do. The feature builds with the pure Rust minilp; building with
+// `--features ilp,good_lp/coin_cbc` solves them with CBC instead, which
+// needs the CBC libraries installed.
+
+use crate::cover::CliqueCover;
+use crate::error::VccError;
+use crate::exact::{min_degree_independent_set_size, ExactResult};
+use crate::Graph;
+use good_lp::{
+  constraint, default_solver, variable, Expression, ProblemVariables, Solution, SolverModel,
+};
+
+// Past this many maximal cliques the program is solved over those found,
+// and its cover is no longer proven optimal.
+pub const MAX_COLUMNS: usize = 20_000;
+const EPS: f64 = 1e-6;
+
+struct Branching<'a> {
+  cliques: &'a [Vec<usize>],
+  // The columns through each vertex.
+  columns: Vec<Vec<usize>>,
+  // Each column's bounds, narrowed by the branching.
+  fixed: Vec<Option<bool>>,
+  best: Vec<Vec<usize>>,
+  lower_bound: usize,
+  nodes: usize,
+  max_nodes: usize,
+  exhausted: bool,
+}
+
+impl Branching<'_> {
+  // Returns true once the search should stop.
+  fn branch(&mut self) -> Result<bool, VccError> {
+    self.nodes += 1;
+    if self.nodes > self.max_nodes {
+      self.exhausted = true;
+      return Ok(true);
+    }
+    // An infeasible relaxation has a vertex whose columns are all out.
+    let Some(x) = self.relaxation()? else {
+      return Ok(false);
+    };
+    let value: f64 = x.iter().sum();
+    if (value - EPS).ceil() as usize >= self.best.len() {
+      return Ok(false);
+    }
+    let fractional = (0..x.len())
+      .filter(|&c| x[c] > EPS && x[c] < 1.0 - EPS)
+      .min_by(|&a, &b| (x[a] - 0.5).abs().total_cmp(&(x[b] - 0.5).abs()));
+    let Some(c) = fractional else {
+      self.best = self.chosen(&x);
+      return Ok(self.best.len() <= self.lower_bound);
+    };
+    for in_cover in [true, false] {
+      self.fixed[c] = Some(in_cover);
+      if self.branch()? {
+        return Ok(true);
+      }
+    }
+    self.fixed[c] = None;
+    Ok(false)
+  }
+
+  fn relaxation(&self) -> Result<Option<Vec<f64>>, VccError> {
+    let mut vars = ProblemVariables::new();
+    let x: Vec<_> = self
+      .fixed
+      .iter()
+      .map(|&fixed| match fixed {
+        Some(in_cover) => {
+          let value = if in_cover { 1.0 } else { 0.0 };
+          vars.add(variable().min(value).max(value))
+        }
+        None => vars.add(variable().min(0.0).max(1.0)),
+      })
+      .collect();
+    let objective: Expression = x.iter().copied().sum();
+    let mut problem = vars.minimise(objective).using(default_solver);
+    for columns in &self.columns {
+      let covered: Expression = columns.iter().map(|&c| x[c]).sum();
+      problem = problem.with(constraint!(covered >= 1.0));
+    }
+    match problem.solve() {
+      Ok(solution) => Ok(Some(x.iter().map(|&var| solution.value(var)).collect())),
+      Err(good_lp::ResolutionError::Infeasible) => Ok(None),
+      Err(e) => Err(VccError::InvalidParameter(format!("LP solver: {}", e))),
+    }
+  }
+
+  // The chosen columns of an integral solution, each less the vertices of
+  // the ones before.
+  fn chosen(&self, x: &[f64]) -> Vec<Vec<usize>> {
+    let mut covered = vec![false; self.columns.len()];
+    let mut cliques = vec![];
+    for (c, members) in self.cliques.iter().enumerate() {
+      if x[c] < 0.5 {
+        continue;
+      }
+      let members: Vec<usize> = members.iter().copied().filter(|&v| !covered[v]).collect();
+      for &v in &members {
+        covered[v] = true;
+      }
+      if !members.is_empty() {
+        cliques.push(members);
+      }
+    }
+    cliques
+  }
+}
+
+impl Graph {
+  // Gives up after max_nodes relaxations, keeping the best cover found.
+  pub fn ilp_cover(&self, max_nodes: usize) -> Result<ExactResult, VccError> {
+    let (cliques, complete) = self.clique_columns(MAX_COLUMNS);
+    let mut columns = vec![vec![]; self.size];
+    for (c, members) in cliques.iter().enumerate() {
+      for &v in members {
+        columns[v].push(c);
+      }
+    }
+    let mut search = Branching {
+      fixed: vec![None; cliques.len()],
+      cliques: &cliques,
+      columns,
+      best: self.dsatur_cover().cliques,
+      lower_bound: min_degree_independent_set_size(self),
+      nodes: 0,
+      max_nodes,
+      exhausted: false,
+    };
+    if search.best.len() > search.lower_bound {
+      search.branch()?;
+    }
+    Ok(ExactResult {
+      cover: CliqueCover {
+        cliques: search.best,
+      },
+      optimal: complete && !search.exhausted,
+      nodes: search.nodes,
+    })
+  }
+}
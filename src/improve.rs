@@ -0,0 +1,63 @@
+// Improving a cover the caller already has, as when iterating on a
+// production cover: the cover becomes the search state and only the
+// polishing phases run, never construction. Each round first dissolves
+// smallest cliques by ejection for as long as that works, then runs one
+// restart's worth of iterated greedy and annealing (vcc_run_restart) from
+// the best cover so far. The best cover is replaced only by a smaller one,
+// so the result is never worse than the cover given, and rounds stop once
+// it matches a greedy independent set, a lower bound. The time budget is
+// checked between rounds, so the last round may overrun it by up to
+// params.max_iterations iterations.
+
+use crate::cover::CliqueCover;
+use crate::error::VccError;
+use crate::solve::SolveParams;
+use crate::Graph;
+use std::time::{Duration, Instant};
+
+pub struct Improvement {
+  // Cliques in the cover given.
+  pub before: usize,
+  pub cover: CliqueCover,
+  pub rounds: usize,
+  pub elapsed: Duration,
+}
+
+impl Graph {
+  // Shrinks cover, which must be valid, until time has passed, params's
+  // max_restarts rounds have run, or it has at most params.target cliques
+  // or provably the fewest. The search state is left at the best cover.
+  pub fn improve_cover(
+    &mut self,
+    cover: &CliqueCover,
+    params: &SolveParams,
+    time: Duration,
+  ) -> Result<Improvement, VccError> {
+    let started = Instant::now();
+    let deadline = started + time;
+    self.load_cover(cover)?;
+    let mut best = cover.clone();
+    let target = params.target.max(self.greedy_independent_set_bound());
+    let mut rounds = 0;
+    while best.len() > target && rounds < params.max_restarts && Instant::now() < deadline {
+      rounds += 1;
+      while self.eject_smallest_clique() {}
+      if self.cliques_ct < best.len() {
+        best = self.cover();
+      }
+      self.vcc_run_restart(params, None);
+      if self.cliques_ct < best.len() {
+        best = self.cover();
+      } else {
+        self.load_cover_unchecked(&best);
+      }
+    }
+    self.load_cover_unchecked(&best);
+    Ok(Improvement {
+      before: cover.len(),
+      cover: best,
+      rounds,
+      elapsed: started.elapsed(),
+    })
+  }
+}
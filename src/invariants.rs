@@ -0,0 +1,145 @@
+// Consistency checks on the search state, for tests and debugging. The
+// active cliques must partition the vertices into cliques, and each
+// clique's bookkeeping must agree with its member list:
+// - members_bv and members_ct match members,
+// - neighbors_bv is the intersection of the members' neighborhoods,
+// - neighbors_ct matches neighbors_bv, and each vertex's matches its degree,
+// - each member's entry in vertex_clique is the clique's id.
+// Inactive cliques must be empty, and frozen ones non-empty. An active
+// clique may be empty too, since annealing can open a clique that
+// receives no vertex.
+
+use crate::bitset::BitVec;
+use crate::error::VccError;
+use crate::{Clique, Graph};
+use alloc::string::String;
+use alloc::{format, vec};
+
+fn corrupt(msg: String) -> VccError {
+  VccError::InvalidCover(msg)
+}
+
+impl Graph {
+  pub fn check_invariants(&self) -> Result<(), VccError> {
+    for (v, vertex) in self.topology.vertices.iter().enumerate() {
+      if vertex.neighbors_bv.get_unchecked(v) {
+        return Err(corrupt(format!("vertex {} is its own neighbor", v)));
+      }
+      if vertex.neighbors_ct != vertex.neighbors_bv.count_ones() {
+        return Err(corrupt(format!(
+          "vertex {} has degree {} but {} neighbor bits",
+          v,
+          vertex.neighbors_ct,
+          vertex.neighbors_bv.count_ones()
+        )));
+      }
+      for u in 0..self.size {
+        if vertex.neighbors_bv.get_unchecked(u)
+          != self.topology.vertices[u].neighbors_bv.get_unchecked(v)
+        {
+          return Err(corrupt(format!("edge ({}, {}) is one-sided", u, v)));
+        }
+      }
+    }
+    if self.cliques_ct > self.size {
+      return Err(corrupt(format!(
+        "{} active cliques for {} vertices",
+        self.cliques_ct, self.size
+      )));
+    }
+    let mut owner = vec![None; self.size];
+    for (c, clique) in self.cliques.iter().enumerate() {
+      let is_active = c < self.cliques_ct;
+      if clique.is_active != is_active {
+        return Err(corrupt(format!(
+          "clique {} is_active is {} at position {} of {}",
+          c, clique.is_active, c, self.cliques_ct
+        )));
+      }
+      if !is_active && clique.members_ct != 0 {
+        return Err(corrupt(format!("inactive clique {} has members", c)));
+      }
+      if clique.is_frozen && clique.members_ct == 0 {
+        return Err(corrupt(format!("empty clique {} is frozen", c)));
+      }
+      check_clique(&self.topology.vertices, c, clique)?;
+      for &v in &clique.members {
+        if let Some(other) = owner[v] {
+          return Err(corrupt(format!(
+            "vertex {} is in cliques {} and {}",
+            v, other, c
+          )));
+        }
+        owner[v] = Some(c);
+        if self.vertex_clique[v] != clique.id {
+          return Err(corrupt(format!(
+            "vertex {} is in clique {} with id {}, but mapped to id {}",
+            v, c, clique.id, self.vertex_clique[v]
+          )));
+        }
+      }
+    }
+    if let Some(v) = owner.iter().position(|o| o.is_none()) {
+      return Err(corrupt(format!("vertex {} is not in any clique", v)));
+    }
+    Ok(())
+  }
+}
+
+fn check_clique(vertices: &[Clique], c: usize, clique: &Clique) -> Result<(), VccError> {
+  if clique.members_ct != clique.members.len()
+    || clique.members_ct != clique.members_bv.count_ones()
+  {
+    return Err(corrupt(format!(
+      "clique {} has members_ct {}, {} listed members and {} member bits",
+      c,
+      clique.members_ct,
+      clique.members.len(),
+      clique.members_bv.count_ones()
+    )));
+  }
+  let mut neighbors_bv = BitVec::ones(vertices.len());
+  for (i, &v) in clique.members.iter().enumerate() {
+    if v >= vertices.len() || !clique.members_bv.get_unchecked(v) {
+      return Err(corrupt(format!(
+        "clique {} lists vertex {} without its member bit",
+        c, v
+      )));
+    }
+    for &u in &clique.members[..i] {
+      if !vertices[v].neighbors_bv.get_unchecked(u) {
+        return Err(corrupt(format!(
+          "clique {} holds non-adjacent vertices {} and {}",
+          c, u, v
+        )));
+      }
+    }
+    neighbors_bv.and_inplace(&vertices[v].neighbors_bv);
+  }
+  if clique.members_ct > 0 && neighbors_bv != clique.neighbors_bv {
+    return Err(corrupt(format!(
+      "clique {} neighbors_bv differs from its members' common neighbors",
+      c
+    )));
+  }
+  if clique.neighbors_ct != clique.neighbors_bv.count_ones() {
+    return Err(corrupt(format!(
+      "clique {} has neighbors_ct {} but {} neighbor bits",
+      c,
+      clique.neighbors_ct,
+      clique.neighbors_bv.count_ones()
+    )));
+  }
+  Ok(())
+}
+
+// Panics if either clique of a transfer was left inconsistent, so that
+// corruption is caught where it happens rather than in the final cover.
+#[cfg(any(debug_assertions, feature = "paranoid"))]
+pub(crate) fn assert_transfer(clique_into: &Clique, clique_from: &Clique, vertices: &[Clique]) {
+  for clique in [clique_into, clique_from] {
+    if let Err(e) = check_clique(vertices, clique.id, clique) {
+      panic!("transfer corrupted the search state: {}", e);
+    }
+  }
+}
@@ -0,0 +1,432 @@
+// Kernelization: reductions that shrink an instance before the heuristic
+// runs, and are undone on the kernel's cover afterwards. A clique cover of
+// G is a coloring of its complement, and the rules below are the classic
+// ones for "saving colors" there, which work on the complement of the
+// complement, i.e. on G itself:
+// - Crowns. If C is an independent set whose neighborhood H can be matched
+//   into C, then theta(G) = |C| + theta(G - C - H): the matched pairs and
+//   the unmatched vertices of C cover C + H optimally, and no clique
+//   through C reaches the rest. Crowns are looked for around the vertices a
+//   maximal matching leaves out (Abu-Khzam et al.), and at the zero
+//   vertices of a half-integral optimum of the vertex cover LP
+//   (Nemhauser-Trotter), found with a matching in the bipartite double
+//   cover.
+// - Low complement degree. Keeping an independent set I in the kernel, any
+//   other vertex with fewer than |I| non-neighbors fits into one of the at
+//   least |I| cliques of any cover of the rest, so it can be removed and
+//   put back at the end. On dense graphs (sparse complements) this peels
+//   off most of the instance.
+// - Degree at most two. A vertex with at most one neighbor takes it into
+//   its clique. Some optimal cover puts a vertex v with neighbors u and w
+//   in a clique with one of them (if v is alone, pull u over). If u and w
+//   are adjacent, {v, u, w} is that clique. If not, the vertex folding of
+//   the MIS reduction doesn't carry over to covers (merging u and w can
+//   raise the cover number), but when w's other neighbors are all u's, u
+//   can take w's place in any clique, so {v, w} is.
+
+use crate::cover::CliqueCover;
+use crate::Graph;
+use std::fmt;
+
+enum Reduction {
+  // Cliques of an optimal cover of the removed vertices.
+  Fixed(Vec<Vec<usize>>),
+  Peel(usize),
+}
+
+#[derive(Default)]
+pub struct KernelStats {
+  pub crown_vertices: usize,
+  pub lp_crown_vertices: usize,
+  pub low_degree: usize,
+  pub peeled: usize,
+}
+
+pub struct Kernel {
+  pub graph: Graph,
+  // Kernel vertex i is vertex vertices[i] of the original graph.
+  pub vertices: Vec<usize>,
+  // Cliques the crowns and low degree rules settled:
+  // theta(original) = theta(kernel) + this.
+  pub fixed_cliques: usize,
+  pub stats: KernelStats,
+  reductions: Vec<Reduction>,
+}
+
+struct Reducer<'a> {
+  g: &'a Graph,
+  alive: Vec<bool>,
+  reductions: Vec<Reduction>,
+  stats: KernelStats,
+}
+
+impl Reducer<'_> {
+  fn adjacent(&self, u: usize, v: usize) -> bool {
+    self.g.topology.vertices[u].neighbors_bv.get_unchecked(v)
+  }
+
+  fn alive_vertices(&self) -> Vec<usize> {
+    (0..self.g.size).filter(|&v| self.alive[v]).collect()
+  }
+
+  // Kuhn's augmenting paths from each vertex of left into the vertices
+  // marked in right. Returns the left mate of every right vertex.
+  fn max_matching(&self, left: &[usize], right: &[bool]) -> Vec<Option<usize>> {
+    let mut mate = vec![None; self.g.size];
+    for &u in left {
+      let mut seen = vec![false; self.g.size];
+      self.augment(u, right, &mut mate, &mut seen);
+    }
+    mate
+  }
+
+  fn augment(
+    &self,
+    u: usize,
+    right: &[bool],
+    mate: &mut [Option<usize>],
+    seen: &mut [bool],
+  ) -> bool {
+    for v in 0..self.g.size {
+      if !right[v] || seen[v] || !self.adjacent(u, v) {
+        continue;
+      }
+      seen[v] = true;
+      if mate[v].is_none_or(|w| self.augment(w, right, mate, seen)) {
+        mate[v] = Some(u);
+        return true;
+      }
+    }
+    false
+  }
+
+  // Removes the crown with independent part `crown` if its neighborhood
+  // can be matched into it; returns the number of vertices removed.
+  fn apply_crown(&mut self, crown: &[usize]) -> usize {
+    let mut in_crown = vec![false; self.g.size];
+    for &c in crown {
+      in_crown[c] = true;
+    }
+    let head: Vec<usize> = self
+      .alive_vertices()
+      .into_iter()
+      .filter(|&v| !in_crown[v] && crown.iter().any(|&c| self.adjacent(v, c)))
+      .collect();
+    let mate = self.max_matching(&head, &in_crown);
+    if mate.iter().flatten().count() < head.len() {
+      return 0;
+    }
+    let cliques = crown
+      .iter()
+      .map(|&c| match mate[c] {
+        Some(h) => vec![c, h],
+        None => vec![c],
+      })
+      .collect();
+    for &v in crown.iter().chain(&head) {
+      self.alive[v] = false;
+    }
+    self.reductions.push(Reduction::Fixed(cliques));
+    crown.len() + head.len()
+  }
+
+  fn matching_crown(&mut self) -> bool {
+    // The vertices a maximal matching leaves out are independent.
+    let mut matched = vec![false; self.g.size];
+    for u in self.alive_vertices() {
+      if matched[u] {
+        continue;
+      }
+      if let Some(v) =
+        ((u + 1)..self.g.size).find(|&v| self.alive[v] && !matched[v] && self.adjacent(u, v))
+      {
+        matched[u] = true;
+        matched[v] = true;
+      }
+    }
+    let mut outside = vec![false; self.g.size];
+    for v in self.alive_vertices() {
+      outside[v] = !matched[v];
+    }
+    let neighbors: Vec<usize> = self
+      .alive_vertices()
+      .into_iter()
+      .filter(|&v| matched[v] && (0..self.g.size).any(|o| outside[o] && self.adjacent(v, o)))
+      .collect();
+    let mate = self.max_matching(&neighbors, &outside);
+    let mut head_mate = vec![None; self.g.size];
+    for (o, h) in mate.iter().enumerate() {
+      if let Some(h) = *h {
+        head_mate[h] = Some(o);
+      }
+    }
+    // Grow the crown from the left-out vertices the matching misses, by
+    // alternating paths.
+    let mut in_crown: Vec<bool> = (0..self.g.size)
+      .map(|o| outside[o] && mate[o].is_none())
+      .collect();
+    if !in_crown.iter().any(|&c| c) {
+      return false;
+    }
+    loop {
+      let mut grown = false;
+      for &h in &neighbors {
+        if let Some(o) = head_mate[h] {
+          if !in_crown[o] && (0..self.g.size).any(|c| in_crown[c] && self.adjacent(h, c)) {
+            in_crown[o] = true;
+            grown = true;
+          }
+        }
+      }
+      if !grown {
+        break;
+      }
+    }
+    let crown: Vec<usize> = (0..self.g.size).filter(|&c| in_crown[c]).collect();
+    let removed = self.apply_crown(&crown);
+    self.stats.crown_vertices += removed;
+    removed > 0
+  }
+
+  fn lp_crown(&mut self) -> bool {
+    let alive = self.alive_vertices();
+    // Left and right copies of the alive vertices, joined where G has an
+    // edge; a minimum vertex cover of this double cover, via Konig, gives
+    // a half-integral LP optimum: x_v = (v left in it + v right in it) / 2.
+    let mate = self.max_matching(&alive, &self.alive);
+    let mut left_matched = vec![false; self.g.size];
+    for u in mate.iter().flatten() {
+      left_matched[*u] = true;
+    }
+    let mut seen_left = vec![false; self.g.size];
+    let mut seen_right = vec![false; self.g.size];
+    let mut stack: Vec<usize> = alive
+      .iter()
+      .copied()
+      .filter(|&u| !left_matched[u])
+      .collect();
+    for &u in &stack {
+      seen_left[u] = true;
+    }
+    while let Some(u) = stack.pop() {
+      for &v in &alive {
+        if seen_right[v] || !self.adjacent(u, v) {
+          continue;
+        }
+        seen_right[v] = true;
+        if let Some(w) = mate[v] {
+          if !seen_left[w] {
+            seen_left[w] = true;
+            stack.push(w);
+          }
+        }
+      }
+    }
+    // The cover is the unseen left copies and the seen right ones, so
+    // x_v = 0 exactly for these; they are independent.
+    let zero: Vec<usize> = alive
+      .into_iter()
+      .filter(|&v| seen_left[v] && !seen_right[v])
+      .collect();
+    if zero.is_empty() {
+      return false;
+    }
+    let removed = self.apply_crown(&zero);
+    self.stats.lp_crown_vertices += removed;
+    removed > 0
+  }
+
+  fn low_degree(&mut self) -> bool {
+    let mut changed = false;
+    for v in 0..self.g.size {
+      if !self.alive[v] {
+        continue;
+      }
+      let neighbors: Vec<usize> = (0..self.g.size)
+        .filter(|&u| self.alive[u] && self.adjacent(v, u))
+        .take(3)
+        .collect();
+      let clique = match *neighbors.as_slice() {
+        [] => vec![v],
+        [u] => vec![v, u],
+        [u, w] if self.adjacent(u, w) => vec![v, u, w],
+        [u, w] if self.stands_in(u, w, v) => vec![v, w],
+        [u, w] if self.stands_in(w, u, v) => vec![v, u],
+        _ => continue,
+      };
+      for &x in &clique {
+        self.alive[x] = false;
+      }
+      self.stats.low_degree += clique.len();
+      self.reductions.push(Reduction::Fixed(vec![clique]));
+      changed = true;
+    }
+    changed
+  }
+
+  // Whether u is adjacent to all of w's alive neighbors but v.
+  fn stands_in(&self, u: usize, w: usize, v: usize) -> bool {
+    (0..self.g.size)
+      .all(|x| x == v || !self.alive[x] || !self.adjacent(w, x) || self.adjacent(u, x))
+  }
+
+  // Min-degree greedy independent set of the alive vertices.
+  fn independent_set(&self) -> Vec<usize> {
+    let mut candidate = self.alive.clone();
+    let mut degrees: Vec<usize> = (0..self.g.size)
+      .map(|v| {
+        (0..self.g.size)
+          .filter(|&u| candidate[u] && self.adjacent(v, u))
+          .count()
+      })
+      .collect();
+    let mut chosen = vec![];
+    while let Some(v) = (0..self.g.size)
+      .filter(|&v| candidate[v])
+      .min_by_key(|&v| degrees[v])
+    {
+      chosen.push(v);
+      candidate[v] = false;
+      for u in 0..self.g.size {
+        if candidate[u] && self.adjacent(v, u) {
+          candidate[u] = false;
+          for w in 0..self.g.size {
+            if candidate[w] && self.adjacent(u, w) {
+              degrees[w] -= 1;
+            }
+          }
+        }
+      }
+    }
+    chosen
+  }
+
+  fn peel(&mut self) -> bool {
+    let independent = self.independent_set();
+    let bound = independent.len();
+    let mut protected = vec![false; self.g.size];
+    for &v in &independent {
+      protected[v] = true;
+    }
+    let mut non_neighbors: Vec<usize> = (0..self.g.size)
+      .map(|v| {
+        (0..self.g.size)
+          .filter(|&u| u != v && self.alive[u] && !self.adjacent(v, u))
+          .count()
+      })
+      .collect();
+    let mut queue: Vec<usize> = self
+      .alive_vertices()
+      .into_iter()
+      .filter(|&v| !protected[v] && non_neighbors[v] < bound)
+      .collect();
+    let mut changed = false;
+    while let Some(v) = queue.pop() {
+      if !self.alive[v] {
+        continue;
+      }
+      self.alive[v] = false;
+      self.reductions.push(Reduction::Peel(v));
+      self.stats.peeled += 1;
+      changed = true;
+      for u in 0..self.g.size {
+        if u != v && self.alive[u] && !self.adjacent(v, u) {
+          non_neighbors[u] -= 1;
+          if !protected[u] && non_neighbors[u] + 1 == bound {
+            queue.push(u);
+          }
+        }
+      }
+    }
+    changed
+  }
+}
+
+impl Graph {
+  // Applies the reductions until none applies. Quadratic to cubic in the
+  // vertex count, so it pays off for instances solved at length.
+  pub fn kernelize(&self) -> Kernel {
+    let mut reducer = Reducer {
+      g: self,
+      alive: vec![true; self.size],
+      reductions: vec![],
+      stats: KernelStats::default(),
+    };
+    loop {
+      let mut changed = reducer.peel();
+      changed |= reducer.matching_crown();
+      changed |= reducer.lp_crown();
+      changed |= reducer.low_degree();
+      if !changed {
+        break;
+      }
+    }
+    let vertices = reducer.alive_vertices();
+    let fixed_cliques = reducer
+      .reductions
+      .iter()
+      .map(|r| match r {
+        Reduction::Fixed(cliques) => cliques.len(),
+        Reduction::Peel(_) => 0,
+      })
+      .sum();
+    let mut graph = self.induced_subgraph(&vertices);
+    graph.rng = self.rng.clone_box();
+    Kernel {
+      graph,
+      vertices,
+      fixed_cliques,
+      stats: reducer.stats,
+      reductions: reducer.reductions,
+    }
+  }
+}
+
+impl fmt::Display for Kernel {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let removed = self.stats.crown_vertices
+      + self.stats.lp_crown_vertices
+      + self.stats.low_degree
+      + self.stats.peeled;
+    write!(
+      f,
+      "kernel: {} of {} vertices left, {} cliques fixed (crowns {}, lp crowns {}, degree <= 2 {}, peeled {})",
+      self.vertices.len(),
+      self.vertices.len() + removed,
+      self.fixed_cliques,
+      self.stats.crown_vertices,
+      self.stats.lp_crown_vertices,
+      self.stats.low_degree,
+      self.stats.peeled
+    )
+  }
+}
+
+impl Kernel {
+  // Turns a cover of the kernel into a cover of the original graph with
+  // fixed_cliques more cliques; optimal if the kernel's cover is.
+  pub fn lift(&self, original: &Graph, cover: &CliqueCover) -> CliqueCover {
+    let mut cliques: Vec<Vec<usize>> = cover
+      .cliques
+      .iter()
+      .map(|members| members.iter().map(|&i| self.vertices[i]).collect())
+      .collect();
+    for reduction in self.reductions.iter().rev() {
+      match reduction {
+        Reduction::Fixed(fixed) => cliques.extend(fixed.iter().cloned()),
+        Reduction::Peel(v) => {
+          let fits = cliques.iter_mut().find(|members| {
+            members
+              .iter()
+              .all(|&u| original.topology.vertices[*v].neighbors_bv.get_unchecked(u))
+          });
+          match fits {
+            Some(members) => members.push(*v),
+            None => cliques.push(vec![*v]),
+          }
+        }
+      }
+    }
+    CliqueCover { cliques }
+  }
+}
@@ -0,0 +1,172 @@
+// Known optima of the standard benchmarks, the DIMACS coloring graphs that
+// fetch.rs downloads: each one's chromatic number, which is the clique
+// cover number of its complement. A graph is recognized by a hash of its
+// adjacency that doesn't depend on how its vertices are numbered, so a
+// benchmark's complement, converted under another name say, is recognized
+// too. The vertex and edge counts come first, a cheap filter before the
+// hash. Benchmarks whose hash isn't in the table, those that can't be
+// rebuilt from a formula here, are recognized only by their file name
+// along with their counts. The counts are of undirected edges, each
+// counted once, though some of the files list every edge both ways.
+
+use alloc::vec::Vec;
+
+use crate::rng::mix64;
+use crate::Graph;
+
+pub struct KnownInstance {
+  pub name: &'static str,
+  pub vertices: usize,
+  pub edges: usize,
+  pub chromatic: usize,
+  // Graph::adjacency_hash of the benchmark, if it's known.
+  pub hash: Option<u64>,
+}
+
+const fn known(
+  name: &'static str,
+  vertices: usize,
+  edges: usize,
+  chromatic: usize,
+  hash: Option<u64>,
+) -> KnownInstance {
+  KnownInstance {
+    name,
+    vertices,
+    edges,
+    chromatic,
+    hash,
+  }
+}
+
+pub const KNOWN_INSTANCES: [KnownInstance; 25] = [
+  known("myciel3", 11, 20, 4, Some(0x4219_e922_d5e9_f874)),
+  known("myciel4", 23, 71, 5, Some(0x86c3_144d_d676_7fc4)),
+  known("myciel5", 47, 236, 6, Some(0x59cc_cfc2_c86c_a9be)),
+  known("myciel6", 95, 755, 7, Some(0x7414_de4f_219e_9ac1)),
+  known("myciel7", 191, 2360, 8, Some(0x6393_1c3f_429c_3300)),
+  known("queen5_5", 25, 160, 5, Some(0xd506_f00b_0d0d_1cf9)),
+  known("queen6_6", 36, 290, 7, Some(0xf783_41cb_32fd_b978)),
+  known("queen7_7", 49, 476, 7, Some(0xe261_1c19_3076_7c0d)),
+  known("queen8_8", 64, 728, 9, Some(0x8a59_a929_fa5d_2431)),
+  known("queen8_12", 96, 1368, 12, Some(0x6af5_d60d_7c48_02f0)),
+  known("queen9_9", 81, 1056, 10, Some(0x7fe9_5834_7be7_beb6)),
+  known("anna", 138, 493, 11, None),
+  known("david", 87, 406, 11, None),
+  known("homer", 561, 1629, 13, None),
+  known("huck", 74, 301, 11, None),
+  known("jean", 80, 254, 10, None),
+  known("games120", 120, 638, 9, None),
+  known("miles250", 128, 387, 8, None),
+  known("miles500", 128, 1170, 20, None),
+  known("DSJC125.1", 125, 736, 5, None),
+  known("DSJC125.5", 125, 3891, 17, None),
+  known("DSJC125.9", 125, 6961, 44, None),
+  known("le450_5a", 450, 5714, 5, None),
+  known("le450_15a", 450, 8168, 15, None),
+  known("le450_25a", 450, 8260, 25, None),
+];
+
+// What the registry knows about a graph: the benchmark it is, whether it
+// is that benchmark's complement, and so its clique cover number if known.
+pub struct Recognized {
+  pub instance: &'static KnownInstance,
+  pub complemented: bool,
+}
+
+impl Recognized {
+  // A benchmark's complement has its chromatic number as clique cover
+  // number; the benchmark's own clique cover number isn't in the table.
+  pub fn cover_number(&self) -> Option<usize> {
+    self.complemented.then_some(self.instance.chromatic)
+  }
+}
+
+impl Graph {
+  // The benchmark this graph is, or the complement of, by its counts and
+  // adjacency hash, or by name, its file name with directories and the
+  // .col extension allowed, for benchmarks without a hash.
+  pub fn recognize(&self, name: Option<&str>) -> Option<Recognized> {
+    let edges = self.edge_count();
+    let pairs = self.size * self.size.saturating_sub(1) / 2;
+    let stem = name.map(|name| {
+      let file = name.rsplit(['/', '\\']).next().unwrap_or(name);
+      file.strip_suffix(".col").unwrap_or(file)
+    });
+    // Each hash is computed at most once, and only past the counts.
+    let mut own_hash = None;
+    let mut complement_hash = None;
+    for complemented in [false, true] {
+      let edges = if complemented { pairs - edges } else { edges };
+      for instance in &KNOWN_INSTANCES {
+        if instance.vertices != self.size || instance.edges != edges {
+          continue;
+        }
+        let matched = match instance.hash {
+          Some(hash) => {
+            let cached = if complemented {
+              &mut complement_hash
+            } else {
+              &mut own_hash
+            };
+            let computed = *cached.get_or_insert_with(|| match complemented {
+              false => self.adjacency_hash(),
+              true => self.complement().adjacency_hash(),
+            });
+            computed == hash
+          }
+          None => Some(instance.name) == stem,
+        };
+        if matched {
+          return Some(Recognized {
+            instance,
+            complemented,
+          });
+        }
+      }
+    }
+    None
+  }
+
+  // A hash of the adjacency that relabeling the vertices leaves alone:
+  // colors start as degrees and are refined by the multiset of neighbors'
+  // colors (Weisfeiler-Lehman) until the classes stop splitting, and the
+  // hash is of the final multiset of colors. Isomorphic graphs hash alike;
+  // graphs the refinement can't tell apart do too, which is rare enough
+  // for telling benchmarks apart.
+  pub fn adjacency_hash(&self) -> u64 {
+    let n = self.size;
+    let mut colors: Vec<u64> = (0..n).map(|v| mix64(self.degree(v) as u64)).collect();
+    let mut classes = class_count(&colors);
+    let mut neighbor_colors = Vec::new();
+    for _ in 0..n {
+      let refined: Vec<u64> = (0..n)
+        .map(|v| {
+          neighbor_colors.clear();
+          neighbor_colors.extend(self.neighbors(v).map(|u| colors[u]));
+          neighbor_colors.sort_unstable();
+          neighbor_colors
+            .iter()
+            .fold(mix64(colors[v]), |hash, &color| mix64(hash ^ color))
+        })
+        .collect();
+      let refined_classes = class_count(&refined);
+      colors = refined;
+      if refined_classes == classes {
+        break;
+      }
+      classes = refined_classes;
+    }
+    colors.sort_unstable();
+    colors
+      .iter()
+      .fold(mix64(n as u64), |hash, &color| mix64(hash ^ color))
+  }
+}
+
+fn class_count(colors: &[u64]) -> usize {
+  let mut sorted = colors.to_vec();
+  sorted.sort_unstable();
+  sorted.dedup();
+  sorted.len()
+}
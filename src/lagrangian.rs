@@ -0,0 +1,224 @@
+// A Lagrangian bound for graphs too big for the LP of fractional.rs. Give
+// the cover up to U cliques (U from a DSatur cover), and move each vertex's
+// covering constraint into the objective with a weight lambda_v >= 0. Each
+// of the U slots then either stays empty or takes the clique C minimizing
+// 1 - lambda(C), so for every choice of weights
+//   L(lambda) = sum(lambda) - U * max(0, W - 1) <= theta,
+// where W is the heaviest clique's weight. Rescaled by W the weights are a
+// fractional independent set, which bounds at least as well:
+//   sum(lambda) / max(1, W) <= theta.
+// Subgradient steps raise L, starting from an independent set so the
+// bound never falls below the plain MIS bound. W is only estimated during
+// the steps, from a pool of heavy cliques found greedily; the best weights
+// are then certified by an exact search for the heaviest clique. If that
+// search runs out of nodes, its bound from a weighted coloring stands in,
+// which is valid but loose.
+
+use crate::bitset::BitVec;
+use crate::exact::min_degree_independent_set;
+use crate::Graph;
+use std::fmt;
+
+// Steps without improvement before the step size halves.
+const PATIENCE: usize = 20;
+const MIN_STEP_SCALE: f64 = 1e-4;
+// Greedy cliques grown per step, from the heaviest vertices.
+const GREEDY_STARTS: usize = 10;
+const MAX_POOL: usize = 1_000;
+const MAX_CERTIFY_NODES: usize = 1_000_000;
+
+pub struct LagrangianBound {
+  pub bound: usize,
+  // The certified value, sum(lambda) / max(1, W).
+  pub value: f64,
+  // The independent set it started from, the plain MIS bound.
+  pub independent_set: usize,
+  // The number of clique slots, U.
+  pub slots: usize,
+  pub iterations: usize,
+  // Whether the search for the heaviest clique finished.
+  pub certified_exactly: bool,
+}
+
+impl fmt::Display for LagrangianBound {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "lagrangian bound {} ({:.3} after {} iterations{}; independent set {})",
+      self.bound,
+      self.value,
+      self.iterations,
+      if self.certified_exactly {
+        ""
+      } else {
+        ", loosely certified"
+      },
+      self.independent_set
+    )
+  }
+}
+
+impl Graph {
+  pub fn lagrangian_bound(&self, max_iterations: usize) -> LagrangianBound {
+    let n = self.size;
+    let independent = min_degree_independent_set(self);
+    let slots = self.dsatur_cover().len();
+    let mut lambda = vec![0.0; n];
+    for &v in &independent {
+      lambda[v] = 1.0;
+    }
+    let mut best_lambda = lambda.clone();
+    let mut best = independent.len() as f64;
+    let mut pool: Vec<Vec<usize>> = vec![];
+    let mut step_scale = 2.0;
+    let mut since_improved = 0;
+    let mut iterations = 0;
+    while iterations < max_iterations && best < slots as f64 {
+      iterations += 1;
+      let weight = |clique: &Vec<usize>| clique.iter().map(|&v| lambda[v]).sum::<f64>();
+      for clique in self.greedy_heavy_cliques(&lambda) {
+        if pool.len() < MAX_POOL && !pool.contains(&clique) {
+          pool.push(clique);
+        }
+      }
+      let Some(heaviest) = pool.iter().max_by(|a, b| weight(a).total_cmp(&weight(b))) else {
+        break;
+      };
+      let heaviest_weight = weight(heaviest);
+      let total: f64 = lambda.iter().sum();
+      let excess = (heaviest_weight - 1.0).max(0.0);
+      let value = total - slots as f64 * excess;
+      let scaled = total / heaviest_weight.max(1.0);
+      if scaled > best + 1e-9 {
+        best = scaled;
+        best_lambda.clone_from(&lambda);
+        since_improved = 0;
+      } else {
+        since_improved += 1;
+        if since_improved >= PATIENCE {
+          step_scale /= 2.0;
+          since_improved = 0;
+          if step_scale < MIN_STEP_SCALE {
+            break;
+          }
+        }
+      }
+      let mut subgradient = vec![1.0; n];
+      if excess > 0.0 {
+        for &v in heaviest {
+          subgradient[v] -= slots as f64;
+        }
+      }
+      let norm: f64 = subgradient.iter().map(|g| g * g).sum();
+      let step = step_scale * (slots as f64 - value).max(1.0) / norm;
+      for (weight, g) in lambda.iter_mut().zip(&subgradient) {
+        *weight = (*weight + step * g).max(0.0);
+      }
+    }
+    let (heaviest, certified_exactly) = self.heaviest_clique_weight(&best_lambda);
+    let value = best_lambda.iter().sum::<f64>() / heaviest.max(1.0);
+    let bound = ((value - 1e-6).ceil().max(0.0) as usize)
+      .clamp(independent.len(), slots.max(independent.len()));
+    LagrangianBound {
+      bound,
+      value,
+      independent_set: independent.len(),
+      slots,
+      iterations,
+      certified_exactly,
+    }
+  }
+
+  // Grows a clique from each of the heaviest vertices, adding the heaviest
+  // vertex that still fits until none does.
+  fn greedy_heavy_cliques(&self, lambda: &[f64]) -> Vec<Vec<usize>> {
+    let mut order: Vec<usize> = (0..self.size).filter(|&v| lambda[v] > 0.0).collect();
+    order.sort_by(|&a, &b| lambda[b].total_cmp(&lambda[a]));
+    let mut cliques = vec![];
+    for &start in order.iter().take(GREEDY_STARTS) {
+      let mut clique = vec![start];
+      let mut joinable = self.topology.vertices[start].neighbors_bv.clone();
+      while let Some(&v) = order.iter().find(|&&v| joinable.get_unchecked(v)) {
+        clique.push(v);
+        joinable.and_inplace(&self.topology.vertices[v].neighbors_bv);
+      }
+      clique.sort_unstable();
+      cliques.push(clique);
+    }
+    cliques
+  }
+
+  // The heaviest clique's weight by branch and bound, and whether the
+  // search finished; if not, an upper bound on it.
+  fn heaviest_clique_weight(&self, lambda: &[f64]) -> (f64, bool) {
+    let mut candidates: Vec<usize> = (0..self.size).filter(|&v| lambda[v] > 0.0).collect();
+    candidates.sort_by(|&a, &b| lambda[b].total_cmp(&lambda[a]));
+    let mut search = CliqueSearch {
+      g: self,
+      lambda,
+      best: 0.0,
+      nodes_left: MAX_CERTIFY_NODES,
+    };
+    if search.expand(0.0, &candidates) {
+      (search.best, true)
+    } else {
+      (
+        coloring_bound(self, lambda, &candidates).max(search.best),
+        false,
+      )
+    }
+  }
+}
+
+struct CliqueSearch<'a> {
+  g: &'a Graph,
+  lambda: &'a [f64],
+  best: f64,
+  nodes_left: usize,
+}
+
+impl CliqueSearch<'_> {
+  // Candidates come heaviest first; returns false once out of nodes.
+  fn expand(&mut self, weight: f64, candidates: &[usize]) -> bool {
+    if self.nodes_left == 0 {
+      return false;
+    }
+    self.nodes_left -= 1;
+    self.best = self.best.max(weight);
+    for (i, &v) in candidates.iter().enumerate() {
+      let rest = &candidates[i..];
+      if weight + coloring_bound(self.g, self.lambda, rest) <= self.best + 1e-12 {
+        break;
+      }
+      let inner: Vec<usize> = candidates[(i + 1)..]
+        .iter()
+        .copied()
+        .filter(|&u| self.g.topology.vertices[v].neighbors_bv.get_unchecked(u))
+        .collect();
+      if !self.expand(weight + self.lambda[v], &inner) {
+        return false;
+      }
+    }
+    true
+  }
+}
+
+// Colors the vertices greedily in the given order (heaviest first) and adds
+// up each color's first, so heaviest, weight: a clique has at most one
+// vertex of each color.
+fn coloring_bound(g: &Graph, lambda: &[f64], vertices: &[usize]) -> f64 {
+  let mut total = 0.0;
+  // blocked[c]: the neighbors of color c's members.
+  let mut blocked: Vec<BitVec> = vec![];
+  for &v in vertices {
+    let neighbors_bv = &g.topology.vertices[v].neighbors_bv;
+    match blocked.iter_mut().find(|colored| !colored.get_unchecked(v)) {
+      Some(colored) => colored.or_inplace(neighbors_bv),
+      None => {
+        total += lambda[v];
+        blocked.push(neighbors_bv.clone());
+      }
+    }
+  }
+  total
+}
@@ -0,0 +1,1131 @@
+// TODO: Test limiting the number of multi-vertex cliques to one fewer than the best we've found
+//       and then forcing remaining vertices into existing multi-vertex cliques,
+//       removing conflicting vertices.
+
+// TODO: Explore combining iterated greedy with Tabu.
+
+// Both of the above to-do's are discussed in:
+// A survey of local search methods for graph coloring, by Galinier & Hertz
+
+// vcc = vertex clique cover
+// mis = maximum independent set
+// ..._bv = bitvector (BitVec)
+// ..._ct = count (usize)
+// force compile
+
+// Without the std feature only the core solver is built, needing nothing
+// but alloc: the graph and its restart search, covers, bounds, graph
+// operations and the exact finish. Timings then read zero (see clock.rs).
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use bitset::BitVec;
+use cadence::CheckClock;
+use core::fmt;
+use forbidden::{trim_to_limits, ForbiddenSet};
+use order::CliqueOrder; // https://docs.rs/bitvec_simd/0.20.5/bitvec_simd/struct.BitVecSimd.html
+use planted::PlantedSizes;
+use rand_core::RngCore;
+use rng::{below_u64, default_rng, entropy_seed, shuffle, DefaultRng, SearchRng};
+use scratch::ScratchPool;
+#[cfg(feature = "smallvec")]
+use smallvec::SmallVec; // https://docs.rs/smallvec/1.10.0/smallvec/struct.SmallVec.html
+#[cfg(feature = "pretty")]
+use thousands::Separable;
+use topology::GraphTopology;
+
+pub mod anneal;
+#[cfg(feature = "std")]
+pub mod batch;
+pub mod bitset;
+pub mod bounds;
+pub mod cadence;
+#[cfg(feature = "std")]
+pub mod checkpoint;
+pub mod clock;
+pub mod cograph;
+pub mod cover;
+#[cfg(feature = "std")]
+pub mod decide;
+pub mod diff;
+#[cfg(feature = "std")]
+pub mod durable;
+#[cfg(feature = "std")]
+pub mod ejection;
+pub mod error;
+#[cfg(feature = "std")]
+pub mod eta;
+pub mod exact;
+#[cfg(feature = "std")]
+pub mod families;
+#[cfg(feature = "std")]
+pub mod fetch;
+pub mod finish;
+pub mod forbidden;
+#[cfg(feature = "std")]
+pub mod formats;
+pub mod fractional;
+pub mod freeze;
+#[cfg(feature = "ilp")]
+pub mod ilp;
+#[cfg(feature = "std")]
+pub mod improve;
+pub mod invariants;
+#[cfg(feature = "std")]
+pub mod kernel;
+pub mod known;
+#[cfg(feature = "std")]
+pub mod lagrangian;
+#[cfg(feature = "std")]
+pub mod links;
+#[cfg(feature = "std")]
+pub mod lns;
+pub mod locality;
+#[cfg(feature = "std")]
+pub mod manifest;
+#[cfg(feature = "std")]
+pub mod maxsat;
+#[cfg(feature = "std")]
+pub mod memetic;
+#[cfg(feature = "std")]
+pub mod minizinc;
+pub mod multilevel;
+#[cfg(feature = "std")]
+pub mod multistart;
+pub mod ops;
+#[cfg(feature = "std")]
+pub mod partial;
+#[cfg(feature = "std")]
+pub mod pareto;
+pub mod order;
+pub mod planted;
+#[cfg(feature = "std")]
+pub mod portfolio;
+#[cfg(feature = "std")]
+pub mod probe;
+pub mod profile;
+#[cfg(feature = "std")]
+pub mod quotient;
+#[cfg(feature = "std")]
+pub mod race;
+pub mod removal;
+pub mod repair;
+pub mod rng;
+pub mod scratch;
+#[cfg(feature = "std")]
+pub mod selftest;
+pub mod separator;
+pub mod shared;
+pub mod snapshot;
+pub mod solve;
+#[cfg(feature = "spectral")]
+pub mod spectral;
+pub mod starts;
+pub mod stats;
+pub mod strategy;
+#[cfg(feature = "std")]
+pub mod summary;
+#[cfg(feature = "std")]
+pub mod tabu;
+#[cfg(feature = "std")]
+pub mod terminal;
+#[cfg(feature = "std")]
+pub mod threads;
+#[cfg(feature = "std")]
+pub mod throttle;
+pub mod topology;
+#[cfg(feature = "std")]
+pub mod ttt;
+#[cfg(feature = "std")]
+pub mod verify;
+
+#[cfg(feature = "std")]
+pub use batch::solve_batch;
+pub use cover::CliqueCover;
+pub use error::VccError;
+#[cfg(feature = "std")]
+pub use quotient::Quotient;
+pub use shared::SharedBounds;
+pub use solve::{SolveParams, SolveResult};
+pub use strategy::SearchStrategy;
+
+// Member lists live inline up to 256 members with the smallvec feature,
+// on the heap without it.
+#[cfg(feature = "smallvec")]
+type Members = SmallVec<[usize; 256]>;
+#[cfg(not(feature = "smallvec"))]
+type Members = Vec<usize>;
+
+// The neighbors of a clique are those vertices that are not in the clique,
+// and are adjacent to every vertex in the clique. members_ct and
+// neighbors_ct are the popcounts of members_bv and neighbors_bv, kept up
+// to date by every routine that changes them; a vertex's neighbors_ct is
+// its degree.
+#[derive(Clone)]
+struct Clique {
+  members_bv: BitVec,
+  members_ct: usize,
+  members: Members,
+  neighbors_bv: BitVec,
+  neighbors_ct: usize,
+  length: usize,
+  id: usize,
+  is_active: bool,
+  // Frozen cliques keep their members; see freeze.rs.
+  is_frozen: bool,
+}
+
+// A clique has at least one member, and at least zero neighbors.
+// A clique with exactly one member is also referred to as a vertex or node.
+impl Clique {
+  fn new(num_vertices: usize, id: usize) -> Clique {
+    Clique {
+      members_bv: BitVec::zeros(num_vertices),
+      members_ct: 1,
+      members: Members::from_iter([id]),
+      neighbors_bv: BitVec::zeros(num_vertices),
+      neighbors_ct: 0,
+      length: num_vertices,
+      id,
+      is_active: true,
+      is_frozen: false,
+    }
+  }
+
+  fn has_neighbors(&self) -> bool {
+    self.neighbors_ct > 0
+  }
+}
+
+// One glyph per vertex: a member, a neighbor of every member, or neither.
+// The alternate form, {:#}, draws them in ASCII for consoles without
+// Unicode (see terminal.rs).
+impl fmt::Display for Clique {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let glyphs = if f.alternate() {
+      ["#", "+", "."]
+    } else {
+      ["\u{25AA}", "\u{25AB}", "\u{2B1D}"]
+    };
+    let mut ret_str = String::new();
+    for i in 0..self.length {
+      if self.members_bv.get(i) == Some(true) {
+        ret_str += glyphs[0];
+      } else if self.neighbors_bv.get(i) == Some(true) {
+        ret_str += glyphs[1];
+      } else {
+        ret_str += glyphs[2];
+      }
+    }
+    if !self.is_active {
+      ret_str += " I";
+    } else {
+      ret_str += &(" ".to_owned() + &self.members_ct.to_string());
+    }
+    write!(f, "{}", ret_str)
+  }
+}
+
+struct CliqueMaker {
+  id: usize,
+  length: usize,
+}
+
+impl CliqueMaker {
+  fn new(num_vertices: usize) -> CliqueMaker {
+    CliqueMaker {
+      id: 0,
+      length: num_vertices,
+    }
+  }
+
+  // Returns a new clique with one member (incrementing which node is
+  // in the clique), and no neighbors
+  fn make_clique(&mut self) -> Clique {
+    let mut ret_clique: Clique = Clique::new(self.length, self.id);
+    ret_clique.members_bv.set(self.id, true);
+    self.id += 1;
+    ret_clique
+  }
+
+  fn get_copy_of_clique(&self, clique_to_copy: &Clique) -> Clique {
+    let mut ret_clique: Clique = Clique::new(clique_to_copy.length, clique_to_copy.id);
+    transcribe_clique_onto_clique(clique_to_copy, &mut ret_clique);
+    ret_clique
+  }
+}
+
+fn transcribe_clique_onto_clique(source_clique: &Clique, target_clique: &mut Clique) {
+  target_clique.members_bv.set_all_false();
+  target_clique.members.clear();
+  if source_clique.members_ct == 1 {
+    target_clique.members_bv.set(source_clique.members[0], true);
+    target_clique.members.push(source_clique.members[0]);
+  } else {
+    target_clique
+      .members_bv
+      .or_inplace(&source_clique.members_bv);
+    target_clique
+      .members
+      .extend_from_slice(&source_clique.members);
+  }
+  target_clique.members_ct = source_clique.members_ct;
+  target_clique.neighbors_bv.set_all_false();
+  target_clique
+    .neighbors_bv
+    .or_inplace(&source_clique.neighbors_bv);
+  target_clique.neighbors_ct = source_clique.neighbors_ct;
+  target_clique.length = source_clique.length;
+  target_clique.id = source_clique.id;
+  target_clique.is_active = source_clique.is_active;
+  target_clique.is_frozen = source_clique.is_frozen;
+}
+
+#[derive(Clone)]
+pub struct Graph {
+  pub(crate) size: usize,
+  // Shared by clones; see topology.rs.
+  pub(crate) topology: Arc<GraphTopology>,
+  pub(crate) cliques: Vec<Clique>,
+  pub(crate) cliques_ct: usize,
+  // Scratch buffers for the transfers; see scratch.rs.
+  pub(crate) scratch: ScratchPool,
+  // The id of the clique holding each vertex, kept up to date by every
+  // routine that moves vertices, so lookups needn't scan the cliques.
+  // Clique ids are stable while the cliques are reordered.
+  pub(crate) vertex_clique: Vec<usize>,
+  pub(crate) rng: Box<dyn SearchRng>,
+  // See forbidden.rs.
+  pub(crate) forbidden: Vec<ForbiddenSet>,
+  // See planted.rs.
+  pub(crate) planted: Option<CliqueCover>,
+  // Search iterations run on this graph, over all its restarts.
+  pub(crate) iterations: usize,
+  pub(crate) last_restart: RestartTrace,
+}
+
+// How far the last restart (vcc_run_restart) got, for progress reports:
+// the library prints nothing itself.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RestartTrace {
+  pub iterations: usize,
+  // The annealing interval it ended with, grown from the schedule's initial one.
+  pub iterations_per_annealing: usize,
+}
+
+impl Graph {
+  pub fn new(num_vertices: usize) -> Graph {
+    let mut clique_maker = CliqueMaker::new(num_vertices);
+    let mut vertices_vec: Vec<Clique> = Vec::with_capacity(num_vertices);
+    let mut cliques_vec: Vec<Clique> = Vec::with_capacity(num_vertices);
+
+    for _i in 0..num_vertices {
+      let vertex = clique_maker.make_clique();
+      let clique = clique_maker.get_copy_of_clique(&vertex);
+      vertices_vec.push(vertex);
+      cliques_vec.push(clique);
+    }
+
+    Graph {
+      size: num_vertices,
+      topology: Arc::new(GraphTopology {
+        vertices: vertices_vec,
+      }),
+      cliques: cliques_vec,
+      cliques_ct: num_vertices,
+      scratch: ScratchPool::new(num_vertices),
+      vertex_clique: (0..num_vertices).collect(),
+      rng: Box::new(default_rng(entropy_seed())),
+      forbidden: vec![],
+      planted: None,
+      iterations: 0,
+      last_restart: RestartTrace::default(),
+    }
+  }
+
+  // Restarts the search's generator as the default generator with this seed.
+  pub fn seed(&mut self, seed: u64) {
+    self.rng = Box::new(default_rng(seed));
+  }
+
+  // The generator searches draw from outside a solve's restarts, and
+  // solves draw their restart seeds from; it needn't be seedable (see
+  // rng.rs).
+  pub fn set_rng<R: RngCore + Clone + Send + Sync + 'static>(&mut self, rng: R) {
+    self.rng = Box::new(rng);
+  }
+
+  // A copy of the generator's state, if it is the default generator
+  // (only that one can be saved in checkpoints).
+  pub fn default_rng_state(&self) -> Option<DefaultRng> {
+    self.rng.as_ref().as_any().downcast_ref::<DefaultRng>().cloned()
+  }
+
+  fn activate_inactive_clique(&mut self) -> bool {
+    if self.size == self.cliques_ct {
+      return false;
+    }
+
+    self.cliques[self.cliques_ct].is_active = true;
+    self.cliques_ct += 1;
+    true
+  }
+
+  fn transfer_vertex_into_clique(
+    clique_into: &mut Clique,
+    clique_from: &mut Clique,
+    utility_bv: &mut BitVec,
+    vertices_vec: &[Clique],
+    vertex_clique: &mut [usize],
+    forbidden: &[ForbiddenSet],
+    vertex_id: usize,
+  ) {
+    if !clique_into.has_neighbors() || clique_from.is_frozen {
+      return;
+    }
+
+    if !clique_from.members_bv.get_unchecked(vertex_id) {
+      return;
+    }
+
+    if !clique_into.neighbors_bv.get_unchecked(vertex_id) {
+      return;
+    }
+
+    if !clique_into.is_active {
+      return;
+    }
+
+    // clear utility_bv
+    utility_bv.set_all_false();
+
+    // set utility_bv to be true for all transferrable vertices
+    utility_bv.set(vertex_id, true);
+
+    Self::transfer_vertices_in_utility_bv_between_cliques(
+      clique_into,
+      clique_from,
+      utility_bv,
+      vertices_vec,
+      vertex_clique,
+      forbidden,
+    )
+  }
+
+  fn transfer_compatible_vertices(
+    clique_into: &mut Clique,
+    clique_from: &mut Clique,
+    utility_bv: &mut BitVec,
+    vertices_vec: &[Clique],
+    vertex_clique: &mut [usize],
+    forbidden: &[ForbiddenSet],
+  ) {
+    if !clique_into.has_neighbors() || clique_from.is_frozen {
+      return;
+    }
+
+    // clear utility_bv
+    utility_bv.set_all_false();
+
+    // set utility_bv to be true for all transferrable vertices (starting
+    // from the members, which span fewer blocks of a blocked bitvector)
+    utility_bv.or_inplace(&clique_from.members_bv);
+    utility_bv.and_inplace(&clique_into.neighbors_bv);
+    if utility_bv.none() {
+      return;
+    }
+
+    Self::transfer_vertices_in_utility_bv_between_cliques(
+      clique_into,
+      clique_from,
+      utility_bv,
+      vertices_vec,
+      vertex_clique,
+      forbidden,
+    )
+  }
+
+  fn transfer_vertices_in_utility_bv_between_cliques(
+    clique_into: &mut Clique,
+    clique_from: &mut Clique,
+    utility_bv: &mut BitVec,
+    vertices_vec: &[Clique],
+    vertex_clique: &mut [usize],
+    forbidden: &[ForbiddenSet],
+  ) {
+    if !forbidden.is_empty() {
+      trim_to_limits(forbidden, &clique_into.members_bv, utility_bv);
+      if utility_bv.none() {
+        return;
+      }
+    }
+
+    // update members_bv for both cliques
+    clique_into.members_bv.or_inplace(utility_bv);
+    clique_from.members_bv.xor_inplace(utility_bv);
+
+    // update members & neighbors_bv for both cliques; clique_from's
+    // neighbors restart from its first remaining member's rather than from
+    // all ones, so only an emptied clique has every bit set
+    let mut kept_one = false;
+    for i in (0..clique_from.members_ct).rev() {
+      let member_neighbors_bv = &vertices_vec[clique_from.members[i]].neighbors_bv;
+      if utility_bv.get_unchecked(clique_from.members[i]) {
+        vertex_clique[clique_from.members[i]] = clique_into.id;
+        clique_into.neighbors_bv.and_inplace(member_neighbors_bv);
+        clique_into.members.push(clique_from.members.swap_remove(i));
+        clique_from.members_ct -= 1;
+        clique_into.members_ct += 1;
+      } else if kept_one {
+        clique_from.neighbors_bv.and_inplace(member_neighbors_bv);
+      } else {
+        kept_one = true;
+        clique_from.neighbors_bv.set_all_false();
+        clique_from.neighbors_bv.or_inplace(member_neighbors_bv);
+      }
+    }
+
+    if clique_from.members_ct == 0 {
+      clique_from.neighbors_bv.set_all_true();
+      clique_from.neighbors_ct = clique_from.length;
+      clique_from.is_active = false;
+    } else {
+      clique_from.neighbors_ct = clique_from.neighbors_bv.count_ones();
+    }
+    clique_into.neighbors_ct = clique_into.neighbors_bv.count_ones();
+
+    #[cfg(any(debug_assertions, feature = "paranoid"))]
+    invariants::assert_transfer(clique_into, clique_from, vertices_vec);
+  }
+
+  // Moves every vertex of active clique j that fits into active clique i.
+  // Exposed for the benchmarks.
+  #[doc(hidden)]
+  pub fn transfer_compatible_vertices_between(&mut self, i: usize, j: usize) {
+    assert!(i < j && j < self.cliques_ct);
+    let mut utility_bv = self.scratch.take();
+    let (cliques_before_j, cliques_from_j) = self.cliques.split_at_mut(j);
+    Self::transfer_compatible_vertices(
+      &mut cliques_before_j[i],
+      &mut cliques_from_j[0],
+      &mut utility_bv,
+      &self.topology.vertices,
+      &mut self.vertex_clique,
+      &self.forbidden,
+    );
+    self.scratch.give(utility_bv);
+  }
+
+  pub fn shuffle_active_cliques(&mut self) {
+    shuffle(&mut self.rng, &mut self.cliques[0..(self.cliques_ct)]);
+  }
+
+  pub fn reverse_active_cliques(&mut self) {
+    self.cliques[0..(self.cliques_ct)].reverse();
+  }
+
+  pub fn vcc_greedy(&mut self) {
+    let mut utility_bv = self.scratch.take();
+    // Try to merge every active pair of cliques
+    for i in 0..self.cliques_ct.saturating_sub(1) {
+      if !self.cliques[i].is_active {
+        continue;
+      }
+      for j in (i + 1)..(self.cliques_ct) {
+        if !self.cliques[j].is_active {
+          continue;
+        }
+        let (cliques_before_j, cliques_from_j) = self.cliques.split_at_mut(j);
+        let cliques_i: &mut Clique = &mut cliques_before_j[i];
+        let cliques_j: &mut Clique = &mut cliques_from_j[0];
+        Self::transfer_compatible_vertices(
+          cliques_i,
+          cliques_j,
+          &mut utility_bv,
+          &self.topology.vertices,
+          &mut self.vertex_clique,
+          &self.forbidden,
+        );
+      }
+    }
+    self.scratch.give(utility_bv);
+
+    let mut i = 1;
+    loop {
+      if i >= self.cliques_ct {
+        break;
+      }
+      if self.cliques[i].is_active {
+        i += 1;
+      } else if self.cliques[self.cliques_ct - 1].is_active {
+        self.cliques.swap(i, self.cliques_ct - 1);
+        i += 1;
+        self.cliques_ct -= 1;
+      } else {
+        self.cliques_ct -= 1;
+      }
+    }
+  }
+
+  pub fn vcc_iterated_greedy(&mut self, reverse_fraction: f64) {
+    self.vcc_iterated_greedy_ordered(reverse_fraction, &CliqueOrder::Shuffle);
+  }
+
+  pub fn vcc_run_iterations_to_target(
+    &mut self,
+    num_iterations: usize,
+    target: usize,
+    reverse_fraction: f64,
+  ) -> bool {
+    let params = SolveParams {
+      max_iterations: num_iterations,
+      reverse_fraction,
+      target,
+      max_restarts: 1,
+      ..SolveParams::default()
+    };
+    self.vcc_run_restart(&params, None)
+  }
+
+  // One restart's worth of the above, configured by params (max_restarts
+  // is ignored). With bounds, also publishes improvements to bounds shared
+  // with other workers, and gives up (returning false) once some worker has
+  // reached the target or matched the best known lower bound.
+  pub fn vcc_run_restart(&mut self, params: &SolveParams, bounds: Option<&SharedBounds>) -> bool {
+    let target = params.target;
+    let schedule = &params.annealing;
+    self.last_restart = RestartTrace {
+      iterations: 0,
+      iterations_per_annealing: schedule.initial_iterations,
+    };
+    if self.settle_trivial_cover() {
+      if let Some(bounds) = bounds {
+        bounds.offer_upper(self.cliques_ct);
+        bounds.offer_lower(self.cliques_ct);
+      }
+      return self.cliques_ct <= target;
+    }
+    // The starting cover may already be good enough.
+    if self.cliques_ct <= target {
+      if let Some(bounds) = bounds {
+        bounds.offer_upper(self.cliques_ct);
+      }
+      return true;
+    }
+    let mut pri_cliques = self.cliques_ct;
+    let mut iterations_per_annealing = schedule.initial_iterations;
+    let annealings_per_slowdown = schedule.annealings_per_slowdown;
+    let mut cur_annealing_iterations: usize = 0;
+    let mut cur_annealing_annealings: usize = 0;
+    // Computed the first time an exact finish is considered.
+    let mut lower_bound: Option<usize> = None;
+    let check_iterations = params.check.iterations.max(1);
+    let mut check_clock = CheckClock::new(&params.check);
+    for i in 1..(params.max_iterations + 1) {
+      if let Some(bounds) = bounds {
+        if bounds.is_done(target) {
+          return false;
+        }
+      }
+      self.iterations += 1;
+      self.last_restart.iterations = i;
+      cur_annealing_iterations += 1;
+      // Stalled: finish exactly if configured and close enough, else anneal!
+      if cur_annealing_iterations >= iterations_per_annealing {
+        cur_annealing_iterations = 0;
+        let finished_exactly = match &params.finish {
+          Some(finish) => self.try_exact_finish(finish, bounds, &mut lower_bound),
+          None => false,
+        };
+        if !finished_exactly {
+          cur_annealing_annealings += 1;
+          if cur_annealing_annealings >= annealings_per_slowdown {
+            cur_annealing_annealings = 0;
+            iterations_per_annealing = schedule.grow(iterations_per_annealing);
+            self.last_restart.iterations_per_annealing = iterations_per_annealing;
+          }
+
+          // open a new clique and seed it
+          self.seed_new_clique(schedule.seed_size, schedule.donor);
+
+          // run one iteration with reverse fraction at 100% (so the new guy is first)
+          self.vcc_iterated_greedy(1.0);
+        }
+      }
+      self.vcc_iterated_greedy_ordered(params.reverse_fraction_at(i), &params.order);
+      if let Some(freeze) = &params.freeze {
+        self.freeze_cliques(freeze);
+      }
+      // Timed checks only compare, leaving the search as it was (see
+      // cadence.rs).
+      if i % check_iterations == 0 || self.cliques_ct < pri_cliques {
+        if self.cliques_ct < pri_cliques {
+          cur_annealing_iterations = 0;
+        }
+
+        /*println!(
+          "Iteration {:0>3}_{:0>3}_{:0>3}: {} -> {} ({:?})",
+          (i % 1_000_000_000) / 1_000_000,
+          (i % 1_000_000) / 1_000,
+          i % 1000,
+          pri_cliques,
+          self.cliques_ct
+        );*/
+        pri_cliques = self.cliques_ct;
+        check_clock.restart();
+        if self.meets_target(target, bounds) {
+          return true;
+        }
+      } else if check_clock.is_due() && self.meets_target(target, bounds) {
+        return true;
+      }
+    }
+    false
+  }
+
+  // Offers the cover to bounds, then whether it has at most target cliques,
+  // or as few as the best known lower bound.
+  fn meets_target(&self, target: usize, bounds: Option<&SharedBounds>) -> bool {
+    let mut target = target;
+    if let Some(bounds) = bounds {
+      bounds.offer_upper(self.cliques_ct);
+      target = target.max(bounds.lower());
+    }
+    self.cliques_ct <= target
+  }
+
+  // Edgeless graphs (including those with fewer than two vertices) are
+  // covered by their singletons, and graphs whose complement is bipartite
+  // (complete ones included) by at most two cliques; cographs are solved
+  // through their cotree. For those, sets the cliques to that optimal
+  // cover and returns true; there is nothing left to search for. Covers
+  // that break a forbidden set don't count.
+  fn settle_trivial_cover(&mut self) -> bool {
+    if self.topology.vertices.iter().all(|v| !v.has_neighbors()) {
+      self.conform_cliques_to_vertices();
+      return true;
+    }
+    let settled = self
+      .small_cover()
+      .or_else(|| self.cograph_cover())
+      .filter(|cover| self.keeps_limits(&cover.cliques));
+    if let Some(cover) = settled {
+      self.load_cover_unchecked(&cover);
+      return true;
+    }
+    false
+  }
+
+  pub fn conform_cliques_to_vertices(&mut self) {
+    for i in 0..self.size {
+      transcribe_clique_onto_clique(&self.topology.vertices[i], &mut self.cliques[i]);
+      self.vertex_clique[i] = self.cliques[i].id;
+    }
+    self.cliques_ct = self.size;
+  }
+
+  pub fn to_vertex_string(&self) -> String {
+    let mut ret_str = String::new();
+    for i in 0..(self.size) {
+      ret_str += &self.topology.vertices[i].to_string();
+      ret_str += "\n";
+    }
+    ret_str
+  }
+
+  pub fn size(&self) -> usize {
+    self.size
+  }
+
+  pub fn cliques_ct(&self) -> usize {
+    self.cliques_ct
+  }
+
+  // Search iterations (see vcc_run_restart) run on this graph so far, over
+  // every restart of every search.
+  pub fn iterations(&self) -> usize {
+    self.iterations
+  }
+
+  pub fn last_restart(&self) -> RestartTrace {
+    self.last_restart
+  }
+
+  // Callers that build a graph edge by edge should finish with
+  // conform_cliques_to_vertices() so the search state sees the edges.
+  pub fn add_edge(&mut self, u: usize, v: usize) -> Result<(), VccError> {
+    if u >= self.size || v >= self.size {
+      return Err(VccError::InvalidGraph(format!(
+        "edge ({}, {}) in a graph with {} vertices",
+        u, v, self.size
+      )));
+    }
+    if u == v {
+      return Err(VccError::InvalidGraph(format!("self-loop at vertex {}", u)));
+    }
+    self.link(u, v);
+    Ok(())
+  }
+
+  // Builds a graph from streamed edges, say off a database cursor, without
+  // collecting them first. With no vertex count the graph grows to fit the
+  // largest vertex seen, doubling its room as needed, so it ends with no
+  // isolated vertices past that one. The cliques are singletons, ready to
+  // solve.
+  pub fn from_edge_iter<I: IntoIterator<Item = (usize, usize)>>(
+    num_vertices: Option<usize>,
+    edges: I,
+  ) -> Result<Graph, VccError> {
+    let mut g = Graph::new(num_vertices.unwrap_or(0));
+    let mut seen = 0;
+    for (u, v) in edges {
+      let needed = u.max(v) + 1;
+      if num_vertices.is_none() && needed > g.size {
+        let mut grown = Graph::new(needed.max(2 * g.size));
+        grown.copy_edges_from(&g, 0);
+        g = grown;
+      }
+      g.add_edge(u, v)?;
+      seen = seen.max(needed);
+    }
+    if num_vertices.is_none() && seen < g.size {
+      let mut fitted = Graph::new(seen);
+      fitted.copy_edges_from(&g, 0);
+      g = fitted;
+    }
+    g.conform_cliques_to_vertices();
+    Ok(g)
+  }
+
+  // add_edge for callers that already know the edge is valid. Linking an
+  // edge twice changes nothing.
+  pub(crate) fn link(&mut self, u: usize, v: usize) {
+    if self.topology.vertices[u].neighbors_bv.get_unchecked(v) {
+      return;
+    }
+    let vertices = &mut Arc::make_mut(&mut self.topology).vertices;
+    vertices[u].neighbors_bv.set(v, true);
+    vertices[v].neighbors_bv.set(u, true);
+    vertices[u].neighbors_ct += 1;
+    vertices[v].neighbors_ct += 1;
+  }
+
+  // The reverse of link, for generators that move edges.
+  pub(crate) fn unlink(&mut self, u: usize, v: usize) {
+    if !self.topology.vertices[u].neighbors_bv.get_unchecked(v) {
+      return;
+    }
+    let vertices = &mut Arc::make_mut(&mut self.topology).vertices;
+    vertices[u].neighbors_bv.set(v, false);
+    vertices[v].neighbors_bv.set(u, false);
+    vertices[u].neighbors_ct -= 1;
+    vertices[v].neighbors_ct -= 1;
+  }
+
+  // Counts kept alongside the search state, so orderings and pruning that
+  // look at sizes don't have to count bits.
+  pub fn degree(&self, v: usize) -> usize {
+    self.topology.degree(v)
+  }
+
+  // Panics if u or v is out of range, as degree does.
+  pub fn has_edge(&self, u: usize, v: usize) -> bool {
+    self.topology.has_edge(u, v)
+  }
+
+  // v's neighbors, in increasing order.
+  pub fn neighbors(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+    self.topology.neighbors(v)
+  }
+
+  pub fn edge_count(&self) -> usize {
+    self.topology.edge_count()
+  }
+
+  // Edges over vertex pairs; one for graphs without pairs, which are
+  // trivially complete.
+  pub fn density(&self) -> f64 {
+    let pair_ct = self.size * self.size.saturating_sub(1) / 2;
+    if pair_ct == 0 {
+      1.0
+    } else {
+      self.edge_count() as f64 / pair_ct as f64
+    }
+  }
+
+  // Members of active clique i.
+  pub fn clique_size(&self, i: usize) -> usize {
+    assert!(i < self.cliques_ct);
+    self.cliques[i].members_ct
+  }
+
+  // Vertices outside active clique i adjacent to all of it, i.e. those it
+  // could still take in.
+  pub fn clique_neighbors_ct(&self, i: usize) -> usize {
+    assert!(i < self.cliques_ct);
+    self.cliques[i].neighbors_ct
+  }
+
+  // The id of the clique holding v, in constant time. Ids, unlike the
+  // positions that clique_size takes, survive the search reordering the
+  // cliques; they change only when v moves.
+  pub fn clique_of(&self, v: usize) -> usize {
+    self.vertex_clique[v]
+  }
+
+  pub fn same_clique(&self, u: usize, v: usize) -> bool {
+    self.vertex_clique[u] == self.vertex_clique[v]
+  }
+
+  // Snapshot of the active cliques as plain member lists.
+  pub fn cover(&self) -> CliqueCover {
+    CliqueCover::from_cliques(&self.cliques[0..(self.cliques_ct)])
+  }
+}
+
+// A line per active clique, in ASCII in the alternate form, {:#}.
+impl fmt::Display for Graph {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    for i in 0..(self.cliques_ct) {
+      if f.alternate() {
+        writeln!(f, "{:#}", self.cliques[i])?;
+      } else {
+        writeln!(f, "{}", self.cliques[i])?;
+      }
+    }
+    Ok(())
+  }
+}
+
+// Counts in progress output, with thousands separators if the pretty
+// feature is on.
+#[cfg(feature = "std")]
+pub fn separated(n: usize) -> String {
+  #[cfg(feature = "pretty")]
+  return n.separate_with_commas();
+  #[cfg(not(feature = "pretty"))]
+  return n.to_string();
+}
+
+fn check_edge_probability(edge_probability: f64) -> Result<(), VccError> {
+  if !(0.0..=1.0).contains(&edge_probability) {
+    return Err(VccError::InvalidParameter(format!(
+      "edge probability {} is not in [0, 1]",
+      edge_probability
+    )));
+  }
+  Ok(())
+}
+
+// The number of vertex pairs, n (n - 1) / 2, which overflows a 32-bit
+// usize from n = 92,682 on.
+fn pair_count(num_vertices: usize) -> Result<u64, VccError> {
+  let pairs = num_vertices as u128 * num_vertices.saturating_sub(1) as u128 / 2;
+  u64::try_from(pairs).map_err(|_| {
+    VccError::InvalidParameter(format!("{} vertices have too many pairs", num_vertices))
+  })
+}
+
+// Selection sampling (Knuth's algorithm S): visiting the candidate pairs
+// in order, each is kept with probability edges / candidates, both counted
+// down as it goes. The draw is in integers, so exactly the target number
+// of edges comes out, however many pairs there are. Sparse graphs skip
+// ahead instead (see skip_sample), so only dense ones get exact counts.
+struct EdgeSampler {
+  candidates: u64,
+  edges: u64,
+}
+
+impl EdgeSampler {
+  // Targets edge_probability of the candidates, rounded down.
+  fn new(candidates: u64, edge_probability: f64) -> EdgeSampler {
+    EdgeSampler {
+      candidates,
+      edges: ((candidates as f64 * edge_probability) as u64).min(candidates),
+    }
+  }
+
+  // Whether few enough edges are wanted that skipping to them, with
+  // skip_sample, beats visiting every candidate. The skips take logarithms,
+  // which need std.
+  fn is_sparse(&self) -> bool {
+    cfg!(feature = "std") && self.edges.saturating_mul(SPARSE_CANDIDATES_PER_EDGE) < self.candidates
+  }
+
+  // The chance of keeping each candidate that skip_sample uses instead.
+  fn keep_probability(&self) -> f64 {
+    self.edges as f64 / self.candidates as f64
+  }
+
+  // Whether to keep the next candidate; one draw per undecided candidate.
+  fn keep<R: RngCore + ?Sized>(&mut self, rng: &mut R) -> bool {
+    let keep = self.edges == self.candidates
+      || (self.edges > 0 && below_u64(rng, self.candidates) < self.edges);
+    self.candidates -= 1;
+    if keep {
+      self.edges -= 1;
+    }
+    keep
+  }
+}
+
+const SPARSE_CANDIDATES_PER_EDGE: u64 = 32;
+
+// The indices of the kept candidates in order, by geometric skipping
+// (Batagelj and Brandes): each candidate is kept independently with
+// probability p, so the gap to the next kept one is geometric and one draw
+// finds it, in O(m) for m edges rather than O(n^2). Unlike
+// EdgeSampler::keep, the number kept is then binomial about p times the
+// candidates rather than exact, as in G(n, p) proper.
+#[cfg(feature = "std")]
+fn skip_sample<R: RngCore + ?Sized>(rng: &mut R, candidates: u64, p: f64) -> Vec<u64> {
+  let mut kept = vec![];
+  if p <= 0.0 {
+    return kept;
+  }
+  let log_miss = (1.0 - p).ln();
+  let mut index = 0;
+  loop {
+    // 1 - U is in (0, 1], so the gap is finite and non-negative.
+    let gap = ((1.0 - rng::unit_f64(rng)).ln() / log_miss).floor();
+    if gap >= (candidates - index) as f64 {
+      return kept;
+    }
+    index += gap as u64;
+    kept.push(index);
+    index += 1;
+  }
+}
+
+#[cfg(not(feature = "std"))]
+fn skip_sample<R: RngCore + ?Sized>(_rng: &mut R, _candidates: u64, _p: f64) -> Vec<u64> {
+  unreachable!("is_sparse is false without std")
+}
+
+// Links the pairs at the given indices, in increasing order. Pairs are
+// numbered as the dense loops visit them: row i holds (i, i + 1) to
+// (i, n - 1) and starts at row_start.
+fn link_pairs(g: &mut Graph, indices: &[u64]) {
+  let num_vertices = g.size;
+  let (mut i, mut row_start) = (0, 0);
+  for &index in indices {
+    while index >= row_start + (num_vertices - 1 - i) as u64 {
+      row_start += (num_vertices - 1 - i) as u64;
+      i += 1;
+    }
+    g.link(i, i + 1 + (index - row_start) as usize);
+  }
+}
+
+// The generators draw edges from rng, and seed the new graph's own search
+// generator from it, so a single seed fixes both the instance and the search.
+pub fn get_random_graph<R: RngCore + ?Sized>(
+  num_vertices: usize,
+  edge_probability: f64,
+  rng: &mut R,
+) -> Result<Graph, VccError> {
+  check_edge_probability(edge_probability)?;
+  let mut sampler = EdgeSampler::new(pair_count(num_vertices)?, edge_probability);
+  let mut ret_graph = Graph::new(num_vertices);
+  ret_graph.seed(rng.next_u64());
+  if sampler.is_sparse() {
+    let kept = skip_sample(rng, sampler.candidates, sampler.keep_probability());
+    link_pairs(&mut ret_graph, &kept);
+  } else {
+    for i in 0..ret_graph.size.saturating_sub(1) {
+      for j in (i + 1)..(ret_graph.size) {
+        if sampler.keep(rng) {
+          ret_graph.link(i, j);
+        }
+      }
+    }
+  }
+  ret_graph.conform_cliques_to_vertices();
+  ret_graph.shuffle_active_cliques();
+  Ok(ret_graph)
+}
+
+pub fn get_random_graph_with_k_cliques<R: RngCore + ?Sized>(
+  num_vertices: usize,
+  cliques_ct: usize,
+  edge_probability: f64,
+  rng: &mut R,
+) -> Result<Graph, VccError> {
+  get_random_graph_with_planted_cliques(
+    num_vertices,
+    cliques_ct,
+    &PlantedSizes::default(),
+    edge_probability,
+    rng,
+  )
+}
+
+// Plants cliques_ct cliques of the given sizes (see planted.rs), then adds
+// random edges up to the edge probability.
+pub fn get_random_graph_with_planted_cliques<R: RngCore + ?Sized>(
+  num_vertices: usize,
+  cliques_ct: usize,
+  sizes: &PlantedSizes,
+  edge_probability: f64,
+  rng: &mut R,
+) -> Result<Graph, VccError> {
+  if cliques_ct == 0 {
+    return get_random_graph(num_vertices, edge_probability, rng);
+  }
+  check_edge_probability(edge_probability)?;
+  let pairs = pair_count(num_vertices)?;
+  let clique_sizes = sizes.sizes(num_vertices, cliques_ct)?;
+  let labels = PlantedSizes::deal(&clique_sizes);
+  let mut reserved_edges = 0;
+  for &size in &clique_sizes {
+    reserved_edges += pair_count(size)?;
+  }
+  // The planted edges count towards the target.
+  let target = (pairs as f64 * edge_probability) as u64;
+  let mut sampler = EdgeSampler {
+    candidates: pairs - reserved_edges,
+    edges: target
+      .saturating_sub(reserved_edges)
+      .min(pairs - reserved_edges),
+  };
+
+  let mut planted = vec![vec![]; clique_sizes.len()];
+  for (v, &c) in labels.iter().enumerate() {
+    planted[c].push(v);
+  }
+
+  let mut ret_graph = Graph::new(num_vertices);
+  ret_graph.seed(rng.next_u64());
+  if sampler.is_sparse() {
+    // Skipping runs over every pair, planted or not; a planted pair it
+    // lands on is linked anyway, so each other pair is kept with the same
+    // chance.
+    let kept = skip_sample(rng, pairs, sampler.keep_probability());
+    link_pairs(&mut ret_graph, &kept);
+    for clique in &planted {
+      for (k, &u) in clique.iter().enumerate() {
+        for &v in &clique[(k + 1)..] {
+          ret_graph.link(u, v);
+        }
+      }
+    }
+  } else {
+    for i in 0..ret_graph.size.saturating_sub(1) {
+      for j in (i + 1)..(ret_graph.size) {
+        if labels[i] == labels[j] || sampler.keep(rng) {
+          ret_graph.link(i, j);
+        }
+      }
+    }
+  }
+  ret_graph.planted = Some(CliqueCover { cliques: planted });
+  ret_graph.conform_cliques_to_vertices();
+  Ok(ret_graph)
+}
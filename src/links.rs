@@ -0,0 +1,407 @@
+// Must-link and cannot-link constraints: pairs of vertices that have to
+// share a clique, and pairs that mustn't. Neither needs the search to know
+// about them. A cannot-link pair just loses its edge, so no transfer can
+// put the two together. Must-linked vertices are contracted into a single
+// vertex, adjacent to another only if every pair between the two groups is
+// linked, so every transfer moves a group whole. Covers of the contracted
+// graph lift to exactly the covers that keep both kinds of constraints.
+// A pre-assigned clique is a group that is cut from every other vertex:
+// it stays exactly as given, and the search only works on the rest.
+// Forbidden sets (see forbidden.rs) carry over to the contracted graph.
+//
+// Cliques can also have fixed identities, labels 0..L, with some vertices
+// only allowed a few of them: list coloring of the complement. Each label
+// becomes one more vertex, adjacent to the groups that allow it and to no
+// other label, so no clique holds two labels. A cover that gives every
+// clique a label, which takes exactly L cliques, then assigns each group an
+// allowed label; cliques holding just their label are unused labels.
+//
+// A constraints file has one constraint per line: a pair `must 3 7` or
+// `cannot 2 9`, a whole clique `clique 3 7 19`, a set that mustn't all
+// share a clique `forbid 1 4 6`, or one that may share at most two
+// `at-most 2 1 4 6 8 9`. `labels 8` sets L, after which `allow 5 0 2 3`
+// lets vertex 5 take only labels 0, 2 and 3. Blank lines and lines
+// starting with '#' are skipped.
+
+use crate::cover::CliqueCover;
+use crate::error::VccError;
+use crate::forbidden::ForbiddenSet;
+use crate::Graph;
+use std::fmt;
+
+#[derive(Clone, Default)]
+pub struct LinkConstraints {
+  pub must_link: Vec<(usize, usize)>,
+  pub cannot_link: Vec<(usize, usize)>,
+  pub pinned: Vec<Vec<usize>>,
+  pub forbidden: Vec<ForbiddenSet>,
+  pub labels: Option<usize>,
+  // Each vertex's allowed labels, if restricted.
+  pub allowed: Vec<(usize, Vec<usize>)>,
+}
+
+impl LinkConstraints {
+  pub fn parse(constraints_str: &str) -> Result<LinkConstraints, VccError> {
+    let mut links = LinkConstraints::default();
+    for line in constraints_str.lines().map(str::trim) {
+      if line.is_empty() || line.starts_with('#') {
+        continue;
+      }
+      let bad = || VccError::Parse(format!("bad constraint: {:?}", line));
+      let mut words = line.split_whitespace();
+      let kind = words.next().ok_or_else(bad)?;
+      let vertices = words
+        .map(|v| v.parse().map_err(|_| bad()))
+        .collect::<Result<Vec<usize>, VccError>>()?;
+      match (kind, &vertices[..]) {
+        ("must", &[u, v]) => links.must_link.push((u, v)),
+        ("cannot", &[u, v]) => links.cannot_link.push((u, v)),
+        ("clique", [_, ..]) => links.pinned.push(vertices),
+        ("forbid", [_, _, ..]) => links.forbidden.push(ForbiddenSet {
+          max_together: vertices.len() - 1,
+          members: vertices,
+        }),
+        ("at-most", [max_together, _, ..]) => links.forbidden.push(ForbiddenSet {
+          max_together: *max_together,
+          members: vertices[1..].to_vec(),
+        }),
+        ("labels", &[labels]) => links.labels = Some(labels),
+        ("allow", [v, _, ..]) => links.allowed.push((*v, vertices[1..].to_vec())),
+        _ => return Err(bad()),
+      }
+    }
+    Ok(links)
+  }
+
+  // Checks a cover of the original graph against every constraint.
+  pub fn check(&self, cover: &CliqueCover) -> Result<(), VccError> {
+    let mut clique_of =
+      vec![usize::MAX; cover.cliques.iter().flatten().max().map_or(0, |&v| v + 1)];
+    for (c, members) in cover.cliques.iter().enumerate() {
+      for &v in members {
+        clique_of[v] = c;
+      }
+    }
+    let clique = |v: usize| clique_of.get(v).copied().unwrap_or(usize::MAX);
+    for &(u, v) in &self.must_link {
+      if clique(u) != clique(v) || clique(u) == usize::MAX {
+        return Err(VccError::InvalidCover(format!(
+          "must-linked vertices {} and {} are in different cliques",
+          u, v
+        )));
+      }
+    }
+    for &(u, v) in &self.cannot_link {
+      if clique(u) == clique(v) && clique(u) != usize::MAX {
+        return Err(VccError::InvalidCover(format!(
+          "cannot-linked vertices {} and {} share a clique",
+          u, v
+        )));
+      }
+    }
+    for members in &self.pinned {
+      let c = clique(members[0]);
+      let kept = c != usize::MAX
+        && cover.cliques[c].len() == members.len()
+        && members.iter().all(|&v| clique(v) == c);
+      if !kept {
+        return Err(VccError::InvalidCover(format!(
+          "pre-assigned clique {:?} isn't in the cover",
+          members
+        )));
+      }
+    }
+    for set in &self.forbidden {
+      for members in &cover.cliques {
+        if set.members.iter().filter(|v| members.contains(v)).count() > set.max_together {
+          return Err(VccError::InvalidCover(format!(
+            "a clique holds more than {} of forbidden set {:?}",
+            set.max_together, set.members
+          )));
+        }
+      }
+    }
+    Ok(())
+  }
+
+  // Checks labeled cliques of the original graph: distinct labels, each
+  // allowed to every member.
+  pub fn check_labels(&self, labeled: &[(usize, Vec<usize>)]) -> Result<(), VccError> {
+    let mut used = vec![];
+    for (label, members) in labeled {
+      if used.contains(label) {
+        return Err(VccError::InvalidCover(format!(
+          "label {} is on two cliques",
+          label
+        )));
+      }
+      used.push(*label);
+      let refused = self
+        .allowed
+        .iter()
+        .find(|(v, labels)| members.contains(v) && !labels.contains(label));
+      if let Some((v, _)) = refused {
+        return Err(VccError::InvalidCover(format!(
+          "vertex {} isn't allowed label {}",
+          v, label
+        )));
+      }
+    }
+    Ok(())
+  }
+}
+
+pub struct Constrained {
+  // Vertex i stands for the vertices of groups[i].
+  pub graph: Graph,
+  pub groups: Vec<Vec<usize>>,
+  pub pinned_ct: usize,
+  // Vertices groups.len().. of the graph are labels 0, 1, ...
+  pub labels: usize,
+}
+
+impl fmt::Display for Constrained {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let vertices: usize = self.groups.iter().map(Vec::len).sum();
+    write!(
+      f,
+      "constraints: {} vertices in {} groups, {} cliques pre-assigned",
+      vertices,
+      self.groups.len(),
+      self.pinned_ct
+    )?;
+    if self.labels > 0 {
+      write!(f, ", {} labels", self.labels)?;
+    }
+    Ok(())
+  }
+}
+
+impl Constrained {
+  pub fn lift(&self, cover: &CliqueCover) -> CliqueCover {
+    CliqueCover {
+      cliques: cover
+        .cliques
+        .iter()
+        .map(|members| {
+          members
+            .iter()
+            .filter(|&&i| i < self.groups.len())
+            .flat_map(|&i| self.groups[i].iter().copied())
+            .collect::<Vec<usize>>()
+        })
+        .filter(|members| !members.is_empty())
+        .collect(),
+    }
+  }
+
+  // The target to search the constrained graph with, given the one asked
+  // for. With labels it is exactly L, since a cover with more cliques
+  // leaves one unlabeled; asking for another is an error.
+  pub fn target(&self, requested: usize) -> Result<usize, VccError> {
+    if self.labels == 0 {
+      return Ok(requested);
+    }
+    if requested != self.labels {
+      return Err(VccError::InvalidParameter(format!(
+        "target {} conflicts with the {} clique labels",
+        requested, self.labels
+      )));
+    }
+    Ok(self.labels)
+  }
+
+  // Lifts a cover of the constrained graph along with each clique's label;
+  // fails if a clique got none, as happens when the restrictions can't all
+  // be met with L cliques.
+  pub fn lift_labeled(&self, cover: &CliqueCover) -> Result<Vec<(usize, Vec<usize>)>, VccError> {
+    let mut labeled = vec![];
+    for members in &cover.cliques {
+      let label = members.iter().find(|&&i| i >= self.groups.len());
+      let lifted = self.lift(&CliqueCover {
+        cliques: vec![members.clone()],
+      });
+      match (label, lifted.cliques.into_iter().next()) {
+        (_, None) => {}
+        (Some(&i), Some(members)) => labeled.push((i - self.groups.len(), members)),
+        (None, Some(members)) => {
+          return Err(VccError::InvalidCover(format!(
+            "no label for clique {:?}",
+            members
+          )))
+        }
+      }
+    }
+    Ok(labeled)
+  }
+}
+
+impl Graph {
+  // Fails if the constraints can't all be kept: must-linked vertices that
+  // aren't adjacent, a cannot-link inside a must-link group, a
+  // pre-assigned clique that overlaps another or is must-linked outside, or
+  // a forbidden set with two members in one group.
+  pub fn constrained(&self, links: &LinkConstraints) -> Result<Constrained, VccError> {
+    let n = self.size;
+    let pairs = links.must_link.iter().chain(&links.cannot_link);
+    let out_of_range = pairs
+      .flat_map(|&(u, v)| [u, v])
+      .chain(links.pinned.iter().flatten().copied())
+      .chain(
+        links
+          .forbidden
+          .iter()
+          .flat_map(|set| set.members.iter().copied()),
+      )
+      .chain(links.allowed.iter().map(|&(v, _)| v))
+      .find(|&v| v >= n);
+    if let Some(v) = out_of_range {
+      return Err(VccError::InvalidParameter(format!(
+        "constraint on vertex {} in a graph with {} vertices",
+        v, n
+      )));
+    }
+    let mut pinned = vec![false; n];
+    for &v in links.pinned.iter().flatten() {
+      if pinned[v] {
+        return Err(VccError::InvalidParameter(format!(
+          "vertex {} is in two pre-assigned cliques",
+          v
+        )));
+      }
+      pinned[v] = true;
+    }
+    let pinned_pairs = links
+      .pinned
+      .iter()
+      .flat_map(|members| members.iter().map(|&v| (members[0], v)));
+    // Merges the must-link groups by relabeling; constraint files are short.
+    let mut group_of: Vec<usize> = (0..n).collect();
+    for (u, v) in links.must_link.iter().copied().chain(pinned_pairs) {
+      let (keep, merged) = (group_of[u], group_of[v]);
+      for group in &mut group_of {
+        if *group == merged {
+          *group = keep;
+        }
+      }
+    }
+    let mut index = vec![usize::MAX; n];
+    let mut groups: Vec<Vec<usize>> = vec![];
+    for (v, &root) in group_of.iter().enumerate() {
+      if index[root] == usize::MAX {
+        index[root] = groups.len();
+        groups.push(vec![]);
+      }
+      groups[index[root]].push(v);
+    }
+    for members in &groups {
+      for (i, &u) in members.iter().enumerate() {
+        for &v in &members[(i + 1)..] {
+          if !self.topology.vertices[u].neighbors_bv.get_unchecked(v) {
+            return Err(VccError::InvalidParameter(format!(
+              "vertices {} and {} must share a clique but aren't adjacent",
+              u, v
+            )));
+          }
+        }
+      }
+    }
+    let mut cut = vec![vec![false; groups.len()]; groups.len()];
+    for &(u, v) in &links.cannot_link {
+      let (a, b) = (index[group_of[u]], index[group_of[v]]);
+      if a == b {
+        return Err(VccError::InvalidParameter(format!(
+          "vertices {} and {} are both must-linked and cannot-linked",
+          u, v
+        )));
+      }
+      cut[a][b] = true;
+      cut[b][a] = true;
+    }
+    let mut isolated = vec![false; groups.len()];
+    for members in &links.pinned {
+      let a = index[group_of[members[0]]];
+      if groups[a].len() > members.len() {
+        return Err(VccError::InvalidParameter(format!(
+          "pre-assigned clique {:?} is must-linked to other vertices",
+          members
+        )));
+      }
+      isolated[a] = true;
+    }
+    let labels = match (links.labels, links.allowed.is_empty()) {
+      (Some(labels), _) => labels,
+      (None, true) => 0,
+      (None, false) => {
+        return Err(VccError::InvalidParameter(
+          "allowed labels need a number of labels".to_string(),
+        ))
+      }
+    };
+    // The labels each group may take, those allowed to all its members.
+    let mut allowed = vec![vec![true; labels]; groups.len()];
+    for (v, list) in &links.allowed {
+      if let Some(&label) = list.iter().find(|&&label| label >= labels) {
+        return Err(VccError::InvalidParameter(format!(
+          "label {} of vertex {} with {} labels",
+          label, v, labels
+        )));
+      }
+      for (label, ok) in allowed[index[group_of[*v]]].iter_mut().enumerate() {
+        *ok &= list.contains(&label);
+      }
+    }
+    if let Some(a) = (0..groups.len()).find(|&a| labels > 0 && !allowed[a].contains(&true)) {
+      return Err(VccError::InvalidParameter(format!(
+        "no label is allowed to all of {:?}",
+        groups[a]
+      )));
+    }
+    let mut graph = Graph::new(groups.len() + labels);
+    for (a, labels_ok) in allowed.iter().enumerate() {
+      for (label, &ok) in labels_ok.iter().enumerate() {
+        if ok {
+          graph.link(a, groups.len() + label);
+        }
+      }
+    }
+    for a in 0..groups.len() {
+      for b in (a + 1)..groups.len() {
+        let linked = groups[a].iter().all(|&u| {
+          groups[b]
+            .iter()
+            .all(|&v| self.topology.vertices[u].neighbors_bv.get_unchecked(v))
+        });
+        if linked && !cut[a][b] && !isolated[a] && !isolated[b] {
+          graph.link(a, b);
+        }
+      }
+    }
+    let mut forbidden = vec![];
+    for set in &links.forbidden {
+      let mut members: Vec<usize> = set.members.iter().map(|&v| index[group_of[v]]).collect();
+      members.sort_unstable();
+      let len = members.len();
+      members.dedup();
+      if members.len() < len {
+        return Err(VccError::InvalidParameter(format!(
+          "forbidden set {:?} has members that must share a clique",
+          set.members
+        )));
+      }
+      forbidden.push(ForbiddenSet {
+        members,
+        max_together: set.max_together,
+      });
+    }
+    graph.set_forbidden(forbidden)?;
+    graph.conform_cliques_to_vertices();
+    graph.rng = self.rng.clone_box();
+    Ok(Constrained {
+      graph,
+      groups,
+      pinned_ct: links.pinned.len(),
+      labels,
+    })
+  }
+}
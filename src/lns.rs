@@ -0,0 +1,37 @@
+// Large neighborhood search: dissolve a few random cliques into singletons
+// and let greedy merging rebuild them, keeping the result unless it is
+// larger than what we started from.
+
+use crate::cover::CliqueCover;
+use crate::rng::shuffle;
+use crate::strategy::SearchStrategy;
+use crate::Graph;
+
+pub struct Lns {
+  // Number of cliques dissolved per step.
+  pub destroy_ct: usize,
+}
+
+impl SearchStrategy for Lns {
+  fn name(&self) -> &'static str {
+    "lns"
+  }
+
+  fn step(&mut self, g: &mut Graph) {
+    let incumbent = g.cover();
+    let mut cliques = incumbent.cliques.clone();
+    shuffle(&mut g.rng, &mut cliques);
+    let destroy_ct = self.destroy_ct.min(cliques.len());
+    let destroyed: Vec<Vec<usize>> = cliques.drain(..destroy_ct).collect();
+    for members in destroyed {
+      for v in members {
+        cliques.push(vec![v]);
+      }
+    }
+    g.load_cover_unchecked(&CliqueCover { cliques });
+    g.vcc_iterated_greedy(0.0);
+    if g.cliques_ct > incumbent.len() {
+      g.load_cover_unchecked(&incumbent);
+    }
+  }
+}
@@ -0,0 +1,70 @@
+// Relabeling a graph so that its bit vectors are local: neighborhoods and
+// cliques fall in a few consecutive blocks, which is what the blocked
+// bitvector backend (see bitset.rs) skips over. The order is Cuthill-McKee,
+// breadth first from a lowest degree vertex of each component, visiting
+// neighbors by increasing degree. It keeps edges short, so a vertex's
+// neighbors, and any clique through it, lie near it in the new order.
+// The relabeling itself is Graph::permute (see ops.rs), so forbidden sets
+// carry over.
+
+use crate::cover::CliqueCover;
+use crate::Graph;
+use alloc::collections::VecDeque;
+use alloc::vec;
+use alloc::vec::Vec;
+
+pub struct Relabeling {
+  pub graph: Graph,
+  // vertices[i] is the original vertex relabeled i.
+  vertices: Vec<usize>,
+}
+
+impl Graph {
+  // Quadratic in the vertex count, like building the graph.
+  pub fn locality_order(&self) -> Vec<usize> {
+    let degrees: Vec<usize> = (0..self.size).map(|v| self.degree(v)).collect();
+    let mut starts: Vec<usize> = (0..self.size).collect();
+    starts.sort_by_key(|&v| degrees[v]);
+    let mut visited = vec![false; self.size];
+    let mut order = Vec::with_capacity(self.size);
+    let mut queue = VecDeque::new();
+    for start in starts {
+      if visited[start] {
+        continue;
+      }
+      visited[start] = true;
+      queue.push_back(start);
+      while let Some(u) = queue.pop_front() {
+        order.push(u);
+        let mut next: Vec<usize> = (0..self.size)
+          .filter(|&v| !visited[v] && self.topology.vertices[u].neighbors_bv.get_unchecked(v))
+          .collect();
+        next.sort_by_key(|&v| degrees[v]);
+        for v in next {
+          visited[v] = true;
+          queue.push_back(v);
+        }
+      }
+    }
+    order
+  }
+
+  pub fn relabel_for_locality(&self) -> Relabeling {
+    let mut perm = vec![0; self.size];
+    for (i, v) in self.locality_order().into_iter().enumerate() {
+      perm[v] = i;
+    }
+    // A locality order is a permutation, so this can't fail.
+    let (mut graph, vertices) = self.permute(&perm).unwrap();
+    graph.rng = self.rng.clone_box();
+    Relabeling { graph, vertices }
+  }
+}
+
+impl Relabeling {
+  // Turns a cover of the relabeled graph into the same cover of the
+  // original.
+  pub fn lift(&self, cover: &CliqueCover) -> CliqueCover {
+    cover.relabel(&self.vertices)
+  }
+}
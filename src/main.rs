@@ -7,534 +7,1906 @@
 // TIME: time cargo run --release 205 12 0.75 100_000
 // FIND COMMON ERRORS: cargo clippy
 
-// TODO: Test limiting the number of multi-vertex cliques to one fewer than the best we've found
-//       and then forcing remaining vertices into existing multi-vertex cliques,
-//       removing conflicting vertices.
+use std::env;
+use std::fs;
+use std::process;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use vcc::anneal::AnnealingSchedule;
+use vcc::checkpoint::Checkpoint;
+use vcc::clock::parse_duration;
+use vcc::decide::{Decide, Decision};
+use vcc::durable::{set_fsync, write_atomic};
+use vcc::ejection::Ejection;
+use vcc::eta::Estimate;
+use vcc::fetch::{InstanceCache, COLORING_INSTANCES, DEFAULT_BASE_URL};
+use vcc::formats::{
+  read_weighted_graph, read_weighted_graph_as, write_graph_as, write_text, write_weighted_graph,
+  GraphFormat, Symmetrize, STDIO,
+};
+use vcc::links::{Constrained, LinkConstraints};
+use vcc::lns::Lns;
+use vcc::manifest::{format_reports, Manifest};
+use vcc::memetic::{island_search, IslandParams};
+use vcc::multilevel::Multilevel;
+use vcc::multistart::multi_start_throttled;
+use vcc::pareto::ParetoFront;
+use vcc::planted::PlantedSizes;
+use vcc::portfolio::Portfolio;
+use vcc::profile::Profile;
+use vcc::race::{race, RaceParams};
+use vcc::rng::{default_rng, entropy_seed};
+use vcc::selftest;
+use vcc::solve::{format_restart_log, SolveProgress};
+use vcc::strategy::IteratedGreedy;
+use vcc::tabu::Tabu;
+use vcc::terminal::Terminal;
+use vcc::threads::ThreadConfig;
+use vcc::throttle::{Pacer, Throttle};
+use vcc::ttt::{format_ttt, time_to_target};
+use vcc::verify::{read_cover, vertex_base, write_cover};
+use vcc::{
+  get_random_graph_with_k_cliques, get_random_graph_with_planted_cliques, separated, CliqueCover,
+  Graph, RestartTrace, SolveParams, VccError,
+};
 
-// TODO: Explore combining iterated greedy with Tabu.
+// Set by the global --quiet option.
+static QUIET: AtomicBool = AtomicBool::new(false);
 
-// Both of the above to-do's are discussed in:
-// A survey of local search methods for graph coloring, by Galinier & Hertz
+// println!, unless --quiet was given.
+macro_rules! say {
+  ($($arg:tt)*) => {
+    if !QUIET.load(Ordering::Relaxed) {
+      println!($($arg)*);
+    }
+  };
+}
 
-// vcc = vertex clique cover
-// mis = maximum independent set
-// ..._bv = bitvector (BitVec)
-// ..._ct = count (usize)
-// force compile
+// How a search ended, for scripts to branch on without parsing its report.
+// It exits 0 if the search reached its target, 2 if its budget ran out
+// first and 3 if its cover is proven optimal short of the target, which
+// is then out of reach; errors exit 1, and decide exits as SAT solvers do.
+#[derive(Clone, Copy)]
+enum Outcome {
+  TargetReached,
+  BudgetExhausted,
+  ProvenOptimal,
+}
 
-use bitvec_simd::BitVec; // https://docs.rs/bitvec_simd/0.20.5/bitvec_simd/struct.BitVecSimd.html
-use smallvec::{smallvec, SmallVec}; // https://docs.rs/smallvec/1.10.0/smallvec/struct.SmallVec.html
-use std::env;
-use std::time::Instant;
-use thousands::Separable;
-
-// The neighbors of a clique are those vertices that are not in the clique,
-// and are adjacent to every vertex in the clique.
-struct Clique {
-  members_bv: BitVec,
-  members_ct: usize,
-  members: SmallVec<[usize; 256]>,
-  neighbors_bv: BitVec,
-  length: usize,
-  id: usize,
-  is_active: bool,
-  has_neighbors: bool,
-}
-
-// A clique has at least one member, and at least zero neighbors.
-// A clique with exactly one member is also referred to as a vertex or node.
-impl Clique {
-  fn new(num_vertices: usize, id: usize) -> Clique {
-    Clique {
-      members_bv: BitVec::zeros(num_vertices),
-      members_ct: 1,
-      members: smallvec![id],
-      neighbors_bv: BitVec::zeros(num_vertices),
-      length: num_vertices,
-      id,
-      is_active: true,
-      has_neighbors: false,
-    }
-  }
-
-  fn to_string(&self) -> String {
-    let mut ret_str = String::new();
-    for i in 0..self.length {
-      if self.members_bv.get(i) == Some(true) {
-        ret_str += "\u{25AA}";
-      } else if self.neighbors_bv.get(i) == Some(true) {
-        ret_str += "\u{25AB}";
-      } else {
-        ret_str += "\u{2B1D}";
-      }
-    }
-    if !self.is_active {
-      ret_str += " I";
+impl Outcome {
+  fn of(size: usize, target: usize, proven_optimal: bool) -> Outcome {
+    if size <= target {
+      Outcome::TargetReached
+    } else if proven_optimal {
+      Outcome::ProvenOptimal
     } else {
-      ret_str += &(" ".to_owned() + &self.members_ct.to_string());
+      Outcome::BudgetExhausted
     }
-    ret_str
   }
 }
 
-struct CliqueMaker {
-  id: usize,
-  length: usize,
+// Prints a search's last report line, or with --quiet just its cover's
+// size, or nothing if the cover went to a file; then exits as the outcome
+// says.
+fn finish_search(
+  line: String,
+  size: usize,
+  wrote_cover: bool,
+  outcome: Outcome,
+) -> Result<(), VccError> {
+  if !QUIET.load(Ordering::Relaxed) {
+    println!("{}", line);
+  } else if !wrote_cover {
+    println!("{}", size);
+  }
+  match outcome {
+    Outcome::TargetReached => Ok(()),
+    Outcome::BudgetExhausted => process::exit(2),
+    Outcome::ProvenOptimal => process::exit(3),
+  }
 }
 
-impl CliqueMaker {
-  fn new(num_vertices: usize) -> CliqueMaker {
-    CliqueMaker {
-      id: 0,
-      length: num_vertices,
-    }
+// Writes the quotient of g by its current cover to whichever paths were
+// requested.
+fn write_quotient(
+  g: &Graph,
+  dot_path: &Option<String>,
+  json_path: &Option<String>,
+) -> Result<(), VccError> {
+  if dot_path.is_none() && json_path.is_none() {
+    return Ok(());
+  }
+  let mut cover = g.cover();
+  cover.check_consistency()?;
+  cover.canonicalize();
+  let quotient = g.quotient(&cover)?;
+  if let Some(path) = dot_path {
+    write_atomic(path, quotient.to_dot())?;
   }
+  if let Some(path) = json_path {
+    write_atomic(path, quotient.to_json())?;
+  }
+  Ok(())
+}
 
-  // Returns a new clique with one member (incrementing which node is
-  // in the clique), and no neighbors
-  fn make_clique(&mut self) -> Clique {
-    let mut ret_clique: Clique = Clique::new(self.length, self.id);
-    ret_clique.members_bv.set(self.id, true);
-    self.id += 1;
-    ret_clique
+// vcc solve --manifest jobs.toml [--parallel]
+// vcc solve <vertices> <cliques> <edge_fraction>
+//   [--max-iterations 100_000] [--restarts 10] [--reverse-fraction 0]
+//   [--reverse-decay linear:0.05] [--order largest-first:0.5]
+//   [--freeze min-size:10] [--seed S] [--checkpoint state.json]
+//   [--restart-log restarts.csv] [--diff-log diffs.txt] [--replay <restart>]
+//   [--initial sequential] [--exact-finish 2:40:100] [--kernel]
+//   [--distinct-starts 65_536:3] [--planted-sizes geometric:0.8]
+//   [--min-clique-size 2] [--planted-noise 0.1]
+//   [--fractional <max cliques>] [--lagrangian <iterations>]
+//   [--export-wcnf model.wcnf] [--maxsat "<solver command>"]
+//   [--export-minizinc model.mzn] [--import-minizinc output.txt]
+//   [--exact <max nodes>] [--constraints links.txt] [--budget <cliques>]
+//   [--pareto] [--clusters clusters.csv] [--labels labels.json]
+//   [--coloring complement.sol] [--stats] [--stats-json stats.json]
+//   [--multilevel 200:1000] [--divide <leaf size>]
+//   [--profile dense|sparse|huge|quick] [--eta] [--duty 0.5]
+//   [--schedule 22-7:0] [--check-every 250_000,30s] [annealing options]
+// vcc solve <graph file> [--format dimacs|graph6] [--target <cliques>]
+//   [-o cover.csv] [options as above]
+// vcc solve --resume state.json [--checkpoint state.json]
+// Loads or generates the instance, prints a quick probe of it (see
+// probe.rs), with its fractional cover (see fractional.rs) and Lagrangian
+// bound (see lagrangian.rs) if asked, and solves it in one of the modes
+// below: a MiniZinc import, a budgeted partial cover, a coarsened solve,
+// an exact solve, a MaxSAT solve or, by default, the restart search.
+// --export-wcnf writes the instance as weighted MaxSAT (see maxsat.rs) and
+// --export-minizinc as a MiniZinc model (see minizinc.rs) beforehand.
+// -o writes the best cover in its extension's format (see verify.rs). Any
+// cover output may be "-", stdout, which then gets nothing else.
+// --profile presets the search options and backend for a class of instance
+// (see profile.rs); options given with it override it.
+fn run_solve(args: &[String]) -> Result<(), VccError> {
+  let mut options = SolveOptions::parse(args)?;
+  options.throttle.validate()?;
+  // The cover alone goes to stdout.
+  if options.outputs.to_stdout() {
+    QUIET.store(true, Ordering::Relaxed);
+  }
+  if let Some(manifest_path) = &options.manifest_path {
+    return solve_manifest(manifest_path, options.parallel);
   }
 
-  fn get_copy_of_clique(&self, clique_to_copy: &Clique) -> Clique {
-    let mut ret_clique: Clique = Clique::new(clique_to_copy.length, clique_to_copy.id);
-    transcribe_clique_onto_clique(clique_to_copy, &mut ret_clique);
-    ret_clique
+  // A graph file may be a benchmark, whose gap is then reported.
+  let graph_path = match (&options.resume_path, &options.positional[..]) {
+    (None, [path]) => Some(path.clone()),
+    _ => None,
+  };
+  let (mut g, progress) = load_instance(&mut options)?;
+  let links = match &options.constraints_path {
+    Some(path) => Some(LinkConstraints::parse(&fs::read_to_string(path)?)?),
+    None => None,
+  };
+  if links.is_some()
+    && (options.maxsat_command.is_some()
+      || options.resume_path.is_some()
+      || options.checkpoint_path.is_some())
+  {
+    return Err(VccError::InvalidParameter(
+      "--constraints can't be combined with --maxsat or checkpoints".to_string(),
+    ));
+  }
+  let has_forbidden = links
+    .as_ref()
+    .is_some_and(|links| !links.forbidden.is_empty());
+  if has_forbidden && (options.kernelize || options.exact.is_some()) {
+    return Err(VccError::InvalidParameter(
+      "forbidden sets can't be combined with --kernel or --exact".to_string(),
+    ));
   }
+  say!("{}", g.probe());
+  if let Some(max_cliques) = options.fractional {
+    say!("{}", g.fractional_cover(max_cliques));
+  }
+  if let Some(iterations) = options.lagrangian {
+    say!("{}", g.lagrangian_bound(iterations));
+  }
+  if let Some(path) = &options.wcnf_path {
+    write_atomic(path, g.to_wcnf(g.dsatur_cover().len()))?;
+  }
+  if let Some(path) = &options.minizinc_path {
+    write_atomic(path, g.to_minizinc(g.dsatur_cover().len()))?;
+  }
+  if let Some(path) = &options.minizinc_output_path {
+    return import_minizinc(&g, path, links.as_ref(), options.params.target);
+  }
+  if let Some(budget) = options.budget {
+    if options.kernelize || links.is_some() {
+      return Err(VccError::InvalidParameter(
+        "--budget can't be combined with --kernel or --constraints".to_string(),
+      ));
+    }
+    say!("{}", g.partial_cover(budget, &options.params)?);
+    return Ok(());
+  }
+  if let Some(profile) = options.profile {
+    if options.multilevel.is_none()
+      && options.divide.is_none()
+      && options.exact.is_none()
+      && options.maxsat_command.is_none()
+      && !options.kernelize
+      && links.is_none()
+      && options.resume_path.is_none()
+      && options.checkpoint_path.is_none()
+      && options.replay.is_none()
+    {
+      options.multilevel = profile.multilevel();
+    }
+  }
+  if options.multilevel.is_some() || options.divide.is_some() {
+    if options.kernelize || links.is_some() {
+      return Err(VccError::InvalidParameter(
+        "--multilevel and --divide can't be combined with --kernel or --constraints".to_string(),
+      ));
+    }
+    return solve_coarsened(g, &options, graph_path.as_deref());
+  }
+  let constrained = match &links {
+    Some(links) => {
+      let constrained = g.constrained(links)?;
+      say!("{}", constrained);
+      // With labels, the target is the label count unless --target says
+      // otherwise, which is an error.
+      if constrained.labels > 0 {
+        options.params.target = constrained.target(options.target.unwrap_or(constrained.labels))?;
+      }
+      Some(constrained)
+    }
+    None => None,
+  };
+  let constraints = match (constrained, &links) {
+    (Some(constrained), Some(links)) => Some((constrained, links)),
+    _ => None,
+  };
+  if let Some(max_nodes) = options.exact {
+    return solve_exact(g, constraints, max_nodes, &options, graph_path.as_deref());
+  }
+  if let Some(command) = &options.maxsat_command {
+    return solve_maxsat(g, command, &options.params);
+  }
+  solve_restarts(g, constraints, progress, &options, graph_path.as_deref())
 }
 
-fn transcribe_clique_onto_clique(source_clique: &Clique, target_clique: &mut Clique) {
-  target_clique.members_bv.set_all_false();
-  target_clique.members.clear();
-  if source_clique.members_ct == 1 {
-    target_clique.members_bv.set(source_clique.members[0], true);
-    target_clique.members.push(source_clique.members[0]);
-  } else {
-    target_clique
-      .members_bv
-      .or_inplace(&source_clique.members_bv);
-    target_clique
-      .members
-      .extend_from_slice(&source_clique.members);
+// The options of vcc solve, as given.
+#[derive(Default)]
+struct SolveOptions {
+  manifest_path: Option<String>,
+  parallel: bool,
+  checkpoint_path: Option<String>,
+  resume_path: Option<String>,
+  seed: Option<u64>,
+  restart_log_path: Option<String>,
+  diff_log_path: Option<String>,
+  replay: Option<usize>,
+  kernelize: bool,
+  fractional: Option<usize>,
+  lagrangian: Option<usize>,
+  wcnf_path: Option<String>,
+  maxsat_command: Option<String>,
+  minizinc_path: Option<String>,
+  minizinc_output_path: Option<String>,
+  exact: Option<usize>,
+  constraints_path: Option<String>,
+  budget: Option<usize>,
+  multilevel: Option<Multilevel>,
+  divide: Option<usize>,
+  pareto: bool,
+  eta: bool,
+  throttle: Throttle,
+  graph_format: Option<GraphFormat>,
+  // As given; params.target is the one solved for.
+  target: Option<usize>,
+  sizes: PlantedSizes,
+  planted_noise: f64,
+  outputs: CoverOutputs,
+  params: SolveParams,
+  profile: Option<Profile>,
+  // The instance: a graph file, or a generated instance's parameters.
+  positional: Vec<String>,
+}
+
+impl SolveOptions {
+  fn parse(args: &[String]) -> Result<SolveOptions, VccError> {
+    let mut options = SolveOptions {
+      profile: profile_option(args)?,
+      ..SolveOptions::default()
+    };
+    if let Some(profile) = options.profile {
+      profile.apply(&mut options.params);
+    }
+    let params = &mut options.params;
+    let mut i = 0;
+    while i < args.len() {
+      match args[i].as_str() {
+        "--manifest" => {
+          options.manifest_path = Some(option_value(args, i)?.to_string());
+          i += 1;
+        }
+        "--parallel" => options.parallel = true,
+        "--kernel" => options.kernelize = true,
+        "--fractional" => {
+          options.fractional = Some(parse_count(option_value(args, i)?)?);
+          i += 1;
+        }
+        "--lagrangian" => {
+          options.lagrangian = Some(parse_count(option_value(args, i)?)?);
+          i += 1;
+        }
+        "--export-wcnf" => {
+          options.wcnf_path = Some(option_value(args, i)?.to_string());
+          i += 1;
+        }
+        "--maxsat" => {
+          options.maxsat_command = Some(option_value(args, i)?.to_string());
+          i += 1;
+        }
+        "--export-minizinc" => {
+          options.minizinc_path = Some(option_value(args, i)?.to_string());
+          i += 1;
+        }
+        "--import-minizinc" => {
+          options.minizinc_output_path = Some(option_value(args, i)?.to_string());
+          i += 1;
+        }
+        "--exact" => {
+          options.exact = Some(parse_count(option_value(args, i)?)?);
+          i += 1;
+        }
+        "--constraints" => {
+          options.constraints_path = Some(option_value(args, i)?.to_string());
+          i += 1;
+        }
+        "--budget" => {
+          options.budget = Some(parse_count(option_value(args, i)?)?);
+          i += 1;
+        }
+        "--multilevel" => {
+          options.multilevel = Some(option_value(args, i)?.parse()?);
+          i += 1;
+        }
+        "--divide" => {
+          options.divide = Some(parse_count(option_value(args, i)?)?);
+          i += 1;
+        }
+        "--pareto" => options.pareto = true,
+        "--eta" => options.eta = true,
+        "--duty" => {
+          options.throttle.duty = parse_value(option_value(args, i)?, "duty cycle")?;
+          i += 1;
+        }
+        "--schedule" => {
+          options.throttle.schedule = Some(option_value(args, i)?.parse()?);
+          i += 1;
+        }
+        "--format" => {
+          options.graph_format = Some(option_value(args, i)?.parse()?);
+          i += 1;
+        }
+        "--target" => {
+          options.target = Some(parse_count(option_value(args, i)?)?);
+          i += 1;
+        }
+        "-o" | "--output" => {
+          options.outputs.output_path = Some(option_value(args, i)?.to_string());
+          i += 1;
+        }
+        "--profile" => i += 1,
+        "--clusters" => {
+          options.outputs.clusters_path = Some(option_value(args, i)?.to_string());
+          i += 1;
+        }
+        "--labels" => {
+          options.outputs.labels_path = Some(option_value(args, i)?.to_string());
+          i += 1;
+        }
+        "--coloring" => {
+          options.outputs.coloring_path = Some(option_value(args, i)?.to_string());
+          i += 1;
+        }
+        "--stats" => options.outputs.stats = true,
+        "--stats-json" => {
+          options.outputs.stats_json_path = Some(option_value(args, i)?.to_string());
+          i += 1;
+        }
+        "--checkpoint" => {
+          options.checkpoint_path = Some(option_value(args, i)?.to_string());
+          i += 1;
+        }
+        "--resume" => {
+          options.resume_path = Some(option_value(args, i)?.to_string());
+          i += 1;
+        }
+        "--seed" => {
+          options.seed = Some(parse_value(option_value(args, i)?, "seed")?);
+          i += 1;
+        }
+        "--restart-log" => {
+          options.restart_log_path = Some(option_value(args, i)?.to_string());
+          i += 1;
+        }
+        "--diff-log" => {
+          options.diff_log_path = Some(option_value(args, i)?.to_string());
+          i += 1;
+        }
+        "--replay" => {
+          options.replay = Some(parse_count(option_value(args, i)?)?);
+          i += 1;
+        }
+        "--max-iterations" => {
+          params.max_iterations = parse_count(option_value(args, i)?)?;
+          i += 1;
+        }
+        "--restarts" => {
+          params.max_restarts = parse_count(option_value(args, i)?)?;
+          i += 1;
+        }
+        "--reverse-fraction" => {
+          params.reverse_fraction = parse_value(option_value(args, i)?, "reverse fraction")?;
+          i += 1;
+        }
+        "--reverse-decay" => {
+          params.reverse_decay = Some(option_value(args, i)?.parse()?);
+          i += 1;
+        }
+        "--order" => {
+          params.order = option_value(args, i)?.parse()?;
+          i += 1;
+        }
+        "--check-every" => {
+          params.check = option_value(args, i)?.parse()?;
+          i += 1;
+        }
+        "--freeze" => {
+          params.freeze = Some(option_value(args, i)?.parse()?);
+          i += 1;
+        }
+        "--initial" => {
+          params.initial = option_value(args, i)?.parse()?;
+          i += 1;
+        }
+        "--planted-sizes" => {
+          options.sizes.shape = option_value(args, i)?.parse()?;
+          i += 1;
+        }
+        "--min-clique-size" => {
+          options.sizes.min_size = parse_count(option_value(args, i)?)?;
+          i += 1;
+        }
+        "--planted-noise" => {
+          options.planted_noise = parse_value(option_value(args, i)?, "planted noise")?;
+          i += 1;
+        }
+        "--distinct-starts" => {
+          params.distinct_starts = Some(option_value(args, i)?.parse()?);
+          i += 1;
+        }
+        "--exact-finish" => {
+          params.finish = Some(option_value(args, i)?.parse()?);
+          i += 1;
+        }
+        arg if ANNEALING_OPTIONS.contains(&arg) => {
+          parse_annealing_option(arg, option_value(args, i)?, &mut params.annealing)?;
+          i += 1;
+        }
+        arg if arg.starts_with("--") => return Err(unknown_option(arg)),
+        arg => options.positional.push(arg.to_string()),
+      }
+      i += 1;
+    }
+    Ok(options)
   }
-  target_clique.members_ct = source_clique.members_ct;
-  target_clique.neighbors_bv.set_all_false();
-  target_clique
-    .neighbors_bv
-    .or_inplace(&source_clique.neighbors_bv);
-  target_clique.length = source_clique.length;
-  target_clique.id = source_clique.id;
-  target_clique.is_active = source_clique.is_active;
-  target_clique.has_neighbors = source_clique.has_neighbors;
 }
 
-struct Graph {
-  size: usize,
-  vertices: SmallVec<[Clique; 256]>,
-  cliques: SmallVec<[Clique; 256]>,
-  cliques_ct: usize,
-  utility_bv: BitVec,
+// --manifest runs the jobs a TOML file lists, in parallel with --parallel
+// or the manifest's own say-so, and prints a table of them (see
+// manifest.rs).
+fn solve_manifest(path: &str, parallel: bool) -> Result<(), VccError> {
+  let manifest = Manifest::from_toml_str(&fs::read_to_string(path)?)?;
+  let reports = manifest.run(parallel || manifest.parallel)?;
+  // The table ends in a newline of its own.
+  say!("{}", format_reports(&reports).trim_end());
+  Ok(())
+}
+
+// The instance: a checkpoint's with --resume, which also brings its params,
+// else a graph file, or "-" for stdin, else a generated one. A graph file's
+// format comes from its extension or --format, and its target, unless
+// given, is a greedy independent set's size, a lower bound. A generated
+// instance plants cliques of equal, geometric:<ratio> or
+// power-law:<exponent> sizes, none smaller than --min-clique-size (see
+// planted.rs); --planted-noise then moves the planted edges of that
+// fraction of the vertices.
+fn load_instance(options: &mut SolveOptions) -> Result<(Graph, Option<SolveProgress>), VccError> {
+  if let Some(path) = &options.resume_path {
+    let checkpoint = Checkpoint::load(path)?;
+    options.params = checkpoint.params;
+    let (g, progress) = checkpoint.restore()?;
+    return Ok((g, Some(progress)));
+  }
+  if options.replay.is_some() && options.seed.is_none() {
+    return Err(VccError::InvalidParameter(
+      "--replay needs the --seed of the run it replays".to_string(),
+    ));
+  }
+  let seed = options.seed.unwrap_or_else(entropy_seed);
+  if let [path] = &options.positional[..] {
+    let format = match options.graph_format {
+      Some(format) => format,
+      None => GraphFormat::from_path(path)?,
+    };
+    let (mut g, _) = read_weighted_graph_as(path, format, Symmetrize::Error)?;
+    g.seed(seed);
+    options.params.target = match options.target {
+      Some(target) => target,
+      None => g.greedy_independent_set_bound(),
+    };
+    return Ok((g, None));
+  }
+  let (num_vertices, cliques_ct, edge_fraction) = parse_instance(&options.positional)?;
+  options.params.target = options.target.unwrap_or(cliques_ct);
+  let mut rng = default_rng(seed);
+  let mut g = get_random_graph_with_planted_cliques(
+    num_vertices,
+    cliques_ct,
+    &options.sizes,
+    edge_fraction,
+    &mut rng,
+  )?;
+  if options.planted_noise > 0.0 {
+    g.add_planted_noise(options.planted_noise, &mut rng)?;
+  }
+  Ok((g, None))
 }
 
-impl Graph {
-  fn new(num_vertices: usize) -> Graph {
-    let mut clique_maker = CliqueMaker::new(num_vertices);
-    let mut vertices_vec: SmallVec<[Clique; 256]> = smallvec![];
-    let mut cliques_vec: SmallVec<[Clique; 256]> = smallvec![];
+// --import-minizinc checks and reports the answer of the model
+// --export-minizinc wrote, instead of searching; under --constraints it
+// has to keep them, labels aside.
+fn import_minizinc(
+  g: &Graph,
+  path: &str,
+  links: Option<&LinkConstraints>,
+  target: usize,
+) -> Result<(), VccError> {
+  let cover = g.parse_minizinc_output(&fs::read_to_string(path)?)?;
+  if let Some(links) = links {
+    links.check(&cover)?;
+  }
+  let line = format!(
+    "imported {} cliques (vs {}), reached target: {}",
+    cover.len(),
+    target,
+    cover.len() <= target
+  );
+  let outcome = Outcome::of(cover.len(), target, false);
+  finish_search(line, cover.len(), false, outcome)
+}
 
-    for _i in 0..num_vertices {
-      let vertex = clique_maker.make_clique();
-      let clique = clique_maker.get_copy_of_clique(&vertex);
-      vertices_vec.push(vertex);
-      cliques_vec.push(clique);
+// --multilevel solves a coarsened instance, down to the given vertex
+// count, and refines its cover back up with the given iterations per level
+// (see multilevel.rs); "on" takes the defaults. --divide splits the
+// instance along vertex separators down to the leaf size and solves the
+// leaves instead (see separator.rs). A profile's multilevel backend gives
+// way to any other mode.
+fn solve_coarsened(
+  mut g: Graph,
+  options: &SolveOptions,
+  graph_path: Option<&str>,
+) -> Result<(), VccError> {
+  let params = &options.params;
+  let cover = match (options.multilevel, options.divide) {
+    (Some(multilevel), None) => {
+      let result = g.solve_multilevel(params, &multilevel)?;
+      say!("{}", result);
+      result.cover
     }
-
-    Graph {
-      size: num_vertices,
-      vertices: vertices_vec,
-      cliques: cliques_vec,
-      cliques_ct: num_vertices,
-      utility_bv: BitVec::zeros(num_vertices),
+    (None, Some(leaf_size)) => g.solve_divided(params, leaf_size)?,
+    _ => {
+      return Err(VccError::InvalidParameter(
+        "--multilevel can't be combined with --divide".to_string(),
+      ))
     }
+  };
+  options.outputs.write(&g, &cover)?;
+  if let Some(path) = graph_path {
+    report_known(&g, path, Some(cover.len()));
   }
+  let line = format!(
+    "best {} (vs {}), reached target: {}",
+    cover.len(),
+    params.target,
+    cover.len() <= params.target
+  );
+  let proven = cover.len() <= g.greedy_independent_set_bound();
+  let outcome = Outcome::of(cover.len(), params.target, proven);
+  finish_search(line, cover.len(), options.outputs.writes_cover(), outcome)
+}
 
-  fn activate_inactive_clique(&mut self) -> bool {
-    if self.size == self.cliques_ct {
-      return false;
-    }
+// --constraints reads must-link and cannot-link pairs, pre-assigned cliques,
+// forbidden sets and allowed clique labels (see links.rs) that every cover
+// found has to keep; the constrained graph is what gets solved, and its
+// covers are lifted back. With labels, the target is the label count, and
+// each clique is printed with its label.
+type Constraints<'a> = Option<(Constrained, &'a LinkConstraints)>;
 
-    self.cliques[self.cliques_ct].is_active = true;
-    self.cliques_ct += 1;
-    true
+// --exact solves the instance exactly instead (see exact.rs, or ilp.rs
+// when built with the `ilp` feature), within max nodes.
+fn solve_exact(
+  mut g: Graph,
+  mut constraints: Constraints,
+  max_nodes: usize,
+  options: &SolveOptions,
+  graph_path: Option<&str>,
+) -> Result<(), VccError> {
+  let target = options.params.target;
+  let base = match &mut constraints {
+    Some((constrained, _)) => &mut constrained.graph,
+    None => &mut g,
+  };
+  #[cfg(feature = "ilp")]
+  let mut result = base.ilp_cover(max_nodes)?;
+  #[cfg(not(feature = "ilp"))]
+  let mut result = base.exact_cover(max_nodes);
+  if let Some((constrained, links)) = &constraints {
+    result.cover = lift_constrained(constrained, links, &result.cover)?;
   }
+  g.validate_cover(&result.cover)?;
+  if let Some((_, links)) = &constraints {
+    links.check(&result.cover)?;
+  }
+  options.outputs.write(&g, &result.cover)?;
+  if let Some(path) = graph_path {
+    report_known(&g, path, Some(result.cover.len()));
+  }
+  let line = format!(
+    "exact: {} cliques (vs {}), {} after {} nodes",
+    result.cover.len(),
+    target,
+    if result.optimal {
+      "proven optimal"
+    } else {
+      "not proven optimal"
+    },
+    result.nodes
+  );
+  let outcome = Outcome::of(result.cover.len(), target, result.optimal);
+  finish_search(
+    line,
+    result.cover.len(),
+    options.outputs.writes_cover(),
+    outcome,
+  )
+}
 
-  fn transfer_vertex_into_clique(
-    clique_into: &mut Clique,
-    clique_from: &mut Clique,
-    utility_bv: &mut BitVec,
-    vertices_vec: &SmallVec<[Clique; 256]>,
-    vertex_id: usize,
-  ) {
-    if !clique_into.has_neighbors {
-      return;
-    }
+// --maxsat runs a solver on the instance as weighted MaxSAT (see maxsat.rs)
+// instead of the search: an answer proven optimal is final, any other is
+// improved by one restart from it.
+fn solve_maxsat(mut g: Graph, command: &str, params: &SolveParams) -> Result<(), VccError> {
+  let solution = g.solve_maxsat(command, g.dsatur_cover().len())?;
+  say!(
+    "maxsat: {} cliques{}",
+    solution.cover.len(),
+    if solution.optimal { ", proven optimal" } else { "" }
+  );
+  g.load_cover(&solution.cover)?;
+  if !solution.optimal && g.vcc_run_restart(params, None) {
+    say!("{}", format_trace(g.last_restart()));
+  }
+  let best = g.cover();
+  let line = format!(
+    "best {} (vs {}), reached target: {}, restarts: 1",
+    best.len(),
+    params.target,
+    best.len() <= params.target
+  );
+  let proven = solution.optimal || best.len() <= g.greedy_independent_set_bound();
+  let outcome = Outcome::of(best.len(), params.target, proven);
+  finish_search(line, best.len(), false, outcome)
+}
 
-    if !clique_from.members_bv.get_unchecked(vertex_id) {
-      return;
+// The restart search, the default mode (see solve.rs). --kernel solves the
+// instance's kernel (see kernel.rs) instead and lifts the cover back.
+// With --checkpoint, the state is saved after every restart; --resume
+// continues such a run exactly where it left off. --restart-log records
+// each restart's seed and result, and --replay reruns just one restart of
+// the same command (which needs --seed or --resume to pick the instance).
+// --diff-log writes a line per restart that improved the best cover,
+// listing the vertices that moved between cliques (see diff.rs). --eta
+// prints, after every restart and at the end, the chance of reaching the
+// target within more restarts and how long that would take (see eta.rs).
+// --duty searches only that fraction of the time, and --schedule pauses
+// during the given hours unless it leaves a worker running; both sleep
+// between restarts, and the time searched is reported (see throttle.rs).
+// --check-every sets how often a restart checks for the target between
+// improvements: every so many iterations, a million by default, and with
+// a duration also that often in time (see cadence.rs).
+// --distinct-starts reshuffles or skips restarts whose start repeats an
+// earlier one, tracked in a Bloom filter of the given bits (see starts.rs);
+// "on" takes the defaults. --pareto also keeps every restart's cover and
+// prints the ones no other beats on both size and balance (see pareto.rs).
+// The search ends with a summary of the instance, the best cover against
+// the target and a lower bound, and the work done, reached or not (see
+// summary.rs).
+fn solve_restarts(
+  mut g: Graph,
+  mut constraints: Constraints,
+  progress: Option<SolveProgress>,
+  options: &SolveOptions,
+  graph_path: Option<&str>,
+) -> Result<(), VccError> {
+  let mut params = options.params;
+  if options.kernelize && (options.resume_path.is_some() || options.checkpoint_path.is_some()) {
+    return Err(VccError::InvalidParameter(
+      "--kernel can't be combined with checkpoints".to_string(),
+    ));
+  }
+  if options.kernelize && options.diff_log_path.is_some() {
+    return Err(VccError::InvalidParameter(
+      "--kernel can't be combined with --diff-log".to_string(),
+    ));
+  }
+  let base = match &mut constraints {
+    Some((constrained, _)) => &mut constrained.graph,
+    None => &mut g,
+  };
+  let target = params.target;
+  let mut kernel = None;
+  if options.kernelize {
+    let reduced = base.kernelize();
+    say!("{}", reduced);
+    params.target = target.saturating_sub(reduced.fixed_cliques);
+    kernel = Some(reduced);
+  }
+  let solved = match &mut kernel {
+    Some(reduced) => &mut reduced.graph,
+    None => base,
+  };
+  if let Some(restart) = options.replay {
+    let record = solved.replay_restart(&params, progress.as_ref(), restart)?;
+    say!(
+      "restart {}: seed {}, {} cliques (vs {}), reached target: {}",
+      record.restart,
+      record.seed,
+      record.cliques,
+      params.target,
+      record.reached_target
+    );
+    return Ok(());
+  }
+  // The callback can't return an error, so the first failed save is kept
+  // and reported once the solve finishes.
+  let mut save_error = None;
+  let mut restart_covers = vec![];
+  let mut diff_log = String::new();
+  let mut last_best = progress
+    .as_ref()
+    .map_or_else(|| solved.cover(), |progress| progress.best_cover.clone());
+  last_best.canonicalize();
+  let mut eta_trace = vec![];
+  let mut pacer = Pacer::new(options.throttle, 0);
+  let started = Instant::now();
+  let result = solved.resume_solve(&params, None, progress, &mut |g, progress| {
+    if options.eta {
+      eta_trace.push(g.cliques_ct());
+      let estimate = Estimate::new(&eta_trace, params.target, started.elapsed());
+      say!("restart {}: {}", progress.restarts, estimate);
     }
-
-    if !clique_into.neighbors_bv.get_unchecked(vertex_id) {
-      return;
+    if options.diff_log_path.is_some() && progress.best_cover.len() < last_best.len() {
+      let mut best = progress.best_cover.clone();
+      best.canonicalize();
+      diff_log.push_str(&format!(
+        "restart {}: {}\n",
+        progress.restarts,
+        last_best.diff(&best)
+      ));
+      last_best = best;
     }
-
-    if !clique_into.is_active {
-      return;
+    if options.pareto {
+      restart_covers.push(g.cover());
     }
+    if let (Some(path), None) = (&options.checkpoint_path, &save_error) {
+      let saved = match Checkpoint::capture(g, &params, progress) {
+        Some(checkpoint) => checkpoint.save(path),
+        None => Err(VccError::InvalidParameter(
+          "checkpoints need the default generator".to_string(),
+        )),
+      };
+      save_error = saved.err();
+    }
+    pacer.pause();
+  })?;
+  if let Some(e) = save_error {
+    return Err(e);
+  }
+  if result.reached_target {
+    say!("{}", format_trace(solved.last_restart()));
+  }
+  if let Some(path) = &options.restart_log_path {
+    write_atomic(path, format_restart_log(&result.restart_log))?;
+  }
+  if options.eta {
+    let cliques: Vec<usize> = result
+      .restart_log
+      .iter()
+      .filter(|record| !record.skipped)
+      .map(|record| record.cliques)
+      .collect();
+    say!("{}", Estimate::new(&cliques, params.target, result.elapsed));
+  }
+  if !options.throttle.is_off() {
+    let busy = result.elapsed.saturating_sub(pacer.idle);
+    say!("{}", throughput(result.restarts, busy, result.elapsed));
+  }
+  if let Some(path) = &options.diff_log_path {
+    write_atomic(path, diff_log)?;
+  }
+  if options.pareto {
+    // The last restart's cover is still on the graph. Balance is measured
+    // on the lifted covers, the ones that answer the instance.
+    restart_covers.push(solved.cover());
+    if let Some(reduced) = &kernel {
+      for cover in &mut restart_covers {
+        *cover = reduced.lift(base, cover);
+      }
+    }
+  }
+  let mut best = match &kernel {
+    Some(reduced) => reduced.lift(base, &result.best_cover),
+    None => result.best_cover.clone(),
+  };
+  if let Some((constrained, links)) = &constraints {
+    best = lift_constrained(constrained, links, &best)?;
+  }
+  if kernel.is_some() || constraints.is_some() {
+    g.validate_cover(&best)?;
+  }
+  if let Some((_, links)) = &constraints {
+    links.check(&best)?;
+  }
+  if options.pareto {
+    let mut front = ParetoFront::default();
+    for cover in &restart_covers {
+      match &constraints {
+        Some((constrained, _)) => front.offer(&constrained.lift(cover)),
+        None => front.offer(cover),
+      };
+    }
+    say!("{}", front);
+  }
+  options.outputs.write(&g, &best)?;
+  let summary = g.run_summary(&best, target, &result);
+  say!("{}", summary);
+  if let Some(path) = graph_path {
+    report_known(&g, path, Some(best.len()));
+  }
+  let line = format!(
+    "best {} (vs {}), reached target: {}, restarts: {}",
+    best.len(),
+    target,
+    result.reached_target,
+    result.restarts
+  );
+  let proven = summary.gap() == 0;
+  let outcome = Outcome::of(best.len(), target, proven);
+  finish_search(line, best.len(), options.outputs.writes_cover(), outcome)
+}
 
-    // clear utility_bv
-    utility_bv.set_all_false();
+// Where and how the best cover is reported, besides its size.
+#[derive(Default)]
+struct CoverOutputs {
+  // In the format its extension names, see verify.rs.
+  output_path: Option<String>,
+  clusters_path: Option<String>,
+  labels_path: Option<String>,
+  coloring_path: Option<String>,
+  stats: bool,
+  stats_json_path: Option<String>,
+}
 
-    // set utility_bv to be true for all transferrable vertices
-    utility_bv.set(vertex_id, true);
+impl CoverOutputs {
+  fn writes_cover(&self) -> bool {
+    self.output_path.is_some()
+      || self.clusters_path.is_some()
+      || self.labels_path.is_some()
+      || self.coloring_path.is_some()
+  }
 
-    Self::transfer_vertices_in_utility_bv_between_cliques(
-      clique_into,
-      clique_from,
-      utility_bv,
-      vertices_vec,
-    )
+  fn to_stdout(&self) -> bool {
+    [
+      &self.output_path,
+      &self.clusters_path,
+      &self.labels_path,
+      &self.coloring_path,
+    ]
+    .iter()
+    .any(|path| path.as_deref() == Some(STDIO))
   }
 
-  fn transfer_compatible_vertices(
-    clique_into: &mut Clique,
-    clique_from: &mut Clique,
-    utility_bv: &mut BitVec,
-    vertices_vec: &SmallVec<[Clique; 256]>,
-  ) {
-    if !clique_into.has_neighbors {
-      return;
+  // Writes the cover in canonical order, so runs finding the same cover
+  // write the same files.
+  fn write(&self, g: &Graph, cover: &CliqueCover) -> Result<(), VccError> {
+    cover.check_consistency()?;
+    let mut cover = cover.clone();
+    cover.canonicalize();
+    if let Some(path) = &self.output_path {
+      write_cover(&cover, path)?;
+    }
+    if let Some(path) = &self.clusters_path {
+      write_text(path, &cover.to_cluster_csv())?;
+    }
+    if let Some(path) = &self.labels_path {
+      write_text(path, &serde_json::to_string(&cover.labels())?)?;
+    }
+    if let Some(path) = &self.coloring_path {
+      write_text(path, &cover.to_dimacs_coloring())?;
     }
+    if self.stats || self.stats_json_path.is_some() {
+      let stats = g.cover_stats(&cover);
+      if self.stats {
+        say!("{}", stats);
+      }
+      if let Some(path) = &self.stats_json_path {
+        write_atomic(path, serde_json::to_string(&stats)?)?;
+      }
+    }
+    Ok(())
+  }
+}
+
+// "iter, 12,345, iterations per annealing, 1,000,000": how far the restart
+// that reached the target got.
+fn format_trace(trace: RestartTrace) -> String {
+  format!(
+    "iter, {}, iterations per annealing, {}",
+    separated(trace.iterations),
+    separated(trace.iterations_per_annealing)
+  )
+}
 
-    // clear utility_bv
-    utility_bv.set_all_false();
+// "busy 12.5s of 25.0s, 3.20 restarts per busy second"
+fn throughput(restarts: usize, busy: Duration, wall: Duration) -> String {
+  format!(
+    "busy {:.1?} of {:.1?}, {:.2} restarts per busy second",
+    busy,
+    wall,
+    restarts as f64 / busy.as_secs_f64()
+  )
+}
 
-    // set utility_bv to be true for all transferrable vertices
-    utility_bv.or_inplace(&clique_into.neighbors_bv);
-    utility_bv.and_inplace(&clique_from.members_bv);
-    if utility_bv.none() {
-      return;
+// Lifts a cover of the constrained graph, checking and printing its
+// cliques' labels if there are any.
+fn lift_constrained(
+  constrained: &Constrained,
+  links: &LinkConstraints,
+  cover: &CliqueCover,
+) -> Result<CliqueCover, VccError> {
+  if constrained.labels > 0 {
+    let mut labeled = constrained.lift_labeled(cover)?;
+    links.check_labels(&labeled)?;
+    for (_, members) in &mut labeled {
+      members.sort_unstable();
     }
+    labeled.sort_unstable();
+    for (label, members) in &labeled {
+      say!("label {}: {:?}", label, members);
+    }
+  }
+  Ok(constrained.lift(cover))
+}
+
+fn parse_value<T: FromStr>(value_str: &str, what: &str) -> Result<T, VccError> {
+  value_str
+    .parse()
+    .map_err(|_| VccError::Parse(format!("bad {}: {:?}", what, value_str)))
+}
+
+// Counts may be written with underscores, e.g. 100_000.
+fn parse_count(count_str: &str) -> Result<usize, VccError> {
+  parse_value(&count_str.replace('_', ""), "count")
+}
+
+// The --profile option's value, wherever it is among args, so that the
+// options given with it can be applied over it.
+fn profile_option(args: &[String]) -> Result<Option<Profile>, VccError> {
+  match args.iter().position(|arg| arg == "--profile") {
+    Some(i) => Ok(Some(option_value(args, i)?.parse()?)),
+    None => Ok(None),
+  }
+}
+
+// The value following the option at args[i].
+fn option_value(args: &[String], i: usize) -> Result<&str, VccError> {
+  match args.get(i + 1) {
+    Some(value) => Ok(value),
+    None => Err(VccError::Parse(format!("{} needs a value", args[i]))),
+  }
+}
+
+// --anneal-iterations 1_000_000 --anneal-growth 0.02 --anneals-per-slowdown 1
+// --anneal-seed-size 1 --anneal-donor random set the annealing schedule; see
+// anneal.rs.
+const ANNEALING_OPTIONS: [&str; 5] = [
+  "--anneal-iterations",
+  "--anneal-growth",
+  "--anneals-per-slowdown",
+  "--anneal-seed-size",
+  "--anneal-donor",
+];
 
-    Self::transfer_vertices_in_utility_bv_between_cliques(
-      clique_into,
-      clique_from,
-      utility_bv,
-      vertices_vec,
-    )
+fn parse_annealing_option(
+  option: &str,
+  value: &str,
+  schedule: &mut AnnealingSchedule,
+) -> Result<(), VccError> {
+  match option {
+    "--anneal-iterations" => schedule.initial_iterations = parse_count(value)?,
+    "--anneal-growth" => schedule.growth = parse_value(value, "annealing growth")?,
+    "--anneals-per-slowdown" => schedule.annealings_per_slowdown = parse_count(value)?,
+    "--anneal-seed-size" => schedule.seed_size = parse_count(value)?,
+    "--anneal-donor" => schedule.donor = value.parse()?,
+    _ => return Err(unknown_option(option)),
   }
+  Ok(())
+}
+
+fn unknown_option(option: &str) -> VccError {
+  VccError::Parse(format!("unknown option: {}", option))
+}
 
-  fn transfer_vertices_in_utility_bv_between_cliques(
-    clique_into: &mut Clique,
-    clique_from: &mut Clique,
-    utility_bv: &mut BitVec,
-    vertices_vec: &SmallVec<[Clique; 256]>,
-  ) {
-    // update members_bv for both cliques
-    clique_into.members_bv.or_inplace(utility_bv);
-    clique_from.members_bv.xor_inplace(utility_bv);
+// <vertices> <cliques> <edge_fraction>
+fn parse_instance(args: &[String]) -> Result<(usize, usize, f64), VccError> {
+  if args.len() < 3 {
+    return Err(VccError::Parse(
+      "expected <vertices> <cliques> <edge_fraction>".to_string(),
+    ));
+  }
+  Ok((
+    parse_count(&args[0])?,
+    parse_count(&args[1])?,
+    parse_value(&args[2], "edge fraction")?,
+  ))
+}
 
-    // update members & neighbors_bv for both cliques
-    clique_from.neighbors_bv.set_all_true();
-    for i in (0..clique_from.members_ct).rev() {
-      if utility_bv.get_unchecked(clique_from.members[i]) {
-        clique_into
-          .neighbors_bv
-          .and_inplace(&vertices_vec[clique_from.members[i]].neighbors_bv);
-        clique_into.members.push(clique_from.members.swap_remove(i));
-        clique_from.members_ct -= 1;
-        clique_into.members_ct += 1;
-      } else {
-        clique_from
-          .neighbors_bv
-          .and_inplace(&vertices_vec[clique_from.members[i]].neighbors_bv);
+// vcc gen <vertices> <cliques> <edge_fraction> [--seed S] [--format dimacs]
+//   [-o -]
+// Writes the instance solve would generate, with the same seed, to a file
+// in the format its extension names, or by default to stdout in --format,
+// for pipelines: vcc gen 200 10 0.5 | vcc solve - --format dimacs -o -
+fn run_gen(args: &[String]) -> Result<(), VccError> {
+  let (num_vertices, cliques_ct, edge_fraction) = parse_instance(args)?;
+  let mut seed: Option<u64> = None;
+  let mut format: Option<GraphFormat> = None;
+  let mut output_path = STDIO;
+  let mut i = 3;
+  while i < args.len() {
+    let value = option_value(args, i)?;
+    match args[i].as_str() {
+      "--seed" => seed = Some(parse_value(value, "seed")?),
+      "--format" => format = Some(value.parse()?),
+      "-o" | "--output" => output_path = value,
+      _ => return Err(unknown_option(&args[i])),
+    }
+    i += 2;
+  }
+  let format = match format {
+    Some(format) => format,
+    None if output_path == STDIO => GraphFormat::Dimacs,
+    None => GraphFormat::from_path(output_path)?,
+  };
+  let mut rng = default_rng(seed.unwrap_or_else(entropy_seed));
+  let g = get_random_graph_with_k_cliques(num_vertices, cliques_ct, edge_fraction, &mut rng)?;
+  write_graph_as(&g, output_path, format)
+}
+
+// vcc race <vertices> <cliques> <edge_fraction>
+//   --reverse-fractions 0,0.25,0.5 --max-iterations 100_000,1_000_000
+//   [--rounds 20] [--min-rounds 3] [--z 2.0] [annealing options]
+// Races every combination of the listed reverse fractions and per-restart
+// iteration budgets on one instance.
+fn run_race(args: &[String]) -> Result<(), VccError> {
+  let (num_vertices, cliques_ct, edge_fraction) = parse_instance(args)?;
+  let mut reverse_fractions: Vec<f64> = vec![0.0];
+  let mut iteration_budgets: Vec<usize> = vec![100_000];
+  let mut race_params = RaceParams {
+    min_rounds: 3,
+    max_rounds: 20,
+    z: 2.0,
+  };
+  let mut annealing = AnnealingSchedule::default();
+  let mut i = 3;
+  while i < args.len() {
+    let value = option_value(args, i)?;
+    match args[i].as_str() {
+      "--reverse-fractions" => {
+        reverse_fractions = value
+          .split(',')
+          .map(|v| parse_value(v, "reverse fraction"))
+          .collect::<Result<_, _>>()?
+      }
+      "--max-iterations" => {
+        iteration_budgets = value
+          .split(',')
+          .map(parse_count)
+          .collect::<Result<_, _>>()?
       }
+      "--rounds" => race_params.max_rounds = parse_count(value)?,
+      "--min-rounds" => race_params.min_rounds = parse_count(value)?,
+      "--z" => race_params.z = parse_value(value, "z")?,
+      option if ANNEALING_OPTIONS.contains(&option) => {
+        parse_annealing_option(option, value, &mut annealing)?
+      }
+      _ => return Err(unknown_option(&args[i])),
     }
+    i += 2;
+  }
 
-    if clique_from.members_ct == 0 {
-      clique_from.neighbors_bv.set_all_true();
-      clique_from.has_neighbors = true;
-      clique_from.is_active = false;
-    } else {
-      // If nothing else, it has some neighbors in clique_into
-      clique_from.has_neighbors = true;
+  let mut configs = vec![];
+  for &reverse_fraction in &reverse_fractions {
+    for &max_iterations in &iteration_budgets {
+      say!(
+        "config {}: reverse_fraction {}, max_iterations {}",
+        configs.len(),
+        reverse_fraction,
+        max_iterations
+      );
+      configs.push(SolveParams {
+        max_iterations,
+        reverse_fraction,
+        target: cliques_ct,
+        max_restarts: 1,
+        annealing,
+        ..SolveParams::default()
+      });
     }
+  }
 
-    if clique_into.neighbors_bv.none() {
-      clique_into.has_neighbors = false;
+  let mut rng = default_rng(entropy_seed());
+  let g = get_random_graph_with_k_cliques(num_vertices, cliques_ct, edge_fraction, &mut rng)?;
+  let outcome = race(&g, &configs, &race_params)?;
+  for (c, round) in &outcome.eliminated {
+    say!("config {} dropped after round {}", c, round);
+  }
+  let line = format!(
+    "winner: config {}, best {} (vs {}), reached target: {}, rounds: {}",
+    outcome.winner,
+    outcome.best_cover.len(),
+    cliques_ct,
+    outcome.reached_target,
+    outcome.rounds
+  );
+  let size = outcome.best_cover.len();
+  finish_search(line, size, false, Outcome::of(size, cliques_ct, false))
+}
+
+// vcc portfolio <vertices> <cliques> <edge_fraction>
+//   [--window 10_000] [--max-steps 1_000_000] [--reverse-fraction 0]
+//   [--tenure 10] [--tenure-policy fixed] [--aspiration off] [--lns-size 3]
+// --tenure-policy is fixed, reactive or reactive:<increase>:<decrease>:<window>
+// and --aspiration is off or new-best; see tabu.rs.
+// Cycles iterated greedy -> tabu -> lns -> ejection, switching on stagnation.
+fn run_portfolio(args: &[String]) -> Result<(), VccError> {
+  let (num_vertices, cliques_ct, edge_fraction) = parse_instance(args)?;
+  let mut stagnation_window = 10_000;
+  let mut max_steps = 1_000_000;
+  let mut reverse_fraction = 0.0;
+  let mut tabu = Tabu::new(10);
+  let mut destroy_ct = 3;
+  let mut i = 3;
+  while i < args.len() {
+    let value = option_value(args, i)?;
+    match args[i].as_str() {
+      "--window" => stagnation_window = parse_count(value)?,
+      "--max-steps" => max_steps = parse_count(value)?,
+      "--reverse-fraction" => reverse_fraction = parse_value(value, "reverse fraction")?,
+      "--tenure" => tabu.base_tenure = parse_count(value)?,
+      "--tenure-policy" => tabu.tenure_policy = value.parse()?,
+      "--aspiration" => tabu.aspiration = value.parse()?,
+      "--lns-size" => destroy_ct = parse_count(value)?,
+      _ => return Err(unknown_option(&args[i])),
     }
+    i += 2;
   }
 
-  fn shuffle_active_cliques(&mut self) {
-    fastrand::shuffle(&mut self.cliques[0..(self.cliques_ct)]);
+  let mut rng = default_rng(entropy_seed());
+  let mut g = get_random_graph_with_k_cliques(num_vertices, cliques_ct, edge_fraction, &mut rng)?;
+  let mut portfolio = Portfolio {
+    strategies: vec![
+      Box::new(IteratedGreedy { reverse_fraction }),
+      Box::new(tabu),
+      Box::new(Lns { destroy_ct }),
+      Box::new(Ejection),
+    ],
+    stagnation_window,
+  };
+  let result = portfolio.run(&mut g, max_steps, cliques_ct)?;
+  for (step, name) in &result.switches {
+    say!("step {}: switched to {}", step, name);
   }
+  let line = format!(
+    "best {} (vs {}), reached target: {}, steps: {}",
+    result.best_cover.len(),
+    cliques_ct,
+    result.reached_target,
+    result.steps
+  );
+  let size = result.best_cover.len();
+  finish_search(line, size, false, Outcome::of(size, cliques_ct, false))
+}
 
-  fn reverse_active_cliques(&mut self) {
-    self.cliques[0..(self.cliques_ct)].reverse();
+// vcc islands <vertices> <cliques> <edge_fraction>
+//   [--islands 4] [--population 10] [--generations 1_000]
+//   [--migration-interval 50] [--local-search 100] [--reverse-fraction 0]
+//   [--seed 0] [--min-distance 5]
+// --min-distance turns on the population diversity policy; see memetic.rs.
+fn run_islands(args: &[String]) -> Result<(), VccError> {
+  let (num_vertices, cliques_ct, edge_fraction) = parse_instance(args)?;
+  let mut params = IslandParams {
+    islands: 4,
+    population_size: 10,
+    generations: 1_000,
+    migration_interval: 50,
+    local_search_iterations: 100,
+    reverse_fraction: 0.0,
+    target: cliques_ct,
+    seed: 0,
+    min_distance: None,
+  };
+  let mut i = 3;
+  while i < args.len() {
+    let value = option_value(args, i)?;
+    match args[i].as_str() {
+      "--islands" => params.islands = parse_count(value)?,
+      "--population" => params.population_size = parse_count(value)?,
+      "--generations" => params.generations = parse_count(value)?,
+      "--migration-interval" => params.migration_interval = parse_count(value)?,
+      "--local-search" => params.local_search_iterations = parse_count(value)?,
+      "--reverse-fraction" => params.reverse_fraction = parse_value(value, "reverse fraction")?,
+      "--seed" => params.seed = parse_value(value, "seed")?,
+      "--min-distance" => params.min_distance = Some(parse_count(value)?),
+      _ => return Err(unknown_option(&args[i])),
+    }
+    i += 2;
   }
 
-  fn vcc_greedy(&mut self) {
-    // Try to merge every active pair of cliques
-    for i in 0..(self.cliques_ct - 1) {
-      if !self.cliques[i].is_active {
-        continue;
-      }
-      for j in (i + 1)..(self.cliques_ct) {
-        if !self.cliques[j].is_active {
-          continue;
-        }
-        let (cliques_before_j, cliques_from_j) = self.cliques.split_at_mut(j);
-        let cliques_i: &mut Clique = &mut cliques_before_j[i];
-        let cliques_j: &mut Clique = &mut cliques_from_j[0];
-        Self::transfer_compatible_vertices(
-          cliques_i,
-          cliques_j,
-          &mut self.utility_bv,
-          &self.vertices,
-        );
+  let mut rng = default_rng(params.seed);
+  let g = get_random_graph_with_k_cliques(num_vertices, cliques_ct, edge_fraction, &mut rng)?;
+  let result = island_search(&g, &params)?;
+  let line = format!(
+    "best {} (vs {}), reached target: {}, generations: {}, island bests: {:?}",
+    result.best_cover.len(),
+    cliques_ct,
+    result.reached_target,
+    result.generations,
+    result.island_bests
+  );
+  let size = result.best_cover.len();
+  finish_search(line, size, false, Outcome::of(size, cliques_ct, false))
+}
+
+// vcc multistart <vertices> <cliques> <edge_fraction>
+//   [--workers 4] [--max-iterations 100_000] [--restarts 10]
+//   [--reverse-fraction 0] [--reverse-decay linear:0.05]
+//   [--order largest-first:0.5] [--freeze largest:3] [--seed S]
+//   [--initial sequential] [--exact-finish on] [--profile quick]
+//   [--duty 0.5] [--schedule 22-7:2] [--check-every 30s]
+//   [annealing options]
+// A seed makes the run reproducible regardless of thread count. --profile
+// presets the search options and workers (see profile.rs), one per core
+// unless it says otherwise; options given with it override it. --duty and
+// --schedule throttle the workers as they do solve's, --schedule limiting
+// the workers running during its hours.
+fn run_multistart(args: &[String]) -> Result<(), VccError> {
+  let (num_vertices, cliques_ct, edge_fraction) = parse_instance(args)?;
+  let mut workers = 4;
+  let mut seed: Option<u64> = None;
+  let mut throttle = Throttle::default();
+  let mut params = SolveParams {
+    target: cliques_ct,
+    ..SolveParams::default()
+  };
+  if let Some(profile) = profile_option(args)? {
+    profile.apply(&mut params);
+    workers = profile
+      .workers()
+      .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |cores| cores.get()));
+  }
+  let mut i = 3;
+  while i < args.len() {
+    let value = option_value(args, i)?;
+    match args[i].as_str() {
+      "--workers" => workers = parse_count(value)?,
+      "--profile" => {}
+      "--duty" => throttle.duty = parse_value(value, "duty cycle")?,
+      "--schedule" => throttle.schedule = Some(value.parse()?),
+      "--seed" => seed = Some(parse_value(value, "seed")?),
+      "--max-iterations" => params.max_iterations = parse_count(value)?,
+      "--restarts" => params.max_restarts = parse_count(value)?,
+      "--reverse-fraction" => params.reverse_fraction = parse_value(value, "reverse fraction")?,
+      "--reverse-decay" => params.reverse_decay = Some(value.parse()?),
+      "--order" => params.order = value.parse()?,
+      "--check-every" => params.check = value.parse()?,
+      "--freeze" => params.freeze = Some(value.parse()?),
+      "--initial" => params.initial = value.parse()?,
+      "--exact-finish" => params.finish = Some(value.parse()?),
+      option if ANNEALING_OPTIONS.contains(&option) => {
+        parse_annealing_option(option, value, &mut params.annealing)?
       }
+      _ => return Err(unknown_option(&args[i])),
     }
+    i += 2;
+  }
 
-    let mut i = 1;
-    loop {
-      if i >= self.cliques_ct {
-        break;
-      }
-      if self.cliques[i].is_active {
-        i += 1;
-      } else if self.cliques[self.cliques_ct - 1].is_active {
-        self.cliques.swap(i, self.cliques_ct - 1);
-        i += 1;
-        self.cliques_ct -= 1;
-      } else {
-        self.cliques_ct -= 1;
-      }
+  let mut rng = default_rng(seed.unwrap_or_else(entropy_seed));
+  let g = get_random_graph_with_k_cliques(num_vertices, cliques_ct, edge_fraction, &mut rng)?;
+  let started = Instant::now();
+  let result = multi_start_throttled(&g, &params, workers, seed, throttle)?;
+  if !throttle.is_off() {
+    say!("{}", throughput(result.restarts, result.busy, started.elapsed()));
+  }
+  let line = format!(
+    "best {} (vs {}), lower bound {}, reached target: {}, restarts: {}",
+    result.best_cover.len(),
+    cliques_ct,
+    result.lower_bound,
+    result.reached_target,
+    result.restarts
+  );
+  let size = result.best_cover.len();
+  let outcome = Outcome::of(size, cliques_ct, size <= result.lower_bound);
+  finish_search(line, size, false, outcome)
+}
+
+// vcc decide <graph file> <k> [--exact-size 60] [--max-nodes 1_000_000]
+//   [--max-iterations 100_000] [--restarts 10] [--seed S]
+//   [--certificate cover.csv]
+// Decides whether the graph has a cover with at most k cliques (see
+// decide.rs). Prints yes, no or unknown, and exits like SAT solvers do:
+// 10 for yes, 20 for no, 0 for unknown. --certificate writes a yes's
+// cover as vertex,cluster rows. The complement of a known benchmark (see
+// known.rs) also gets its known optimum, and a yes's gap to it.
+fn run_decide(args: &[String]) -> Result<(), VccError> {
+  let [path, k, ..] = args else {
+    return Err(VccError::Parse("expected <graph file> <k>".to_string()));
+  };
+  let mut decide = Decide {
+    k: parse_count(k)?,
+    exact_size: 60,
+    max_nodes: 1_000_000,
+  };
+  let mut seed: Option<u64> = None;
+  let mut certificate_path: Option<String> = None;
+  let mut params = SolveParams::default();
+  let mut i = 2;
+  while i < args.len() {
+    let value = option_value(args, i)?;
+    match args[i].as_str() {
+      "--exact-size" => decide.exact_size = parse_count(value)?,
+      "--max-nodes" => decide.max_nodes = parse_count(value)?,
+      "--max-iterations" => params.max_iterations = parse_count(value)?,
+      "--restarts" => params.max_restarts = parse_count(value)?,
+      "--seed" => seed = Some(parse_value(value, "seed")?),
+      "--certificate" => certificate_path = Some(value.to_string()),
+      _ => return Err(unknown_option(&args[i])),
     }
+    i += 2;
   }
 
-  fn vcc_iterated_greedy(&mut self, reverse_fraction: f64) {
-    if fastrand::f64() < reverse_fraction {
-      self.reverse_active_cliques();
-    } else {
-      self.shuffle_active_cliques();
-    }
-    self.vcc_greedy();
-  }
-
-  fn vcc_run_iterations_to_target(
-    &mut self,
-    num_iterations: usize,
-    target: usize,
-    reverse_fraction: f64,
-  ) -> bool {
-    let mut pri_cliques = self.cliques_ct;
-    let mut current = Instant::now();
-    let mut vertex_id_to_transfer: usize;
-    let mut iterations_per_annealing: usize = 1_000_000;
-    let annealings_per_slowdown: usize = 1; //100;
-    let mut cur_annealing_iterations: usize = 0;
-    let mut cur_annealing_annealings: usize = 0;
-    for i in 1..(num_iterations + 1) {
-      cur_annealing_iterations += 1;
-      // Anneal!
-      if cur_annealing_iterations >= iterations_per_annealing {
-        cur_annealing_iterations = 0;
-        cur_annealing_annealings += 1;
-        if cur_annealing_annealings >= annealings_per_slowdown {
-          cur_annealing_annealings = 0;
-          iterations_per_annealing += iterations_per_annealing / 50; //*= 2;
-        }
-
-        // activate a new clique
-        self.activate_inactive_clique();
-
-        // Transfer a random vertex from the first clique into the new clique
-        vertex_id_to_transfer = fastrand::usize(..self.cliques[0].members_ct);
-
-        let (cliques_before_new, cliques_from_new) = self.cliques.split_at_mut(self.cliques_ct - 1);
-        let clique_from: &mut Clique = &mut cliques_before_new[0];
-        let clique_into: &mut Clique = &mut cliques_from_new[0];
-
-        Self::transfer_vertex_into_clique(
-          clique_into,
-          clique_from,
-          &mut self.utility_bv,
-          &self.vertices,
-          vertex_id_to_transfer,
-        );
-        // run one iteration with reverse fraction at 100% (so the new guy is first)
-        self.vcc_iterated_greedy(1.0);
-      }
-      self.vcc_iterated_greedy(reverse_fraction);
-      if i % 1_000_000 == 0 || self.cliques_ct < pri_cliques {
-        if self.cliques_ct < pri_cliques {
-          cur_annealing_iterations = 0;
-        }
-
-        /*println!(
-          "Iteration {:0>3}_{:0>3}_{:0>3}: {} -> {} ({:?})",
-          (i % 1_000_000_000) / 1_000_000,
-          (i % 1_000_000) / 1_000,
-          i % 1000,
-          pri_cliques,
-          self.cliques_ct,
-          current.elapsed()
-        );*/
-        current = Instant::now();
-        pri_cliques = self.cliques_ct;
-        if self.cliques_ct <= target {
-          println!(
-            "iter, {}, iterations per annealing, {}",
-            i.separate_with_commas(), iterations_per_annealing.separate_with_commas()
-          );
-          return true;
-        }
+  let (mut g, _) = read_weighted_graph(path, Symmetrize::Error)?;
+  g.seed(seed.unwrap_or_else(entropy_seed));
+  let decision = g.decide(&decide, &params)?;
+  say!("{}", decision);
+  let size = match &decision {
+    Decision::Yes(cover) => Some(cover.len()),
+    _ => None,
+  };
+  report_known(&g, path, size);
+  match decision {
+    Decision::Yes(mut cover) => {
+      cover.check_consistency()?;
+      g.validate_cover(&cover)?;
+      cover.canonicalize();
+      if let Some(path) = certificate_path {
+        write_atomic(path, cover.to_cluster_csv())?;
       }
+      process::exit(10);
     }
-    false
+    Decision::No { .. } => process::exit(20),
+    Decision::Unknown { .. } => Ok(()),
   }
+}
 
-  fn conform_cliques_to_vertices(&mut self) {
-    for i in 0..self.size {
-      transcribe_clique_onto_clique(&self.vertices[i], &mut self.cliques[i]);
-    }
-    self.cliques_ct = self.size;
+// For a benchmark, or its complement, solved from path: its optimum if
+// known and the gap to the cover size found.
+fn report_known(g: &Graph, path: &str, size: Option<usize>) {
+  let Some(recognized) = g.recognize(Some(path)) else {
+    return;
+  };
+  match (recognized.cover_number(), size) {
+    (Some(optimum), Some(size)) => say!(
+      "complement of {}: optimum {} cliques, gap {}",
+      recognized.instance.name,
+      optimum,
+      // Signed: a cover below the recorded optimum means the record is wrong.
+      size as isize - optimum as isize
+    ),
+    (Some(optimum), None) => say!(
+      "complement of {}: optimum {} cliques",
+      recognized.instance.name, optimum
+    ),
+    (None, _) => say!("{}: optimum not known", recognized.instance.name),
   }
+}
 
-  fn to_vertex_string(&self) -> String {
-    let mut ret_str = String::new();
-    for i in 0..(self.size) {
-      ret_str += &self.vertices[i].to_string();
-      ret_str += "\n";
+// vcc convert <in> <out> [--complement] [--symmetrize union|intersection|error]
+// Converts a graph between the file formats of formats.rs, picked by the
+// files' extensions. DIMACS weights carry over to DIMACS output; with
+// --complement, which writes the complement instead, only vertex weights do.
+// Arcs of a directed input are rejected unless --symmetrize says how they
+// become edges: wherever there's an arc either way, or only both ways.
+fn run_convert(args: &[String]) -> Result<(), VccError> {
+  let mut complement = false;
+  let mut symmetrize = Symmetrize::Error;
+  let mut paths = vec![];
+  let mut i = 0;
+  while i < args.len() {
+    match args[i].as_str() {
+      "--complement" => complement = true,
+      "--symmetrize" => {
+        symmetrize = option_value(args, i)?.parse()?;
+        i += 1;
+      }
+      arg if arg.starts_with("--") => return Err(unknown_option(arg)),
+      arg => paths.push(arg),
     }
-    ret_str
+    i += 1;
   }
+  let [input, output] = paths[..] else {
+    return Err(VccError::Parse("expected <in> <out>".to_string()));
+  };
+  let (mut g, mut weights) = read_weighted_graph(input, symmetrize)?;
+  if complement {
+    g = g.complement();
+    weights.edge.clear();
+  }
+  write_weighted_graph(&g, &weights, output)
+}
 
-  fn to_string(&self) -> String {
-    let mut ret_str = String::new();
-    for i in 0..(self.cliques_ct) {
-      ret_str += &self.cliques[i].to_string();
-      ret_str += "\n";
+// vcc verify --cover <cover file> --graph <graph file>
+// Checks a cover from any tool against the graph (see verify.rs) and
+// prints every violation, vertices named as the graph file numbers them;
+// fails if there are any.
+fn run_verify(args: &[String]) -> Result<(), VccError> {
+  let mut cover_path: Option<&str> = None;
+  let mut graph_path: Option<&str> = None;
+  let mut i = 0;
+  while i < args.len() {
+    let value = option_value(args, i)?;
+    match args[i].as_str() {
+      "--cover" => cover_path = Some(value),
+      "--graph" => graph_path = Some(value),
+      _ => return Err(unknown_option(&args[i])),
     }
-    ret_str
+    i += 2;
+  }
+  let (Some(cover_path), Some(graph_path)) = (cover_path, graph_path) else {
+    return Err(VccError::Parse(
+      "expected --cover <file> --graph <file>".to_string(),
+    ));
+  };
+  let (g, _) = read_weighted_graph(graph_path, Symmetrize::Error)?;
+  let cover = read_cover(cover_path)?;
+  let violations = g.cover_violations(&cover);
+  if violations.is_empty() {
+    say!("valid: {} cliques cover {} vertices", cover.len(), g.size());
+    return Ok(());
+  }
+  let base = vertex_base(GraphFormat::from_path(graph_path)?);
+  for violation in &violations {
+    say!("{}", violation.named(base));
   }
+  Err(VccError::InvalidCover(format!(
+    "{} violations",
+    violations.len()
+  )))
 }
 
-fn get_random_graph(num_vertices: usize, edge_probability: f64) -> Graph {
-  let mut ret_graph = Graph::new(num_vertices);
-  let mut edge_candidates_remaining = num_vertices * (num_vertices - 1) / 2;
-  let mut edges_remaining = (edge_candidates_remaining as f64 * edge_probability) as usize;
-  for i in 0..(ret_graph.size - 1) {
-    for j in (i + 1)..(ret_graph.size) {
-      if fastrand::f64() < (edges_remaining as f64) / (edge_candidates_remaining as f64) {
-        edges_remaining -= 1;
-        ret_graph.vertices[i].neighbors_bv.set(j, true);
-        ret_graph.vertices[j].neighbors_bv.set(i, true);
-      }
-      edge_candidates_remaining -= 1;
+// vcc repair --cover <cover file> --graph <graph file> --output <cover file>
+// Turns an invalid or partial cover into a valid one, moving as few
+// vertices as it can (see repair.rs), writes it in the output's format
+// (see verify.rs) and prints each vertex it placed.
+fn run_repair(args: &[String]) -> Result<(), VccError> {
+  let mut cover_path: Option<&str> = None;
+  let mut graph_path: Option<&str> = None;
+  let mut output_path: Option<&str> = None;
+  let mut i = 0;
+  while i < args.len() {
+    let value = option_value(args, i)?;
+    match args[i].as_str() {
+      "--cover" => cover_path = Some(value),
+      "--graph" => graph_path = Some(value),
+      "--output" => output_path = Some(value),
+      _ => return Err(unknown_option(&args[i])),
     }
+    i += 2;
   }
-  for i in 0..(ret_graph.size) {
-    if ret_graph.vertices[i].neighbors_bv.any() {
-      ret_graph.vertices[i].has_neighbors = true;
+  let (Some(cover_path), Some(graph_path), Some(output_path)) =
+    (cover_path, graph_path, output_path)
+  else {
+    return Err(VccError::Parse(
+      "expected --cover <file> --graph <file> --output <file>".to_string(),
+    ));
+  };
+  let (g, _) = read_weighted_graph(graph_path, Symmetrize::Error)?;
+  let (repaired, repair) = g.repair_cover(&read_cover(cover_path)?);
+  g.validate_cover(&repaired)?;
+  write_cover(&repaired, output_path)?;
+  let base = vertex_base(GraphFormat::from_path(graph_path)?);
+  for placement in &repair.placements {
+    match placement.from {
+      Some(from) => say!(
+        "vertex {}: clique {} -> {}",
+        placement.vertex + base,
+        from,
+        placement.to
+      ),
+      None => say!(
+        "vertex {}: uncovered -> {}",
+        placement.vertex + base,
+        placement.to
+      ),
     }
   }
-  ret_graph.conform_cliques_to_vertices();
-  ret_graph.shuffle_active_cliques();
-  ret_graph
+  say!("{} cliques: {}", repaired.len(), repair);
+  Ok(())
 }
 
-fn get_random_graph_with_k_cliques(
-  num_vertices: usize,
-  cliques_ct: usize,
-  edge_probability: f64,
-) -> Graph {
-  if cliques_ct == 0 {
-    return get_random_graph(num_vertices, edge_probability);
+// vcc improve --cover <cover file> --graph <graph file> --output <cover file>
+//   [--time 60s] [--max-iterations 100_000] [--rounds 1_000_000]
+//   [--reverse-fraction 0] [--seed S] [annealing options]
+// Shrinks a valid cover by local search alone, skipping construction (see
+// improve.rs), for as long as --time allows; writes the best cover found,
+// never larger than the one read, in the output's format (see verify.rs).
+fn run_improve(args: &[String]) -> Result<(), VccError> {
+  let mut cover_path: Option<&str> = None;
+  let mut graph_path: Option<&str> = None;
+  let mut output_path: Option<&str> = None;
+  let mut time = Duration::from_secs(60);
+  let mut seed: Option<u64> = None;
+  let mut params = SolveParams {
+    max_restarts: 1_000_000,
+    ..SolveParams::default()
+  };
+  let mut i = 0;
+  while i < args.len() {
+    let value = option_value(args, i)?;
+    match args[i].as_str() {
+      "--cover" => cover_path = Some(value),
+      "--graph" => graph_path = Some(value),
+      "--output" => output_path = Some(value),
+      "--time" => time = parse_duration(value)?,
+      "--max-iterations" => params.max_iterations = parse_count(value)?,
+      "--rounds" => params.max_restarts = parse_count(value)?,
+      "--reverse-fraction" => params.reverse_fraction = parse_value(value, "reverse fraction")?,
+      "--seed" => seed = Some(parse_value(value, "seed")?),
+      option if ANNEALING_OPTIONS.contains(&option) => {
+        parse_annealing_option(option, value, &mut params.annealing)?
+      }
+      _ => return Err(unknown_option(&args[i])),
+    }
+    i += 2;
   }
+  let (Some(cover_path), Some(graph_path), Some(output_path)) =
+    (cover_path, graph_path, output_path)
+  else {
+    return Err(VccError::Parse(
+      "expected --cover <file> --graph <file> --output <file>".to_string(),
+    ));
+  };
+  let (mut g, _) = read_weighted_graph(graph_path, Symmetrize::Error)?;
+  g.seed(seed.unwrap_or_else(entropy_seed));
+  let improvement = g.improve_cover(&read_cover(cover_path)?, &params, time)?;
+  write_cover(&improvement.cover, output_path)?;
+  say!(
+    "{} -> {} cliques in {} rounds ({:?})",
+    improvement.before,
+    improvement.cover.len(),
+    improvement.rounds,
+    improvement.elapsed
+  );
+  Ok(())
+}
 
-  let mut ret_graph = Graph::new(num_vertices);
-  let mut edge_candidates_remaining = num_vertices * (num_vertices - 1) / 2;
-  let mut edges_remaining = (edge_candidates_remaining as f64 * edge_probability) as usize;
+// vcc fetch [<instance>...] [--dir instances] [--url <base url>]
+// Downloads the named DIMACS coloring benchmarks, or the standard set,
+// into the instance directory unless they are there already, and checks
+// them against the benchmarks they must be and its checksums (see
+// fetch.rs). Prints each instance's path,
+// for decide or convert --complement.
+fn run_fetch(args: &[String]) -> Result<(), VccError> {
+  let mut dir = "instances".to_string();
+  let mut base_url = DEFAULT_BASE_URL.to_string();
+  let mut names = vec![];
+  let mut i = 0;
+  while i < args.len() {
+    match args[i].as_str() {
+      "--dir" => {
+        dir = option_value(args, i)?.to_string();
+        i += 1;
+      }
+      "--url" => {
+        base_url = option_value(args, i)?.to_string();
+        i += 1;
+      }
+      arg if arg.starts_with("--") => return Err(unknown_option(arg)),
+      arg => names.push(arg.to_string()),
+    }
+    i += 1;
+  }
+  if names.is_empty() {
+    names = COLORING_INSTANCES
+      .iter()
+      .map(|(name, _)| name.to_string())
+      .collect();
+  }
+  let mut cache = InstanceCache::open(&dir)?;
+  for name in &names {
+    let path = cache.fetch(name, &base_url)?;
+    say!("{}", path.display());
+  }
+  Ok(())
+}
 
-  let reserved_edges = cliques_ct * (num_vertices / cliques_ct) * (num_vertices / cliques_ct - 1)
-    / 2
-    + (num_vertices % cliques_ct) * (num_vertices / cliques_ct);
-  edge_candidates_remaining -= reserved_edges;
-  if reserved_edges > edges_remaining {
-    edges_remaining = 0;
-  } else {
-    edges_remaining -= reserved_edges;
+// vcc selftest [--seed 0]
+// Solves family instances with known optima and verifies every cover.
+fn run_selftest(args: &[String]) -> Result<(), VccError> {
+  let mut seed = 0;
+  let mut i = 0;
+  while i < args.len() {
+    let value = option_value(args, i)?;
+    match args[i].as_str() {
+      "--seed" => seed = parse_value(value, "seed")?,
+      _ => return Err(unknown_option(&args[i])),
+    }
+    i += 2;
   }
 
-  for i in 0..(ret_graph.size - 1) {
-    for j in (i + 1)..(ret_graph.size) {
-      if i % cliques_ct == j % cliques_ct {
-        ret_graph.vertices[i].neighbors_bv.set(j, true);
-        ret_graph.vertices[j].neighbors_bv.set(i, true);
-      } else if fastrand::f64() < (edges_remaining as f64) / (edge_candidates_remaining as f64) {
-        edges_remaining -= 1;
-        ret_graph.vertices[i].neighbors_bv.set(j, true);
-        ret_graph.vertices[j].neighbors_bv.set(i, true);
+  let cases = selftest::cases();
+  let mut failed_ct = 0;
+  for case in &cases {
+    let outcome = selftest::run_case(case, seed);
+    let found = outcome.found.map_or("-".to_string(), |f| f.to_string());
+    match &outcome.failure {
+      None => say!(
+        "pass  {:<24} {:>3} / {:<3} {:>8.1?}",
+        outcome.name, found, outcome.optimum, outcome.elapsed
+      ),
+      Some(failure) => {
+        failed_ct += 1;
+        say!(
+          "FAIL  {:<24} {:>3} / {:<3} {}",
+          outcome.name, found, outcome.optimum, failure
+        );
       }
+    }
+  }
+  say!("{} of {} passed", cases.len() - failed_ct, cases.len());
+  if failed_ct > 0 {
+    process::exit(1);
+  }
+  Ok(())
+}
 
-      if i % cliques_ct != j % cliques_ct {
-        edge_candidates_remaining -= 1;
+// vcc ttt <vertices> <cliques> <edge_fraction>
+//   [--runs 100] [--seed 0] [--target <cliques>] [--output ttt.csv]
+//   [--max-iterations 100_000] [--restarts 10] [--reverse-fraction 0]
+//   [--reverse-decay linear:0.05] [--order largest-first:0.5]
+//   [--freeze largest:3] [--initial sequential] [--exact-finish on]
+//   [--check-every 250_000] [annealing options]
+// Solves the instance generated from the seed once per run and writes the
+// times to target as CSV for TTT plots (to a file, since the solver prints
+// progress to stdout); see ttt.rs.
+fn run_ttt(args: &[String]) -> Result<(), VccError> {
+  let (num_vertices, cliques_ct, edge_fraction) = parse_instance(args)?;
+  let mut runs = 100;
+  let mut seed = 0;
+  let mut output_path = "ttt.csv".to_string();
+  let mut params = SolveParams {
+    target: cliques_ct,
+    ..SolveParams::default()
+  };
+  let mut i = 3;
+  while i < args.len() {
+    let value = option_value(args, i)?;
+    match args[i].as_str() {
+      "--runs" => runs = parse_count(value)?,
+      "--seed" => seed = parse_value(value, "seed")?,
+      "--target" => params.target = parse_count(value)?,
+      "--output" => output_path = value.to_string(),
+      "--max-iterations" => params.max_iterations = parse_count(value)?,
+      "--restarts" => params.max_restarts = parse_count(value)?,
+      "--reverse-fraction" => params.reverse_fraction = parse_value(value, "reverse fraction")?,
+      "--reverse-decay" => params.reverse_decay = Some(value.parse()?),
+      "--order" => params.order = value.parse()?,
+      "--check-every" => params.check = value.parse()?,
+      "--freeze" => params.freeze = Some(value.parse()?),
+      "--initial" => params.initial = value.parse()?,
+      "--exact-finish" => params.finish = Some(value.parse()?),
+      option if ANNEALING_OPTIONS.contains(&option) => {
+        parse_annealing_option(option, value, &mut params.annealing)?
       }
+      _ => return Err(unknown_option(&args[i])),
     }
+    i += 2;
   }
-  for i in 0..(ret_graph.size) {
-    if ret_graph.vertices[i].neighbors_bv.any() {
-      ret_graph.vertices[i].has_neighbors = true;
+
+  let mut rng = default_rng(seed);
+  let g = get_random_graph_with_k_cliques(num_vertices, cliques_ct, edge_fraction, &mut rng)?;
+  let results = time_to_target(&g, &params, runs, seed)?;
+  write_atomic(&output_path, format_ttt(&results))?;
+  let reached_ct = results.iter().filter(|r| r.reached_target).count();
+  say!(
+    "{} of {} runs reached {}; wrote {}",
+    reached_ct, runs, params.target, output_path
+  );
+  Ok(())
+}
+
+// Removes the global options, which come before the subcommand, from args:
+//   --quiet                 The searches print only their covers' sizes (see
+//                           finish_search), the other commands only errors.
+//   --fsync none|file|full  How much the files written are synced (see
+//                           durable.rs).
+//   --threads N, --pin-cores  Size the parallel drivers' pool, and pin its
+//                           threads to the allowed CPUs (see threads.rs).
+// Options after the subcommand, and their values, are the subcommand's.
+fn take_global_options(args: &mut Vec<String>) -> Result<ThreadConfig, VccError> {
+  let mut thread_config = ThreadConfig::default();
+  while args.len() > 1 {
+    match args[1].as_str() {
+      "--quiet" => {
+        QUIET.store(true, Ordering::Relaxed);
+        args.remove(1);
+      }
+      "--fsync" => {
+        set_fsync(option_value(args, 1)?.parse()?);
+        args.drain(1..3);
+      }
+      "--threads" => {
+        thread_config.threads = Some(parse_count(option_value(args, 1)?)?);
+        args.drain(1..3);
+      }
+      "--pin-cores" => {
+        thread_config.pin_cores = true;
+        args.remove(1);
+      }
+      _ => break,
     }
   }
-  ret_graph.conform_cliques_to_vertices();
-  ret_graph
+  Ok(thread_config)
 }
 
-fn clear_screen() {
-  print!("\x1B[2J\x1B[1;1H");
+fn main() {
+  let mut args: Vec<String> = env::args().collect();
+  let result = take_global_options(&mut args)
+    .and_then(|thread_config| thread_config.install(|| dispatch(&args))?);
+  if let Err(e) = result {
+    eprintln!("error: {}", e);
+    process::exit(1);
+  }
 }
 
-fn main() {
-  let args: Vec<String> = env::args().collect();
-  let num_vertices: usize = args[1].parse().unwrap();
-  let cliques_ct: usize = args[2].parse().unwrap();
-  let edge_fraction: f64 = args[3].parse().unwrap();
-  let max_iterations_str: String = args[4].parse().unwrap();
-  let max_iterations: usize = max_iterations_str.replace('_', "").parse().unwrap();
-  let reverse_fraction: f64 = args[5].parse().unwrap();
-  clear_screen();
-  println!(
+fn dispatch(args: &[String]) -> Result<(), VccError> {
+  if args.len() > 1 && args[1] == "solve" {
+    return run_solve(&args[2..]);
+  }
+  if args.len() > 1 && args[1] == "gen" {
+    return run_gen(&args[2..]);
+  }
+  if args.len() > 1 && args[1] == "race" {
+    return run_race(&args[2..]);
+  }
+  if args.len() > 1 && args[1] == "islands" {
+    return run_islands(&args[2..]);
+  }
+  if args.len() > 1 && args[1] == "multistart" {
+    return run_multistart(&args[2..]);
+  }
+  if args.len() > 1 && args[1] == "portfolio" {
+    return run_portfolio(&args[2..]);
+  }
+  if args.len() > 1 && args[1] == "ttt" {
+    return run_ttt(&args[2..]);
+  }
+  if args.len() > 1 && args[1] == "decide" {
+    return run_decide(&args[2..]);
+  }
+  if args.len() > 1 && args[1] == "convert" {
+    return run_convert(&args[2..]);
+  }
+  if args.len() > 1 && args[1] == "verify" {
+    return run_verify(&args[2..]);
+  }
+  if args.len() > 1 && args[1] == "repair" {
+    return run_repair(&args[2..]);
+  }
+  if args.len() > 1 && args[1] == "improve" {
+    return run_improve(&args[2..]);
+  }
+  if args.len() > 1 && args[1] == "fetch" {
+    return run_fetch(&args[2..]);
+  }
+  if args.len() > 1 && args[1] == "selftest" {
+    return run_selftest(&args[2..]);
+  }
+  if args.len() < 6 {
+    return Err(VccError::Parse(
+      "expected <vertices> <cliques> <edge_fraction> <max_iterations> <reverse_fraction>"
+        .to_string(),
+    ));
+  }
+  let (num_vertices, cliques_ct, edge_fraction) = parse_instance(&args[1..4])?;
+  let max_iterations_str = &args[4];
+  let max_iterations = parse_count(max_iterations_str)?;
+  let reverse_fraction: f64 = parse_value(&args[5], "reverse fraction")?;
+  let mut quotient_dot_path: Option<String> = None;
+  let mut quotient_json_path: Option<String> = None;
+  let mut params = SolveParams {
+    max_iterations,
+    reverse_fraction,
+    target: cliques_ct,
+    max_restarts: 1,
+    ..SolveParams::default()
+  };
+  let mut i = 6;
+  while i < args.len() {
+    let value = option_value(args, i)?;
+    match args[i].as_str() {
+      "--quotient-dot" => quotient_dot_path = Some(value.to_string()),
+      "--quotient-json" => quotient_json_path = Some(value.to_string()),
+      "--reverse-decay" => params.reverse_decay = Some(value.parse()?),
+      "--order" => params.order = value.parse()?,
+      "--check-every" => params.check = value.parse()?,
+      "--freeze" => params.freeze = Some(value.parse()?),
+      "--initial" => params.initial = value.parse()?,
+      "--exact-finish" => params.finish = Some(value.parse()?),
+      option if ANNEALING_OPTIONS.contains(&option) => {
+        parse_annealing_option(option, value, &mut params.annealing)?
+      }
+      _ => return Err(unknown_option(&args[i])),
+    }
+    i += 2;
+  }
+  params.validate()?;
+  let terminal = Terminal::detect();
+  if !QUIET.load(Ordering::Relaxed) {
+    terminal.clear_screen();
+  }
+  say!(
     "cargo run --release {} {} {} {} {}",
     num_vertices, cliques_ct, edge_fraction, max_iterations_str, reverse_fraction
   );
-  let mut g = get_random_graph_with_k_cliques(num_vertices, cliques_ct, edge_fraction);
+  let mut rng = default_rng(entropy_seed());
+  let mut g = get_random_graph_with_k_cliques(num_vertices, cliques_ct, edge_fraction, &mut rng)?;
   let mut best_result: usize = num_vertices;
   loop {
-    if g.vcc_run_iterations_to_target(max_iterations, cliques_ct, reverse_fraction) {
-      println!("\n{}", g.to_string());
-      g = get_random_graph_with_k_cliques(num_vertices, cliques_ct, edge_fraction);
+    g.prepare_restart(&params);
+    if g.vcc_run_restart(&params, None) {
+      say!("{}", format_trace(g.last_restart()));
+      say!("\n{}", terminal.draw(&g));
+      write_quotient(&g, &quotient_dot_path, &quotient_json_path)?;
+      g = get_random_graph_with_k_cliques(num_vertices, cliques_ct, edge_fraction, &mut rng)?;
     } else {
-      if g.cliques_ct < best_result {
-        best_result = g.cliques_ct;
-        println!("\nNew best result: {} (vs {})", best_result, cliques_ct);
+      if g.cliques_ct() < best_result {
+        best_result = g.cliques_ct();
+        say!("\nNew best result: {} (vs {})", best_result, cliques_ct);
+        write_quotient(&g, &quotient_dot_path, &quotient_json_path)?;
         //println!("{}", g.to_string());
       }
-      g.conform_cliques_to_vertices();
-      g.shuffle_active_cliques();
     }
   }
   //println!("{}", g.to_string());
@@ -0,0 +1,188 @@
+// Batch solving: a TOML manifest lists many instances, each with its own
+// budget and parameters. For example:
+//
+//   parallel = true
+//
+//   [[job]]
+//   name = "n205"
+//   vertices = 205
+//   cliques = 12
+//   edge_fraction = 0.75
+//   max_iterations = 100_000
+//   reverse_fraction = 0.0
+//   restarts = 10
+//
+// `target` defaults to `cliques`; `restarts` defaults to 1. An optional
+// `seed` fixes both the generated instance and the search, and an optional
+// `[job.annealing]` table overrides any of the annealing schedule's
+// initial_iterations, growth, annealings_per_slowdown, seed_size and donor. To
+// decay the reverse fraction, add e.g. `reverse_decay = { kind = "linear", end = 0.05 }`,
+// to sort cliques by size, e.g. `order = { by = "largest-first", noise = 0.5 }`,
+// to freeze large cliques, e.g. `freeze = { min-size = 10 }`, to start
+// restarts from singletons, `initial = "singletons"`, and to finish stalls
+// exactly, e.g. `finish = { max_vertices = 50 }` (see finish.rs), and to
+// avoid repeated starts, e.g. `distinct_starts = { bits = 4096 }` (see
+// starts.rs). To plant cliques of uneven sizes, add e.g.
+// `sizes = { shape = "geometric", ratio = 0.8, min_size = 2 }` (see
+// planted.rs); the table needs its shape. `planted_noise = 0.1` then moves
+// the planted edges of a tenth of the vertices.
+
+use crate::anneal::AnnealingSchedule;
+use crate::error::VccError;
+use crate::finish::ExactFinish;
+use crate::freeze::Freeze;
+use crate::order::CliqueOrder;
+use crate::planted::PlantedSizes;
+use crate::rng::{default_rng, entropy_seed};
+use crate::solve::{InitialCover, ReverseDecay, SolveParams};
+use crate::starts::DistinctStarts;
+use crate::{get_random_graph_with_planted_cliques, separated};
+use rayon::prelude::*;
+use serde::Deserialize;
+use std::time::Duration;
+
+#[derive(Deserialize)]
+pub struct Manifest {
+  #[serde(default)]
+  pub parallel: bool,
+  #[serde(rename = "job", default)]
+  pub jobs: Vec<Job>,
+}
+
+#[derive(Deserialize)]
+pub struct Job {
+  pub name: Option<String>,
+  pub vertices: usize,
+  pub cliques: usize,
+  #[serde(default)]
+  pub sizes: PlantedSizes,
+  #[serde(default)]
+  pub planted_noise: f64,
+  pub edge_fraction: f64,
+  pub max_iterations: usize,
+  #[serde(default)]
+  pub reverse_fraction: f64,
+  #[serde(default = "default_restarts")]
+  pub restarts: usize,
+  pub target: Option<usize>,
+  pub seed: Option<u64>,
+  #[serde(default)]
+  pub annealing: AnnealingSchedule,
+  pub reverse_decay: Option<ReverseDecay>,
+  #[serde(default)]
+  pub order: CliqueOrder,
+  pub freeze: Option<Freeze>,
+  #[serde(default)]
+  pub initial: InitialCover,
+  pub finish: Option<ExactFinish>,
+  pub distinct_starts: Option<DistinctStarts>,
+}
+
+fn default_restarts() -> usize {
+  1
+}
+
+pub struct JobReport {
+  pub name: String,
+  pub vertices: usize,
+  pub target: usize,
+  pub best: usize,
+  pub reached_target: bool,
+  pub restarts: usize,
+  pub elapsed: Duration,
+}
+
+impl Manifest {
+  pub fn from_toml_str(toml_str: &str) -> Result<Manifest, VccError> {
+    Ok(toml::from_str(toml_str)?)
+  }
+
+  // Runs every job, in manifest order or across the rayon pool. Reports
+  // are returned in manifest order either way.
+  pub fn run(&self, parallel: bool) -> Result<Vec<JobReport>, VccError> {
+    if parallel {
+      self
+        .jobs
+        .par_iter()
+        .enumerate()
+        .map(|(i, job)| job.run(i))
+        .collect()
+    } else {
+      self
+        .jobs
+        .iter()
+        .enumerate()
+        .map(|(i, job)| job.run(i))
+        .collect()
+    }
+  }
+}
+
+impl Job {
+  pub fn run(&self, index: usize) -> Result<JobReport, VccError> {
+    let target = self.target.unwrap_or(self.cliques);
+    let mut rng = default_rng(self.seed.unwrap_or_else(entropy_seed));
+    let mut g = get_random_graph_with_planted_cliques(
+      self.vertices,
+      self.cliques,
+      &self.sizes,
+      self.edge_fraction,
+      &mut rng,
+    )?;
+    if self.planted_noise > 0.0 {
+      g.add_planted_noise(self.planted_noise, &mut rng)?;
+    }
+    let result = g.solve(&SolveParams {
+      max_iterations: self.max_iterations,
+      reverse_fraction: self.reverse_fraction,
+      target,
+      max_restarts: self.restarts,
+      annealing: self.annealing,
+      reverse_decay: self.reverse_decay,
+      order: self.order,
+      freeze: self.freeze,
+      initial: self.initial,
+      finish: self.finish,
+      distinct_starts: self.distinct_starts,
+      ..SolveParams::default()
+    })?;
+    Ok(JobReport {
+      name: self.name.clone().unwrap_or_else(|| format!("job{}", index)),
+      vertices: self.vertices,
+      target,
+      best: result.best_cover.len(),
+      reached_target: result.reached_target,
+      restarts: result.restarts,
+      elapsed: result.elapsed,
+    })
+  }
+}
+
+pub fn format_reports(reports: &[JobReport]) -> String {
+  let mut ret_str = String::from("name, vertices, target, best, reached, restarts, seconds\n");
+  let mut reached_ct = 0;
+  let mut total_elapsed = Duration::ZERO;
+  for report in reports {
+    ret_str += &format!(
+      "{}, {}, {}, {}, {}, {}, {:.3}\n",
+      report.name,
+      separated(report.vertices),
+      report.target,
+      report.best,
+      report.reached_target,
+      report.restarts,
+      report.elapsed.as_secs_f64()
+    );
+    if report.reached_target {
+      reached_ct += 1;
+    }
+    total_elapsed += report.elapsed;
+  }
+  ret_str += &format!(
+    "reached target in {} of {} jobs, {:.3} seconds of solver time\n",
+    reached_ct,
+    reports.len(),
+    total_elapsed.as_secs_f64()
+  );
+  ret_str
+}
@@ -0,0 +1,133 @@
+// Minimum clique cover as weighted MaxSAT, for external solvers. Given a
+// number of slots (the size of any known cover), variable x(v, k) puts
+// vertex v in slot k and y(k) marks slot k used:
+// - hard: every vertex is in some slot, non-adjacent vertices never share
+//   one, and a vertex's slot is used,
+// - soft, weight 1 each: slots stay unused.
+// Vertex v only gets slots 0..=v and slots are used in order, which cuts
+// the symmetric solutions without losing any cover size. The file is in
+// the classic "p wcnf" format, which every solver still reads; a solver's
+// answer comes back in either "v" line format.
+
+use crate::cover::CliqueCover;
+use crate::error::VccError;
+use crate::Graph;
+use std::fmt::Write;
+use std::fs;
+use std::process::Command;
+
+pub struct MaxSatSolution {
+  pub cover: CliqueCover,
+  // Whether the solver proved the cover optimal ("s OPTIMUM FOUND").
+  pub optimal: bool,
+}
+
+impl Graph {
+  pub fn to_wcnf(&self, slots: usize) -> String {
+    let n = self.size;
+    let x = |v: usize, k: usize| 1 + v * slots + k;
+    let y = |k: usize| 1 + n * slots + k;
+    let top = slots + 1;
+    let mut clauses = vec![];
+    for v in 0..n {
+      let allowed = slots.min(v + 1);
+      clauses.push((
+        top,
+        (0..allowed).map(|k| x(v, k) as i64).collect::<Vec<_>>(),
+      ));
+      for k in 0..allowed {
+        clauses.push((top, vec![-(x(v, k) as i64), y(k) as i64]));
+      }
+      for u in (v + 1)..n {
+        if self.topology.vertices[v].neighbors_bv.get_unchecked(u) {
+          continue;
+        }
+        for k in 0..allowed {
+          clauses.push((top, vec![-(x(v, k) as i64), -(x(u, k) as i64)]));
+        }
+      }
+    }
+    for k in 1..slots {
+      clauses.push((top, vec![-(y(k) as i64), y(k - 1) as i64]));
+    }
+    for k in 0..slots {
+      clauses.push((1, vec![-(y(k) as i64)]));
+    }
+    let mut wcnf = format!("c minimum clique cover, {} vertices, {} slots\n", n, slots);
+    writeln!(wcnf, "p wcnf {} {} {}", (n + 1) * slots, clauses.len(), top).unwrap();
+    for (weight, literals) in clauses {
+      write!(wcnf, "{}", weight).unwrap();
+      for literal in literals {
+        write!(wcnf, " {}", literal).unwrap();
+      }
+      wcnf.push_str(" 0\n");
+    }
+    wcnf
+  }
+
+  // Reads a solver's output for to_wcnf(slots): "v" lines of literals, or
+  // a single one of 0/1 digits. A vertex placed twice keeps its first slot.
+  pub fn parse_maxsat_output(
+    &self,
+    output: &str,
+    slots: usize,
+  ) -> Result<MaxSatSolution, VccError> {
+    let n = self.size;
+    let mut assignment = vec![false; (n + 1) * slots + 1];
+    let mut found = false;
+    for line in output.lines() {
+      let Some(values) = line.strip_prefix("v ") else {
+        continue;
+      };
+      found = true;
+      let values = values.trim();
+      if !values.is_empty() && values.chars().all(|c| c == '0' || c == '1') {
+        for (i, c) in values.chars().enumerate() {
+          if let Some(value) = assignment.get_mut(i + 1) {
+            *value = c == '1';
+          }
+        }
+        continue;
+      }
+      for literal in values.split_whitespace() {
+        let literal: i64 = literal
+          .parse()
+          .map_err(|_| VccError::Parse(format!("bad literal in solver output: {:?}", literal)))?;
+        if let Some(value) = assignment.get_mut(literal.unsigned_abs() as usize) {
+          *value = literal > 0;
+        }
+      }
+    }
+    if !found {
+      return Err(VccError::Parse("no solution in solver output".to_string()));
+    }
+    let mut cliques = vec![vec![]; slots];
+    for v in 0..n {
+      let slot = (0..slots)
+        .find(|&k| assignment[1 + v * slots + k])
+        .ok_or_else(|| VccError::InvalidCover(format!("vertex {} is in no slot", v)))?;
+      cliques[slot].push(v);
+    }
+    cliques.retain(|members| !members.is_empty());
+    let cover = CliqueCover { cliques };
+    self.validate_cover(&cover)?;
+    Ok(MaxSatSolution {
+      cover,
+      optimal: output.lines().any(|line| line.trim() == "s OPTIMUM FOUND"),
+    })
+  }
+
+  // Runs `command <file>` on a temporary WCNF file and reads its answer.
+  // command is split on whitespace, so it can carry the solver's options.
+  pub fn solve_maxsat(&self, command: &str, slots: usize) -> Result<MaxSatSolution, VccError> {
+    let mut words = command.split_whitespace();
+    let program = words
+      .next()
+      .ok_or_else(|| VccError::InvalidParameter("empty MaxSAT command".to_string()))?;
+    let path = std::env::temp_dir().join(format!("vcc-{}.wcnf", std::process::id()));
+    fs::write(&path, self.to_wcnf(slots))?;
+    let output = Command::new(program).args(words).arg(&path).output();
+    fs::remove_file(&path)?;
+    self.parse_maxsat_output(&String::from_utf8_lossy(&output?.stdout), slots)
+  }
+}
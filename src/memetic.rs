@@ -0,0 +1,227 @@
+// Island-model memetic search. Each island keeps a small population of
+// covers; a generation crosses two members, repairs the
+// child with iterated greedy and replaces the worst member if the child is
+// no worse. Every migration_interval generations each island sends a copy
+// of its best cover to the next island in the ring, which replaces its own
+// worst member with it.
+//
+// With min_distance set, a population also keeps its members apart: a
+// newcomer (child or migrant) within min_distance of a member is rejected,
+// and otherwise it replaces the member closest to it among those no
+// smaller than it, if any.
+//
+// Between migrations the islands evolve in parallel on the current rayon
+// pool, so --threads bounds them like the other drivers (see threads.rs).
+// They synchronize at every migration, and island i's generator is seeded
+// with seed + i, so a fixed seed gives the same result on every run and
+// any thread count.
+
+use crate::cover::CliqueCover;
+use crate::error::VccError;
+use crate::rng::below;
+use crate::Graph;
+use rayon::prelude::*;
+
+pub struct IslandParams {
+  pub islands: usize,
+  pub population_size: usize,
+  // Generations per island.
+  pub generations: usize,
+  pub migration_interval: usize,
+  // Iterated greedy passes applied to every new cover.
+  pub local_search_iterations: usize,
+  pub reverse_fraction: f64,
+  pub target: usize,
+  pub seed: u64,
+  // Minimum partition distance between members (see CliqueCover::distance).
+  pub min_distance: Option<usize>,
+}
+
+pub struct IslandResult {
+  pub best_cover: CliqueCover,
+  pub reached_target: bool,
+  // Generations run by each island.
+  pub generations: usize,
+  pub island_bests: Vec<usize>,
+}
+
+// Greedy partition crossover: alternately take the clique with the most
+// still-uncovered vertices from each parent. Any subset of a clique is a
+// clique, so the child is a valid cover; vertices left over at the end
+// become singletons for the repair step to absorb.
+pub fn crossover(a: &CliqueCover, b: &CliqueCover, num_vertices: usize) -> CliqueCover {
+  let mut uncovered = vec![true; num_vertices];
+  let mut uncovered_ct = num_vertices;
+  let parents = [a, b];
+  let mut cliques = vec![];
+  for turn in 0..a.len().max(b.len()) {
+    if uncovered_ct == 0 {
+      break;
+    }
+    let best = parents[turn % 2]
+      .cliques
+      .iter()
+      .max_by_key(|members| members.iter().filter(|&&v| uncovered[v]).count())
+      .unwrap();
+    let members: Vec<usize> = best.iter().copied().filter(|&v| uncovered[v]).collect();
+    for &v in &members {
+      uncovered[v] = false;
+    }
+    uncovered_ct -= members.len();
+    if !members.is_empty() {
+      cliques.push(members);
+    }
+  }
+  for (v, &is_uncovered) in uncovered.iter().enumerate() {
+    if is_uncovered {
+      cliques.push(vec![v]);
+    }
+  }
+  CliqueCover { cliques }
+}
+
+struct Island {
+  graph: Graph,
+  population: Vec<CliqueCover>,
+}
+
+impl Island {
+  fn new(graph: &Graph, params: &IslandParams, seed: u64) -> Island {
+    let mut island = Island {
+      graph: graph.clone(),
+      population: Vec::with_capacity(params.population_size),
+    };
+    island.graph.seed(seed);
+    for _ in 0..params.population_size {
+      island.graph.conform_cliques_to_vertices();
+      island.graph.shuffle_active_cliques();
+      island.local_search(params);
+      island.population.push(island.graph.cover());
+    }
+    island
+  }
+
+  fn local_search(&mut self, params: &IslandParams) {
+    for _ in 0..params.local_search_iterations {
+      self.graph.vcc_iterated_greedy(params.reverse_fraction);
+    }
+  }
+
+  fn best(&self) -> &CliqueCover {
+    self.population.iter().min_by_key(|c| c.len()).unwrap()
+  }
+
+  fn worst_index(&self) -> usize {
+    (0..self.population.len())
+      .max_by_key(|&i| self.population[i].len())
+      .unwrap()
+  }
+
+  fn generation(&mut self, params: &IslandParams) {
+    let a = below(&mut self.graph.rng, self.population.len());
+    let mut b = below(&mut self.graph.rng, self.population.len());
+    if self.population.len() > 1 {
+      while b == a {
+        b = below(&mut self.graph.rng, self.population.len());
+      }
+    }
+    let child = crossover(&self.population[a], &self.population[b], self.graph.size);
+    self.graph.load_cover_unchecked(&child);
+    self.local_search(params);
+    let child = self.graph.cover();
+    match params.min_distance {
+      Some(min_distance) => self.offer_diverse(child, min_distance),
+      None => {
+        let worst = self.worst_index();
+        if child.len() <= self.population[worst].len() {
+          self.population[worst] = child;
+        }
+      }
+    }
+  }
+
+  fn offer_diverse(&mut self, newcomer: CliqueCover, min_distance: usize) {
+    let distances: Vec<usize> = self
+      .population
+      .iter()
+      .map(|c| c.distance(&newcomer))
+      .collect();
+    if distances.iter().any(|&d| d < min_distance) {
+      return;
+    }
+    let closest = (0..self.population.len())
+      .filter(|&i| self.population[i].len() >= newcomer.len())
+      .min_by_key(|&i| distances[i]);
+    if let Some(closest) = closest {
+      self.population[closest] = newcomer;
+    }
+  }
+
+  fn accept_migrant(&mut self, migrant: CliqueCover, params: &IslandParams) {
+    match params.min_distance {
+      Some(min_distance) => self.offer_diverse(migrant, min_distance),
+      None => {
+        let worst = self.worst_index();
+        self.population[worst] = migrant;
+      }
+    }
+  }
+}
+
+pub fn island_search(graph: &Graph, params: &IslandParams) -> Result<IslandResult, VccError> {
+  if params.population_size == 0 {
+    return Err(VccError::InvalidParameter(
+      "islands need a population of at least one".to_string(),
+    ));
+  }
+  if !(0.0..=1.0).contains(&params.reverse_fraction) {
+    return Err(VccError::InvalidParameter(format!(
+      "reverse fraction {} is not in [0, 1]",
+      params.reverse_fraction
+    )));
+  }
+  let island_ct = params.islands.max(1);
+  let interval = params.migration_interval.max(1);
+  let mut islands: Vec<Island> = (0..island_ct)
+    .into_par_iter()
+    .map(|i| Island::new(graph, params, params.seed.wrapping_add(i as u64)))
+    .collect();
+  let mut generations = 0;
+  while generations < params.generations {
+    let epoch = interval.min(params.generations - generations);
+    islands.par_iter_mut().for_each(|island| {
+      for _ in 0..epoch {
+        island.generation(params);
+      }
+    });
+    generations += epoch;
+
+    // Island i sends to island i + 1.
+    let mut migrants: Vec<CliqueCover> =
+      islands.iter().map(|island| island.best().clone()).collect();
+    migrants.rotate_right(1);
+    islands
+      .par_iter_mut()
+      .zip(migrants)
+      .for_each(|(island, migrant)| island.accept_migrant(migrant, params));
+    if islands
+      .iter()
+      .any(|island| island.best().len() <= params.target)
+    {
+      break;
+    }
+  }
+  let island_bests: Vec<usize> = islands.iter().map(|island| island.best().len()).collect();
+  let best_cover = islands
+    .iter()
+    .map(|island| island.best())
+    .min_by_key(|cover| cover.len())
+    .unwrap()
+    .clone();
+  Ok(IslandResult {
+    reached_target: best_cover.len() <= params.target,
+    best_cover,
+    generations,
+    island_bests,
+  })
+}
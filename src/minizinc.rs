@@ -0,0 +1,89 @@
+// Minimum clique cover as a MiniZinc model, for constraint programming
+// toolchains: one clique label per vertex, different labels across every
+// non-edge, labels used in order (value_precede_chain, which also breaks
+// the label symmetry), and as few labels as possible. The model prints
+// `clique = [...];`, which parse_minizinc_output reads back and checks.
+
+use crate::cover::CliqueCover;
+use crate::error::VccError;
+use crate::Graph;
+use std::fmt::Write;
+
+impl Graph {
+  // slots caps the labels; any known cover's size will do.
+  pub fn to_minizinc(&self, slots: usize) -> String {
+    let n = self.size;
+    let mut non_edges = vec![];
+    for u in 0..n {
+      for v in (u + 1)..n {
+        if !self.topology.vertices[u].neighbors_bv.get_unchecked(v) {
+          non_edges.push(format!("{}, {}", u + 1, v + 1));
+        }
+      }
+    }
+    let mut model = format!("% minimum clique cover, {} vertices\n", n);
+    model.push_str("include \"globals.mzn\";\n\n");
+    writeln!(model, "int: n = {};", n).unwrap();
+    writeln!(model, "int: slots = {};", slots).unwrap();
+    writeln!(
+      model,
+      "array[int, 1..2] of int: non_edges = array2d(1..{}, 1..2, [{}]);\n",
+      non_edges.len(),
+      non_edges.join(", ")
+    )
+    .unwrap();
+    model.push_str("array[1..n] of var 1..slots: clique;\n");
+    model.push_str("var 0..slots: used = max([0] ++ clique);\n\n");
+    model.push_str(
+      "constraint forall(e in index_set_1of2(non_edges))(\n  \
+       clique[non_edges[e, 1]] != clique[non_edges[e, 2]]);\n",
+    );
+    model.push_str("constraint value_precede_chain([i | i in 1..slots], clique);\n\n");
+    model.push_str("solve minimize used;\n\n");
+    model.push_str("output [\"clique = \\(clique);\\n\"];\n");
+    model
+  }
+
+  // Reads the last `clique = [...]` line of the model's output, with
+  // labels from 1, into a checked cover.
+  pub fn parse_minizinc_output(&self, output: &str) -> Result<CliqueCover, VccError> {
+    let line = output
+      .lines()
+      .rev()
+      .find_map(|line| line.trim().strip_prefix("clique = "))
+      .ok_or_else(|| VccError::Parse("no clique assignment in output".to_string()))?;
+    let bad = || VccError::Parse(format!("bad clique assignment: {:?}", line));
+    let values = line
+      .trim_end_matches(';')
+      .strip_prefix('[')
+      .and_then(|values| values.strip_suffix(']'))
+      .ok_or_else(bad)?;
+    let labels = values
+      .split(',')
+      .map(str::trim)
+      .filter(|label| !label.is_empty())
+      .map(|label| {
+        label
+          .parse::<usize>()
+          .ok()
+          .filter(|&l| l > 0)
+          .ok_or_else(bad)
+      })
+      .collect::<Result<Vec<usize>, VccError>>()?;
+    if labels.len() != self.size {
+      return Err(VccError::InvalidCover(format!(
+        "{} labels for {} vertices",
+        labels.len(),
+        self.size
+      )));
+    }
+    let mut cliques = vec![vec![]; labels.iter().max().copied().unwrap_or(0)];
+    for (v, &label) in labels.iter().enumerate() {
+      cliques[label - 1].push(v);
+    }
+    cliques.retain(|members| !members.is_empty());
+    let cover = CliqueCover { cliques };
+    self.validate_cover(&cover)?;
+    Ok(cover)
+  }
+}
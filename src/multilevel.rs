@@ -0,0 +1,207 @@
+// Multilevel solving, the usual way to scale partition heuristics to very
+// large graphs. Each coarsening level matches vertices with a neighbor, in
+// random order and preferring neighbors of low degree, and contracts the
+// matched pairs. Two coarse vertices are adjacent when every vertex behind
+// one is adjacent to every vertex behind the other, so a clique of the
+// coarse graph expands to a clique of the finer one, and so does a cover.
+// Coarsening stops at the coarsest size, or once a level shrinks the graph
+// by less than a tenth. The coarsest graph is solved with the given params;
+// then, level by level, its cover is expanded to the finer graph and
+// refined there by one restart of the search starting from it.
+
+use crate::cover::CliqueCover;
+use crate::error::VccError;
+use crate::rng::{default_rng, shuffle};
+use crate::solve::SolveParams;
+use crate::Graph;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::fmt;
+use core::str::FromStr;
+
+#[derive(Clone, Copy)]
+pub struct Multilevel {
+  pub coarsest: usize,
+  // Iterations of the refining restart at each finer level.
+  pub refine_iterations: usize,
+}
+
+impl Default for Multilevel {
+  fn default() -> Multilevel {
+    Multilevel {
+      coarsest: 200,
+      refine_iterations: 1_000,
+    }
+  }
+}
+
+// "on" for the defaults, or "<coarsest>:<refine iterations>"
+impl FromStr for Multilevel {
+  type Err = VccError;
+
+  fn from_str(multilevel_str: &str) -> Result<Multilevel, VccError> {
+    if multilevel_str == "on" {
+      return Ok(Multilevel::default());
+    }
+    let bad = || VccError::Parse(format!("bad multilevel: {:?}", multilevel_str));
+    let (coarsest, refine_iterations) = multilevel_str.split_once(':').ok_or_else(bad)?;
+    Ok(Multilevel {
+      coarsest: coarsest.replace('_', "").parse().map_err(|_| bad())?,
+      refine_iterations: refine_iterations
+        .replace('_', "")
+        .parse()
+        .map_err(|_| bad())?,
+    })
+  }
+}
+
+pub struct MultilevelResult {
+  pub cover: CliqueCover,
+  // Vertex counts from the original graph down to the coarsest.
+  pub levels: Vec<usize>,
+}
+
+impl fmt::Display for MultilevelResult {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let levels: Vec<String> = self.levels.iter().map(|n| n.to_string()).collect();
+    write!(
+      f,
+      "multilevel: {} cliques, levels {}",
+      self.cover.len(),
+      levels.join(" -> ")
+    )
+  }
+}
+
+// One coarsening level: the coarse graph, and the finer vertices behind
+// each of its vertices.
+struct Level {
+  graph: Graph,
+  groups: Vec<Vec<usize>>,
+}
+
+impl Graph {
+  // Leaves the final cover loaded.
+  pub fn solve_multilevel(
+    &mut self,
+    params: &SolveParams,
+    multilevel: &Multilevel,
+  ) -> Result<MultilevelResult, VccError> {
+    params.validate()?;
+    if !self.forbidden.is_empty() {
+      return Err(VccError::InvalidParameter(
+        "multilevel solving doesn't support forbidden sets".to_string(),
+      ));
+    }
+    let mut levels: Vec<Level> = vec![];
+    loop {
+      let seed = self.rng.next_u64();
+      let finer = levels.last().map_or(&*self, |level| &level.graph);
+      if finer.size <= multilevel.coarsest {
+        break;
+      }
+      let level = finer.coarsen(seed);
+      if level.graph.size * 10 > finer.size * 9 {
+        break;
+      }
+      levels.push(level);
+    }
+    let sizes = core::iter::once(self.size)
+      .chain(levels.iter().map(|level| level.graph.size))
+      .collect();
+
+    let mut cover = match levels.last_mut() {
+      Some(coarsest) => coarsest.graph.solve(params)?.best_cover,
+      None => self.solve(params)?.best_cover,
+    };
+    let refine = SolveParams {
+      max_iterations: multilevel.refine_iterations,
+      ..*params
+    };
+    for i in (0..levels.len()).rev() {
+      let expanded = CliqueCover {
+        cliques: cover
+          .cliques
+          .iter()
+          .map(|members| {
+            members
+              .iter()
+              .flat_map(|&v| levels[i].groups[v].iter().copied())
+              .collect()
+          })
+          .collect(),
+      };
+      let finer = match i {
+        0 => &mut *self,
+        _ => &mut levels[i - 1].graph,
+      };
+      cover = finer.refine(&expanded, &refine);
+    }
+    self.load_cover_unchecked(&cover);
+    Ok(MultilevelResult {
+      cover,
+      levels: sizes,
+    })
+  }
+
+  fn coarsen(&self, seed: u64) -> Level {
+    let mut order: Vec<usize> = (0..self.size).collect();
+    let mut rng = default_rng(seed);
+    shuffle(&mut rng, &mut order);
+    let mut group_of: Vec<Option<usize>> = vec![None; self.size];
+    let mut groups: Vec<Vec<usize>> = vec![];
+    for u in order {
+      if group_of[u].is_some() {
+        continue;
+      }
+      let mate = (0..self.size)
+        .filter(|&v| {
+          group_of[v].is_none() && self.topology.vertices[u].neighbors_bv.get_unchecked(v)
+        })
+        .min_by_key(|&v| self.degree(v));
+      group_of[u] = Some(groups.len());
+      match mate {
+        Some(v) => {
+          group_of[v] = Some(groups.len());
+          groups.push(vec![u, v]);
+        }
+        None => groups.push(vec![u]),
+      }
+    }
+    // Coarse vertices are adjacent when their groups are fully joined.
+    let common: Vec<_> = groups
+      .iter()
+      .map(|group| {
+        let mut common = self.topology.vertices[group[0]].neighbors_bv.clone();
+        for &v in &group[1..] {
+          common.and_inplace(&self.topology.vertices[v].neighbors_bv);
+        }
+        common
+      })
+      .collect();
+    let mut graph = Graph::new(groups.len());
+    graph.seed(seed);
+    for (x, common) in common.iter().enumerate() {
+      for (y, group) in groups.iter().enumerate().skip(x + 1) {
+        if group.iter().all(|&v| common.get_unchecked(v)) {
+          graph.link(x, y);
+        }
+      }
+    }
+    graph.conform_cliques_to_vertices();
+    Level { graph, groups }
+  }
+
+  // One restart from cover, keeping cover if the restart ends up larger.
+  fn refine(&mut self, cover: &CliqueCover, params: &SolveParams) -> CliqueCover {
+    self.load_cover_unchecked(cover);
+    self.shuffle_active_cliques();
+    self.vcc_run_restart(params, None);
+    if self.cliques_ct < cover.len() {
+      self.cover()
+    } else {
+      cover.clone()
+    }
+  }
+}
@@ -0,0 +1,92 @@
+// Parallel multi-start: independent workers restart the solver from their
+// own shuffles of the same instance and share bounds, so all of them stop
+// as soon as one reaches the target or matches the best lower bound.
+//
+// Worker w draws from its own stream. With a seed, that stream is seeded
+// with seed + w and the run is deterministic: every worker spends its
+// whole budget (stopping early on another worker's progress would depend
+// on timing), and ties between equally good covers go to the lowest
+// worker. The result then depends only on the seed and the number of
+// workers, not on how many threads execute them. A throttle (see
+// throttle.rs) paces each worker between its restarts, which changes
+// timings but not seeded results.
+
+use crate::cover::CliqueCover;
+use crate::error::VccError;
+use crate::shared::SharedBounds;
+use crate::solve::{SolveParams, SolveResult};
+use crate::throttle::{Pacer, Throttle};
+use crate::Graph;
+use rand_core::RngCore;
+use rayon::prelude::*;
+use std::time::Duration;
+
+pub struct MultiStartResult {
+  pub best_cover: CliqueCover,
+  pub reached_target: bool,
+  pub lower_bound: usize,
+  // Restarts summed over workers.
+  pub restarts: usize,
+  // Time spent searching, summed over workers, throttled time aside.
+  pub busy: Duration,
+}
+
+pub fn multi_start(
+  graph: &Graph,
+  params: &SolveParams,
+  workers: usize,
+  seed: Option<u64>,
+) -> Result<MultiStartResult, VccError> {
+  multi_start_throttled(graph, params, workers, seed, Throttle::default())
+}
+
+pub fn multi_start_throttled(
+  graph: &Graph,
+  params: &SolveParams,
+  workers: usize,
+  seed: Option<u64>,
+  throttle: Throttle,
+) -> Result<MultiStartResult, VccError> {
+  params.validate()?;
+  throttle.validate()?;
+  let bounds = SharedBounds::new();
+  let mut seed_rng = graph.rng.clone();
+  let worker_seeds: Vec<u64> = (0..workers.max(1))
+    .map(|w| match seed {
+      Some(seed) => seed.wrapping_add(w as u64),
+      None => seed_rng.next_u64(),
+    })
+    .collect();
+  let results: Vec<(SolveResult, Duration)> = worker_seeds
+    .into_par_iter()
+    .enumerate()
+    .map(|(w, worker_seed)| {
+      let mut g = graph.clone();
+      g.seed(worker_seed);
+      bounds.offer_lower(g.greedy_independent_set_bound());
+      g.conform_cliques_to_vertices();
+      g.shuffle_active_cliques();
+      let shared = seed.is_none().then_some(&bounds);
+      let mut pacer = Pacer::new(throttle, w);
+      let result = g.resume_solve(params, shared, None, &mut |_, _| pacer.pause())?;
+      Ok((result, pacer.idle))
+    })
+    .collect::<Result<_, VccError>>()?;
+
+  let restarts = results.iter().map(|(r, _)| r.restarts).sum();
+  let busy = results
+    .iter()
+    .map(|(r, idle)| r.elapsed.saturating_sub(*idle))
+    .sum();
+  let (best, _) = results
+    .into_iter()
+    .min_by_key(|(r, _)| r.best_cover.len())
+    .unwrap();
+  Ok(MultiStartResult {
+    reached_target: best.best_cover.len() <= params.target.max(bounds.lower()),
+    best_cover: best.best_cover,
+    lower_bound: bounds.lower(),
+    restarts,
+    busy,
+  })
+}
@@ -0,0 +1,183 @@
+// Composable graph operations. Each returns a new graph whose search state
+// has been reset to singleton cliques, ready to solve.
+//
+// Useful identities for building instances with known cover numbers
+// (theta = minimum clique cover size):
+//   theta(disjoint_sum(g, h)) = theta(g) + theta(h)
+//   theta(join(g, h))         = max(theta(g), theta(h))
+// and bounds, alpha being the independence number and omega the clique
+// number:
+//   theta(cartesian_product(g, h))     <= min(theta(g) |h|, theta(h) |g|)
+//   theta(tensor_product(g, h))        >= |g| |h| / min(omega(g), omega(h))
+//   theta(lexicographic_product(g, h)) <= theta(g) theta(h)
+//   theta(lexicographic_product(g, h)) >= alpha(g) alpha(h)
+// the last two meeting when theta = alpha for both, as for perfect graphs.
+
+use crate::error::VccError;
+use crate::forbidden::ForbiddenSet;
+use crate::Graph;
+use alloc::format;
+use alloc::vec;
+use alloc::vec::Vec;
+
+impl Graph {
+  // Edge union on a shared vertex set. If the graphs differ in size, the
+  // result has the larger size and the extra vertices keep only their own edges.
+  pub fn union(&self, other: &Graph) -> Graph {
+    let mut ret_graph = Graph::new(self.size.max(other.size));
+    ret_graph.copy_edges_from(self, 0);
+    ret_graph.copy_edges_from(other, 0);
+    ret_graph.conform_cliques_to_vertices();
+    ret_graph
+  }
+
+  // Vertices of other are relabeled to follow those of self; no edges
+  // are added between the two parts.
+  pub fn disjoint_sum(&self, other: &Graph) -> Graph {
+    let mut ret_graph = Graph::new(self.size + other.size);
+    ret_graph.copy_edges_from(self, 0);
+    ret_graph.copy_edges_from(other, self.size);
+    ret_graph.conform_cliques_to_vertices();
+    ret_graph
+  }
+
+  // The disjoint sum plus every edge between the two parts.
+  pub fn join(&self, other: &Graph) -> Graph {
+    let mut ret_graph = Graph::new(self.size + other.size);
+    ret_graph.copy_edges_from(self, 0);
+    ret_graph.copy_edges_from(other, self.size);
+    for u in 0..self.size {
+      for v in 0..other.size {
+        ret_graph.link(u, self.size + v);
+      }
+    }
+    ret_graph.conform_cliques_to_vertices();
+    ret_graph
+  }
+
+  // The subgraph induced by vertices, with vertices[i] relabeled i.
+  pub fn induced_subgraph(&self, vertices: &[usize]) -> Graph {
+    let mut ret_graph = Graph::new(vertices.len());
+    for (i, &u) in vertices.iter().enumerate() {
+      for (j, &v) in vertices.iter().enumerate().skip(i + 1) {
+        if self.topology.vertices[u].neighbors_bv.get_unchecked(v) {
+          ret_graph.link(i, j);
+        }
+      }
+    }
+    ret_graph.conform_cliques_to_vertices();
+    ret_graph
+  }
+
+  // The same graph with vertex v relabeled perm[v], forbidden sets
+  // included, and the inverse permutation: inverse[i] is the vertex
+  // relabeled i, so cover.relabel(&inverse) turns a cover of the relabeled
+  // graph back into one of this graph.
+  pub fn permute(&self, perm: &[usize]) -> Result<(Graph, Vec<usize>), VccError> {
+    if perm.len() != self.size {
+      return Err(VccError::InvalidParameter(format!(
+        "permutation of {} vertices for a graph with {}",
+        perm.len(),
+        self.size
+      )));
+    }
+    let mut inverse = vec![usize::MAX; self.size];
+    for (v, &i) in perm.iter().enumerate() {
+      if i >= self.size || inverse[i] != usize::MAX {
+        return Err(VccError::InvalidParameter(format!(
+          "not a permutation: {} appears twice or out of range",
+          i
+        )));
+      }
+      inverse[i] = v;
+    }
+    let mut ret_graph = Graph::new(self.size);
+    for u in 0..self.size {
+      for v in (u + 1)..self.size {
+        if self.topology.vertices[u].neighbors_bv.get_unchecked(v) {
+          ret_graph.link(perm[u], perm[v]);
+        }
+      }
+    }
+    ret_graph.set_forbidden(
+      self
+        .forbidden
+        .iter()
+        .map(|set| ForbiddenSet {
+          members: set.members.iter().map(|&v| perm[v]).collect(),
+          max_together: set.max_together,
+        })
+        .collect(),
+    )?;
+    ret_graph.planted = self.planted.as_ref().map(|cover| cover.relabel(perm));
+    ret_graph.conform_cliques_to_vertices();
+    Ok((ret_graph, inverse))
+  }
+
+  pub fn complement(&self) -> Graph {
+    let mut ret_graph = Graph::new(self.size);
+    for u in 0..self.size {
+      for v in (u + 1)..self.size {
+        if !self.topology.vertices[u].neighbors_bv.get_unchecked(v) {
+          ret_graph.link(u, v);
+        }
+      }
+    }
+    ret_graph.conform_cliques_to_vertices();
+    ret_graph
+  }
+
+  // Products pair a vertex u of self with a vertex v of other as vertex
+  // u * other.size() + v, so each copy of other is a run of vertices.
+
+  // (u, v) and (u', v') are adjacent if u = u' and v ~ v', or v = v' and
+  // u ~ u'.
+  pub fn cartesian_product(&self, other: &Graph) -> Graph {
+    self.product(other, |u_eq, u_adj, v_eq, v_adj| {
+      (u_eq && v_adj) || (v_eq && u_adj)
+    })
+  }
+
+  // Adjacent if u ~ u' and v ~ v'.
+  pub fn tensor_product(&self, other: &Graph) -> Graph {
+    self.product(other, |_, u_adj, _, v_adj| u_adj && v_adj)
+  }
+
+  // Adjacent if u ~ u', or u = u' and v ~ v': a copy of other in place of
+  // each vertex of self.
+  pub fn lexicographic_product(&self, other: &Graph) -> Graph {
+    self.product(other, |u_eq, u_adj, _, v_adj| u_adj || (u_eq && v_adj))
+  }
+
+  // adjacent(u == u', u ~ u', v == v', v ~ v') decides each pair.
+  fn product(&self, other: &Graph, adjacent: impl Fn(bool, bool, bool, bool) -> bool) -> Graph {
+    let m = other.size;
+    let mut ret_graph = Graph::new(self.size * m);
+    for a in 0..ret_graph.size {
+      for b in (a + 1)..ret_graph.size {
+        let (u, v, u2, v2) = (a / m, a % m, b / m, b % m);
+        if adjacent(
+          u == u2,
+          self.topology.vertices[u].neighbors_bv.get_unchecked(u2),
+          v == v2,
+          other.topology.vertices[v].neighbors_bv.get_unchecked(v2),
+        ) {
+          ret_graph.link(a, b);
+        }
+      }
+    }
+    ret_graph.conform_cliques_to_vertices();
+    ret_graph
+  }
+
+  // Adds every edge of source, with source vertex i mapped to offset + i.
+  pub(crate) fn copy_edges_from(&mut self, source: &Graph, offset: usize) {
+    for u in 0..source.size {
+      for v in (u + 1)..source.size {
+        if source.topology.vertices[u].neighbors_bv.get_unchecked(v) {
+          self.link(offset + u, offset + v);
+        }
+      }
+    }
+  }
+}
@@ -0,0 +1,82 @@
+// Orders for the cliques before a greedy merging pass. Iterated greedy
+// never loses ground whatever the order, since each clique can at worst
+// stay as it is; the order only decides which merges are tried first.
+// Besides the default shuffle, cliques can be sorted by size, which tends
+// to help on coloring-type problems; noise keeps such runs from repeating.
+
+use crate::error::VccError;
+use crate::rng::unit_f64;
+use crate::{Clique, Graph};
+use alloc::format;
+use alloc::vec::Vec;
+use core::str::FromStr;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(tag = "by", rename_all = "kebab-case")]
+pub enum CliqueOrder {
+  #[default]
+  Shuffle,
+  // Sizes are perturbed by up to `noise` before sorting; ties are random.
+  LargestFirst {
+    #[serde(default)]
+    noise: f64,
+  },
+  SmallestFirst {
+    #[serde(default)]
+    noise: f64,
+  },
+}
+
+// "shuffle", "largest-first" or "smallest-first", optionally followed by
+// ":noise", e.g. "largest-first:0.5".
+impl FromStr for CliqueOrder {
+  type Err = VccError;
+
+  fn from_str(order_str: &str) -> Result<CliqueOrder, VccError> {
+    let bad = || VccError::Parse(format!("bad clique order: {:?}", order_str));
+    let (name, noise) = match order_str.split_once(':') {
+      Some((name, noise)) => (name, noise.parse().map_err(|_| bad())?),
+      None => (order_str, 0.0),
+    };
+    match name {
+      "shuffle" => Ok(CliqueOrder::Shuffle),
+      "largest-first" => Ok(CliqueOrder::LargestFirst { noise }),
+      "smallest-first" => Ok(CliqueOrder::SmallestFirst { noise }),
+      _ => Err(bad()),
+    }
+  }
+}
+
+impl Graph {
+  pub fn order_active_cliques(&mut self, order: &CliqueOrder) {
+    self.shuffle_active_cliques();
+    let (noise, largest_first) = match *order {
+      CliqueOrder::Shuffle => return,
+      CliqueOrder::LargestFirst { noise } => (noise, true),
+      CliqueOrder::SmallestFirst { noise } => (noise, false),
+    };
+    let rng = &mut self.rng;
+    let mut keyed: Vec<(f64, Clique)> = self
+      .cliques
+      .drain(0..self.cliques_ct)
+      .map(|c| (c.members_ct as f64 + noise * unit_f64(rng), c))
+      .collect();
+    if largest_first {
+      keyed.sort_by(|a, b| b.0.total_cmp(&a.0));
+    } else {
+      keyed.sort_by(|a, b| a.0.total_cmp(&b.0));
+    }
+    self.cliques.splice(0..0, keyed.into_iter().map(|(_, c)| c));
+  }
+
+  // vcc_iterated_greedy with the given order in place of the shuffle.
+  pub fn vcc_iterated_greedy_ordered(&mut self, reverse_fraction: f64, order: &CliqueOrder) {
+    if unit_f64(&mut self.rng) < reverse_fraction {
+      self.reverse_active_cliques();
+    } else {
+      self.order_active_cliques(order);
+    }
+    self.vcc_greedy();
+  }
+}
@@ -0,0 +1,62 @@
+// A Pareto front of covers, for when the fewest cliques isn't the only
+// concern: covers are also compared by their imbalance, the gap between
+// their largest and smallest clique. A cover joins the front unless some
+// cover already in it is at least as good on both counts, and pushes out
+// the covers it is at least as good as. With both counts whole numbers the
+// front stays small, one cover per clique count at most.
+
+use crate::cover::CliqueCover;
+use std::fmt;
+
+pub struct ParetoPoint {
+  pub cover: CliqueCover,
+  pub cliques: usize,
+  pub imbalance: usize,
+}
+
+impl ParetoPoint {
+  fn dominates(&self, other: &ParetoPoint) -> bool {
+    self.cliques <= other.cliques && self.imbalance <= other.imbalance
+  }
+}
+
+#[derive(Default)]
+pub struct ParetoFront {
+  // By increasing clique count, so by decreasing imbalance.
+  pub points: Vec<ParetoPoint>,
+}
+
+impl ParetoFront {
+  // Returns whether the cover joined the front.
+  pub fn offer(&mut self, cover: &CliqueCover) -> bool {
+    let sizes = cover.cliques.iter().map(Vec::len);
+    let point = ParetoPoint {
+      cover: cover.clone(),
+      cliques: cover.len(),
+      imbalance: sizes.clone().max().unwrap_or(0) - sizes.min().unwrap_or(0),
+    };
+    if self.points.iter().any(|kept| kept.dominates(&point)) {
+      return false;
+    }
+    self.points.retain(|kept| !point.dominates(kept));
+    let at = self
+      .points
+      .partition_point(|kept| kept.cliques < point.cliques);
+    self.points.insert(at, point);
+    true
+  }
+}
+
+impl fmt::Display for ParetoFront {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "pareto front:")?;
+    for point in &self.points {
+      write!(
+        f,
+        "\n  {} cliques, imbalance {}",
+        point.cliques, point.imbalance
+      )?;
+    }
+    Ok(())
+  }
+}
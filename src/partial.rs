@@ -0,0 +1,97 @@
+// Partial covers: with a budget of k cliques, cover as many vertices as
+// possible and leave the rest out, for when only k slots exist. The search
+// runs as usual with the budget as its target; if it gets there, nothing
+// is left out. Otherwise the best cover keeps its k largest cliques, and a
+// swap search grows them: an uncovered vertex joins a clique that accepts
+// it, or trades places with the one member it conflicts with. A vertex
+// swapped out can't swap back in for a few steps, though it may still join
+// a clique outright. The fewest vertices left out wins.
+
+use crate::cover::CliqueCover;
+use crate::error::VccError;
+use crate::rng::below;
+use crate::solve::SolveParams;
+use crate::Graph;
+use std::cmp::Reverse;
+use std::fmt;
+
+const SWAP_STEPS_PER_VERTEX: usize = 100;
+const TENURE: usize = 7;
+
+pub struct PartialCover {
+  // At most the budget's worth of cliques.
+  pub cover: CliqueCover,
+  pub uncovered: Vec<usize>,
+}
+
+impl fmt::Display for PartialCover {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let covered: usize = self.cover.cliques.iter().map(Vec::len).sum();
+    write!(
+      f,
+      "covered {} of {} vertices in {} cliques",
+      covered,
+      covered + self.uncovered.len(),
+      self.cover.len()
+    )?;
+    if !self.uncovered.is_empty() {
+      write!(f, ", left out: {:?}", self.uncovered)?;
+    }
+    Ok(())
+  }
+}
+
+impl Graph {
+  pub fn partial_cover(
+    &mut self,
+    budget: usize,
+    params: &SolveParams,
+  ) -> Result<PartialCover, VccError> {
+    let mut params = *params;
+    params.target = budget;
+    let mut cliques = self.solve(&params)?.best_cover.cliques;
+    cliques.sort_by_key(|members| Reverse(members.len()));
+    let mut uncovered: Vec<usize> = cliques
+      .drain(budget.min(cliques.len())..)
+      .flatten()
+      .collect();
+    let mut best = (cliques.clone(), uncovered.clone());
+    let mut tabu_until = vec![0; self.size];
+    for step in 1..=(SWAP_STEPS_PER_VERTEX * self.size) {
+      if uncovered.is_empty() || cliques.is_empty() {
+        break;
+      }
+      let i = below(&mut self.rng, uncovered.len());
+      let u = uncovered[i];
+      let accepting: Vec<usize> = (0..cliques.len())
+        .filter(|&c| self.accepts(&cliques[c], u))
+        .collect();
+      if !accepting.is_empty() {
+        cliques[accepting[below(&mut self.rng, accepting.len())]].push(u);
+        uncovered.swap_remove(i);
+        if uncovered.len() < best.1.len() {
+          best = (cliques.clone(), uncovered.clone());
+        }
+        continue;
+      }
+      if tabu_until[u] > step {
+        continue;
+      }
+      let swaps: Vec<(usize, usize)> = (0..cliques.len())
+        .filter_map(|c| self.sole_conflict(&cliques[c], u).map(|w| (c, w)))
+        .collect();
+      if swaps.is_empty() {
+        continue;
+      }
+      let (c, w) = swaps[below(&mut self.rng, swaps.len())];
+      uncovered[i] = std::mem::replace(&mut cliques[c][w], u);
+      tabu_until[uncovered[i]] = step + TENURE;
+    }
+    let (cliques, mut uncovered) = best;
+    uncovered.sort_unstable();
+    Ok(PartialCover {
+      cover: CliqueCover { cliques },
+      uncovered,
+    })
+  }
+}
@@ -0,0 +1,248 @@
+// Planted covers: the sizes get_random_graph_with_planted_cliques gives
+// its cliques, and the cover it planted, kept with the graph so callers
+// checking how well a search recovered it needn't rebuild it from the
+// generator's dealing. The planted cover is a cover of at most its own
+// size, so a search's best cover should never be larger. Graphs made any
+// other way have none, and so do graphs derived from a planted one, save
+// permutations, which carry it relabeled: a complement or subgraph doesn't
+// keep the planted cliques as cliques.
+//
+// Equal sizes, the default, make every clique about as hard to find as
+// every other, which flatters the annealing step: real instances mix a
+// few large cliques with many small ones. Geometric sizes fall by a
+// constant ratio from one clique to the next, power-law sizes as
+// 1/(c+1)^exponent for clique c; either way each clique first gets the
+// minimum size and the rest of the vertices are shared out in proportion
+// to its weight.
+//
+// Overlap noise makes the planted structure only approximate, for
+// benchmarking how a search copes when the "true" cover isn't one. Each
+// noisy vertex moves half its edges into its planted clique, rounded up,
+// to random vertices of other cliques it wasn't adjacent to, so its clique
+// stops being a clique while the edge count stays as it was. The planted
+// cover is kept as planted, now only a reference partition.
+
+use crate::cover::CliqueCover;
+use crate::error::VccError;
+use crate::rng::{below, shuffle};
+use crate::Graph;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::str::FromStr;
+use rand_core::RngCore;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(tag = "shape", rename_all = "kebab-case")]
+pub enum SizeShape {
+  #[default]
+  Equal,
+  Geometric {
+    ratio: f64,
+  },
+  // Needs std's float functions.
+  PowerLaw {
+    exponent: f64,
+  },
+}
+
+// "equal", "geometric:<ratio>" or "power-law:<exponent>"
+impl FromStr for SizeShape {
+  type Err = VccError;
+
+  fn from_str(shape_str: &str) -> Result<SizeShape, VccError> {
+    let bad = || VccError::Parse(format!("bad clique sizes: {:?}", shape_str));
+    if shape_str == "equal" {
+      return Ok(SizeShape::Equal);
+    }
+    let (name, value) = shape_str.split_once(':').ok_or_else(bad)?;
+    let value = value.parse().map_err(|_| bad())?;
+    match name {
+      "geometric" => Ok(SizeShape::Geometric { ratio: value }),
+      "power-law" => Ok(SizeShape::PowerLaw { exponent: value }),
+      _ => Err(bad()),
+    }
+  }
+}
+
+// Fields missing from a serialized config take their default values.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlantedSizes {
+  #[serde(flatten)]
+  pub shape: SizeShape,
+  pub min_size: usize,
+}
+
+impl Default for PlantedSizes {
+  fn default() -> PlantedSizes {
+    PlantedSizes {
+      shape: SizeShape::Equal,
+      min_size: 1,
+    }
+  }
+}
+
+impl PlantedSizes {
+  // The sizes of the cliques planted among num_vertices vertices, largest
+  // first, summing to num_vertices. There are cliques_ct of them, or one
+  // per vertex if that is fewer.
+  pub fn sizes(&self, num_vertices: usize, cliques_ct: usize) -> Result<Vec<usize>, VccError> {
+    let k = cliques_ct.min(num_vertices);
+    if k
+      .checked_mul(self.min_size)
+      .is_none_or(|least| least > num_vertices)
+    {
+      return Err(VccError::InvalidParameter(format!(
+        "{} cliques of at least {} vertices don't fit in {}",
+        k, self.min_size, num_vertices
+      )));
+    }
+    let rest = num_vertices - k * self.min_size;
+    let weights: Vec<f64> = match self.shape {
+      SizeShape::Equal => {
+        // Exactly, without rounding: the first rest % k one larger.
+        return Ok(
+          (0..k)
+            .map(|c| self.min_size + rest / k + usize::from(c < rest % k))
+            .collect(),
+        );
+      }
+      SizeShape::Geometric { ratio } => {
+        if !(ratio > 0.0 && ratio <= 1.0) {
+          return Err(VccError::InvalidParameter(format!(
+            "clique size ratio {} is not in (0, 1]",
+            ratio
+          )));
+        }
+        let mut weight = 1.0;
+        (0..k)
+          .map(|_| {
+            let w = weight;
+            weight *= ratio;
+            w
+          })
+          .collect()
+      }
+      SizeShape::PowerLaw { exponent } => power_law_weights(k, exponent)?,
+    };
+    // Rounds the running totals of the shares rather than each share, so
+    // the sizes sum to exactly num_vertices whatever the float error.
+    let total: f64 = weights.iter().sum();
+    let mut sizes = Vec::with_capacity(k);
+    let (mut cumulative, mut dealt) = (0.0, 0);
+    for (c, w) in weights.iter().enumerate() {
+      cumulative += w;
+      let upto = if c + 1 == k {
+        rest
+      } else {
+        ((rest as f64 * cumulative / total + 0.5) as usize).clamp(dealt, rest)
+      };
+      sizes.push(self.min_size + upto - dealt);
+      dealt = upto;
+    }
+    sizes.sort_unstable_by(|a, b| b.cmp(a));
+    Ok(sizes)
+  }
+
+  // Deals vertices to cliques of the given sizes in turn, skipping full
+  // ones: with equal sizes, vertex v goes to clique v % sizes.len(). Each
+  // vertex's clique, by vertex.
+  pub(crate) fn deal(sizes: &[usize]) -> Vec<usize> {
+    let mut labels = vec![];
+    for round in 0..sizes.first().copied().unwrap_or(0) {
+      labels.extend((0..sizes.len()).filter(|&c| sizes[c] > round));
+    }
+    labels
+  }
+}
+
+#[cfg(feature = "std")]
+fn power_law_weights(k: usize, exponent: f64) -> Result<Vec<f64>, VccError> {
+  if !(exponent >= 0.0 && exponent.is_finite()) {
+    return Err(VccError::InvalidParameter(format!(
+      "clique size exponent {} is not a nonnegative number",
+      exponent
+    )));
+  }
+  Ok((0..k).map(|c| ((c + 1) as f64).powf(-exponent)).collect())
+}
+
+#[cfg(not(feature = "std"))]
+fn power_law_weights(_k: usize, _exponent: f64) -> Result<Vec<f64>, VccError> {
+  Err(VccError::InvalidParameter(
+    "power-law clique sizes need the std feature".to_string(),
+  ))
+}
+
+impl Graph {
+  pub fn planted(&self) -> Option<&CliqueCover> {
+    self.planted.as_ref()
+  }
+
+  // Checks that cover is a valid cover no larger than the planted one,
+  // and returns its distance to it: 0 when the search recovered the
+  // planted cover, up to the order of the cliques. Once noise has broken
+  // the planted cover, the optimum may be larger, so size isn't checked.
+  pub fn verify_against_planted(&self, cover: &CliqueCover) -> Result<usize, VccError> {
+    let planted = self
+      .planted
+      .as_ref()
+      .ok_or_else(|| VccError::InvalidParameter("the graph has no planted cover".to_string()))?;
+    self.validate_cover(cover)?;
+    if cover.len() > planted.len() && self.validate_cover(planted).is_ok() {
+      return Err(VccError::InvalidCover(format!(
+        "{} cliques where {} were planted",
+        cover.len(),
+        planted.len()
+      )));
+    }
+    Ok(cover.distance(planted))
+  }
+  // Moves planted edges of fraction of the vertices, chosen at random (see
+  // above), and returns how many edges moved. The search state is reset
+  // to one clique per vertex.
+  pub fn add_planted_noise<R: RngCore + ?Sized>(
+    &mut self,
+    fraction: f64,
+    rng: &mut R,
+  ) -> Result<usize, VccError> {
+    if !(0.0..=1.0).contains(&fraction) {
+      return Err(VccError::InvalidParameter(format!(
+        "planted noise {} is not in [0, 1]",
+        fraction
+      )));
+    }
+    let labels = self
+      .planted
+      .as_ref()
+      .ok_or_else(|| VccError::InvalidParameter("the graph has no planted cover".to_string()))?
+      .labels();
+    let mut order: Vec<usize> = (0..self.size).collect();
+    shuffle(rng, &mut order);
+    let noisy_ct = (fraction * self.size as f64) as usize;
+    let mut moved = 0;
+    for &v in &order[..noisy_ct] {
+      let mut mates: Vec<usize> = self
+        .neighbors(v)
+        .filter(|&u| labels[u] == labels[v])
+        .collect();
+      let mut strangers: Vec<usize> = (0..self.size)
+        .filter(|&w| labels[w] != labels[v] && !self.has_edge(v, w))
+        .collect();
+      shuffle(rng, &mut mates);
+      for &u in &mates[..mates.len().div_ceil(2)] {
+        if strangers.is_empty() {
+          break;
+        }
+        let w = strangers.swap_remove(below(rng, strangers.len()));
+        self.unlink(v, u);
+        self.link(v, w);
+        moved += 1;
+      }
+    }
+    self.conform_cliques_to_vertices();
+    Ok(moved)
+  }
+}
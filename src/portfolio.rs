@@ -0,0 +1,71 @@
+// Portfolio search: one strategy is active at a time. When it goes
+// stagnation_window steps without improving the best cover, the incumbent
+// is handed to the next strategy in the list.
+
+use crate::cover::CliqueCover;
+use crate::error::VccError;
+use crate::strategy::SearchStrategy;
+use crate::Graph;
+
+pub struct Portfolio {
+  pub strategies: Vec<Box<dyn SearchStrategy>>,
+  pub stagnation_window: usize,
+}
+
+pub struct PortfolioResult {
+  pub best_cover: CliqueCover,
+  pub reached_target: bool,
+  pub steps: usize,
+  // (step, name of the strategy taking over)
+  pub switches: Vec<(usize, &'static str)>,
+}
+
+impl Portfolio {
+  pub fn run(
+    &mut self,
+    g: &mut Graph,
+    max_steps: usize,
+    target: usize,
+  ) -> Result<PortfolioResult, VccError> {
+    if self.strategies.is_empty() {
+      return Err(VccError::InvalidParameter(
+        "portfolio needs at least one strategy".to_string(),
+      ));
+    }
+    let mut best_cover = g.cover();
+    let mut switches = vec![];
+    let mut active = 0;
+    let mut since_improvement = 0;
+    self.strategies[active].start(g);
+    for step in 1..(max_steps + 1) {
+      self.strategies[active].step(g);
+      if g.cliques_ct < best_cover.len() {
+        best_cover = g.cover();
+        since_improvement = 0;
+        if best_cover.len() <= target {
+          return Ok(PortfolioResult {
+            best_cover,
+            reached_target: true,
+            steps: step,
+            switches,
+          });
+        }
+      } else {
+        since_improvement += 1;
+      }
+      if since_improvement >= self.stagnation_window && self.strategies.len() > 1 {
+        since_improvement = 0;
+        active = (active + 1) % self.strategies.len();
+        switches.push((step, self.strategies[active].name()));
+        g.load_cover_unchecked(&best_cover);
+        self.strategies[active].start(g);
+      }
+    }
+    Ok(PortfolioResult {
+      best_cover,
+      reached_target: false,
+      steps: max_steps,
+      switches,
+    })
+  }
+}
@@ -0,0 +1,161 @@
+// A quick look at an instance before solving it: density, bounds and a few
+// short greedy runs, turned into a rough difficulty rating and a suggested
+// budget. The rating goes by the gap between the best lower bound and the
+// best cover the probe found:
+// - trivial: no gap, the probe's cover is already optimal (always so for
+//   cographs, which are solved exactly),
+// - easy: a gap of at most one that every greedy run agreed on,
+// - moderate: a gap of at most three,
+// - hard: anything wider.
+// The budgets are rules of thumb scaled by the vertex count, not promises.
+
+use crate::exact::min_degree_independent_set_size;
+use crate::Graph;
+use std::fmt;
+
+const GREEDY_RUNS: usize = 5;
+const GREEDY_PASSES: usize = 20;
+const INDEPENDENT_SET_TRIES: usize = 10;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Difficulty {
+  Trivial,
+  Easy,
+  Moderate,
+  Hard,
+}
+
+pub struct Probe {
+  pub density: f64,
+  pub complement_degeneracy: usize,
+  // Largest independent set found.
+  pub lower_bound: usize,
+  pub dsatur_bound: usize,
+  // The exact cover number, if the graph is a cograph.
+  pub cograph: Option<usize>,
+  // Best and worst of the short greedy runs.
+  pub greedy_best: usize,
+  pub greedy_worst: usize,
+  pub difficulty: Difficulty,
+  pub max_iterations: usize,
+  pub max_restarts: usize,
+  pub strategy: &'static str,
+}
+
+impl Probe {
+  pub fn upper_bound(&self) -> usize {
+    self
+      .cograph
+      .unwrap_or(self.dsatur_bound.min(self.greedy_best))
+  }
+}
+
+impl Graph {
+  // Works on a copy, so the graph's search state and generator are left
+  // as they were.
+  pub fn probe(&self) -> Probe {
+    let n = self.size;
+    let mut g = self.clone();
+    let density = self.density();
+
+    let mut lower_bound = min_degree_independent_set_size(self);
+    for _ in 0..INDEPENDENT_SET_TRIES {
+      lower_bound = lower_bound.max(g.greedy_independent_set_bound());
+    }
+    let dsatur_bound = self.dsatur_cover().len();
+    let cograph = self.cograph_cover().map(|cover| cover.len());
+    if let Some(optimum) = cograph {
+      lower_bound = optimum;
+    }
+
+    let mut greedy_best = usize::MAX;
+    let mut greedy_worst = 0;
+    for _ in 0..GREEDY_RUNS {
+      g.conform_cliques_to_vertices();
+      g.shuffle_active_cliques();
+      g.vcc_greedy();
+      for _ in 0..GREEDY_PASSES {
+        g.vcc_iterated_greedy(0.0);
+      }
+      greedy_best = greedy_best.min(g.cliques_ct);
+      greedy_worst = greedy_worst.max(g.cliques_ct);
+    }
+
+    let gap = dsatur_bound.min(greedy_best).saturating_sub(lower_bound);
+    let (difficulty, max_iterations, max_restarts, strategy) = if cograph.is_some() {
+      (
+        Difficulty::Trivial,
+        n,
+        1,
+        "none needed: a cograph, solved exactly",
+      )
+    } else if gap == 0 {
+      (
+        Difficulty::Trivial,
+        n,
+        1,
+        "none needed: the probe's cover is optimal",
+      )
+    } else if gap <= 1 && greedy_best == greedy_worst {
+      (Difficulty::Easy, 100 * n, 1, "iterated greedy")
+    } else if gap <= 3 {
+      (
+        Difficulty::Moderate,
+        1_000 * n,
+        10,
+        "iterated greedy with annealing",
+      )
+    } else {
+      (Difficulty::Hard, 10_000 * n, 100, "multistart or portfolio")
+    };
+    Probe {
+      density,
+      complement_degeneracy: self.complement_degeneracy(),
+      lower_bound,
+      dsatur_bound,
+      cograph,
+      greedy_best,
+      greedy_worst,
+      difficulty,
+      max_iterations,
+      max_restarts,
+      strategy,
+    }
+  }
+}
+
+impl fmt::Display for Difficulty {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let name = match self {
+      Difficulty::Trivial => "trivial",
+      Difficulty::Easy => "easy",
+      Difficulty::Moderate => "moderate",
+      Difficulty::Hard => "hard",
+    };
+    write!(f, "{}", name)
+  }
+}
+
+impl fmt::Display for Probe {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    writeln!(
+      f,
+      "density {:.3}, complement degeneracy {}",
+      self.density, self.complement_degeneracy
+    )?;
+    writeln!(
+      f,
+      "bounds: independent set {} <= cover <= {} (dsatur {}, greedy {}..{})",
+      self.lower_bound,
+      self.upper_bound(),
+      self.dsatur_bound,
+      self.greedy_best,
+      self.greedy_worst
+    )?;
+    write!(
+      f,
+      "difficulty {}: suggest {} iterations x {} restarts, {}",
+      self.difficulty, self.max_iterations, self.max_restarts, self.strategy
+    )
+  }
+}
@@ -0,0 +1,134 @@
+// Presets of the solver's options for common classes of instance, since
+// most users want good defaults for their regime rather than a dozen
+// knobs. A profile sets the search parameters, the backend and the
+// parallelism; any option given alongside it overrides the profile's
+// choice, whatever their order.
+//   dense   Large cliques and few of them: long restarts from the rounded
+//           fractional cover, larger cliques refilled first (with noise),
+//           a reverse fraction decaying from 0.5 to explore early,
+//           annealing seeded with two vertices from the largest clique,
+//           and an exact finish once close to the lower bound.
+//   sparse  Many small cliques: many short restarts from the sequential
+//           cover in shuffled order, annealing ten times sooner and
+//           seeded from the most conflicted clique.
+//   huge    Too large to search directly: the multilevel backend (see
+//           multilevel.rs) down to 2,000 vertices, few short restarts,
+//           the target checked every ten seconds (see cadence.rs), and
+//           two workers, since each holds its own copy of the graph.
+//   quick   One short restart from the sequential cover, on one worker,
+//           for a fast upper bound.
+// The target is left as it was.
+
+use crate::anneal::{AnnealingSchedule, DonorPolicy};
+use crate::cadence::CheckCadence;
+use crate::error::VccError;
+use crate::finish::ExactFinish;
+use crate::multilevel::Multilevel;
+use crate::order::CliqueOrder;
+use crate::solve::{DecayKind, InitialCover, ReverseDecay, SolveParams};
+use alloc::format;
+use core::str::FromStr;
+use core::time::Duration;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Profile {
+  Dense,
+  Sparse,
+  Huge,
+  Quick,
+}
+
+// "dense", "sparse", "huge" or "quick"
+impl FromStr for Profile {
+  type Err = VccError;
+
+  fn from_str(profile_str: &str) -> Result<Profile, VccError> {
+    match profile_str {
+      "dense" => Ok(Profile::Dense),
+      "sparse" => Ok(Profile::Sparse),
+      "huge" => Ok(Profile::Huge),
+      "quick" => Ok(Profile::Quick),
+      _ => Err(VccError::Parse(format!("bad profile: {:?}", profile_str))),
+    }
+  }
+}
+
+impl Profile {
+  // Sets every search parameter but the target.
+  pub fn apply(&self, params: &mut SolveParams) {
+    let annealing = AnnealingSchedule::default();
+    params.reverse_decay = None;
+    params.freeze = None;
+    params.finish = None;
+    params.distinct_starts = None;
+    params.check = CheckCadence::default();
+    match self {
+      Profile::Dense => {
+        params.max_iterations = 1_000_000;
+        params.max_restarts = 10;
+        params.reverse_fraction = 0.5;
+        params.reverse_decay = Some(ReverseDecay {
+          kind: DecayKind::Linear,
+          end: 0.0,
+        });
+        params.order = CliqueOrder::LargestFirst { noise: 0.5 };
+        params.initial = InitialCover::Fractional;
+        params.annealing = AnnealingSchedule {
+          seed_size: 2,
+          donor: DonorPolicy::Largest,
+          ..annealing
+        };
+        params.finish = Some(ExactFinish::default());
+      }
+      Profile::Sparse => {
+        params.max_iterations = 100_000;
+        params.max_restarts = 50;
+        params.reverse_fraction = 0.0;
+        params.order = CliqueOrder::Shuffle;
+        params.initial = InitialCover::Sequential;
+        params.annealing = AnnealingSchedule {
+          initial_iterations: 100_000,
+          donor: DonorPolicy::MostConflicted,
+          ..annealing
+        };
+      }
+      Profile::Huge => {
+        params.max_iterations = 10_000;
+        params.max_restarts = 3;
+        params.reverse_fraction = 0.0;
+        params.order = CliqueOrder::Shuffle;
+        params.initial = InitialCover::Sequential;
+        params.annealing = annealing;
+        params.check.interval = Some(Duration::from_secs(10));
+      }
+      Profile::Quick => {
+        params.max_iterations = 10_000;
+        params.max_restarts = 1;
+        params.reverse_fraction = 0.0;
+        params.order = CliqueOrder::Shuffle;
+        params.initial = InitialCover::Sequential;
+        params.annealing = annealing;
+      }
+    }
+  }
+
+  // The backend: multilevel solving, or None for the plain search.
+  pub fn multilevel(&self) -> Option<Multilevel> {
+    match self {
+      Profile::Huge => Some(Multilevel {
+        coarsest: 2_000,
+        refine_iterations: 1_000,
+      }),
+      _ => None,
+    }
+  }
+
+  // Workers for parallel drivers, or None for one per core.
+  pub fn workers(&self) -> Option<usize> {
+    match self {
+      Profile::Dense | Profile::Sparse => None,
+      Profile::Huge => Some(2),
+      Profile::Quick => Some(1),
+    }
+  }
+}
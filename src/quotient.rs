@@ -0,0 +1,114 @@
+// Quotient of a graph by a clique cover: each clique becomes a single node,
+// and two nodes are adjacent if any vertex of one clique is adjacent to any
+// vertex of the other.
+
+use crate::bitset::BitVec;
+use crate::cover::CliqueCover;
+use crate::error::VccError;
+use crate::Graph;
+
+pub struct Quotient {
+  pub graph: Graph,
+  // The original vertices represented by each quotient node.
+  pub cliques: Vec<Vec<usize>>,
+  // (i, j, number of original edges between clique i and clique j), i < j
+  pub edges: Vec<(usize, usize, usize)>,
+}
+
+impl Graph {
+  pub fn quotient(&self, cover: &CliqueCover) -> Result<Quotient, VccError> {
+    self.validate_cover(cover)?;
+    let cliques_ct = cover.len();
+    let mut members_bvs: Vec<BitVec> = Vec::with_capacity(cliques_ct);
+    for clique in &cover.cliques {
+      let mut members_bv = BitVec::zeros(self.size);
+      for &v in clique {
+        members_bv.set(v, true);
+      }
+      members_bvs.push(members_bv);
+    }
+
+    let mut ret_graph = Graph::new(cliques_ct);
+    let mut edges = vec![];
+    for i in 0..cliques_ct {
+      for (j, members_bv) in members_bvs.iter().enumerate().skip(i + 1) {
+        let mut edge_ct = 0;
+        for &v in &cover.cliques[i] {
+          edge_ct += self.topology.vertices[v]
+            .neighbors_bv
+            .and_cloned(members_bv)
+            .count_ones();
+        }
+        if edge_ct > 0 {
+          ret_graph.link(i, j);
+          edges.push((i, j, edge_ct));
+        }
+      }
+    }
+    ret_graph.conform_cliques_to_vertices();
+
+    Ok(Quotient {
+      graph: ret_graph,
+      cliques: cover.cliques.clone(),
+      edges,
+    })
+  }
+}
+
+impl Quotient {
+  pub fn to_dot(&self) -> String {
+    let mut ret_str = String::from("graph quotient {\n");
+    for (i, clique) in self.cliques.iter().enumerate() {
+      ret_str += &format!(
+        "  q{} [label=\"{}\", size={}];\n",
+        i,
+        join_ids(clique, " "),
+        clique.len()
+      );
+    }
+    for &(i, j, edge_ct) in &self.edges {
+      ret_str += &format!("  q{} -- q{} [weight={}];\n", i, j, edge_ct);
+    }
+    ret_str += "}\n";
+    ret_str
+  }
+
+  pub fn to_json(&self) -> String {
+    let nodes: Vec<String> = self
+      .cliques
+      .iter()
+      .enumerate()
+      .map(|(i, clique)| {
+        format!(
+          "{{\"id\": {}, \"size\": {}, \"members\": [{}]}}",
+          i,
+          clique.len(),
+          join_ids(clique, ", ")
+        )
+      })
+      .collect();
+    let edges: Vec<String> = self
+      .edges
+      .iter()
+      .map(|&(i, j, edge_ct)| {
+        format!(
+          "{{\"source\": {}, \"target\": {}, \"weight\": {}}}",
+          i, j, edge_ct
+        )
+      })
+      .collect();
+    format!(
+      "{{\n  \"nodes\": [\n    {}\n  ],\n  \"edges\": [\n    {}\n  ]\n}}\n",
+      nodes.join(",\n    "),
+      edges.join(",\n    ")
+    )
+  }
+}
+
+fn join_ids(ids: &[usize], sep: &str) -> String {
+  ids
+    .iter()
+    .map(|v| v.to_string())
+    .collect::<Vec<String>>()
+    .join(sep)
+}
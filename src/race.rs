@@ -0,0 +1,133 @@
+// Racing: several solver configurations attack the same instance one round
+// (one restart) at a time, concurrently. After min_rounds, any configuration
+// whose mean cover size is worse than the leader's by more than z standard
+// errors is dropped. Once a single configuration survives it keeps the
+// remaining rounds to itself.
+
+use crate::cover::CliqueCover;
+use crate::error::VccError;
+use crate::solve::SolveParams;
+use crate::Graph;
+use rand_core::RngCore;
+use rayon::prelude::*;
+
+pub struct RaceParams {
+  pub min_rounds: usize,
+  pub max_rounds: usize,
+  pub z: f64,
+}
+
+pub struct RaceOutcome {
+  pub winner: usize,
+  // (config index, round in which it was dropped)
+  pub eliminated: Vec<(usize, usize)>,
+  pub best_cover: CliqueCover,
+  pub reached_target: bool,
+  pub rounds: usize,
+}
+
+pub fn race(
+  graph: &Graph,
+  configs: &[SolveParams],
+  race_params: &RaceParams,
+) -> Result<RaceOutcome, VccError> {
+  if configs.is_empty() {
+    return Err(VccError::InvalidParameter(
+      "race needs at least one configuration".to_string(),
+    ));
+  }
+  for config in configs {
+    config.validate()?;
+  }
+  let mut survivors: Vec<usize> = (0..configs.len()).collect();
+  let mut scores: Vec<Vec<f64>> = vec![vec![]; configs.len()];
+  let mut eliminated = vec![];
+  let mut best_cover = graph.cover();
+  let mut rounds = 0;
+  let mut seed_rng = graph.rng.clone();
+
+  while rounds < race_params.max_rounds {
+    rounds += 1;
+    let entrants: Vec<(usize, u64)> = survivors
+      .iter()
+      .map(|&c| (c, seed_rng.next_u64()))
+      .collect();
+    let round_results: Vec<(usize, bool, CliqueCover)> = entrants
+      .par_iter()
+      .map(|&(c, seed)| {
+        let mut g = graph.clone();
+        g.seed(seed);
+        g.conform_cliques_to_vertices();
+        g.shuffle_active_cliques();
+        let result = g.solve(&SolveParams {
+          max_restarts: 1,
+          ..configs[c]
+        })?;
+        Ok((c, result.reached_target, result.best_cover))
+      })
+      .collect::<Result<_, VccError>>()?;
+
+    let mut finisher = None;
+    for (c, reached_target, cover) in round_results {
+      scores[c].push(cover.len() as f64);
+      if cover.len() < best_cover.len() {
+        best_cover = cover;
+      }
+      if reached_target && finisher.is_none() {
+        finisher = Some(c);
+      }
+    }
+    if let Some(c) = finisher {
+      return Ok(RaceOutcome {
+        winner: c,
+        eliminated,
+        best_cover,
+        reached_target: true,
+        rounds,
+      });
+    }
+
+    if survivors.len() > 1 && rounds >= race_params.min_rounds {
+      let leader = leader(&survivors, &scores);
+      let (leader_mean, leader_var) = mean_and_variance(&scores[leader]);
+      survivors.retain(|&c| {
+        let (mean, var) = mean_and_variance(&scores[c]);
+        let std_err = ((var + leader_var) / rounds as f64).sqrt();
+        let keep = mean - leader_mean <= race_params.z * std_err;
+        if !keep {
+          eliminated.push((c, rounds));
+        }
+        keep
+      });
+    }
+  }
+
+  Ok(RaceOutcome {
+    winner: leader(&survivors, &scores),
+    eliminated,
+    best_cover,
+    reached_target: false,
+    rounds,
+  })
+}
+
+fn leader(survivors: &[usize], scores: &[Vec<f64>]) -> usize {
+  let mut leader = survivors[0];
+  for &c in survivors {
+    if mean_and_variance(&scores[c]).0 < mean_and_variance(&scores[leader]).0 {
+      leader = c;
+    }
+  }
+  leader
+}
+
+// Sample mean and (unbiased) variance.
+fn mean_and_variance(samples: &[f64]) -> (f64, f64) {
+  let n = samples.len() as f64;
+  let mean = samples.iter().sum::<f64>() / n;
+  if samples.len() < 2 {
+    return (mean, 0.0);
+  }
+  let var = samples.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / (n - 1.0);
+  (mean, var)
+}
@@ -0,0 +1,106 @@
+// Updating a cover as vertices are deleted, without solving again, for
+// jobs that shrink a graph and refresh its cover. A clique minus some of
+// its vertices is still a clique, so dropping the deleted vertices from
+// the cover, and the cliques they emptied, leaves a valid cover of the
+// rest. Deletion can also let cliques merge, the vertices a clique's
+// neighbors weren't all adjacent to being gone; so each clique in turn,
+// smallest first, is dissolved if every member can join another clique,
+// each the largest that accepts it as repair.rs places vertices, and is
+// otherwise left as it was. That costs about as much as a cover check per
+// clique, far less than a search. Forbidden sets' limits are kept while
+// merging, though like any induced subgraph the new graph has none.
+
+use crate::cover::CliqueCover;
+use crate::error::VccError;
+use crate::Graph;
+use alloc::format;
+use alloc::vec;
+use alloc::vec::Vec;
+
+pub struct Removal {
+  // The remaining vertices, vertex i being kept[i] of the old graph.
+  pub graph: Graph,
+  pub kept: Vec<usize>,
+  // A valid cover of graph.
+  pub cover: CliqueCover,
+  // Cliques holding only deleted vertices.
+  pub emptied: usize,
+  // Cliques dissolved into others.
+  pub merged: usize,
+}
+
+impl Graph {
+  // The graph without the removed vertices, and cover, which must be a
+  // valid cover of this graph, updated to it as above.
+  pub fn remove_vertices(
+    &self,
+    cover: &CliqueCover,
+    removed: &[usize],
+  ) -> Result<Removal, VccError> {
+    self.validate_cover(cover)?;
+    let mut is_removed = vec![false; self.size];
+    for &v in removed {
+      if v >= self.size {
+        return Err(VccError::InvalidParameter(format!(
+          "can't remove vertex {} of {}",
+          v, self.size
+        )));
+      }
+      is_removed[v] = true;
+    }
+    let mut cliques: Vec<Vec<usize>> = vec![];
+    let mut emptied = 0;
+    for members in &cover.cliques {
+      let rest: Vec<usize> = members
+        .iter()
+        .copied()
+        .filter(|&v| !is_removed[v])
+        .collect();
+      if rest.is_empty() {
+        emptied += 1;
+      } else {
+        cliques.push(rest);
+      }
+    }
+
+    let mut order: Vec<usize> = (0..cliques.len()).collect();
+    order.sort_by_key(|&c| cliques[c].len());
+    let mut merged = 0;
+    for c in order {
+      let members = core::mem::take(&mut cliques[c]);
+      let mut joined = vec![];
+      for &v in &members {
+        match self.accepting_clique(&cliques, v, c) {
+          Some(to) => {
+            cliques[to].push(v);
+            joined.push(to);
+          }
+          None => break,
+        }
+      }
+      if joined.len() == members.len() {
+        merged += 1;
+      } else {
+        // Each clique joined got its new members last.
+        for to in joined {
+          cliques[to].pop();
+        }
+        cliques[c] = members;
+      }
+    }
+    cliques.retain(|members| !members.is_empty());
+
+    let kept: Vec<usize> = (0..self.size).filter(|&v| !is_removed[v]).collect();
+    let mut new_id = vec![usize::MAX; self.size];
+    for (i, &v) in kept.iter().enumerate() {
+      new_id[v] = i;
+    }
+    Ok(Removal {
+      graph: self.induced_subgraph(&kept),
+      cover: CliqueCover { cliques }.relabel(&new_id),
+      kept,
+      emptied,
+      merged,
+    })
+  }
+}
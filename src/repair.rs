@@ -0,0 +1,186 @@
+// Repairing a cover that no longer fits its graph, say after a few edges
+// changed, or that covers only some vertices, changing as little as
+// possible. Vertices the graph doesn't have are dropped, and a vertex in
+// several cliques stays in the first. Then, clique by clique, the member
+// with the most non-neighbors in its clique is evicted until the rest are
+// pairwise adjacent and keep every forbidden set's limit; ties evict the
+// later member. Evicted and uncovered vertices are placed one at a time in
+// the largest clique that accepts them, or a new clique if none does, and
+// a last pass moves the vertices left alone in new cliques to any clique
+// that accepts them now. Every other vertex stays where it was.
+
+use crate::cover::CliqueCover;
+use crate::Graph;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+// A vertex the repair placed: from its clique in the old cover, if it had
+// one, to its clique in the repaired cover.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Placement {
+  pub vertex: usize,
+  pub from: Option<usize>,
+  pub to: usize,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Repair {
+  // Vertex ids past the graph's size, as the old cover listed them.
+  pub dropped: Vec<usize>,
+  // Later appearances of vertices listed in several cliques.
+  pub repeated: usize,
+  // By vertex.
+  pub placements: Vec<Placement>,
+  pub new_cliques: usize,
+}
+
+impl Repair {
+  pub fn is_empty(&self) -> bool {
+    self.dropped.is_empty() && self.repeated == 0 && self.placements.is_empty()
+  }
+}
+
+// "placed 3 vertices, 1 of them uncovered, 1 new cliques, dropped 0
+// unknown and 2 repeated"
+impl fmt::Display for Repair {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let uncovered = self.placements.iter().filter(|p| p.from.is_none()).count();
+    write!(
+      f,
+      "placed {} vertices, {} of them uncovered, {} new cliques, ",
+      self.placements.len(),
+      uncovered,
+      self.new_cliques
+    )?;
+    write!(
+      f,
+      "dropped {} unknown and {} repeated",
+      self.dropped.len(),
+      self.repeated
+    )
+  }
+}
+
+impl Graph {
+  // A valid cover as close to cover as the repair above gets, and what it
+  // changed.
+  pub fn repair_cover(&self, cover: &CliqueCover) -> (CliqueCover, Repair) {
+    let mut repair = Repair::default();
+    let mut seen = vec![false; self.size];
+    let mut cliques: Vec<Vec<usize>> = vec![];
+    // Vertices to place, with their old cliques.
+    let mut loose: Vec<(usize, Option<usize>)> = vec![];
+    for (c, members) in cover.cliques.iter().enumerate() {
+      let mut kept = vec![];
+      for &v in members {
+        if v >= self.size {
+          repair.dropped.push(v);
+        } else if seen[v] {
+          repair.repeated += 1;
+        } else {
+          seen[v] = true;
+          kept.push(v);
+        }
+      }
+      while let Some(i) = self.worst_member(&kept) {
+        loose.push((kept.remove(i), Some(c)));
+      }
+      cliques.push(kept);
+    }
+    loose.extend((0..self.size).filter(|&v| !seen[v]).map(|v| (v, None)));
+    loose.sort_unstable();
+
+    let old_ct = cliques.len();
+    let mut placed_in = vec![usize::MAX; self.size];
+    for &(v, _) in &loose {
+      let c = self.accepting_clique(&cliques, v, usize::MAX);
+      placed_in[v] = c.unwrap_or(cliques.len());
+      match c {
+        Some(c) => cliques[c].push(v),
+        None => cliques.push(vec![v]),
+      }
+    }
+    // Local search: empty the new singletons where another clique now
+    // accepts their vertex.
+    for c in old_ct..cliques.len() {
+      if let [v] = cliques[c][..] {
+        if let Some(to) = self.accepting_clique(&cliques, v, c) {
+          cliques[c].clear();
+          cliques[to].push(v);
+          placed_in[v] = to;
+        }
+      }
+    }
+
+    // Empty cliques go, renumbering the rest.
+    let mut renumbered = vec![usize::MAX; cliques.len()];
+    let mut repaired = CliqueCover { cliques: vec![] };
+    for (c, members) in cliques.into_iter().enumerate() {
+      if !members.is_empty() {
+        renumbered[c] = repaired.cliques.len();
+        repair.new_cliques += usize::from(c >= old_ct);
+        repaired.cliques.push(members);
+      }
+    }
+    // An evicted vertex may end up back in its clique, once the members
+    // it conflicted with are gone; that isn't a move.
+    repair.placements = loose
+      .into_iter()
+      .map(|(vertex, from)| Placement {
+        vertex,
+        from,
+        to: renumbered[placed_in[vertex]],
+      })
+      .filter(|p| p.from.map(|from| renumbered[from]) != Some(p.to))
+      .collect();
+    (repaired, repair)
+  }
+
+  // The member to evict next, if members aren't a clique keeping the
+  // forbidden sets' limits: the one with the most non-neighbors among
+  // them, else the last member of a set they hold too many of.
+  fn worst_member(&self, members: &[usize]) -> Option<usize> {
+    let (conflicts, i) = members
+      .iter()
+      .enumerate()
+      .map(|(i, &v)| {
+        let conflicts = members
+          .iter()
+          .filter(|&&u| u != v && !self.has_edge(u, v))
+          .count();
+        (conflicts, i)
+      })
+      .max()?;
+    if conflicts > 0 {
+      return Some(i);
+    }
+    let set = self.broken_limit(members)?;
+    members.iter().rposition(|v| set.members.contains(v))
+  }
+
+  // The largest clique but skip that v can join, the first of those tied.
+  pub(crate) fn accepting_clique(
+    &self,
+    cliques: &[Vec<usize>],
+    v: usize,
+    skip: usize,
+  ) -> Option<usize> {
+    let mut best: Option<usize> = None;
+    for (c, members) in cliques.iter().enumerate() {
+      if c == skip
+        || members.is_empty()
+        || best.is_some_and(|b| cliques[b].len() >= members.len())
+        || !members.iter().all(|&u| self.has_edge(u, v))
+      {
+        continue;
+      }
+      let mut joined = members.clone();
+      joined.push(v);
+      if self.broken_limit(&joined).is_none() {
+        best = Some(c);
+      }
+    }
+    best
+  }
+}
@@ -0,0 +1,101 @@
+// Random number generation. Each graph owns the generator its search draws
+// from, so every worker thread can run its own reproducible stream. Any
+// rand_core generator can be plugged in with Graph::set_rng; by default a
+// xoshiro256++ seeded from the system's hash randomness is used (without
+// std, from a counter instead). A solve draws its restart seeds from the
+// graph's generator, and each restart then runs on a default generator
+// seeded with its own seed, so that it can be replayed alone (see
+// solve.rs); the graph's generator is left as the draws left it.
+
+use alloc::boxed::Box;
+use core::any::Any;
+use rand_core::{RngCore, SeedableRng};
+#[cfg(feature = "std")]
+use std::collections::hash_map::RandomState;
+#[cfg(feature = "std")]
+use std::hash::{BuildHasher, Hasher};
+
+pub type DefaultRng = rand_xoshiro::Xoshiro256PlusPlus;
+
+// Object-safe generator that can be cloned along with the graph owning it.
+pub trait SearchRng: RngCore + Send + Sync {
+  fn clone_box(&self) -> Box<dyn SearchRng>;
+  fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: RngCore + Clone + Send + Sync + 'static> SearchRng for T {
+  fn clone_box(&self) -> Box<dyn SearchRng> {
+    Box::new(self.clone())
+  }
+
+  fn as_any(&self) -> &dyn Any {
+    self
+  }
+}
+
+impl Clone for Box<dyn SearchRng> {
+  fn clone(&self) -> Box<dyn SearchRng> {
+    self.as_ref().clone_box()
+  }
+}
+
+// A seed that differs from run to run.
+#[cfg(feature = "std")]
+pub fn entropy_seed() -> u64 {
+  RandomState::new().build_hasher().finish()
+}
+
+// Without std there's nothing to draw on, so the seeds only differ from
+// call to call.
+#[cfg(not(feature = "std"))]
+pub fn entropy_seed() -> u64 {
+  use core::sync::atomic::{AtomicU64, Ordering};
+  static CALLS: AtomicU64 = AtomicU64::new(0);
+  mix64(CALLS.fetch_add(1, Ordering::Relaxed))
+}
+
+pub fn default_rng(seed: u64) -> DefaultRng {
+  DefaultRng::seed_from_u64(seed)
+}
+
+// SplitMix64's output function: a fixed scrambling of x.
+pub(crate) fn mix64(x: u64) -> u64 {
+  let mut z = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+  z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+  z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+  z ^ (z >> 31)
+}
+
+// Uniform in 0..n (Lemire's multiply-and-reject); n must be positive.
+pub(crate) fn below<R: RngCore + ?Sized>(rng: &mut R, n: usize) -> usize {
+  below_u64(rng, n as u64) as usize
+}
+
+// below for counts that may not fit a usize, like vertex pairs on 32-bit
+// targets.
+pub(crate) fn below_u64<R: RngCore + ?Sized>(rng: &mut R, n: u64) -> u64 {
+  let threshold = n.wrapping_neg() % n;
+  loop {
+    let m = (rng.next_u64() as u128) * (n as u128);
+    if (m as u64) >= threshold {
+      return (m >> 64) as u64;
+    }
+  }
+}
+
+// Uniform in [0, 1).
+pub(crate) fn unit_f64<R: RngCore + ?Sized>(rng: &mut R) -> f64 {
+  (rng.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+}
+
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+pub(crate) fn coin<R: RngCore + ?Sized>(rng: &mut R) -> bool {
+  rng.next_u32() & 1 == 1
+}
+
+// Fisher-Yates.
+pub(crate) fn shuffle<R: RngCore + ?Sized, T>(rng: &mut R, slice: &mut [T]) {
+  for i in (1..slice.len()).rev() {
+    slice.swap(i, below(rng, i + 1));
+  }
+}
@@ -0,0 +1,59 @@
+// Scratch bit vectors for the transfer routines, which mark the vertices
+// they move in one. The routines take the buffer as an argument rather
+// than from the graph, so any number can run side by side, each on a
+// buffer of its own: a graph keeps a pool for its own transfers, and
+// workers evaluating merges on a shared graph (see
+// Graph::transferable_ct) keep one each. A pool hands out released
+// buffers before allocating new ones, so a steady caller allocates once.
+
+use crate::bitset::BitVec;
+use crate::forbidden::trim_to_limits;
+use crate::Graph;
+use alloc::vec::Vec;
+
+#[derive(Clone)]
+pub struct ScratchPool {
+  len: usize,
+  free: Vec<BitVec>,
+}
+
+impl ScratchPool {
+  // Pools of buffers of len bits, for graphs of len vertices.
+  pub fn new(len: usize) -> ScratchPool {
+    ScratchPool {
+      len,
+      free: Vec::new(),
+    }
+  }
+
+  // A buffer of the pool's length; its bits are left as they were.
+  pub fn take(&mut self) -> BitVec {
+    self.free.pop().unwrap_or_else(|| BitVec::zeros(self.len))
+  }
+
+  pub fn give(&mut self, buffer: BitVec) {
+    debug_assert_eq!(buffer.len(), self.len);
+    self.free.push(buffer);
+  }
+}
+
+impl Graph {
+  // How many vertices transfer_compatible_vertices_between(i, j) would
+  // move, without moving them. Takes the graph by shared reference and
+  // the scratch buffer from the caller, for workers scoring merges in
+  // parallel.
+  pub fn transferable_ct(&self, i: usize, j: usize, scratch: &mut BitVec) -> usize {
+    assert!(i < self.cliques_ct && j < self.cliques_ct && i != j);
+    let (clique_into, clique_from) = (&self.cliques[i], &self.cliques[j]);
+    if !clique_into.has_neighbors() || clique_from.is_frozen {
+      return 0;
+    }
+    scratch.set_all_false();
+    scratch.or_inplace(&clique_from.members_bv);
+    scratch.and_inplace(&clique_into.neighbors_bv);
+    if !self.forbidden.is_empty() {
+      trim_to_limits(&self.forbidden, &clique_into.members_bv, scratch);
+    }
+    scratch.count_ones()
+  }
+}
@@ -0,0 +1,120 @@
+// A quick end-to-end check of a build: solve a spread of family instances
+// with known clique cover numbers on a small budget, verify every cover
+// and compare its size with the known optimum.
+
+use crate::families::{
+  complement, complete, complete_bipartite, cycle, disjoint_cliques, mycielski, path, petersen,
+  queen,
+};
+use crate::solve::SolveParams;
+use crate::Graph;
+use std::time::Duration;
+
+pub struct SelfTestCase {
+  pub name: String,
+  pub graph: Graph,
+  pub optimum: usize,
+}
+
+pub struct SelfTestOutcome {
+  pub name: String,
+  pub optimum: usize,
+  // None if the solver returned an error.
+  pub found: Option<usize>,
+  // Why the case failed, if it did.
+  pub failure: Option<String>,
+  pub elapsed: Duration,
+}
+
+impl SelfTestOutcome {
+  pub fn passed(&self) -> bool {
+    self.failure.is_none()
+  }
+}
+
+pub fn cases() -> Vec<SelfTestCase> {
+  let mut cases = vec![];
+  let mut add = |name: String, graph: Graph, optimum: usize| {
+    cases.push(SelfTestCase {
+      name,
+      graph,
+      optimum,
+    })
+  };
+  add("empty 8".to_string(), Graph::new(8), 8);
+  for n in [2, 9, 40] {
+    add(format!("K{}", n), complete(n), 1);
+  }
+  for n in [5, 8, 31, 64] {
+    add(format!("C{}", n), cycle(n), n.div_ceil(2));
+    add(
+      format!("C{} complement", n),
+      complement(&cycle(n)),
+      2 + n % 2,
+    );
+  }
+  for n in [7, 50] {
+    add(format!("P{}", n), path(n), n.div_ceil(2));
+  }
+  add("K4,9".to_string(), complete_bipartite(4, 9), 9);
+  add("petersen".to_string(), petersen(), 5);
+  add(
+    "petersen complement".to_string(),
+    complement(&petersen()),
+    3,
+  );
+  add("6 x K7".to_string(), disjoint_cliques(6, 7), 6);
+  for k in [3, 4, 5] {
+    add(
+      format!("myciel{} complement", k),
+      complement(&mycielski(k)),
+      k + 1,
+    );
+  }
+  for n in [5, 7] {
+    add(
+      format!("queen{}_{} complement", n, n),
+      complement(&queen(n)),
+      n,
+    );
+  }
+  cases
+}
+
+pub fn run_case(case: &SelfTestCase, seed: u64) -> SelfTestOutcome {
+  let mut g = case.graph.clone();
+  g.seed(seed);
+  g.shuffle_active_cliques();
+  let params = SolveParams {
+    max_iterations: 50_000,
+    target: case.optimum,
+    max_restarts: 20,
+    ..SolveParams::default()
+  };
+  let mut outcome = SelfTestOutcome {
+    name: case.name.clone(),
+    optimum: case.optimum,
+    found: None,
+    failure: None,
+    elapsed: Duration::ZERO,
+  };
+  let result = match g.solve(&params) {
+    Ok(result) => result,
+    Err(e) => {
+      outcome.failure = Some(e.to_string());
+      return outcome;
+    }
+  };
+  outcome.found = Some(result.best_cover.len());
+  outcome.elapsed = result.elapsed;
+  if let Err(e) = g.validate_cover(&result.best_cover) {
+    outcome.failure = Some(e.to_string());
+  } else if result.best_cover.len() != case.optimum {
+    outcome.failure = Some(format!(
+      "found {} cliques, optimum is {}",
+      result.best_cover.len(),
+      case.optimum
+    ));
+  }
+  outcome
+}
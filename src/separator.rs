@@ -0,0 +1,173 @@
+// Divide and conquer over vertex separators, for large sparse graphs such
+// as road networks where a global greedy pass over all pairs of cliques is
+// hopeless. A separator splits the vertices into two sides with no edges
+// between them; the sides are solved independently (recursively, down to
+// a leaf size) and their covers put together. The separator's vertices are
+// then repaired in locally: each joins the largest clique it is adjacent
+// to entirely, among the cliques of its neighbors, or starts a clique of
+// its own.
+//
+// Separators come from BFS level structures: from a pseudo-peripheral
+// vertex, every BFS level separates the levels before it from those after.
+// The smallest level that leaves both sides at least a quarter of the rest
+// is used. Disconnected graphs are split between components instead, with
+// an empty separator.
+
+use crate::cover::CliqueCover;
+use crate::error::VccError;
+use crate::solve::SolveParams;
+use crate::Graph;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+
+pub struct Separator {
+  pub left: Vec<usize>,
+  pub separator: Vec<usize>,
+  pub right: Vec<usize>,
+}
+
+impl Graph {
+  // None if no balanced separator was found.
+  pub fn bfs_separator(&self) -> Option<Separator> {
+    let n = self.size;
+    if n < 3 {
+      return None;
+    }
+    let reached = self.bfs_levels(0, &mut vec![false; n]);
+    if reached.iter().map(|level| level.len()).sum::<usize>() < n {
+      return Some(self.split_components());
+    }
+    let far = *reached.last()?.last()?;
+    let levels = self.bfs_levels(far, &mut vec![false; n]);
+    let mut before = 0;
+    let mut best: Option<(usize, usize)> = None;
+    for (i, level) in levels.iter().enumerate() {
+      let after = n - before - level.len();
+      let balanced = 4 * before.min(after) >= n - level.len();
+      if balanced && best.is_none_or(|(_, size)| level.len() < size) {
+        best = Some((i, level.len()));
+      }
+      before += level.len();
+    }
+    let (i, _) = best?;
+    Some(Separator {
+      left: levels[..i].concat(),
+      separator: levels[i].clone(),
+      right: levels[i + 1..].concat(),
+    })
+  }
+
+  // Solves the graph by recursive separation down to leaf_size vertices,
+  // solving each leaf with params. Leaves the cover loaded.
+  pub fn solve_divided(
+    &mut self,
+    params: &SolveParams,
+    leaf_size: usize,
+  ) -> Result<CliqueCover, VccError> {
+    params.validate()?;
+    if !self.forbidden.is_empty() {
+      return Err(VccError::InvalidParameter(
+        "divide and conquer doesn't support forbidden sets".to_string(),
+      ));
+    }
+    let cover = self.divide(params, leaf_size.max(1))?;
+    self.load_cover_unchecked(&cover);
+    Ok(cover)
+  }
+
+  fn divide(&mut self, params: &SolveParams, leaf_size: usize) -> Result<CliqueCover, VccError> {
+    let split = (self.size > leaf_size)
+      .then(|| self.bfs_separator())
+      .flatten();
+    let Some(split) = split else {
+      return Ok(self.solve(params)?.best_cover);
+    };
+    let mut cliques = vec![];
+    for side in [&split.left, &split.right] {
+      let mut sub = self.induced_subgraph(side);
+      sub.seed(self.rng.next_u64());
+      let cover = sub.divide(params, leaf_size)?;
+      cliques.extend(
+        cover
+          .cliques
+          .into_iter()
+          .map(|members| members.into_iter().map(|v| side[v]).collect::<Vec<_>>()),
+      );
+    }
+    Ok(self.repair_separator(cliques, &split.separator))
+  }
+
+  // Adds each separator vertex to the largest clique of its neighbors
+  // that it is adjacent to entirely, or to a new clique.
+  fn repair_separator(&self, mut cliques: Vec<Vec<usize>>, separator: &[usize]) -> CliqueCover {
+    let mut clique_of = vec![None; self.size];
+    for (c, members) in cliques.iter().enumerate() {
+      for &v in members {
+        clique_of[v] = Some(c);
+      }
+    }
+    for &v in separator {
+      let neighbors_bv = &self.topology.vertices[v].neighbors_bv;
+      let joinable = (0..self.size)
+        .filter(|&u| neighbors_bv.get_unchecked(u))
+        .filter_map(|u| clique_of[u])
+        .filter(|&c| cliques[c].iter().all(|&u| neighbors_bv.get_unchecked(u)))
+        .max_by_key(|&c| cliques[c].len());
+      let c = joinable.unwrap_or_else(|| {
+        cliques.push(vec![]);
+        cliques.len() - 1
+      });
+      cliques[c].push(v);
+      clique_of[v] = Some(c);
+    }
+    CliqueCover { cliques }
+  }
+
+  // The BFS levels from start, marking what they reach as seen.
+  fn bfs_levels(&self, start: usize, seen: &mut [bool]) -> Vec<Vec<usize>> {
+    let mut levels = vec![vec![start]];
+    seen[start] = true;
+    loop {
+      let mut next = vec![];
+      for &u in levels.last().unwrap() {
+        for (v, seen) in seen.iter_mut().enumerate() {
+          if !*seen && self.topology.vertices[u].neighbors_bv.get_unchecked(v) {
+            *seen = true;
+            next.push(v);
+          }
+        }
+      }
+      if next.is_empty() {
+        return levels;
+      }
+      levels.push(next);
+    }
+  }
+
+  // Components go to whichever side is smaller, largest first.
+  fn split_components(&self) -> Separator {
+    let mut seen = vec![false; self.size];
+    let mut components: Vec<Vec<usize>> = vec![];
+    for v in 0..self.size {
+      if !seen[v] {
+        components.push(self.bfs_levels(v, &mut seen).concat());
+      }
+    }
+    components.sort_by_key(|component| core::cmp::Reverse(component.len()));
+    let (mut left, mut right) = (vec![], vec![]);
+    for component in components {
+      let side = if left.len() <= right.len() {
+        &mut left
+      } else {
+        &mut right
+      };
+      side.extend(component);
+    }
+    Separator {
+      left,
+      separator: vec![],
+      right,
+    }
+  }
+}
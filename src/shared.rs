@@ -0,0 +1,50 @@
+// Bounds shared between worker threads. Both only ever move towards each
+// other (the upper bound down, the lower bound up), so relaxed atomics are
+// enough: a worker that reads a stale value just stops a little later.
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+pub struct SharedBounds {
+  upper: AtomicUsize,
+  lower: AtomicUsize,
+}
+
+impl SharedBounds {
+  pub fn new() -> SharedBounds {
+    SharedBounds {
+      upper: AtomicUsize::new(usize::MAX),
+      lower: AtomicUsize::new(0),
+    }
+  }
+
+  // Best cover size found by any worker.
+  pub fn upper(&self) -> usize {
+    self.upper.load(Ordering::Relaxed)
+  }
+
+  // Largest proven lower bound on the cover size.
+  pub fn lower(&self) -> usize {
+    self.lower.load(Ordering::Relaxed)
+  }
+
+  // Returns true if cover_size improves the shared upper bound.
+  pub fn offer_upper(&self, cover_size: usize) -> bool {
+    self.upper.fetch_min(cover_size, Ordering::Relaxed) > cover_size
+  }
+
+  // Returns true if bound improves the shared lower bound.
+  pub fn offer_lower(&self, bound: usize) -> bool {
+    self.lower.fetch_max(bound, Ordering::Relaxed) < bound
+  }
+
+  // Some worker has reached the target, or the best cover is provably optimal.
+  pub fn is_done(&self, target: usize) -> bool {
+    self.upper() <= target.max(self.lower())
+  }
+}
+
+impl Default for SharedBounds {
+  fn default() -> SharedBounds {
+    SharedBounds::new()
+  }
+}
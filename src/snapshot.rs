@@ -0,0 +1,151 @@
+// Immutable snapshots of a cover for readers outside the search, such as
+// monitoring threads and between-restart callbacks. A snapshot holds only
+// each vertex's clique id, one word per vertex taken from the member lists,
+// never the cliques' bit vectors, and it sits behind an Arc so that
+// handing it to another reader is a pointer copy.
+//
+// With std, a SnapshotCell publishes the best snapshot so far. The solve
+// swaps in a new one only when its best cover improves, under a lock held
+// just long enough to compare sizes and swap the pointer; readers take the
+// same lock just long enough to clone the pointer. Neither side ever waits
+// on the other's copying.
+
+use crate::cover::CliqueCover;
+#[cfg(feature = "std")]
+use crate::error::VccError;
+#[cfg(feature = "std")]
+use crate::solve::{SolveParams, SolveResult};
+use crate::Graph;
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::sync::Mutex;
+
+#[derive(Clone)]
+pub struct CoverSnapshot {
+  clique_of: Arc<[usize]>,
+  cliques: usize,
+}
+
+impl CoverSnapshot {
+  // The number of cliques.
+  pub fn len(&self) -> usize {
+    self.cliques
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.cliques == 0
+  }
+
+  // The id of the clique holding v, counting the cliques from 0.
+  pub fn clique_of(&self, v: usize) -> usize {
+    self.clique_of[v]
+  }
+
+  pub fn to_cover(&self) -> CliqueCover {
+    let mut cliques = vec![vec![]; self.cliques];
+    for (v, &c) in self.clique_of.iter().enumerate() {
+      cliques[c].push(v);
+    }
+    CliqueCover { cliques }
+  }
+}
+
+// Vertices the cover leaves out (there are none in a valid cover) are put
+// in cliques of their own.
+impl From<&CliqueCover> for CoverSnapshot {
+  fn from(cover: &CliqueCover) -> CoverSnapshot {
+    let size = cover.cliques.iter().flatten().max().map_or(0, |&v| v + 1);
+    let mut clique_of = vec![usize::MAX; size];
+    for (c, members) in cover.cliques.iter().enumerate() {
+      for &v in members {
+        clique_of[v] = c;
+      }
+    }
+    let mut cliques = cover.len();
+    for c in clique_of.iter_mut().filter(|c| **c == usize::MAX) {
+      *c = cliques;
+      cliques += 1;
+    }
+    CoverSnapshot {
+      clique_of: clique_of.into(),
+      cliques,
+    }
+  }
+}
+
+impl Graph {
+  // The current cover, skipping empty cliques like Graph::cover does.
+  pub fn snapshot(&self) -> CoverSnapshot {
+    let mut clique_of: Vec<usize> = vec![0; self.size];
+    let mut cliques = 0;
+    for clique in &self.cliques[..self.cliques_ct] {
+      if clique.members_ct == 0 {
+        continue;
+      }
+      for &v in clique.members.iter() {
+        clique_of[v] = cliques;
+      }
+      cliques += 1;
+    }
+    CoverSnapshot {
+      clique_of: clique_of.into(),
+      cliques,
+    }
+  }
+}
+
+#[cfg(feature = "std")]
+#[derive(Default)]
+pub struct SnapshotCell {
+  best: Mutex<Option<Arc<CoverSnapshot>>>,
+}
+
+#[cfg(feature = "std")]
+impl SnapshotCell {
+  pub fn new() -> SnapshotCell {
+    SnapshotCell::default()
+  }
+
+  // Keeps snapshot if it has fewer cliques than the current one; returns
+  // whether it did.
+  pub fn offer(&self, snapshot: CoverSnapshot) -> bool {
+    let mut best = self.best.lock().unwrap();
+    if best
+      .as_ref()
+      .is_some_and(|best| best.len() <= snapshot.len())
+    {
+      return false;
+    }
+    *best = Some(Arc::new(snapshot));
+    true
+  }
+
+  // The best snapshot offered so far.
+  pub fn latest(&self) -> Option<Arc<CoverSnapshot>> {
+    self.best.lock().unwrap().clone()
+  }
+}
+
+#[cfg(feature = "std")]
+impl Graph {
+  // Solves as Graph::solve does, offering cell a snapshot of the starting
+  // cover and of every improvement on it, for other threads to read.
+  pub fn solve_with_snapshots(
+    &mut self,
+    params: &SolveParams,
+    cell: &SnapshotCell,
+  ) -> Result<SolveResult, VccError> {
+    cell.offer(self.snapshot());
+    let mut best = usize::MAX;
+    let result = self.resume_solve(params, None, None, &mut |_, progress| {
+      if progress.best_cover.len() < best {
+        best = progress.best_cover.len();
+        cell.offer((&progress.best_cover).into());
+      }
+    })?;
+    cell.offer((&result.best_cover).into());
+    Ok(result)
+  }
+}
@@ -0,0 +1,454 @@
+// Restart driver around vcc_run_restart: keeps the best cover
+// seen across restarts and stops at the target or when the budget runs out.
+//
+// Every restart starts from the initial cover chosen in the params, in
+// shuffled order, on a default generator seeded with its own restart seed,
+// derived from a base the solve draws once at the start from the graph's
+// generator. All of a restart's random choices, the initial order
+// included, come from that seed, so any single restart can be replayed in
+// isolation with Graph::replay_restart. The graph's generator is put back
+// after every restart, advanced only by the base's draw. With distinct
+// starts, a restart whose start repeats an earlier one is reshuffled or
+// skipped (see starts.rs).
+
+use crate::anneal::AnnealingSchedule;
+use crate::cadence::CheckCadence;
+use crate::clock::Instant;
+use crate::cover::CliqueCover;
+use crate::error::VccError;
+use crate::finish::ExactFinish;
+use crate::fractional::DEFAULT_MAX_CLIQUES;
+use crate::freeze::Freeze;
+use crate::order::CliqueOrder;
+use crate::rng::{default_rng, mix64};
+use crate::shared::SharedBounds;
+#[cfg(feature = "spectral")]
+use crate::spectral::SpectralMatrix;
+use crate::starts::{DistinctStarts, StartFilter};
+use crate::Graph;
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::mem;
+use core::str::FromStr;
+use core::time::Duration;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct SolveParams {
+  // Iterations per restart.
+  pub max_iterations: usize,
+  pub reverse_fraction: f64,
+  pub target: usize,
+  pub max_restarts: usize,
+  #[serde(default)]
+  pub annealing: AnnealingSchedule,
+  // If set, reverse_fraction is only the value at the start of each restart.
+  #[serde(default)]
+  pub reverse_decay: Option<ReverseDecay>,
+  // How cliques are ordered for merging when they aren't reversed.
+  #[serde(default)]
+  pub order: CliqueOrder,
+  // Cliques to lock after every merging pass.
+  #[serde(default)]
+  pub freeze: Option<Freeze>,
+  // What each restart starts from.
+  #[serde(default)]
+  pub initial: InitialCover,
+  // Whether stalls near the lower bound try an exact finish first.
+  #[serde(default)]
+  pub finish: Option<ExactFinish>,
+  // Whether restarts avoid starts already run (see starts.rs).
+  #[serde(default)]
+  pub distinct_starts: Option<DistinctStarts>,
+  // How often the target is checked between improvements (see cadence.rs).
+  #[serde(default)]
+  pub check: CheckCadence,
+}
+
+// Singletons leave all the merging to iterated greedy; a sequential
+// coloring of the complement (see bounds.rs) does the easy merges up front
+// in a fraction of the time. Fractional rounds the fractional cover (see
+// fractional.rs), which costs an LP per restart but starts from the
+// cliques the LP prefers. Spectral clusters a spectral embedding of the
+// adjacency or modularity matrix into candidate groups (see spectral.rs),
+// with the spectral feature.
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InitialCover {
+  Singletons,
+  #[default]
+  Sequential,
+  Fractional,
+  #[cfg(feature = "spectral")]
+  Spectral,
+  #[cfg(feature = "spectral")]
+  #[serde(rename = "spectral-modularity")]
+  SpectralModularity,
+}
+
+// "singletons", "sequential" or "fractional"; with the spectral feature,
+// also "spectral" or "spectral-modularity"
+impl FromStr for InitialCover {
+  type Err = VccError;
+
+  fn from_str(initial_str: &str) -> Result<InitialCover, VccError> {
+    match initial_str {
+      "singletons" => Ok(InitialCover::Singletons),
+      "sequential" => Ok(InitialCover::Sequential),
+      "fractional" => Ok(InitialCover::Fractional),
+      #[cfg(feature = "spectral")]
+      "spectral" => Ok(InitialCover::Spectral),
+      #[cfg(feature = "spectral")]
+      "spectral-modularity" => Ok(InitialCover::SpectralModularity),
+      _ => Err(VccError::Parse(format!(
+        "bad initial cover: {:?}",
+        initial_str
+      ))),
+    }
+  }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DecayKind {
+  Linear,
+  Geometric,
+}
+
+// Moves reverse_fraction from its starting value to `end` over the
+// iterations of each restart, so a run explores early and exploits late.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct ReverseDecay {
+  pub kind: DecayKind,
+  pub end: f64,
+}
+
+// "linear:0.05" or "geometric:0.01"
+impl FromStr for ReverseDecay {
+  type Err = VccError;
+
+  fn from_str(decay_str: &str) -> Result<ReverseDecay, VccError> {
+    let bad = || VccError::Parse(format!("bad reverse decay: {:?}", decay_str));
+    let (kind, end) = decay_str.split_once(':').ok_or_else(bad)?;
+    let kind = match kind {
+      "linear" => DecayKind::Linear,
+      "geometric" => DecayKind::Geometric,
+      _ => return Err(bad()),
+    };
+    Ok(ReverseDecay {
+      kind,
+      end: end.parse().map_err(|_| bad())?,
+    })
+  }
+}
+
+// The CLI's defaults: 10 restarts of 100,000 iterations each from the
+// sequential cover, no reversals and no target. Callers usually set the
+// target and a few options over it.
+impl Default for SolveParams {
+  fn default() -> SolveParams {
+    SolveParams {
+      max_iterations: 100_000,
+      reverse_fraction: 0.0,
+      target: 0,
+      max_restarts: 10,
+      annealing: AnnealingSchedule::default(),
+      reverse_decay: None,
+      order: CliqueOrder::Shuffle,
+      freeze: None,
+      initial: InitialCover::Sequential,
+      finish: None,
+      distinct_starts: None,
+      check: CheckCadence::default(),
+    }
+  }
+}
+
+impl SolveParams {
+  pub fn validate(&self) -> Result<(), VccError> {
+    if !(0.0..=1.0).contains(&self.reverse_fraction) {
+      return Err(VccError::InvalidParameter(format!(
+        "reverse fraction {} is not in [0, 1]",
+        self.reverse_fraction
+      )));
+    }
+    if let Some(decay) = self.reverse_decay {
+      if !(0.0..=1.0).contains(&decay.end) {
+        return Err(VccError::InvalidParameter(format!(
+          "final reverse fraction {} is not in [0, 1]",
+          decay.end
+        )));
+      }
+      if let DecayKind::Geometric = decay.kind {
+        // Its powers need std's float functions.
+        if cfg!(not(feature = "std")) {
+          return Err(VccError::InvalidParameter(
+            "geometric decay needs the std feature".to_string(),
+          ));
+        }
+        if self.reverse_fraction == 0.0 || decay.end == 0.0 {
+          return Err(VccError::InvalidParameter(
+            "geometric decay needs positive start and end reverse fractions".to_string(),
+          ));
+        }
+      }
+    }
+    self.check.validate()?;
+    self.annealing.validate()
+  }
+
+  // The reverse fraction for iteration i (counting from 1) of a restart.
+  pub fn reverse_fraction_at(&self, i: usize) -> f64 {
+    let Some(decay) = self.reverse_decay else {
+      return self.reverse_fraction;
+    };
+    let t = i as f64 / self.max_iterations.max(1) as f64;
+    let start = self.reverse_fraction;
+    match decay.kind {
+      DecayKind::Linear => start + (decay.end - start) * t,
+      #[cfg(feature = "std")]
+      DecayKind::Geometric => start * (decay.end / start).powf(t),
+      #[cfg(not(feature = "std"))]
+      DecayKind::Geometric => unreachable!("validate rejects geometric decay without std"),
+    }
+  }
+}
+
+pub struct RestartRecord {
+  // Counting from 1.
+  pub restart: usize,
+  pub seed: u64,
+  pub cliques: usize,
+  pub reached_target: bool,
+  // Whether the start was a repeat and the restart wasn't run.
+  pub skipped: bool,
+}
+
+pub struct SolveResult {
+  pub best_cover: CliqueCover,
+  pub reached_target: bool,
+  pub restarts: usize,
+  // Search iterations run by this call.
+  pub iterations: usize,
+  pub elapsed: Duration,
+  // One record per restart run by this call (a resumed solve doesn't
+  // repeat the earlier ones).
+  pub restart_log: Vec<RestartRecord>,
+}
+
+// Where a solve stands between two restarts.
+pub struct SolveProgress {
+  pub restarts: usize,
+  pub best_cover: CliqueCover,
+  pub restart_seed_base: u64,
+}
+
+// CSV with one line per restart, to find the restart worth replaying.
+pub fn format_restart_log(log: &[RestartRecord]) -> String {
+  let mut out = "restart,seed,cliques,reached_target,skipped\n".to_string();
+  for record in log {
+    out.push_str(&format!(
+      "{},{},{},{},{}\n",
+      record.restart, record.seed, record.cliques, record.reached_target, record.skipped
+    ));
+  }
+  out
+}
+
+pub fn restart_seed(base: u64, restart: usize) -> u64 {
+  mix64(base.wrapping_add(restart as u64))
+}
+
+impl Graph {
+  pub fn solve(&mut self, params: &SolveParams) -> Result<SolveResult, VccError> {
+    self.solve_with_bounds(params, None)
+  }
+
+  // Stops early once the shared bounds show the search is done.
+  pub fn solve_with_bounds(
+    &mut self,
+    params: &SolveParams,
+    bounds: Option<&SharedBounds>,
+  ) -> Result<SolveResult, VccError> {
+    self.resume_solve(params, bounds, None, &mut |_, _| {})
+  }
+
+  // The general driver. between_restarts is called after every restart that
+  // doesn't end the solve; that is the moment a checkpoint captures. To
+  // resume from one, pass its progress.
+  pub fn resume_solve(
+    &mut self,
+    params: &SolveParams,
+    bounds: Option<&SharedBounds>,
+    progress: Option<SolveProgress>,
+    between_restarts: &mut dyn FnMut(&Graph, &SolveProgress),
+  ) -> Result<SolveResult, VccError> {
+    params.validate()?;
+    let start = Instant::now();
+    let iterations = self.iterations;
+    let (mut restarts, mut best_cover, restart_seed_base) = match progress {
+      Some(progress) => (
+        progress.restarts,
+        progress.best_cover,
+        progress.restart_seed_base,
+      ),
+      None => (0, self.cover(), self.rng.next_u64()),
+    };
+    let mut reached_target = false;
+    let mut restart_log = vec![];
+    let mut starts = self.start_filter_after(params, restart_seed_base, restarts);
+    while restarts < params.max_restarts {
+      restarts += 1;
+      let seed = restart_seed(restart_seed_base, restarts);
+      let outcome = self.run_seeded_restart(params, bounds, seed, starts.as_mut());
+      reached_target = outcome == Some(true);
+      restart_log.push(RestartRecord {
+        restart: restarts,
+        seed,
+        cliques: self.cliques_ct,
+        reached_target,
+        skipped: outcome.is_none(),
+      });
+      if self.cliques_ct < best_cover.len() {
+        best_cover = self.cover();
+      }
+      if reached_target || bounds.is_some_and(|b| b.is_done(params.target)) {
+        break;
+      }
+      if restarts < params.max_restarts {
+        let progress = SolveProgress {
+          restarts,
+          best_cover: best_cover.clone(),
+          restart_seed_base,
+        };
+        between_restarts(self, &progress);
+      }
+    }
+    Ok(SolveResult {
+      best_cover,
+      reached_target,
+      restarts,
+      iterations: self.iterations - iterations,
+      elapsed: start.elapsed(),
+      restart_log,
+    })
+  }
+
+  // Whether the restart reached the target, or None if it was skipped as
+  // a repeat of a start in starts.
+  fn run_seeded_restart(
+    &mut self,
+    params: &SolveParams,
+    bounds: Option<&SharedBounds>,
+    seed: u64,
+    starts: Option<&mut StartFilter>,
+  ) -> Option<bool> {
+    self.with_restart_rng(seed, |g| {
+      if !g.prepare_distinct_restart(params, starts) {
+        return None;
+      }
+      if let Some(bounds) = bounds {
+        bounds.offer_upper(g.cliques_ct);
+      }
+      Some(g.vcc_run_restart(params, bounds))
+    })
+  }
+
+  // Runs f on the restart generator for seed, then puts the graph's own
+  // generator back as it was.
+  fn with_restart_rng<T>(&mut self, seed: u64, f: impl FnOnce(&mut Graph) -> T) -> T {
+    let own = mem::replace(&mut self.rng, Box::new(default_rng(seed)));
+    let ret = f(self);
+    self.rng = own;
+    ret
+  }
+
+  // prepare_restart, then reshuffles while starts has seen the start, up
+  // to the params' perturbations. False if the start is still a repeat.
+  fn prepare_distinct_restart(
+    &mut self,
+    params: &SolveParams,
+    starts: Option<&mut StartFilter>,
+  ) -> bool {
+    self.prepare_restart(params);
+    let (Some(starts), Some(distinct)) = (starts, params.distinct_starts) else {
+      return true;
+    };
+    for _ in 0..distinct.perturbations {
+      if starts.insert(self.start_hash()) {
+        return true;
+      }
+      self.shuffle_active_cliques();
+    }
+    starts.insert(self.start_hash())
+  }
+
+  // The filter of a solve with distinct starts after its first `restarts`
+  // restarts, rebuilt from their seeds; None without distinct starts.
+  fn start_filter_after(
+    &mut self,
+    params: &SolveParams,
+    restart_seed_base: u64,
+    restarts: usize,
+  ) -> Option<StartFilter> {
+    let mut starts = StartFilter::new(params.distinct_starts?.bits);
+    for restart in 1..=restarts {
+      self.with_restart_rng(restart_seed(restart_seed_base, restart), |g| {
+        g.prepare_distinct_restart(params, Some(&mut starts))
+      });
+    }
+    Some(starts)
+  }
+
+  // Sets the cliques to the params' initial cover, in random order; to
+  // singletons instead if that cover breaks a forbidden set.
+  pub fn prepare_restart(&mut self, params: &SolveParams) {
+    let cover = match params.initial {
+      InitialCover::Singletons => None,
+      InitialCover::Sequential => Some(self.sequential_cover()),
+      InitialCover::Fractional => Some(
+        self
+          .fractional_cover(DEFAULT_MAX_CLIQUES)
+          .round(&mut self.rng),
+      ),
+      #[cfg(feature = "spectral")]
+      InitialCover::Spectral => Some(self.spectral_cover(SpectralMatrix::Adjacency)),
+      #[cfg(feature = "spectral")]
+      InitialCover::SpectralModularity => Some(self.spectral_cover(SpectralMatrix::Modularity)),
+    };
+    match cover.filter(|cover| self.keeps_limits(&cover.cliques)) {
+      Some(cover) => self.load_cover_unchecked(&cover),
+      None => self.conform_cliques_to_vertices(),
+    }
+    self.shuffle_active_cliques();
+  }
+
+  // Reruns restart `restart` of the solve that the graph would run with
+  // these params, or of the solve progress was taken from: the graph must
+  // have the generator state it had when that solve began. Leaves the
+  // restart's final cover in the graph.
+  pub fn replay_restart(
+    &mut self,
+    params: &SolveParams,
+    progress: Option<&SolveProgress>,
+    restart: usize,
+  ) -> Result<RestartRecord, VccError> {
+    params.validate()?;
+    let base = match progress {
+      Some(progress) => progress.restart_seed_base,
+      None => self.rng.next_u64(),
+    };
+    let seed = restart_seed(base, restart);
+    let mut starts = self.start_filter_after(params, base, restart.saturating_sub(1));
+    let outcome = self.run_seeded_restart(params, None, seed, starts.as_mut());
+    Ok(RestartRecord {
+      restart,
+      seed,
+      cliques: self.cliques_ct,
+      reached_target: outcome == Some(true),
+      skipped: outcome.is_none(),
+    })
+  }
+}
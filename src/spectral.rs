@@ -0,0 +1,192 @@
+// Spectral initial covers. Vertices are embedded by a few leading
+// eigenvectors of the adjacency matrix, or of the modularity matrix
+// A - d d^T / 2m, which discounts the edges expected from degrees alone.
+// Vertices of a dense cluster, and so of a big clique, get nearby rows in
+// the embedding. The rows are normalized and clustered by k-means into
+// candidate groups, about as many as a cover of cliques of average degree
+// size would have. Each group is then cleaned up greedily into cliques,
+// its vertices closest to its center first, the way sequential_cover
+// does (see bounds.rs); iterated greedy merges across groups from there.
+//
+// The eigensolver is subspace iteration with Gram-Schmidt, on the matrix
+// shifted by a bound on its norm so that the leading eigenvalues are the
+// largest in magnitude, for a fixed number of products. It only needs to
+// separate the clusters, not to converge.
+
+use crate::bitset::BitVec;
+use crate::cover::CliqueCover;
+use crate::rng::{shuffle, unit_f64};
+use crate::Graph;
+use alloc::vec;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+const DIMENSIONS: usize = 4;
+const PRODUCTS: usize = 40;
+const KMEANS_ROUNDS: usize = 10;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SpectralMatrix {
+  Adjacency,
+  Modularity,
+}
+
+impl Graph {
+  // A row of DIMENSIONS coordinates for each vertex, of unit length
+  // (or zero, for isolated vertices under the modularity matrix).
+  pub fn spectral_embedding(&mut self, matrix: SpectralMatrix) -> Vec<Vec<f64>> {
+    let n = self.size;
+    let adjacency: Vec<Vec<usize>> = (0..n)
+      .map(|u| {
+        (0..n)
+          .filter(|&v| self.topology.vertices[u].neighbors_bv.get_unchecked(v))
+          .collect()
+      })
+      .collect();
+    let degrees: Vec<f64> = adjacency.iter().map(|row| row.len() as f64).collect();
+    let twice_edges: f64 = degrees.iter().sum();
+    let max_degree = degrees.iter().copied().fold(0.0, f64::max);
+    let shift = match matrix {
+      SpectralMatrix::Adjacency => max_degree,
+      SpectralMatrix::Modularity => 2.0 * max_degree,
+    };
+    let product = |x: &[f64]| -> Vec<f64> {
+      let projection = match matrix {
+        SpectralMatrix::Modularity if twice_edges > 0.0 => dot(&degrees, x) / twice_edges,
+        _ => 0.0,
+      };
+      (0..n)
+        .map(|u| {
+          let a: f64 = adjacency[u].iter().map(|&v| x[v]).sum();
+          a - degrees[u] * projection + shift * x[u]
+        })
+        .collect()
+    };
+
+    let dims = DIMENSIONS.min(n);
+    let mut basis: Vec<Vec<f64>> = (0..dims)
+      .map(|_| (0..n).map(|_| unit_f64(&mut self.rng) - 0.5).collect())
+      .collect();
+    orthonormalize(&mut basis);
+    for _ in 0..PRODUCTS {
+      basis = basis.iter().map(|x| product(x)).collect();
+      orthonormalize(&mut basis);
+    }
+
+    (0..n)
+      .map(|v| {
+        let mut row: Vec<f64> = basis.iter().map(|x| x[v]).collect();
+        normalize(&mut row);
+        row
+      })
+      .collect()
+  }
+
+  pub fn spectral_cover(&mut self, matrix: SpectralMatrix) -> CliqueCover {
+    let n = self.size;
+    if n == 0 {
+      return CliqueCover { cliques: vec![] };
+    }
+    let embedding = self.spectral_embedding(matrix);
+    let mean_degree = (0..n).map(|v| self.degree(v)).sum::<usize>() / n;
+    let groups = n.div_ceil(mean_degree + 1);
+    let (centers, group_of) = self.kmeans(&embedding, groups);
+
+    let mut members: Vec<Vec<usize>> = vec![vec![]; centers.len()];
+    for v in 0..n {
+      members[group_of[v]].push(v);
+    }
+    let mut cliques: Vec<Vec<usize>> = vec![];
+    for (center, group) in centers.iter().zip(&mut members) {
+      group.sort_by(|&a, &b| dot(&embedding[b], center).total_cmp(&dot(&embedding[a], center)));
+      // joinable[c]: the common neighbors of the group's clique c.
+      let first = cliques.len();
+      let mut joinable: Vec<BitVec> = vec![];
+      for &v in group.iter() {
+        let neighbors_bv = &self.topology.vertices[v].neighbors_bv;
+        match (0..joinable.len()).find(|&c| joinable[c].get_unchecked(v)) {
+          Some(c) => {
+            cliques[first + c].push(v);
+            joinable[c].and_inplace(neighbors_bv);
+          }
+          None => {
+            cliques.push(vec![v]);
+            joinable.push(neighbors_bv.clone());
+          }
+        }
+      }
+    }
+    CliqueCover { cliques }
+  }
+
+  // Lloyd's algorithm from distinct random rows, by cosine similarity.
+  fn kmeans(&mut self, rows: &[Vec<f64>], k: usize) -> (Vec<Vec<f64>>, Vec<usize>) {
+    let mut starts: Vec<usize> = (0..rows.len()).collect();
+    shuffle(&mut self.rng, &mut starts);
+    let mut centers: Vec<Vec<f64>> = starts[..k.min(rows.len())]
+      .iter()
+      .map(|&v| rows[v].clone())
+      .collect();
+    let mut group_of = vec![0; rows.len()];
+    for _ in 0..KMEANS_ROUNDS {
+      for (row, group) in rows.iter().zip(&mut group_of) {
+        *group = (0..centers.len())
+          .max_by(|&a, &b| dot(row, &centers[a]).total_cmp(&dot(row, &centers[b])))
+          .unwrap();
+      }
+      for center in &mut centers {
+        center.iter_mut().for_each(|x| *x = 0.0);
+      }
+      for (row, &group) in rows.iter().zip(&group_of) {
+        for (x, y) in centers[group].iter_mut().zip(row) {
+          *x += y;
+        }
+      }
+      for center in &mut centers {
+        normalize(center);
+      }
+    }
+    (centers, group_of)
+  }
+}
+
+fn dot(x: &[f64], y: &[f64]) -> f64 {
+  x.iter().zip(y).map(|(a, b)| a * b).sum()
+}
+
+fn normalize(x: &mut [f64]) {
+  let norm = sqrt(dot(x, x));
+  if norm > 1e-12 {
+    x.iter_mut().for_each(|a| *a /= norm);
+  }
+}
+
+// Modified Gram-Schmidt; a vector that falls into the span of the ones
+// before it is left near zero.
+fn orthonormalize(basis: &mut [Vec<f64>]) {
+  for i in 0..basis.len() {
+    let (done, rest) = basis.split_at_mut(i);
+    let x = &mut rest[0];
+    for y in done.iter() {
+      let p = dot(x, y);
+      x.iter_mut().zip(y).for_each(|(a, b)| *a -= p * b);
+    }
+    normalize(x);
+  }
+}
+
+// Newton's method, as f64::sqrt needs std.
+fn sqrt(x: f64) -> f64 {
+  if x <= 0.0 {
+    return 0.0;
+  }
+  let mut r = if x > 1.0 { x } else { 1.0 };
+  loop {
+    let next = 0.5 * (r + x / r);
+    if next >= r {
+      return r;
+    }
+    r = next;
+  }
+}
@@ -0,0 +1,101 @@
+// Duplicate-restart avoidance. On small instances there are few distinct
+// starts: a sequential cover of a dozen cliques has only so many orders,
+// and long runs draw the same one again and again. With distinct starts
+// on, each restart's start is hashed into a Bloom filter of the given
+// number of bits: its cliques in order, each as a set of members, as the
+// order within a clique hardly matters to the search. A start the filter
+// has seen is reshuffled, up to perturbations times, and the restart is
+// skipped if every shuffle was seen too.
+//
+// The filter is bounded, so it forgets nothing but fills up: once most of
+// its bits are set, fresh starts are taken for repeats and perturbed for
+// no reason. That costs a shuffle, never correctness. A start depends on
+// its restart's seed alone, so a replayed or resumed restart rebuilds the
+// filter from the seeds of the restarts before it and sees what the
+// original run saw.
+
+use crate::error::VccError;
+use crate::rng::mix64;
+use crate::Graph;
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::str::FromStr;
+use serde::{Deserialize, Serialize};
+
+const HASHES: u64 = 3;
+
+// Fields missing from a serialized config take their default values.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DistinctStarts {
+  pub bits: usize,
+  pub perturbations: usize,
+}
+
+impl Default for DistinctStarts {
+  fn default() -> DistinctStarts {
+    DistinctStarts {
+      bits: 1 << 16,
+      perturbations: 3,
+    }
+  }
+}
+
+// "on" for the defaults, or "<bits>:<perturbations>"
+impl FromStr for DistinctStarts {
+  type Err = VccError;
+
+  fn from_str(starts_str: &str) -> Result<DistinctStarts, VccError> {
+    if starts_str == "on" {
+      return Ok(DistinctStarts::default());
+    }
+    let bad = || VccError::Parse(format!("bad distinct starts: {:?}", starts_str));
+    let (bits, perturbations) = starts_str.split_once(':').ok_or_else(bad)?;
+    Ok(DistinctStarts {
+      bits: bits.replace('_', "").parse().map_err(|_| bad())?,
+      perturbations: perturbations.parse().map_err(|_| bad())?,
+    })
+  }
+}
+
+// A Bloom filter of start hashes, rounded up to whole words.
+pub struct StartFilter {
+  words: Vec<u64>,
+}
+
+impl StartFilter {
+  pub fn new(bits: usize) -> StartFilter {
+    StartFilter {
+      words: vec![0; bits.div_ceil(64).max(1)],
+    }
+  }
+
+  // Adds hash; returns false if it was (probably) there already.
+  pub fn insert(&mut self, hash: u64) -> bool {
+    let bits = self.words.len() as u64 * 64;
+    let step = mix64(hash) | 1;
+    let mut fresh = false;
+    for i in 0..HASHES {
+      let bit = (hash.wrapping_add(i.wrapping_mul(step)) % bits) as usize;
+      let mask = 1 << (bit % 64);
+      fresh |= self.words[bit / 64] & mask == 0;
+      self.words[bit / 64] |= mask;
+    }
+    fresh
+  }
+}
+
+impl Graph {
+  // A hash of the active cliques in order, each hashed as a set.
+  pub fn start_hash(&self) -> u64 {
+    let mut hash = 0;
+    for clique in &self.cliques[..self.cliques_ct] {
+      let members = clique
+        .members
+        .iter()
+        .fold(0u64, |h, &v| h.wrapping_add(mix64(v as u64)));
+      hash = mix64(hash ^ members);
+    }
+    hash
+  }
+}
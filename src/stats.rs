@@ -0,0 +1,104 @@
+// Quality statistics of a cover beyond its size: how its cliques' sizes
+// are spread, how many vertices are left alone, and how dense its groups
+// are. A valid cover's groups are cliques, of density one; the density is
+// there for covers that only approximate cliques, like imported answers
+// before they are checked, or cliques relaxed by constraints.
+
+use crate::cover::CliqueCover;
+use crate::Graph;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use serde::Serialize;
+
+#[derive(Clone, Serialize)]
+pub struct CoverStats {
+  pub cliques: usize,
+  // Vertices in some clique.
+  pub covered: usize,
+  pub singletons: usize,
+  pub largest: usize,
+  pub mean_size: f64,
+  // (size, cliques of that size), by increasing size.
+  pub histogram: Vec<(usize, usize)>,
+  // Adjacent pairs over all pairs within the cliques, one without pairs.
+  pub density: f64,
+  // The sparsest clique's density.
+  pub min_density: f64,
+}
+
+impl fmt::Display for CoverStats {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "cover: {} cliques over {} vertices, {} singletons, largest {}, \
+       mean {:.2}, density {:.3} (min {:.3}); sizes",
+      self.cliques,
+      self.covered,
+      self.singletons,
+      self.largest,
+      self.mean_size,
+      self.density,
+      self.min_density
+    )?;
+    for (size, count) in &self.histogram {
+      write!(f, " {}x{}", count, size)?;
+    }
+    Ok(())
+  }
+}
+
+impl Graph {
+  // Quadratic in the clique sizes.
+  pub fn cover_stats(&self, cover: &CliqueCover) -> CoverStats {
+    let sizes: Vec<usize> = cover.cliques.iter().map(Vec::len).collect();
+    let covered: usize = sizes.iter().sum();
+    let largest = sizes.iter().copied().max().unwrap_or(0);
+    let mut counts = vec![0; largest + 1];
+    for &size in &sizes {
+      counts[size] += 1;
+    }
+    let histogram = counts
+      .into_iter()
+      .enumerate()
+      .filter(|&(_, count)| count > 0)
+      .collect();
+
+    let (mut edges, mut pairs) = (0, 0);
+    let mut min_density: f64 = 1.0;
+    for members in &cover.cliques {
+      let mut clique_edges = 0;
+      for (i, &u) in members.iter().enumerate() {
+        clique_edges += members[i + 1..]
+          .iter()
+          .filter(|&&v| self.topology.vertices[u].neighbors_bv.get_unchecked(v))
+          .count();
+      }
+      let clique_pairs = members.len() * members.len().saturating_sub(1) / 2;
+      if clique_pairs > 0 {
+        min_density = min_density.min(clique_edges as f64 / clique_pairs as f64);
+      }
+      edges += clique_edges;
+      pairs += clique_pairs;
+    }
+
+    CoverStats {
+      cliques: cover.len(),
+      covered,
+      singletons: sizes.iter().filter(|&&size| size == 1).count(),
+      largest,
+      mean_size: if sizes.is_empty() {
+        0.0
+      } else {
+        covered as f64 / sizes.len() as f64
+      },
+      histogram,
+      density: if pairs == 0 {
+        1.0
+      } else {
+        edges as f64 / pairs as f64
+      },
+      min_density,
+    }
+  }
+}
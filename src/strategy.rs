@@ -0,0 +1,30 @@
+// A search strategy improves the cover held in a graph's search state one
+// step at a time. Strategies are interchangeable so that drivers such as the
+// portfolio can hand the incumbent from one to the next.
+
+use crate::Graph;
+
+pub trait SearchStrategy: Send {
+  fn name(&self) -> &'static str;
+
+  // Called whenever the strategy takes over; g holds the incumbent.
+  fn start(&mut self, _g: &mut Graph) {}
+
+  // One unit of work. Afterwards g's active cliques must still be a valid
+  // cover, and should be no larger than before.
+  fn step(&mut self, g: &mut Graph);
+}
+
+pub struct IteratedGreedy {
+  pub reverse_fraction: f64,
+}
+
+impl SearchStrategy for IteratedGreedy {
+  fn name(&self) -> &'static str {
+    "iterated greedy"
+  }
+
+  fn step(&mut self, g: &mut Graph) {
+    g.vcc_iterated_greedy(self.reverse_fraction);
+  }
+}
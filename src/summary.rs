@@ -0,0 +1,118 @@
+// The block a solve ends with, whether or not it reached its target, so
+// that a run which spent its whole budget still says what it found and
+// how far that may be from the best possible: the instance, the best
+// cover against the target and a lower bound, and the work done.
+//
+//   instance     200 vertices, 9,950 edges, density 0.500
+//   best cover   12 cliques, target 10 not reached
+//   lower bound  9 (greedy independent set), gap 3 (33.3%)
+//   search       4,000,000 iterations over 10 restarts
+//   time         12.5s, 320,000 iterations/s, 0.80 restarts/s
+//
+// The gap is the cover's excess over the bound, also as a percentage of
+// the bound. The bound is a greedy independent set's size, so the gap is
+// an upper limit on how far the cover is from optimal, not the distance.
+
+use crate::cover::CliqueCover;
+use crate::separated;
+use crate::solve::SolveResult;
+use crate::Graph;
+use std::fmt;
+use std::time::Duration;
+
+pub struct RunSummary {
+  pub vertices: usize,
+  pub edges: usize,
+  pub density: f64,
+  pub best: usize,
+  pub target: usize,
+  pub lower_bound: usize,
+  pub iterations: usize,
+  pub restarts: usize,
+  pub elapsed: Duration,
+}
+
+impl Graph {
+  // Summarizes a solve of this graph, best being its answer, which may be
+  // result's cover lifted from a reduced graph.
+  pub fn run_summary(
+    &mut self,
+    best: &CliqueCover,
+    target: usize,
+    result: &SolveResult,
+  ) -> RunSummary {
+    RunSummary {
+      vertices: self.size,
+      edges: self.edge_count(),
+      density: self.density(),
+      best: best.len(),
+      target,
+      lower_bound: self.greedy_independent_set_bound(),
+      iterations: result.iterations,
+      restarts: result.restarts,
+      elapsed: result.elapsed,
+    }
+  }
+}
+
+impl RunSummary {
+  // Cliques the best cover may have over an optimal one.
+  pub fn gap(&self) -> usize {
+    self.best.saturating_sub(self.lower_bound)
+  }
+
+  pub fn reached_target(&self) -> bool {
+    self.best <= self.target
+  }
+}
+
+impl fmt::Display for RunSummary {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let seconds = self.elapsed.as_secs_f64();
+    writeln!(
+      f,
+      "instance     {} vertices, {} edges, density {:.3}",
+      separated(self.vertices),
+      separated(self.edges),
+      self.density
+    )?;
+    writeln!(
+      f,
+      "best cover   {} cliques, target {} {}",
+      self.best,
+      self.target,
+      if self.reached_target() {
+        "reached"
+      } else {
+        "not reached"
+      }
+    )?;
+    write!(
+      f,
+      "lower bound  {} (greedy independent set), ",
+      self.lower_bound
+    )?;
+    match self.gap() {
+      0 => writeln!(f, "proven optimal")?,
+      gap => writeln!(
+        f,
+        "gap {} ({:.1}%)",
+        gap,
+        100.0 * gap as f64 / self.lower_bound.max(1) as f64
+      )?,
+    }
+    writeln!(
+      f,
+      "search       {} iterations over {} restarts",
+      separated(self.iterations),
+      separated(self.restarts)
+    )?;
+    write!(
+      f,
+      "time         {:.1?}, {} iterations/s, {:.2} restarts/s",
+      self.elapsed,
+      separated((self.iterations as f64 / seconds) as usize),
+      self.restarts as f64 / seconds
+    )
+  }
+}
@@ -0,0 +1,303 @@
+// Tabu search for a cover with a fixed number of cliques, in the style of
+// TabuCol run on the complement graph: every vertex is assigned one of k
+// clique labels, and a conflict is a pair of non-adjacent vertices sharing
+// a label. Each step moves one conflicting vertex to the label that removes
+// the most conflicts, ignoring moves that were undone recently. When the
+// conflicts reach zero the assignment is a cover with k cliques; it is
+// written back to the graph and the search continues with k - 1.
+//
+// The tenure can be made reactive: it grows whenever the search revisits
+// an assignment it has already seen at this k, and shrinks when the
+// fewest conflicts seen haven't improved for a while. With aspiration, a
+// tabu move is still taken if it leaves fewer conflicts than ever seen at
+// this k.
+
+use crate::cover::CliqueCover;
+use crate::error::VccError;
+use crate::rng::{below, coin, mix64};
+use crate::strategy::SearchStrategy;
+use crate::Graph;
+use std::collections::HashSet;
+use std::str::FromStr;
+
+#[derive(Clone, Copy)]
+pub enum TenurePolicy {
+  Fixed,
+  // The tenure is multiplied by 1 + increase on each revisited assignment,
+  // and by 1 - decrease (down to 1) after every window steps without
+  // fewer conflicts.
+  Reactive {
+    increase: f64,
+    decrease: f64,
+    window: usize,
+  },
+}
+
+// "fixed", "reactive" or "reactive:<increase>:<decrease>:<window>"
+impl FromStr for TenurePolicy {
+  type Err = VccError;
+
+  fn from_str(policy_str: &str) -> Result<TenurePolicy, VccError> {
+    let bad = || VccError::Parse(format!("bad tenure policy: {:?}", policy_str));
+    let mut parts = policy_str.split(':');
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+      (Some("fixed"), None, _, _) => Ok(TenurePolicy::Fixed),
+      (Some("reactive"), None, _, _) => Ok(TenurePolicy::Reactive {
+        increase: 0.2,
+        decrease: 0.1,
+        window: 1_000,
+      }),
+      (Some("reactive"), Some(increase), Some(decrease), Some(window))
+        if parts.next().is_none() =>
+      {
+        let increase: f64 = increase.parse().map_err(|_| bad())?;
+        let decrease: f64 = decrease.parse().map_err(|_| bad())?;
+        let window: usize = window.replace('_', "").parse().map_err(|_| bad())?;
+        let valid = increase >= 0.0 && increase.is_finite() && (0.0..1.0).contains(&decrease);
+        if !valid || window == 0 {
+          return Err(bad());
+        }
+        Ok(TenurePolicy::Reactive {
+          increase,
+          decrease,
+          window,
+        })
+      }
+      _ => Err(bad()),
+    }
+  }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Aspiration {
+  Off,
+  // Tabu moves are allowed if they beat the fewest conflicts seen.
+  NewBest,
+}
+
+// "off" or "new-best"
+impl FromStr for Aspiration {
+  type Err = VccError;
+
+  fn from_str(aspiration_str: &str) -> Result<Aspiration, VccError> {
+    match aspiration_str {
+      "off" => Ok(Aspiration::Off),
+      "new-best" => Ok(Aspiration::NewBest),
+      _ => Err(VccError::Parse(format!(
+        "bad aspiration: {:?}",
+        aspiration_str
+      ))),
+    }
+  }
+}
+
+// A fixed pseudo-random key for vertex v having label c; an assignment's
+// hash is the xor of its keys.
+fn zobrist(v: usize, c: usize) -> u64 {
+  mix64((v as u64) << 32 | c as u64)
+}
+
+const MAX_SEEN: usize = 1 << 20;
+
+pub struct Tabu {
+  // A vertex that leaves a label may not return to it for
+  // tenure + random(0..10) + 0.6 * (conflicting vertices) steps, where
+  // tenure starts at base_tenure and only changes if the policy is reactive.
+  pub base_tenure: usize,
+  pub tenure_policy: TenurePolicy,
+  pub aspiration: Aspiration,
+  tenure: f64,
+  k: usize,
+  labels: Vec<usize>,
+  // label_conflicts[v * k + c] = number of non-neighbors of v labelled c
+  label_conflicts: Vec<usize>,
+  tabu_until: Vec<usize>,
+  conflicts: usize,
+  iteration: usize,
+  best_conflicts: usize,
+  last_improvement: usize,
+  hash: u64,
+  seen: HashSet<u64>,
+}
+
+impl Tabu {
+  pub fn new(base_tenure: usize) -> Tabu {
+    Tabu {
+      base_tenure,
+      tenure_policy: TenurePolicy::Fixed,
+      aspiration: Aspiration::Off,
+      tenure: base_tenure as f64,
+      k: 0,
+      labels: vec![],
+      label_conflicts: vec![],
+      tabu_until: vec![],
+      conflicts: 0,
+      iteration: 0,
+      best_conflicts: 0,
+      last_improvement: 0,
+      hash: 0,
+      seen: HashSet::new(),
+    }
+  }
+
+  // Labels vertices with the cliques of g's cover, then folds the last
+  // clique into the others to aim for one clique fewer.
+  fn reset_from(&mut self, g: &Graph) {
+    let cover = g.cover();
+    self.k = cover.len().saturating_sub(1).max(1);
+    self.labels = vec![0; g.size];
+    for (c, members) in cover.cliques.iter().enumerate() {
+      for &v in members {
+        self.labels[v] = c.min(self.k - 1);
+      }
+    }
+    self.rebuild_conflicts(g);
+    if cover.len() > self.k {
+      for &v in &cover.cliques[cover.len() - 1] {
+        let best = (0..self.k)
+          .min_by_key(|&c| self.label_conflicts[v * self.k + c])
+          .unwrap();
+        self.move_vertex(g, v, best);
+      }
+    }
+    self.tabu_until = vec![0; g.size * self.k];
+    self.best_conflicts = self.conflicts;
+    self.last_improvement = self.iteration;
+    self.hash = self
+      .labels
+      .iter()
+      .enumerate()
+      .fold(0, |hash, (v, &c)| hash ^ zobrist(v, c));
+    self.seen.clear();
+    self.seen.insert(self.hash);
+  }
+
+  fn rebuild_conflicts(&mut self, g: &Graph) {
+    self.label_conflicts = vec![0; g.size * self.k];
+    self.conflicts = 0;
+    for u in 0..g.size {
+      for v in 0..g.size {
+        if u != v && !g.topology.vertices[u].neighbors_bv.get_unchecked(v) {
+          self.label_conflicts[u * self.k + self.labels[v]] += 1;
+          if u < v && self.labels[u] == self.labels[v] {
+            self.conflicts += 1;
+          }
+        }
+      }
+    }
+  }
+
+  fn move_vertex(&mut self, g: &Graph, v: usize, to: usize) {
+    let from = self.labels[v];
+    if from == to {
+      return;
+    }
+    self.conflicts = self.conflicts + self.label_conflicts[v * self.k + to]
+      - self.label_conflicts[v * self.k + from];
+    for u in 0..g.size {
+      if u != v && !g.topology.vertices[v].neighbors_bv.get_unchecked(u) {
+        self.label_conflicts[u * self.k + from] -= 1;
+        self.label_conflicts[u * self.k + to] += 1;
+      }
+    }
+    self.labels[v] = to;
+    self.hash ^= zobrist(v, from) ^ zobrist(v, to);
+  }
+
+  // Applies the tenure policy after a move. The tenure never exceeds the
+  // vertex count, and the memory of assignments is dropped once it holds
+  // MAX_SEEN of them.
+  fn react(&mut self, g: &Graph) {
+    if let TenurePolicy::Reactive {
+      increase,
+      decrease,
+      window,
+    } = self.tenure_policy
+    {
+      if self.seen.len() >= MAX_SEEN {
+        self.seen.clear();
+      }
+      if !self.seen.insert(self.hash) {
+        self.tenure = (self.tenure * (1.0 + increase)).min(g.size as f64);
+      } else if self.iteration - self.last_improvement >= window {
+        self.tenure = (self.tenure * (1.0 - decrease)).max(1.0);
+        self.last_improvement = self.iteration;
+      }
+    }
+  }
+
+  fn labels_to_cover(&self) -> CliqueCover {
+    let mut cliques = vec![vec![]; self.k];
+    for (v, &c) in self.labels.iter().enumerate() {
+      cliques[c].push(v);
+    }
+    cliques.retain(|members| !members.is_empty());
+    CliqueCover { cliques }
+  }
+}
+
+impl SearchStrategy for Tabu {
+  fn name(&self) -> &'static str {
+    "tabu"
+  }
+
+  fn start(&mut self, g: &mut Graph) {
+    self.iteration = 0;
+    self.tenure = self.base_tenure as f64;
+    self.reset_from(g);
+  }
+
+  fn step(&mut self, g: &mut Graph) {
+    if self.k <= 1 {
+      return;
+    }
+    self.iteration += 1;
+
+    let mut best_move = None;
+    let mut best_delta = isize::MAX;
+    let mut conflicting_ct = 0;
+    for v in 0..g.size {
+      let from = self.labels[v];
+      let current = self.label_conflicts[v * self.k + from];
+      if current == 0 {
+        continue;
+      }
+      conflicting_ct += 1;
+      for c in 0..self.k {
+        if c == from {
+          continue;
+        }
+        let delta = self.label_conflicts[v * self.k + c] as isize - current as isize;
+        if self.tabu_until[v * self.k + c] > self.iteration
+          && !(self.aspiration == Aspiration::NewBest
+            && (self.conflicts as isize + delta) < self.best_conflicts as isize)
+        {
+          continue;
+        }
+        if delta < best_delta || (delta == best_delta && coin(&mut g.rng)) {
+          best_delta = delta;
+          best_move = Some((v, c));
+        }
+      }
+    }
+
+    if let Some((v, c)) = best_move {
+      let from = self.labels[v];
+      self.move_vertex(g, v, c);
+      self.tabu_until[v * self.k + from] = self.iteration
+        + self.tenure as usize
+        + below(&mut g.rng, 10)
+        + (0.6 * conflicting_ct as f64) as usize;
+      if self.conflicts < self.best_conflicts {
+        self.best_conflicts = self.conflicts;
+        self.last_improvement = self.iteration;
+      }
+      self.react(g);
+    }
+
+    if self.conflicts == 0 {
+      g.load_cover_unchecked(&self.labels_to_cover());
+      self.reset_from(g);
+    }
+  }
+}
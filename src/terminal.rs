@@ -0,0 +1,113 @@
+// What the console the CLI writes to can display. The live display clears
+// the screen with ANSI escapes and draws cliques with Unicode squares (see
+// Graph's Display), neither of which a legacy Windows console shows: it
+// needs virtual terminal processing switched on for escapes, and a UTF-8
+// code page for the squares. Detection turns the former on where it can,
+// and otherwise falls back to not clearing and to ASCII glyphs, so the
+// output stays readable everywhere. Output that isn't to a console gets no
+// escapes, and Unicode, as files and pipes take UTF-8.
+//
+// On Unix, escapes are sent to any terminal but TERM=dumb, and Unicode
+// unless the locale (LC_ALL, LC_CTYPE or LANG, the first set) names
+// another encoding than UTF-8.
+
+use crate::Graph;
+use std::io::{self, IsTerminal, Write};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Terminal {
+  // ANSI escape sequences are interpreted.
+  pub ansi: bool,
+  // Unicode glyphs display.
+  pub unicode: bool,
+}
+
+impl Terminal {
+  // Probes stdout, enabling escape sequences on Windows consoles.
+  pub fn detect() -> Terminal {
+    if !io::stdout().is_terminal() {
+      return Terminal {
+        ansi: false,
+        unicode: true,
+      };
+    }
+    Terminal {
+      ansi: console::enable_ansi(),
+      unicode: console::unicode(),
+    }
+  }
+
+  // Clears the screen and homes the cursor, where escapes are understood.
+  pub fn clear_screen(&self) {
+    if self.ansi {
+      print!("\x1B[2J\x1B[1;1H");
+      let _ = io::stdout().flush();
+    }
+  }
+
+  // g's cliques, one per line, in glyphs this terminal displays.
+  pub fn draw(&self, g: &Graph) -> String {
+    if self.unicode {
+      format!("{}", g)
+    } else {
+      format!("{:#}", g)
+    }
+  }
+}
+
+#[cfg(windows)]
+mod console {
+  use std::ffi::c_void;
+
+  const STD_OUTPUT_HANDLE: u32 = -11i32 as u32;
+  const ENABLE_VIRTUAL_TERMINAL_PROCESSING: u32 = 0x0004;
+  const CP_UTF8: u32 = 65001;
+
+  #[link(name = "kernel32")]
+  extern "system" {
+    fn GetStdHandle(std_handle: u32) -> *mut c_void;
+    fn GetConsoleMode(console: *mut c_void, mode: *mut u32) -> i32;
+    fn SetConsoleMode(console: *mut c_void, mode: u32) -> i32;
+    fn GetConsoleOutputCP() -> u32;
+  }
+
+  // Fails on consoles older than Windows 10, which can't interpret them.
+  pub fn enable_ansi() -> bool {
+    unsafe {
+      let console = GetStdHandle(STD_OUTPUT_HANDLE);
+      let mut mode = 0;
+      if console.is_null() || GetConsoleMode(console, &mut mode) == 0 {
+        return false;
+      }
+      mode & ENABLE_VIRTUAL_TERMINAL_PROCESSING != 0
+        || SetConsoleMode(console, mode | ENABLE_VIRTUAL_TERMINAL_PROCESSING) != 0
+    }
+  }
+
+  pub fn unicode() -> bool {
+    unsafe { GetConsoleOutputCP() == CP_UTF8 }
+  }
+}
+
+#[cfg(not(windows))]
+mod console {
+  use std::env;
+
+  pub fn enable_ansi() -> bool {
+    env::var("TERM").map_or(true, |term| term != "dumb")
+  }
+
+  pub fn unicode() -> bool {
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+      .iter()
+      .filter_map(|name| env::var(name).ok())
+      .find(|value| !value.is_empty());
+    match locale {
+      Some(locale) => {
+        let locale = locale.to_ascii_lowercase();
+        locale.contains("utf-8") || locale.contains("utf8")
+      }
+      None => true,
+    }
+  }
+}
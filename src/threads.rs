@@ -0,0 +1,87 @@
+// Sizing of the rayon pool used by the parallel drivers (multi-start,
+// race, islands, batch manifests, solve_batch). By default rayon uses
+// every core; on a shared server that is rarely what you want.
+
+use crate::error::VccError;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+
+#[derive(Default)]
+pub struct ThreadConfig {
+  // None leaves the choice to rayon (one thread per core).
+  pub threads: Option<usize>,
+  // Pin pool thread i to the i-th of the CPUs the process may run on,
+  // wrapping around (Linux only; ignored elsewhere).
+  pub pin_cores: bool,
+}
+
+impl ThreadConfig {
+  pub fn build_pool(&self) -> Result<ThreadPool, VccError> {
+    let mut builder = ThreadPoolBuilder::new();
+    if let Some(threads) = self.threads {
+      builder = builder.num_threads(threads);
+    }
+    let pool = builder.build()?;
+    if self.pin_cores {
+      pin_threads(&pool)?;
+    }
+    Ok(pool)
+  }
+
+  // Runs f with this configuration's pool as the current rayon pool, so
+  // every parallel driver called from f uses it.
+  pub fn install<R: Send>(&self, f: impl FnOnce() -> R + Send) -> Result<R, VccError> {
+    Ok(self.build_pool()?.install(f))
+  }
+}
+
+// Pins every pool thread before the pool is handed out, so a failure is
+// returned rather than lost on a worker thread.
+#[cfg(target_os = "linux")]
+fn pin_threads(pool: &ThreadPool) -> Result<(), VccError> {
+  let cpus = allowed_cpus()?;
+  pool
+    .broadcast(|context| pin_to_cpu(cpus[context.index() % cpus.len()]))
+    .into_iter()
+    .collect()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn pin_threads(_pool: &ThreadPool) -> Result<(), VccError> {
+  Ok(())
+}
+
+// The CPUs the calling thread may run on, per its affinity mask, which
+// taskset or a container may have narrowed; never empty.
+#[cfg(target_os = "linux")]
+fn allowed_cpus() -> Result<Vec<usize>, VccError> {
+  let size = std::mem::size_of::<libc::cpu_set_t>();
+  // SAFETY: cpu_set_t is a plain bit array, valid all zeroes, and
+  // sched_getaffinity writes at most size bytes of it. CPU_ISSET reads
+  // the set within CPU_SETSIZE.
+  unsafe {
+    let mut set: libc::cpu_set_t = std::mem::zeroed();
+    if libc::sched_getaffinity(0, size, &mut set) != 0 {
+      return Err(std::io::Error::last_os_error().into());
+    }
+    Ok(
+      (0..libc::CPU_SETSIZE as usize)
+        .filter(|&cpu| libc::CPU_ISSET(cpu, &set))
+        .collect(),
+    )
+  }
+}
+
+#[cfg(target_os = "linux")]
+fn pin_to_cpu(cpu: usize) -> Result<(), VccError> {
+  let size = std::mem::size_of::<libc::cpu_set_t>();
+  // SAFETY: as in allowed_cpus; cpu came from it, so is below CPU_SETSIZE,
+  // and sched_setaffinity only reads size bytes of the set.
+  unsafe {
+    let mut set: libc::cpu_set_t = std::mem::zeroed();
+    libc::CPU_SET(cpu, &mut set);
+    if libc::sched_setaffinity(0, size, &set) != 0 {
+      return Err(std::io::Error::last_os_error().into());
+    }
+  }
+  Ok(())
+}
@@ -0,0 +1,159 @@
+// Throttling for runs on shared or battery-powered machines. The solver
+// busy-loops, so throttling happens between restarts, the batches it can
+// stop between. A duty cycle below 1 sleeps after each restart long
+// enough that searching takes that fraction of the wall time. A schedule
+// pauses the workers past a smaller count during some hours of the day,
+// local time on Linux and UTC elsewhere: workers index from 0, so with 0
+// workers scheduled even a single-threaded solve waits the hours out.
+//
+// Time spent sleeping is accounted for apart from time spent searching,
+// so a throttled run's throughput, in restarts per busy second, compares
+// with an unthrottled run's.
+
+use crate::error::VccError;
+use std::str::FromStr;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+// How often a paused worker checks whether its hours are over.
+const SCHEDULE_POLL: Duration = Duration::from_secs(60);
+
+// "22-7:2": from 22:00 to 7:00, only workers 0 and 1 run.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ThreadSchedule {
+  pub start_hour: u32,
+  pub end_hour: u32,
+  pub workers: usize,
+}
+
+impl FromStr for ThreadSchedule {
+  type Err = VccError;
+
+  fn from_str(schedule_str: &str) -> Result<ThreadSchedule, VccError> {
+    let bad = || VccError::Parse(format!("bad thread schedule: {:?}", schedule_str));
+    let (hours, workers) = schedule_str.split_once(':').ok_or_else(bad)?;
+    let (start_hour, end_hour) = hours.split_once('-').ok_or_else(bad)?;
+    let schedule = ThreadSchedule {
+      start_hour: start_hour.parse().map_err(|_| bad())?,
+      end_hour: end_hour.parse().map_err(|_| bad())?,
+      workers: workers.parse().map_err(|_| bad())?,
+    };
+    if schedule.start_hour > 23 || schedule.end_hour > 23 {
+      return Err(bad());
+    }
+    Ok(schedule)
+  }
+}
+
+impl ThreadSchedule {
+  // Whether the hours include hour, wrapping past midnight.
+  pub fn covers(&self, hour: u32) -> bool {
+    if self.start_hour <= self.end_hour {
+      (self.start_hour..self.end_hour).contains(&hour)
+    } else {
+      hour >= self.start_hour || hour < self.end_hour
+    }
+  }
+
+  pub fn runs(&self, worker: usize, hour: u32) -> bool {
+    !self.covers(hour) || worker < self.workers
+  }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Throttle {
+  // The fraction of wall time spent searching, in (0, 1].
+  pub duty: f64,
+  pub schedule: Option<ThreadSchedule>,
+}
+
+impl Default for Throttle {
+  fn default() -> Throttle {
+    Throttle {
+      duty: 1.0,
+      schedule: None,
+    }
+  }
+}
+
+impl Throttle {
+  pub fn validate(&self) -> Result<(), VccError> {
+    if !(self.duty > 0.0 && self.duty <= 1.0) {
+      return Err(VccError::InvalidParameter(format!(
+        "duty cycle {} is not in (0, 1]",
+        self.duty
+      )));
+    }
+    Ok(())
+  }
+
+  pub fn is_off(&self) -> bool {
+    self.duty >= 1.0 && self.schedule.is_none()
+  }
+}
+
+// One worker's throttling and its account of the time it slept.
+pub struct Pacer {
+  throttle: Throttle,
+  worker: usize,
+  busy_since: Instant,
+  pub idle: Duration,
+}
+
+impl Pacer {
+  pub fn new(throttle: Throttle, worker: usize) -> Pacer {
+    Pacer {
+      throttle,
+      worker,
+      busy_since: Instant::now(),
+      idle: Duration::ZERO,
+    }
+  }
+
+  // Called after each restart: sleeps off the duty cycle for the time
+  // searched since the last call, then while the schedule pauses this
+  // worker.
+  pub fn pause(&mut self) {
+    if self.throttle.is_off() {
+      return;
+    }
+    let paused = Instant::now();
+    let busy = paused - self.busy_since;
+    thread::sleep(busy.mul_f64(1.0 / self.throttle.duty - 1.0));
+    if let Some(schedule) = self.throttle.schedule {
+      while !schedule.runs(self.worker, local_hour()) {
+        thread::sleep(SCHEDULE_POLL);
+      }
+    }
+    self.busy_since = Instant::now();
+    self.idle += self.busy_since - paused;
+  }
+}
+
+// The hour of the day, from 0 to 23.
+#[cfg(target_os = "linux")]
+pub fn local_hour() -> u32 {
+  let now = unix_seconds() as libc::time_t;
+  unsafe {
+    let mut tm: libc::tm = std::mem::zeroed();
+    if libc::localtime_r(&now, &mut tm).is_null() {
+      return utc_hour();
+    }
+    tm.tm_hour as u32
+  }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn local_hour() -> u32 {
+  utc_hour()
+}
+
+fn utc_hour() -> u32 {
+  (unix_seconds() / 3600 % 24) as u32
+}
+
+fn unix_seconds() -> u64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map_or(0, |since| since.as_secs())
+}
@@ -0,0 +1,74 @@
+// The part of a graph the search never changes: each vertex's adjacency,
+// kept as a one-member Clique whose neighbors are the vertex's (see
+// lib.rs). A Graph holds its topology behind an Arc, so clones of a graph,
+// one per worker say, share it and copy only the search state: the
+// cliques, the vertex-to-clique index, the scratch bit vector and the
+// generator. Adding an edge to a graph whose topology is shared copies
+// the topology first, leaving the other graphs as they were.
+//
+// Dense graphs keep their adjacency as it is rather than complemented: a
+// vertex's non-neighbors take the same n bits as its neighbors, so storing
+// them would save no memory and make no intersection cheaper, while every
+// bit vector reader in the search would have to translate. This was
+// asked for and declined; callers who want the complement can build it
+// with Graph::complement.
+
+use crate::scratch::ScratchPool;
+use crate::{Clique, Graph};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+#[derive(Clone)]
+pub struct GraphTopology {
+  pub(crate) vertices: Vec<Clique>,
+}
+
+impl GraphTopology {
+  pub fn size(&self) -> usize {
+    self.vertices.len()
+  }
+
+  pub fn degree(&self, v: usize) -> usize {
+    self.vertices[v].neighbors_ct
+  }
+
+  // Panics if u or v is out of range, as degree does.
+  pub fn has_edge(&self, u: usize, v: usize) -> bool {
+    assert!(
+      v < self.size(),
+      "vertex {} in a graph with {} vertices",
+      v,
+      self.size()
+    );
+    self.vertices[u].neighbors_bv.get_unchecked(v)
+  }
+
+  // v's neighbors, in increasing order.
+  pub fn neighbors(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+    let neighbors_bv = &self.vertices[v].neighbors_bv;
+    (0..self.size()).filter(move |&u| neighbors_bv.get_unchecked(u))
+  }
+
+  pub fn edge_count(&self) -> usize {
+    self.vertices.iter().map(|v| v.neighbors_ct).sum::<usize>() / 2
+  }
+}
+
+impl Graph {
+  pub fn topology(&self) -> &Arc<GraphTopology> {
+    &self.topology
+  }
+
+  // A graph on a shared topology, its search state fresh: one clique per
+  // vertex, a new generator and no forbidden sets.
+  pub fn with_topology(topology: Arc<GraphTopology>) -> Graph {
+    let mut g = Graph::new(0);
+    g.size = topology.size();
+    g.topology = topology;
+    g.cliques = g.topology.vertices.clone();
+    g.scratch = ScratchPool::new(g.size);
+    g.vertex_clique = (0..g.size).collect();
+    g.cliques_ct = g.size;
+    g
+  }
+}
@@ -0,0 +1,69 @@
+// Time-to-target experiments: solve one instance over and over with
+// different seeds and record how long each run takes to reach the target.
+// Sorted, these times give the empirical distribution plotted in TTT plots
+// (Aiex, Resende and Ribeiro, 2007), the usual way to compare the running
+// times of randomized heuristics.
+//
+// Runs are sequential so that their timings don't compete for cores, and
+// run r is seeded with seed + r. Runs that use up their budget without
+// reaching the target are kept, marked as missed.
+
+use crate::error::VccError;
+use crate::solve::SolveParams;
+use crate::Graph;
+use std::time::{Duration, Instant};
+
+pub struct TttRun {
+  pub seed: u64,
+  pub elapsed: Duration,
+  pub reached_target: bool,
+  pub cover_size: usize,
+}
+
+pub fn time_to_target(
+  graph: &Graph,
+  params: &SolveParams,
+  runs: usize,
+  seed: u64,
+) -> Result<Vec<TttRun>, VccError> {
+  params.validate()?;
+  (0..runs)
+    .map(|r| {
+      let run_seed = seed.wrapping_add(r as u64);
+      let mut g = graph.clone();
+      g.seed(run_seed);
+      g.conform_cliques_to_vertices();
+      g.shuffle_active_cliques();
+      let start = Instant::now();
+      let result = g.solve(params)?;
+      Ok(TttRun {
+        seed: run_seed,
+        elapsed: start.elapsed(),
+        reached_target: result.reached_target,
+        cover_size: result.best_cover.len(),
+      })
+    })
+    .collect()
+}
+
+// CSV of the runs that reached the target, fastest first, with the
+// empirical probability (i - 1/2) / n of the i-th of n runs; missed runs
+// count towards n, so the curve tops out below 1 if any missed.
+pub fn format_ttt(runs: &[TttRun]) -> String {
+  let mut reached: Vec<&TttRun> = runs.iter().filter(|r| r.reached_target).collect();
+  reached.sort_by_key(|r| r.elapsed);
+  let mut out = format!(
+    "# {} of {} runs reached the target\nseconds,probability,seed\n",
+    reached.len(),
+    runs.len()
+  );
+  for (i, run) in reached.iter().enumerate() {
+    out.push_str(&format!(
+      "{:.6},{:.6},{}\n",
+      run.elapsed.as_secs_f64(),
+      (i as f64 + 0.5) / runs.len() as f64,
+      run.seed
+    ));
+  }
+  out
+}
@@ -0,0 +1,249 @@
+// Checking covers from other tools. A cover file is read in any of the
+// formats this crate writes, picked by extension:
+//   .csv   "vertex,cluster" rows, as --clusters writes, with or without
+//          the header; clusters may be any labels, numbers or not.
+//   .json  a labels array, as --labels writes, null for an uncovered
+//          vertex, or a {"cliques": [[...], ...]} object.
+//   .sol   a DIMACS coloring solution of the complement, as --coloring
+//          writes: "l <vertex> <color>" lines, vertices from 1.
+// Vertices are numbered from 0 except in .sol files; write_cover writes
+// the same formats, for covers repair.rs fixed say. The path "-", stdin or
+// stdout, takes .csv rows. Unlike
+// validate_cover, which stops at the first problem, cover_violations
+// lists them all, and names vertices as the graph's file does: from 1 for
+// DIMACS graphs, from 0 for graph6.
+
+use crate::cover::CliqueCover;
+use crate::error::VccError;
+use crate::formats::{read_text, write_text, GraphFormat, STDIO};
+use crate::Graph;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CoverFormat {
+  ClusterCsv,
+  Json,
+  DimacsColoring,
+}
+
+impl CoverFormat {
+  pub fn from_path(path: &str) -> Result<CoverFormat, VccError> {
+    let extension = Path::new(path)
+      .extension()
+      .and_then(|extension| extension.to_str())
+      .unwrap_or("")
+      .to_ascii_lowercase();
+    match extension.as_str() {
+      _ if path == STDIO => Ok(CoverFormat::ClusterCsv),
+      "csv" => Ok(CoverFormat::ClusterCsv),
+      "json" => Ok(CoverFormat::Json),
+      "sol" => Ok(CoverFormat::DimacsColoring),
+      _ => Err(VccError::Parse(format!(
+        "no cover format for {:?} (expected .csv, .json or .sol)",
+        path
+      ))),
+    }
+  }
+}
+
+pub fn read_cover(path: &str) -> Result<CliqueCover, VccError> {
+  parse_cover(&read_text(path)?, CoverFormat::from_path(path)?)
+}
+
+// Writes a .json cover as a labels array.
+pub fn write_cover(cover: &CliqueCover, path: &str) -> Result<(), VccError> {
+  let text = match CoverFormat::from_path(path)? {
+    CoverFormat::ClusterCsv => cover.to_cluster_csv(),
+    CoverFormat::Json => serde_json::to_string(&cover.labels())?,
+    CoverFormat::DimacsColoring => cover.to_dimacs_coloring(),
+  };
+  write_text(path, &text)
+}
+
+// Vertices may be listed in any order and under any cluster labels; the
+// cliques come out in order of first appearance. Out-of-range vertices and
+// vertices in two cliques are kept, for cover_violations to report.
+pub fn parse_cover(text: &str, format: CoverFormat) -> Result<CliqueCover, VccError> {
+  let mut assignments: Vec<(usize, String)> = vec![];
+  match format {
+    CoverFormat::ClusterCsv => {
+      for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || (i == 0 && line == "vertex,cluster") {
+          continue;
+        }
+        let bad = || VccError::Parse(format!("bad cluster row {}: {:?}", i + 1, line));
+        let (vertex, cluster) = line.split_once(',').ok_or_else(bad)?;
+        let vertex = vertex.trim().parse().map_err(|_| bad())?;
+        assignments.push((vertex, cluster.trim().to_string()));
+      }
+    }
+    CoverFormat::Json => {
+      if text.trim_start().starts_with('{') {
+        let cover: CliqueCover = serde_json::from_str(text)?;
+        return Ok(cover);
+      }
+      let labels: Vec<Option<usize>> = serde_json::from_str(text)?;
+      for (v, label) in labels.into_iter().enumerate() {
+        if let Some(label) = label.filter(|&label| label != usize::MAX) {
+          assignments.push((v, label.to_string()));
+        }
+      }
+    }
+    CoverFormat::DimacsColoring => {
+      for (i, line) in text.lines().enumerate() {
+        let mut words = line.split_whitespace();
+        if words.next() != Some("l") {
+          continue;
+        }
+        let bad = || VccError::Parse(format!("bad solution line {}: {:?}", i + 1, line));
+        let vertex: usize = words.next().and_then(|w| w.parse().ok()).ok_or_else(bad)?;
+        let color = words.next().ok_or_else(bad)?;
+        if vertex == 0 {
+          return Err(bad());
+        }
+        assignments.push((vertex - 1, color.to_string()));
+      }
+    }
+  }
+  let mut clique_of: BTreeMap<String, usize> = BTreeMap::new();
+  let mut cliques: Vec<Vec<usize>> = vec![];
+  for (vertex, label) in assignments {
+    let next = cliques.len();
+    let c = *clique_of.entry(label).or_insert(next);
+    if c == next {
+      cliques.push(vec![]);
+    }
+    cliques[c].push(vertex);
+  }
+  Ok(CliqueCover { cliques })
+}
+
+// Vertices are 0-based ids here; named displays them as a graph file
+// numbers them.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Violation {
+  EmptyClique {
+    clique: usize,
+  },
+  OutOfRange {
+    clique: usize,
+    vertex: usize,
+  },
+  InTwoCliques {
+    vertex: usize,
+    first: usize,
+    second: usize,
+  },
+  NonAdjacent {
+    clique: usize,
+    u: usize,
+    v: usize,
+  },
+  Uncovered {
+    vertex: usize,
+  },
+}
+
+impl Violation {
+  // The violation with vertices numbered from base.
+  pub fn named(&self, base: usize) -> Named<'_> {
+    Named {
+      violation: self,
+      base,
+    }
+  }
+}
+
+pub struct Named<'a> {
+  violation: &'a Violation,
+  base: usize,
+}
+
+impl fmt::Display for Named<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let name = |v: usize| v + self.base;
+    match *self.violation {
+      Violation::EmptyClique { clique } => write!(f, "clique {} is empty", clique),
+      Violation::OutOfRange { clique, vertex } => write!(
+        f,
+        "clique {} holds vertex {}, which the graph doesn't have",
+        clique,
+        name(vertex)
+      ),
+      Violation::InTwoCliques {
+        vertex,
+        first,
+        second,
+      } => write!(
+        f,
+        "vertex {} is in cliques {} and {}",
+        name(vertex),
+        first,
+        second
+      ),
+      Violation::NonAdjacent { clique, u, v } => write!(
+        f,
+        "clique {} holds non-adjacent vertices {} and {}",
+        clique,
+        name(u),
+        name(v)
+      ),
+      Violation::Uncovered { vertex } => write!(f, "vertex {} is not covered", name(vertex)),
+    }
+  }
+}
+
+// The number the graph's file gives vertex 0.
+pub fn vertex_base(format: GraphFormat) -> usize {
+  match format {
+    GraphFormat::Dimacs => 1,
+    GraphFormat::Graph6 => 0,
+  }
+}
+
+impl Graph {
+  // Every way cover fails to partition the graph's vertices into cliques;
+  // empty if it is a valid cover, forbidden sets aside.
+  pub fn cover_violations(&self, cover: &CliqueCover) -> Vec<Violation> {
+    let mut violations = vec![];
+    let mut owner: Vec<Option<usize>> = vec![None; self.size];
+    for (c, members) in cover.cliques.iter().enumerate() {
+      if members.is_empty() {
+        violations.push(Violation::EmptyClique { clique: c });
+      }
+      let mut in_range = vec![];
+      for &v in members {
+        if v >= self.size {
+          violations.push(Violation::OutOfRange {
+            clique: c,
+            vertex: v,
+          });
+          continue;
+        }
+        match owner[v] {
+          Some(first) => violations.push(Violation::InTwoCliques {
+            vertex: v,
+            first,
+            second: c,
+          }),
+          None => owner[v] = Some(c),
+        }
+        for &u in &in_range {
+          if u != v && !self.has_edge(u, v) {
+            violations.push(Violation::NonAdjacent { clique: c, u, v });
+          }
+        }
+        in_range.push(v);
+      }
+    }
+    for (v, owner) in owner.iter().enumerate() {
+      if owner.is_none() {
+        violations.push(Violation::Uncovered { vertex: v });
+      }
+    }
+    violations
+  }
+}
@@ -0,0 +1,163 @@
+// The vcc binary end to end, for behavior that lives in the CLI: option
+// handling, exit codes and what is printed.
+
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+fn vcc(args: &[&str]) -> Output {
+  Command::new(env!("CARGO_BIN_EXE_vcc"))
+    .args(args)
+    .output()
+    .unwrap()
+}
+
+// A file in a directory of the test's own, which it removes when done.
+fn scratch_file(test: &str, name: &str, contents: &str) -> PathBuf {
+  let dir = std::env::temp_dir().join(format!("vcc-cli-{}-{}", test, std::process::id()));
+  std::fs::create_dir_all(&dir).unwrap();
+  let path = dir.join(name);
+  std::fs::write(&path, contents).unwrap();
+  path
+}
+
+// C5, whose cover number is 3.
+const CYCLE5: &str = "p edge 5 5\ne 1 2\ne 2 3\ne 3 4\ne 4 5\ne 5 1\n";
+
+#[test]
+fn labeled_solves_reject_other_targets() {
+  let graph = scratch_file("labels", "c5.col", CYCLE5);
+  let links = scratch_file("labels", "links.txt", "labels 3\nallow 0 1\n");
+  let graph = graph.to_str().unwrap();
+  let links = links.to_str().unwrap();
+  let conflicting = vcc(&["solve", graph, "--constraints", links, "--target", "4"]);
+  assert_eq!(conflicting.status.code(), Some(1));
+  assert!(
+    String::from_utf8_lossy(&conflicting.stderr).contains("conflicts with the 3 clique labels")
+  );
+  let labeled = vcc(&["solve", graph, "--constraints", links]);
+  assert_eq!(labeled.status.code(), Some(0));
+  assert!(String::from_utf8_lossy(&labeled.stdout).contains("label 1: [0"));
+  std::fs::remove_dir_all(std::path::Path::new(graph).parent().unwrap()).unwrap();
+}
+
+#[test]
+fn quiet_silences_verify() {
+  let graph = scratch_file("quiet", "c5.col", CYCLE5);
+  let cover = scratch_file("quiet", "cover.json", "[0, 0, 1, 1, 2]");
+  let graph = graph.to_str().unwrap();
+  let cover = cover.to_str().unwrap();
+  let verified = vcc(&["verify", "--cover", cover, "--graph", graph]);
+  assert_eq!(verified.status.code(), Some(0));
+  assert!(String::from_utf8_lossy(&verified.stdout).contains("valid: 3 cliques"));
+  let quiet = vcc(&[
+    "--quiet",
+    "--threads",
+    "2",
+    "verify",
+    "--cover",
+    cover,
+    "--graph",
+    graph,
+  ]);
+  assert_eq!(quiet.status.code(), Some(0));
+  assert!(quiet.stdout.is_empty());
+  std::fs::remove_dir_all(std::path::Path::new(graph).parent().unwrap()).unwrap();
+}
+
+#[test]
+fn global_options_after_the_subcommand_are_the_subcommands() {
+  let graph = scratch_file("globals", "c5.col", CYCLE5);
+  let cover = scratch_file("globals", "cover.json", "[0, 0, 1, 1, 2]");
+  let graph = graph.to_str().unwrap();
+  let cover = cover.to_str().unwrap();
+  let late = vcc(&["verify", "--cover", cover, "--graph", graph, "--quiet"]);
+  assert_eq!(late.status.code(), Some(1));
+  assert!(String::from_utf8_lossy(&late.stderr).contains("--quiet"));
+  std::fs::remove_dir_all(std::path::Path::new(graph).parent().unwrap()).unwrap();
+}
+
+#[test]
+fn fsync_is_taken_before_the_subcommand_only() {
+  let graph = scratch_file("fsync", "c5.col", CYCLE5);
+  let cover = scratch_file("fsync", "cover.json", "[0, 0, 1, 1, 2]");
+  let output = graph.with_file_name("repaired.json");
+  let graph = graph.to_str().unwrap();
+  let cover = cover.to_str().unwrap();
+  let repaired = vcc(&[
+    "--fsync",
+    "full",
+    "repair",
+    "--cover",
+    cover,
+    "--graph",
+    graph,
+    "--output",
+    output.to_str().unwrap(),
+  ]);
+  assert_eq!(repaired.status.code(), Some(0));
+  assert!(output.exists());
+  let late = vcc(&[
+    "repair", "--cover", cover, "--fsync", "none", "--graph", graph,
+  ]);
+  assert_eq!(late.status.code(), Some(1));
+  assert!(String::from_utf8_lossy(&late.stderr).contains("unknown option: --fsync"));
+  std::fs::remove_dir_all(std::path::Path::new(graph).parent().unwrap()).unwrap();
+}
+
+#[test]
+fn solved_benchmarks_report_their_gap() {
+  use vcc::families::{complement, mycielski};
+  use vcc::formats::GraphFormat;
+  let dimacs = complement(&mycielski(3)).to_format(GraphFormat::Dimacs);
+  let graph = scratch_file("known", "myciel3-complement.col", &dimacs);
+  let graph = graph.to_str().unwrap();
+  let solved = vcc(&[
+    "solve",
+    graph,
+    "--seed",
+    "1",
+    "--restarts",
+    "3",
+    "--max-iterations",
+    "10_000",
+  ]);
+  assert_eq!(solved.status.code(), Some(2));
+  let stdout = String::from_utf8_lossy(&solved.stdout);
+  assert!(stdout.contains("complement of myciel3: optimum 4 cliques, gap 0"));
+  std::fs::remove_dir_all(std::path::Path::new(graph).parent().unwrap()).unwrap();
+}
+
+#[test]
+fn quiet_silences_manifests_and_the_legacy_mode() {
+  let manifest = scratch_file(
+    "quiet-modes",
+    "jobs.toml",
+    "[[job]]\nname = \"easy\"\nvertices = 20\ncliques = 2\nedge_fraction = 0.3\n\
+     max_iterations = 10_000\nreverse_fraction = 0.0\nrestarts = 5\nseed = 1\n",
+  );
+  let manifest = manifest.to_str().unwrap();
+  let loud = vcc(&["solve", "--manifest", manifest]);
+  assert_eq!(loud.status.code(), Some(0));
+  assert!(String::from_utf8_lossy(&loud.stdout).starts_with("name, vertices"));
+  let quiet = vcc(&["--quiet", "solve", "--manifest", manifest]);
+  assert_eq!(quiet.status.code(), Some(0));
+  assert!(quiet.stdout.is_empty());
+  // The legacy positional mode searches until killed, printing as it goes.
+  let legacy = |quiet: bool| {
+    let mut args = vec!["12", "3", "0.3", "1_000", "0"];
+    if quiet {
+      args.insert(0, "--quiet");
+    }
+    let mut child = Command::new(env!("CARGO_BIN_EXE_vcc"))
+      .args(&args)
+      .stdout(Stdio::piped())
+      .spawn()
+      .unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    child.kill().unwrap();
+    child.wait_with_output().unwrap().stdout
+  };
+  assert!(String::from_utf8_lossy(&legacy(false)).contains("cargo run --release 12 3"));
+  assert!(legacy(true).is_empty());
+  std::fs::remove_dir_all(std::path::Path::new(manifest).parent().unwrap()).unwrap();
+}
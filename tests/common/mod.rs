@@ -0,0 +1,24 @@
+// Helpers shared by the integration tests; each test file uses some.
+#![allow(dead_code)]
+
+use vcc::{Graph, SolveParams};
+
+pub fn params(target: usize) -> SolveParams {
+  SolveParams {
+    max_iterations: 1_000,
+    target,
+    max_restarts: 2,
+    ..SolveParams::default()
+  }
+}
+
+pub fn complete_graph(n: usize) -> Graph {
+  let mut g = Graph::new(n);
+  for u in 0..n {
+    for v in (u + 1)..n {
+      g.add_edge(u, v).unwrap();
+    }
+  }
+  g.conform_cliques_to_vertices();
+  g
+}
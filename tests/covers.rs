@@ -0,0 +1,291 @@
+// Operations on covers apart from searching: checks, statistics, diffs,
+// constraints, repair, improvement, vertex removal, quotients and drawing.
+
+mod common;
+
+use common::{complete_graph, params};
+use std::time::Duration;
+use vcc::families::{cycle, disjoint_cliques, path};
+use vcc::forbidden::ForbiddenSet;
+use vcc::links::LinkConstraints;
+use vcc::repair::Placement;
+use vcc::rng::default_rng;
+use vcc::{get_random_graph_with_k_cliques, CliqueCover, Graph, SolveParams};
+
+#[test]
+fn impossible_link_constraints_are_rejected() {
+  let g = cycle(5);
+  let parse = |s: &str| LinkConstraints::parse(s).unwrap();
+  assert!(LinkConstraints::parse("must 0").is_err());
+  assert!(LinkConstraints::parse("maybe 0 1").is_err());
+  assert!(g
+    .constrained(&parse("# comment\n\nmust 0 1\ncannot 1 2"))
+    .is_ok());
+  assert!(g.constrained(&parse("must 0 5")).is_err());
+  assert!(g.constrained(&parse("must 0 2")).is_err());
+  assert!(g.constrained(&parse("must 0 1\nmust 1 2")).is_err());
+  assert!(g.constrained(&parse("must 0 1\ncannot 1 0")).is_err());
+  assert!(g.constrained(&parse("cannot 3 3")).is_err());
+  assert!(LinkConstraints::parse("clique").is_err());
+  assert!(g.constrained(&parse("clique 0 1\ncannot 2 3")).is_ok());
+  assert!(g.constrained(&parse("clique 0 2")).is_err());
+  assert!(g.constrained(&parse("clique 0 1\nclique 1 2")).is_err());
+  assert!(g.constrained(&parse("clique 0 1\nmust 1 2")).is_err());
+  assert!(LinkConstraints::parse("forbid 0").is_err());
+  assert!(LinkConstraints::parse("at-most 1").is_err());
+  assert!(g.constrained(&parse("forbid 0 1 2\nat-most 1 3 4")).is_ok());
+  assert!(g.constrained(&parse("at-most 0 3 4")).is_err());
+  assert!(g.constrained(&parse("must 0 1\nforbid 0 1 2")).is_err());
+  assert!(g.constrained(&parse("labels 3\nallow 0 1 2")).is_ok());
+  assert!(g.constrained(&parse("allow 0 1")).is_err());
+  assert!(g.constrained(&parse("labels 3\nallow 0 3")).is_err());
+  assert!(g
+    .constrained(&parse("labels 3\nallow 0 1\nallow 1 2\nmust 0 1"))
+    .is_err());
+}
+
+#[test]
+fn labeled_searches_target_one_clique_per_label() {
+  let g = cycle(5);
+  let constrained = g
+    .constrained(&LinkConstraints::parse("labels 3\nallow 0 1\nallow 2 1 2").unwrap())
+    .unwrap();
+  // More cliques than labels would leave one unlabeled.
+  assert!(constrained.target(5).is_err());
+  let target = constrained.target(3).unwrap();
+  let mut graph = constrained.graph.clone();
+  let params = SolveParams {
+    max_restarts: 10,
+    ..params(target)
+  };
+  let result = graph.solve(&params).unwrap();
+  assert!(result.reached_target);
+  let labeled = constrained.lift_labeled(&result.best_cover).unwrap();
+  assert_eq!(labeled.len(), 3);
+  assert!(labeled
+    .iter()
+    .any(|(label, members)| *label == 1 && members.contains(&0)));
+  let unlabeled = g.constrained(&LinkConstraints::default()).unwrap();
+  assert_eq!(unlabeled.target(5).unwrap(), 5);
+}
+
+#[test]
+fn stats_describe_the_cover() {
+  let g = disjoint_cliques(3, 2);
+  let stats = g.cover_stats(&CliqueCover {
+    cliques: vec![vec![0, 1], vec![2], vec![3], vec![4, 5]],
+  });
+  assert_eq!((stats.cliques, stats.covered, stats.singletons), (4, 6, 2));
+  assert_eq!(stats.histogram, [(1, 2), (2, 2)]);
+  assert_eq!((stats.density, stats.min_density), (1.0, 1.0));
+  // Grouping across cliques loses density.
+  let stats = g.cover_stats(&CliqueCover {
+    cliques: vec![vec![0, 2], vec![1, 3], vec![4, 5]],
+  });
+  assert_eq!((stats.density, stats.min_density), (1.0 / 3.0, 0.0));
+  let stats = Graph::new(0).cover_stats(&CliqueCover { cliques: vec![] });
+  assert_eq!(
+    (stats.largest, stats.mean_size, stats.density),
+    (0, 0.0, 1.0)
+  );
+}
+
+#[test]
+fn quotients_merge_each_clique_into_a_node() {
+  // The path 0-1-2-3-4 covered by {0, 1}, {2, 3} and {4}.
+  let g = path(5);
+  let cover = CliqueCover {
+    cliques: vec![vec![0, 1], vec![2, 3], vec![4]],
+  };
+  let quotient = g.quotient(&cover).unwrap();
+  assert_eq!(quotient.graph.size(), 3);
+  assert_eq!(quotient.edges, vec![(0, 1, 1), (1, 2, 1)]);
+  assert_eq!(
+    quotient.to_dot(),
+    "graph quotient {\n  q0 [label=\"0 1\", size=2];\n  q1 [label=\"2 3\", size=2];\n  \
+     q2 [label=\"4\", size=1];\n  q0 -- q1 [weight=1];\n  q1 -- q2 [weight=1];\n}\n"
+  );
+  assert_eq!(
+    quotient.to_json(),
+    "{\n  \"nodes\": [\n    {\"id\": 0, \"size\": 2, \"members\": [0, 1]},\n    \
+     {\"id\": 1, \"size\": 2, \"members\": [2, 3]},\n    \
+     {\"id\": 2, \"size\": 1, \"members\": [4]}\n  ],\n  \"edges\": [\n    \
+     {\"source\": 0, \"target\": 1, \"weight\": 1},\n    \
+     {\"source\": 1, \"target\": 2, \"weight\": 1}\n  ]\n}\n"
+  );
+  let invalid = CliqueCover {
+    cliques: vec![vec![0, 2], vec![1], vec![3, 4]],
+  };
+  assert!(g.quotient(&invalid).is_err());
+}
+
+#[test]
+fn canonical_covers_are_sorted() {
+  let mut cover = CliqueCover {
+    cliques: vec![vec![5, 3], vec![], vec![4, 0, 2], vec![1]],
+  };
+  cover.canonicalize();
+  assert_eq!(cover.cliques, [vec![], vec![0, 2, 4], vec![1], vec![3, 5]]);
+  let mut again = CliqueCover {
+    cliques: vec![vec![1], vec![5, 3], vec![2, 4, 0], vec![]],
+  };
+  again.canonicalize();
+  assert_eq!(again.cliques, cover.cliques);
+}
+
+#[test]
+fn inconsistent_covers_are_flagged() {
+  let check = |cliques: Vec<Vec<usize>>| CliqueCover { cliques }.check_consistency();
+  assert!(check(vec![]).is_ok());
+  assert!(check(vec![vec![2, 0], vec![1]]).is_ok());
+  assert!(check(vec![vec![0], vec![]]).is_err());
+  assert!(check(vec![vec![0, 1], vec![1, 0]]).is_err());
+  assert!(check(vec![vec![0, 1], vec![1, 2]]).is_err());
+  assert!(check(vec![vec![0, 0]]).is_err());
+  let mut g = complete_graph(4);
+  g.solve(&params(1)).unwrap();
+  assert!(g.cover().check_consistency().is_ok());
+}
+
+#[test]
+fn permutations_are_checked() {
+  let mut g = path(3);
+  g.set_forbidden(vec![ForbiddenSet {
+    members: vec![0, 1],
+    max_together: 1,
+  }])
+  .unwrap();
+  assert!(g.permute(&[0, 1]).is_err());
+  assert!(g.permute(&[0, 1, 1]).is_err());
+  assert!(g.permute(&[0, 1, 3]).is_err());
+  let (permuted, inverse) = g.permute(&[2, 0, 1]).unwrap();
+  assert_eq!(inverse, [1, 2, 0]);
+  // The forbidden set follows its vertices to 2 and 0.
+  let cover = CliqueCover {
+    cliques: vec![vec![0, 2], vec![1]],
+  };
+  assert!(permuted.validate_cover(&cover).is_err());
+  assert!(g.validate_cover(&cover.relabel(&inverse)).is_err());
+  assert_eq!(Graph::new(0).permute(&[]).unwrap().1, Vec::<usize>::new());
+}
+
+#[test]
+fn diffs_run_length_encode_moves() {
+  let before = CliqueCover {
+    cliques: vec![vec![0, 1, 2, 3], vec![4, 5], vec![6]],
+  };
+  let after = CliqueCover {
+    cliques: vec![vec![0, 1], vec![2, 3, 4, 5], vec![6]],
+  };
+  let diff = before.diff(&after);
+  assert_eq!(diff.moved(), before.distance(&after));
+  assert_eq!(diff.to_string(), "3 -> 3 cliques, 2 moved: 2-3:0>1");
+  let merged = CliqueCover {
+    cliques: vec![vec![0, 1, 2, 3], vec![4, 5, 6]],
+  };
+  assert_eq!(
+    before.diff(&merged).to_string(),
+    "3 -> 2 cliques, 1 moved: 6:2>1"
+  );
+  assert_eq!(before.diff(&before).moved(), 0);
+}
+
+#[test]
+fn repairs_move_only_the_conflicting_vertices() {
+  let g = path(6);
+  let stale = CliqueCover {
+    cliques: vec![vec![0, 1, 2], vec![3, 4, 5, 9]],
+  };
+  let (repaired, repair) = g.repair_cover(&stale);
+  g.validate_cover(&repaired).unwrap();
+  assert_eq!(
+    repaired.cliques,
+    vec![vec![0, 1], vec![3, 4], vec![2], vec![5]]
+  );
+  assert_eq!(repair.dropped, vec![9]);
+  assert_eq!(repair.new_cliques, 2);
+  assert_eq!(
+    repair.placements[0],
+    Placement {
+      vertex: 2,
+      from: Some(0),
+      to: 2
+    }
+  );
+  // Uncovered vertices join cliques that accept them.
+  let partial = CliqueCover {
+    cliques: vec![vec![0], vec![0, 4]],
+  };
+  let (repaired, repair) = g.repair_cover(&partial);
+  g.validate_cover(&repaired).unwrap();
+  assert_eq!(repair.repeated, 1);
+  assert_eq!(repaired.cliques[0], vec![0, 1]);
+  // A valid cover comes back as it was.
+  let (same, repair) = g.repair_cover(&repaired);
+  assert!(repair.is_empty());
+  assert_eq!(same.cliques, repaired.cliques);
+}
+
+#[test]
+fn improving_a_cover_never_makes_it_larger() {
+  let mut rng = default_rng(5);
+  let mut g = get_random_graph_with_k_cliques(30, 4, 0.2, &mut rng).unwrap();
+  let singletons = CliqueCover {
+    cliques: (0..30).map(|v| vec![v]).collect(),
+  };
+  let improvement = g
+    .improve_cover(&singletons, &params(4), Duration::from_secs(60))
+    .unwrap();
+  assert_eq!(improvement.before, 30);
+  g.validate_cover(&improvement.cover).unwrap();
+  assert!(improvement.cover.len() < 30);
+  assert_eq!(g.cliques_ct(), improvement.cover.len());
+  // No rounds leave the cover as it was; an invalid one is refused.
+  let mut none = params(4);
+  none.max_restarts = 0;
+  let same = g.improve_cover(&singletons, &none, Duration::ZERO).unwrap();
+  assert_eq!((same.rounds, same.cover.len()), (0, 30));
+  let broken = CliqueCover {
+    cliques: vec![(0..30).collect()],
+  };
+  assert!(g
+    .improve_cover(&broken, &params(4), Duration::ZERO)
+    .is_err());
+}
+
+#[test]
+fn removing_vertices_keeps_the_cover_valid() {
+  // A triangle 0 1 2 and a vertex 3 adjacent to 0 and 1 but not 2.
+  let g = Graph::from_edge_iter(Some(4), [(0, 1), (0, 2), (1, 2), (0, 3), (1, 3)]).unwrap();
+  let cover = CliqueCover {
+    cliques: vec![vec![0, 1, 2], vec![3]],
+  };
+  // Without 2, vertex 3 (now 2) merges into the triangle's rest.
+  let removal = g.remove_vertices(&cover, &[2]).unwrap();
+  assert_eq!(removal.kept, vec![0, 1, 3]);
+  assert_eq!(removal.cover.cliques, vec![vec![0, 1, 2]]);
+  assert_eq!((removal.emptied, removal.merged), (0, 1));
+  let removal = g.remove_vertices(&cover, &[3]).unwrap();
+  assert_eq!(removal.cover.cliques, vec![vec![0, 1, 2]]);
+  assert_eq!((removal.emptied, removal.merged), (1, 0));
+  assert!(g.remove_vertices(&cover, &[4]).is_err());
+  // Random deletions from a solved graph leave a valid cover no larger.
+  let mut rng = default_rng(9);
+  let mut g = get_random_graph_with_k_cliques(40, 5, 0.3, &mut rng).unwrap();
+  let cover = g.solve(&params(5)).unwrap().best_cover;
+  let removed: Vec<usize> = (0..40).filter(|v| v % 3 == 0).collect();
+  let removal = g.remove_vertices(&cover, &removed).unwrap();
+  assert_eq!(removal.graph.size(), 26);
+  removal.graph.validate_cover(&removal.cover).unwrap();
+  assert!(removal.cover.len() <= cover.len());
+}
+
+#[test]
+fn cliques_draw_in_ascii_in_the_alternate_form() {
+  let mut g = Graph::from_edge_iter(Some(4), [(0, 1), (1, 2)]).unwrap();
+  g.solve(&params(2)).unwrap();
+  let ascii = format!("{:#}", g);
+  assert!(ascii.is_ascii());
+  assert_eq!(ascii.lines().count(), format!("{}", g).lines().count());
+  assert!(ascii.lines().all(|line| line.len() == "#+.. 2".len()));
+}
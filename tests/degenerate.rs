@@ -0,0 +1,191 @@
+// Degenerate inputs: graphs with no, one or two vertices, edgeless and
+// complete graphs, through every search and generator.
+
+mod common;
+
+use common::{complete_graph, params};
+use vcc::decide::{Decide, Decision};
+use vcc::ejection::Ejection;
+use vcc::families::{complement, complete_bipartite, cycle, disjoint_cliques};
+use vcc::lns::Lns;
+use vcc::memetic::{island_search, IslandParams};
+use vcc::multilevel::Multilevel;
+use vcc::multistart::multi_start;
+use vcc::portfolio::Portfolio;
+use vcc::rng::default_rng;
+use vcc::strategy::IteratedGreedy;
+use vcc::tabu::Tabu;
+use vcc::ttt::{format_ttt, time_to_target};
+use vcc::{get_random_graph, get_random_graph_with_k_cliques, Graph};
+
+#[test]
+fn generators_accept_tiny_graphs() {
+  let mut rng = default_rng(1);
+  for n in 0..3 {
+    assert_eq!(get_random_graph(n, 0.5, &mut rng).unwrap().size(), n);
+    for k in 0..5 {
+      let g = get_random_graph_with_k_cliques(n, k, 0.5, &mut rng).unwrap();
+      assert_eq!(g.size(), n);
+      assert_eq!(g.cliques_ct(), n);
+    }
+  }
+}
+
+#[test]
+fn zero_vertices_has_empty_cover() {
+  let mut g = Graph::new(0);
+  g.vcc_greedy();
+  assert_eq!(g.cliques_ct(), 0);
+  let result = g.solve(&params(0)).unwrap();
+  assert!(result.reached_target);
+  assert!(result.best_cover.is_empty());
+  assert_eq!(g.greedy_independent_set_bound(), 0);
+}
+
+#[test]
+fn one_vertex_is_one_clique() {
+  let mut g = Graph::new(1);
+  let result = g.solve(&params(0)).unwrap();
+  assert_eq!(result.best_cover.cliques, vec![vec![0]]);
+}
+
+#[test]
+fn edgeless_graph_stays_singletons() {
+  let mut g = Graph::new(6);
+  let result = g.solve(&params(6)).unwrap();
+  assert!(result.reached_target);
+  assert_eq!(result.best_cover.len(), 6);
+  assert_eq!(result.restarts, 1);
+}
+
+#[test]
+fn complete_graph_is_one_clique() {
+  let mut g = complete_graph(7);
+  let result = g.solve(&params(1)).unwrap();
+  assert!(result.reached_target);
+  assert_eq!(result.best_cover.len(), 1);
+
+  let mut rng = default_rng(2);
+  let mut g = get_random_graph(5, 1.0, &mut rng).unwrap();
+  assert!(g.vcc_run_iterations_to_target(10, 1, 0.0));
+  assert_eq!(g.cliques_ct(), 1);
+}
+
+#[test]
+fn parallel_searches_handle_tiny_graphs() {
+  for n in 0..2 {
+    let g = Graph::new(n);
+    let result = multi_start(&g, &params(n), 2, Some(3)).unwrap();
+    assert_eq!(result.best_cover.len(), n);
+    let result = island_search(
+      &g,
+      &IslandParams {
+        islands: 2,
+        population_size: 2,
+        generations: 4,
+        migration_interval: 2,
+        local_search_iterations: 2,
+        reverse_fraction: 0.0,
+        target: n,
+        seed: 3,
+        min_distance: Some(1),
+      },
+    )
+    .unwrap();
+    assert_eq!(result.best_cover.len(), n);
+  }
+}
+
+#[test]
+fn strategies_handle_tiny_graphs() {
+  for mut g in [Graph::new(0), Graph::new(1), complete_graph(2)] {
+    let mut portfolio = Portfolio {
+      strategies: vec![
+        Box::new(IteratedGreedy {
+          reverse_fraction: 0.0,
+        }),
+        Box::new(Tabu::new(10)),
+        Box::new(Lns { destroy_ct: 3 }),
+        Box::new(Ejection),
+      ],
+      stagnation_window: 2,
+    };
+    let result = portfolio.run(&mut g, 10, 0).unwrap();
+    assert_eq!(result.best_cover.len(), g.size().min(1));
+  }
+}
+
+#[test]
+fn time_to_target_handles_tiny_graphs() {
+  for n in 0..3 {
+    let runs = time_to_target(&Graph::new(n), &params(n), 3, 5).unwrap();
+    assert!(runs.iter().all(|r| r.reached_target && r.cover_size == n));
+    assert_eq!(format_ttt(&runs).lines().count(), 2 + 3);
+  }
+}
+
+#[test]
+fn covers_of_two_cliques_are_settled_exactly() {
+  assert_eq!(complete_graph(4).small_cover().unwrap().len(), 1);
+  assert_eq!(disjoint_cliques(2, 6).small_cover().unwrap().len(), 2);
+  assert_eq!(
+    complement(&complete_bipartite(3, 4))
+      .small_cover()
+      .unwrap()
+      .len(),
+    2
+  );
+  assert!(complement(&cycle(7)).small_cover().is_none());
+  assert!(disjoint_cliques(3, 2).small_cover().is_none());
+
+  // An unreachable target would otherwise keep the search going for the
+  // whole budget.
+  let mut g = disjoint_cliques(2, 40);
+  let mut params = params(1);
+  params.max_iterations = usize::MAX;
+  let result = g.solve(&params).unwrap();
+  assert_eq!(result.best_cover.len(), 2);
+  assert!(!result.reached_target);
+}
+
+#[test]
+fn multilevel_handles_tiny_graphs() {
+  for n in 0..3 {
+    let mut g = Graph::new(n);
+    let result = g
+      .solve_multilevel(&params(0), &Multilevel::default())
+      .unwrap();
+    assert_eq!(result.cover.len(), n);
+    assert_eq!(result.levels, [n]);
+  }
+  assert!("on".parse::<Multilevel>().is_ok());
+  assert!("10:1_000".parse::<Multilevel>().is_ok());
+  assert!("10".parse::<Multilevel>().is_err());
+  assert!("x:10".parse::<Multilevel>().is_err());
+}
+
+#[test]
+fn decisions_settle_tiny_and_known_graphs() {
+  let decide = |g: &Graph, k: usize| {
+    let decide = Decide {
+      k,
+      exact_size: 60,
+      max_nodes: 1_000_000,
+    };
+    g.decide(&decide, &params(0)).unwrap()
+  };
+  for n in 0..3 {
+    let g = Graph::new(n);
+    assert!(matches!(decide(&g, n), Decision::Yes(cover) if cover.len() == n));
+    if n > 0 {
+      assert!(matches!(decide(&g, n - 1), Decision::No { lower_bound } if lower_bound == n));
+    }
+  }
+  // Both need 3 cliques, one more than their largest independent sets.
+  let g = cycle(5);
+  assert!(matches!(decide(&g, 3), Decision::Yes(_)));
+  assert!(matches!(decide(&g, 2), Decision::No { lower_bound: 3 }));
+  let g = complement(&cycle(7));
+  assert!(matches!(decide(&g, 3), Decision::Yes(_)));
+  assert!(matches!(decide(&g, 2), Decision::No { lower_bound: 3 }));
+}
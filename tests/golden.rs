@@ -0,0 +1,244 @@
+// Small instances with known clique cover numbers, from standard families
+// and the complements of tiny DIMACS coloring graphs, some of them read
+// from the files' own formats.
+
+use vcc::families::{
+  complement, complete, complete_bipartite, cycle, disjoint_cliques, mycielski, path, petersen,
+  queen,
+};
+use vcc::formats::GraphFormat;
+use vcc::solve::InitialCover;
+use vcc::{Graph, SolveParams};
+
+// myciel3.col as published.
+const MYCIEL3: &str = "c myciel3\np edge 11 20\n\
+  e 1 2\ne 1 4\ne 1 7\ne 1 9\ne 2 3\ne 2 6\ne 2 8\ne 3 5\ne 3 7\ne 3 10\n\
+  e 4 5\ne 4 6\ne 4 10\ne 5 8\ne 5 9\ne 6 11\ne 7 11\ne 8 11\ne 9 11\ne 10 11\n";
+
+fn golden() -> Vec<(&'static str, Graph, usize)> {
+  let parse = |text, format| Graph::parse(text, format).unwrap();
+  vec![
+    ("K6", complete(6), 1),
+    ("empty 5", Graph::new(5), 5),
+    ("C7", cycle(7), 4),
+    ("C8", cycle(8), 4),
+    ("P9", path(9), 5),
+    ("K3,5", complete_bipartite(3, 5), 5),
+    ("petersen", petersen(), 5),
+    ("petersen complement", complement(&petersen()), 3),
+    ("C7 complement", complement(&cycle(7)), 3),
+    ("C8 complement", complement(&cycle(8)), 2),
+    ("4 x K5", disjoint_cliques(4, 5), 4),
+    ("myciel3 complement", complement(&mycielski(3)), 4),
+    ("myciel4 complement", complement(&mycielski(4)), 5),
+    ("queen5_5 complement", complement(&queen(5)), 5),
+    ("petersen.g6", parse("IheA@GUAo", GraphFormat::Graph6), 5),
+    (
+      "myciel3.col complement",
+      complement(&parse(MYCIEL3, GraphFormat::Dimacs)),
+      4,
+    ),
+  ]
+}
+
+#[test]
+fn exact_solver_matches_known_optima() {
+  for (name, g, optimum) in golden() {
+    let result = g.exact_cover(10_000_000);
+    assert!(result.optimal, "{}: node budget ran out", name);
+    assert_eq!(result.cover.len(), optimum, "{}", name);
+    g.validate_cover(&result.cover).unwrap();
+  }
+}
+
+#[test]
+fn heuristic_reaches_known_optima() {
+  for initial in [InitialCover::Sequential, InitialCover::Fractional] {
+    for (name, mut g, optimum) in golden() {
+      g.seed(0);
+      g.shuffle_active_cliques();
+      let result = g
+        .solve(&SolveParams {
+          max_iterations: 20_000,
+          target: optimum,
+          max_restarts: 20,
+          initial,
+          ..SolveParams::default()
+        })
+        .unwrap();
+      assert!(
+        result.reached_target,
+        "{}: best {}",
+        name,
+        result.best_cover.len()
+      );
+      assert_eq!(result.best_cover.len(), optimum, "{}", name);
+      g.validate_cover(&result.best_cover).unwrap();
+    }
+  }
+}
+
+#[test]
+fn probe_bounds_bracket_known_optima() {
+  for (name, g, optimum) in golden() {
+    let probe = g.probe();
+    assert!(
+      probe.lower_bound <= optimum,
+      "{}: lower bound {}",
+      name,
+      probe.lower_bound
+    );
+    assert!(
+      probe.upper_bound() >= optimum,
+      "{}: upper bound {}",
+      name,
+      probe.upper_bound()
+    );
+    assert!(probe.complement_degeneracy + 1 >= optimum, "{}", name);
+    g.validate_cover(&g.dsatur_cover()).unwrap();
+  }
+}
+
+#[test]
+fn kernels_keep_known_optima() {
+  for (name, g, optimum) in golden() {
+    let kernel = g.kernelize();
+    let reduced = kernel.graph.exact_cover(10_000_000);
+    assert!(reduced.optimal, "{}: node budget ran out", name);
+    let lifted = kernel.lift(&g, &reduced.cover);
+    g.validate_cover(&lifted).unwrap();
+    assert_eq!(lifted.len(), optimum, "{}: {}", name, kernel);
+  }
+  // Trees fall apart into crowns, two C4s into dominated pairs and edges,
+  // and sparse complements peel away.
+  assert_eq!(path(9).kernelize().graph.size(), 0);
+  let mut two_c4s = Graph::new(8);
+  for v in 0..8 {
+    let u = v / 4 * 4 + (v + 1) % 4;
+    two_c4s.add_edge(u, v).unwrap();
+  }
+  two_c4s.conform_cliques_to_vertices();
+  let kernel = two_c4s.kernelize();
+  assert_eq!((kernel.graph.size(), kernel.stats.low_degree), (0, 8));
+  assert_eq!(complement(&path(12)).kernelize().graph.size(), 0);
+}
+
+#[test]
+fn cographs_are_solved_through_their_cotree() {
+  for (name, g, optimum) in golden() {
+    if let Some(cover) = g.cograph_cover() {
+      g.validate_cover(&cover).unwrap();
+      assert_eq!(cover.len(), optimum, "{}", name);
+    }
+  }
+  for g in [
+    complete(6),
+    Graph::new(5),
+    complete_bipartite(3, 5),
+    disjoint_cliques(4, 5),
+    cycle(4),
+  ] {
+    assert!(g.cotree().is_some());
+  }
+  for g in [path(4), cycle(5), petersen()] {
+    assert!(g.cotree().is_none());
+  }
+}
+
+#[test]
+fn fractional_bounds_bracket_known_optima() {
+  for (name, g, optimum) in golden() {
+    let fractional = g.fractional_cover(10_000);
+    assert!(fractional.complete && fractional.optimal, "{}", name);
+    let bound = fractional.lower_bound().unwrap();
+    assert!(bound <= optimum, "{}: {}", name, fractional);
+  }
+  // The odd antihole: independent sets of two, but cliques of three at most.
+  let antihole = complement(&cycle(7)).fractional_cover(10_000);
+  assert_eq!(antihole.lower_bound(), Some(3));
+}
+
+#[test]
+fn lagrangian_bounds_bracket_known_optima() {
+  for (name, g, optimum) in golden() {
+    let lagrangian = g.lagrangian_bound(500);
+    assert!(lagrangian.bound <= optimum, "{}: {}", name, lagrangian);
+    assert!(lagrangian.bound >= lagrangian.independent_set, "{}", name);
+  }
+  // Odd antiholes again, where every independent set has two vertices.
+  let antihole = complement(&cycle(7)).lagrangian_bound(500);
+  assert_eq!((antihole.independent_set, antihole.bound), (2, 3));
+}
+
+#[test]
+fn optimal_covers_satisfy_the_maxsat_encoding() {
+  for (name, g, optimum) in golden() {
+    let slots = g.dsatur_cover().len();
+    let mut cover = g.exact_cover(10_000_000).cover;
+    // Slot k holds the clique with the k-th smallest first vertex.
+    for members in &mut cover.cliques {
+      members.sort_unstable();
+    }
+    cover.cliques.sort_unstable();
+    let mut values = vec![false; (g.size() + 1) * slots];
+    for (k, members) in cover.cliques.iter().enumerate() {
+      values[g.size() * slots + k] = true;
+      for &v in members {
+        values[v * slots + k] = true;
+      }
+    }
+    let wcnf = g.to_wcnf(slots);
+    let top = slots + 1;
+    let mut falsified = 0;
+    for line in wcnf.lines().skip(2) {
+      let numbers: Vec<i64> = line.split(' ').map(|x| x.parse().unwrap()).collect();
+      let satisfied = numbers[1..numbers.len() - 1]
+        .iter()
+        .any(|&l| values[l.unsigned_abs() as usize - 1] == (l > 0));
+      if !satisfied {
+        assert_ne!(numbers[0], top as i64, "{}: hard clause {}", name, line);
+        falsified += 1;
+      }
+    }
+    assert_eq!(falsified, optimum, "{}", name);
+
+    let bits: String = values.iter().map(|&b| if b { '1' } else { '0' }).collect();
+    let output = format!("o {}\ns OPTIMUM FOUND\nv {}\n", optimum, bits);
+    let solution = g.parse_maxsat_output(&output, slots).unwrap();
+    assert!(solution.optimal);
+    assert_eq!(solution.cover.cliques, cover.cliques, "{}", name);
+  }
+}
+
+#[test]
+fn minizinc_answers_are_read_back() {
+  for (name, g, optimum) in golden() {
+    let model = g.to_minizinc(g.dsatur_cover().len());
+    assert!(model.contains("solve minimize used;"), "{}", name);
+    let cover = g.exact_cover(10_000_000).cover;
+    let mut labels = vec![0; g.size()];
+    for (k, members) in cover.cliques.iter().enumerate() {
+      for &v in members {
+        labels[v] = k + 1;
+      }
+    }
+    let output = format!("clique = {:?};\n----------\n==========\n", labels);
+    let read = g.parse_minizinc_output(&output).unwrap();
+    assert_eq!(read.len(), optimum, "{}", name);
+  }
+  let g = cycle(5);
+  assert!(g.parse_minizinc_output("clique = [1, 1, 2, 2, 3];").is_ok());
+  assert!(g.parse_minizinc_output("clique = [1, 1, 1, 2, 2];").is_err());
+  assert!(g.parse_minizinc_output("clique = [1, 2, 3];").is_err());
+}
+
+#[cfg(feature = "ilp")]
+#[test]
+fn ilp_matches_known_optima() {
+  for (name, g, optimum) in golden() {
+    let result = g.ilp_cover(100_000).unwrap();
+    assert!(result.optimal, "{}", name);
+    assert_eq!(result.cover.len(), optimum, "{}", name);
+    g.validate_cover(&result.cover).unwrap();
+  }
+}
@@ -0,0 +1,291 @@
+// Building graphs: adjacency queries, shared topology, graph operations,
+// generators, planted covers and the benchmark families.
+
+mod common;
+
+use common::{complete_graph, params};
+use std::sync::Arc;
+use vcc::bitset::BitVec;
+use vcc::families::{complement, complete, cycle, disjoint_cliques, paley, path, rook, triangular};
+use vcc::planted::{PlantedSizes, SizeShape};
+use vcc::rng::default_rng;
+use vcc::{
+  get_random_graph, get_random_graph_with_k_cliques, get_random_graph_with_planted_cliques, Graph,
+};
+
+#[test]
+fn adjacency_queries_read_the_graph() {
+  let g = path(4);
+  assert!(g.has_edge(1, 2) && g.has_edge(2, 1));
+  assert!(!g.has_edge(0, 2) && !g.has_edge(3, 3));
+  assert_eq!(g.neighbors(1).collect::<Vec<_>>(), [0, 2]);
+  assert_eq!(g.neighbors(3).count(), g.degree(3));
+  assert_eq!(g.edge_count(), 3);
+  assert_eq!(g.density(), 0.5);
+  assert_eq!(complete_graph(5).density(), 1.0);
+  assert_eq!(Graph::new(1).density(), 1.0);
+  assert_eq!(Graph::new(3).edge_count(), 0);
+}
+
+#[test]
+fn clones_share_the_topology_until_edited() {
+  let g = disjoint_cliques(3, 4);
+  let mut worker = g.clone();
+  assert!(Arc::ptr_eq(g.topology(), worker.topology()));
+  let mut fresh = Graph::with_topology(Arc::clone(g.topology()));
+  let result = fresh.solve(&params(3)).unwrap();
+  assert_eq!(result.best_cover.len(), 3);
+  assert!(g.validate_cover(&result.best_cover).is_ok());
+  worker.add_edge(0, 4).unwrap();
+  assert!(!Arc::ptr_eq(g.topology(), worker.topology()));
+  assert!(worker.has_edge(0, 4) && !g.has_edge(0, 4));
+  assert_eq!(
+    Graph::with_topology(Arc::clone(g.topology())).cliques_ct(),
+    12
+  );
+}
+
+#[test]
+fn bit_reads_past_the_length_fail_in_checked_builds() {
+  // 10 bits share their word with padding that reads as clear.
+  let bits = BitVec::ones(10);
+  assert_eq!(bits.get(12), None);
+  assert!(bits.get_unchecked(9));
+  if cfg!(any(debug_assertions, feature = "paranoid")) {
+    assert!(std::panic::catch_unwind(|| bits.get_unchecked(12)).is_err());
+  }
+}
+
+#[test]
+fn sparse_generation_draws_every_pair() {
+  // Sparse graphs are G(n, p) proper: their edge counts are binomial.
+  // 448.5 edges are expected here, with a standard deviation near 21.
+  let mut rng = default_rng(7);
+  let g = get_random_graph(300, 0.01, &mut rng).unwrap();
+  assert!((350..550).contains(&g.edge_count()));
+  // 3 of 190 pairs are sparse enough to be skipped to, 6,000 over all.
+  let mut hit = std::collections::BTreeSet::new();
+  let mut edge_ct = 0;
+  for _ in 0..2_000 {
+    let g = get_random_graph(20, 0.02, &mut rng).unwrap();
+    edge_ct += g.edge_count();
+    for u in 0..20 {
+      hit.extend(g.neighbors(u).filter(|&v| v > u).map(|v| (u, v)));
+    }
+  }
+  assert!((5_600..6_400).contains(&edge_ct));
+  assert_eq!(hit.len(), 190);
+  // Planted cliques are kept whole around the skipped-to edges.
+  let g = get_random_graph_with_k_cliques(400, 40, 0.03, &mut rng).unwrap();
+  assert_eq!(g.verify_against_planted(g.planted().unwrap()).unwrap(), 0);
+  assert!(g.edge_count() > 40 * 45);
+}
+
+#[test]
+fn planted_covers_are_kept_and_checked() {
+  let mut rng = default_rng(3);
+  let g = get_random_graph_with_k_cliques(30, 4, 0.2, &mut rng).unwrap();
+  let planted = g.planted().unwrap().clone();
+  assert_eq!(planted.len(), 4);
+  assert_eq!(g.verify_against_planted(&planted).unwrap(), 0);
+  // Survives a permutation, relabeled.
+  let perm: Vec<usize> = (0..30).rev().collect();
+  let (h, inverse) = g.permute(&perm).unwrap();
+  assert_eq!(
+    h.verify_against_planted(&planted.relabel(&perm)).unwrap(),
+    0
+  );
+  assert_eq!(h.planted().unwrap().relabel(&inverse).distance(&planted), 0);
+  // A cover with more cliques than were planted fails.
+  assert!(g.verify_against_planted(&g.cover()).is_err());
+  assert!(get_random_graph(30, 0.2, &mut rng)
+    .unwrap()
+    .planted()
+    .is_none());
+  assert!(g.complement().planted().is_none());
+}
+
+#[test]
+fn planted_sizes_follow_their_shape() {
+  let shapes = [
+    SizeShape::Geometric { ratio: 0.7 },
+    SizeShape::PowerLaw { exponent: 1.5 },
+  ];
+  for shape in shapes {
+    let sizes = PlantedSizes { shape, min_size: 3 };
+    let clique_sizes = sizes.sizes(100, 8).unwrap();
+    assert_eq!(clique_sizes.iter().sum::<usize>(), 100);
+    assert!(clique_sizes.windows(2).all(|w| w[0] >= w[1]));
+    assert!(clique_sizes[0] > 2 * clique_sizes[7] && clique_sizes[7] >= 3);
+    let mut rng = default_rng(5);
+    let g = get_random_graph_with_planted_cliques(100, 8, &sizes, 0.3, &mut rng).unwrap();
+    let mut planted: Vec<usize> = g
+      .planted()
+      .unwrap()
+      .cliques
+      .iter()
+      .map(|c| c.len())
+      .collect();
+    planted.sort_unstable_by(|a, b| b.cmp(a));
+    assert_eq!(planted, clique_sizes);
+    g.validate_cover(g.planted().unwrap()).unwrap();
+  }
+  // Equal sizes keep the residue classes.
+  let equal = PlantedSizes::default().sizes(10, 4).unwrap();
+  assert_eq!(equal, vec![3, 3, 2, 2]);
+  let g = get_random_graph_with_k_cliques(10, 4, 0.5, &mut default_rng(1)).unwrap();
+  assert_eq!(g.planted().unwrap().cliques[1], vec![1, 5, 9]);
+  let too_large = PlantedSizes {
+    shape: SizeShape::Equal,
+    min_size: 4,
+  };
+  assert!(too_large.sizes(10, 3).is_err());
+  assert!("geometric:0.5".parse::<SizeShape>().is_ok());
+  assert!("zipf:2".parse::<SizeShape>().is_err());
+}
+
+#[test]
+fn planted_noise_breaks_the_planted_cover() {
+  let mut rng = default_rng(11);
+  let mut g = get_random_graph_with_k_cliques(40, 4, 0.3, &mut rng).unwrap();
+  let edges = g.edge_count();
+  let planted = g.planted().unwrap().clone();
+  assert_eq!(g.add_planted_noise(0.0, &mut rng).unwrap(), 0);
+  assert!(g.add_planted_noise(0.25, &mut rng).unwrap() > 0);
+  assert_eq!(g.edge_count(), edges);
+  assert!(g.validate_cover(&planted).is_err());
+  // The planted cover stays as the reference, and larger covers pass.
+  g.vcc_greedy();
+  assert!(g.cover().len() > planted.len());
+  g.verify_against_planted(&g.cover()).unwrap();
+  assert!(g.add_planted_noise(1.5, &mut rng).is_err());
+  let mut plain = get_random_graph(40, 0.3, &mut rng).unwrap();
+  assert!(plain.add_planted_noise(0.1, &mut rng).is_err());
+}
+
+#[test]
+fn sums_and_joins_meet_their_cover_identities() {
+  let theta = |g: &Graph| {
+    let result = g.exact_cover(1_000_000);
+    assert!(result.optimal);
+    result.cover.len()
+  };
+  // Adding the chord 0-2 to P3 closes a triangle.
+  let mut chord = Graph::new(3);
+  chord.add_edge(0, 2).unwrap();
+  let triangle = path(3).union(&chord);
+  assert_eq!((triangle.size(), triangle.edge_count()), (3, 3));
+  assert_eq!(theta(&triangle), 1);
+  // theta(g + h) = theta(g) + theta(h), with h's vertices following g's.
+  let sum = cycle(5).disjoint_sum(&complete(2));
+  assert_eq!((sum.size(), sum.edge_count()), (7, 6));
+  assert!(sum.has_edge(5, 6) && !sum.has_edge(4, 5));
+  assert_eq!(theta(&sum), 3 + 1);
+  // theta(g * h) = max(theta(g), theta(h)).
+  let join = cycle(5).join(&Graph::new(2));
+  assert_eq!(join.edge_count(), 5 + 5 * 2);
+  assert_eq!(theta(&join), 3);
+  // P4 is self-complementary.
+  let complement = path(4).complement();
+  assert_eq!(complement.edge_count(), 3);
+  assert!(complement.has_edge(0, 2) && complement.has_edge(0, 3) && complement.has_edge(1, 3));
+}
+
+#[test]
+fn products_meet_their_cover_bounds() {
+  let theta = |g: &Graph| {
+    let result = g.exact_cover(1_000_000);
+    assert!(result.optimal);
+    result.cover.len()
+  };
+  // K2 x K2 is C4.
+  let square = complete(2).cartesian_product(&complete(2));
+  assert_eq!((square.size(), square.edge_count()), (4, 4));
+  assert_eq!(theta(&square), 2);
+  // 9 vertices, cliques of at most 3.
+  let tensor = complete(3).tensor_product(&complete(3));
+  assert_eq!(tensor.edge_count(), 9 * 4 / 2);
+  assert_eq!(theta(&tensor), 3);
+  // P3 is perfect, so theta(P3[C4]) = theta(P3) theta(C4) = 4.
+  let lex = path(3).lexicographic_product(&cycle(4));
+  assert_eq!(lex.edge_count(), 2 * 16 + 3 * 4);
+  assert_eq!(theta(&lex), 4);
+  assert!(lex.has_edge(0, 4) && lex.has_edge(0, 1) && !lex.has_edge(0, 2));
+}
+
+#[test]
+fn strongly_regular_families_have_their_parameters() {
+  // (vertices, degree, common neighbors of adjacent and non-adjacent pairs)
+  let parameters = |g: &Graph| {
+    let common = |u: usize, v: usize| g.neighbors(u).filter(|&w| g.has_edge(v, w)).count();
+    let n = g.size();
+    let (mut lambda, mut mu) = (None, None);
+    for u in 0..n {
+      assert_eq!(g.degree(u), g.degree(0));
+      for v in (u + 1)..n {
+        let seen = if g.has_edge(u, v) {
+          &mut lambda
+        } else {
+          &mut mu
+        };
+        assert_eq!(*seen.get_or_insert(common(u, v)), common(u, v));
+      }
+    }
+    (n, g.degree(0), lambda.unwrap(), mu.unwrap())
+  };
+  let theta = |g: &Graph| g.exact_cover(10_000_000).cover.len();
+  assert_eq!(parameters(&paley(13).unwrap()), (13, 6, 2, 3));
+  assert_eq!(theta(&paley(5).unwrap()), 3);
+  assert_eq!(theta(&paley(13).unwrap()), 5);
+  assert!(paley(7).is_err() && paley(9).is_err());
+  assert_eq!(parameters(&triangular(6)), (15, 8, 4, 4));
+  assert_eq!(theta(&triangular(6)), 4);
+  assert_eq!(parameters(&rook(4)), (16, 6, 2, 2));
+  assert_eq!(theta(&rook(4)), 4);
+}
+
+#[test]
+fn benchmarks_are_recognized_with_their_optima() {
+  use vcc::families::{mycielski, queen, queen_board};
+  for (g, name) in [(mycielski(4), "myciel4"), (queen(7), "queen7_7")] {
+    let recognized = g.recognize(None).unwrap();
+    assert_eq!(
+      (recognized.instance.name, recognized.complemented),
+      (name, false)
+    );
+    assert!(recognized.cover_number().is_none());
+    let h = complement(&g);
+    let recognized = h.recognize(Some("graphs/other.col")).unwrap();
+    assert_eq!(recognized.instance.name, name);
+    let optimum = recognized.cover_number().unwrap();
+    let result = h.exact_cover(10_000_000);
+    assert!(result.optimal && result.cover.len() == optimum);
+  }
+  for k in 3..=7 {
+    let recognized = mycielski(k).recognize(None).unwrap();
+    assert_eq!(recognized.instance.name, format!("myciel{}", k));
+  }
+  for n in 5..=9 {
+    let recognized = queen(n).recognize(None).unwrap();
+    assert_eq!(recognized.instance.name, format!("queen{}_{}", n, n));
+  }
+  let recognized = queen_board(8, 12).recognize(None).unwrap();
+  assert_eq!(recognized.instance.name, "queen8_12");
+  // The hash ignores vertex numbering, but not the edges themselves: a
+  // graph with myciel3's counts isn't myciel3, whatever its name.
+  let perm: Vec<usize> = (0..23).map(|v| (v * 7) % 23).collect();
+  let (shuffled, _) = mycielski(4).permute(&perm).unwrap();
+  assert_eq!(shuffled.recognize(None).unwrap().instance.name, "myciel4");
+  let first_pairs = |n: usize, m| {
+    let pairs = (0..n).flat_map(move |u| ((u + 1)..n).map(move |v| (u, v)));
+    Graph::from_edge_iter(Some(n), pairs.take(m)).unwrap()
+  };
+  let fake = first_pairs(11, 20);
+  assert!(fake.recognize(Some("myciel3.col")).is_none());
+  assert!(cycle(11).recognize(Some("myciel3.col")).is_none());
+  // Benchmarks without a hash are recognized by name and counts only.
+  let david = first_pairs(87, 406);
+  let recognized = david.recognize(Some("graphs/david.col")).unwrap();
+  assert!(recognized.instance.name == "david" && !recognized.complemented);
+  assert!(david.recognize(None).is_none());
+}
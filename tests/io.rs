@@ -0,0 +1,277 @@
+// Reading and writing graphs and covers: file formats, stdin and stdout,
+// atomic writes, the instance cache and the errors the API returns.
+
+use std::error::Error;
+use vcc::durable::{write_atomic, Fsync};
+use vcc::families::{mycielski, path};
+use vcc::fetch::InstanceCache;
+use vcc::formats::{read_weighted_graph, GraphFormat, Symmetrize, Weights};
+use vcc::verify::{parse_cover, CoverFormat, Violation};
+use vcc::{CliqueCover, Graph, SolveParams, VccError};
+
+#[test]
+fn malformed_graph_files_are_rejected() {
+  let dimacs = |s: &str| Graph::parse(s, GraphFormat::Dimacs);
+  let graph6 = |s: &str| Graph::parse(s, GraphFormat::Graph6);
+  assert_eq!(dimacs("c nothing\np edge 0 0\n").unwrap().size(), 0);
+  assert_eq!(dimacs("p edge 3 9\ne 1 2\ne 2 1\n").unwrap().size(), 3);
+  assert!(dimacs("").is_err());
+  assert!(dimacs("e 1 2\np edge 3 1").is_err());
+  assert!(dimacs("p edge 3 1\np edge 3 1").is_err());
+  assert!(dimacs("p edge 3 1\ne 0 1").is_err());
+  assert!(dimacs("p edge 3 1\ne 1 4").is_err());
+  assert!(dimacs("p edge 3 1\ne 2 2").is_err());
+  assert!(dimacs("p edge 3 1\ne 1 x").is_err());
+  assert!(dimacs("p edge 3 1\nx 1 2").is_err());
+  // Headers claiming more vertices than could be stored are parse errors.
+  assert!(matches!(
+    dimacs("p edge 4000000000 0"),
+    Err(VccError::Parse(_))
+  ));
+  assert!(matches!(graph6("~~~~~~~~"), Err(VccError::Parse(_))));
+  assert!(matches!(graph6("~~??"), Err(VccError::Parse(_))));
+  assert_eq!(graph6("?").unwrap().size(), 0);
+  assert_eq!(graph6(">>graph6<<A_\n").unwrap().size(), 2);
+  assert!(graph6("").is_err());
+  assert!(graph6("A").is_err());
+  assert!(graph6("A__").is_err());
+  assert!(graph6("A ").is_err());
+  assert!(graph6("~").is_err());
+  assert!(dimacs("n 1 2\np edge 3 1").is_err());
+  assert!(dimacs("p edge 3 1\nn 4 2").is_err());
+  assert!(dimacs("p edge 3 1\nn 1 -2").is_err());
+  assert!(dimacs("p edge 3 1\ne 1 2 x").is_err());
+  assert!(GraphFormat::from_path("graph.txt").is_err());
+  assert_eq!(
+    GraphFormat::from_path("dir.v2/graph.COL").unwrap(),
+    GraphFormat::Dimacs
+  );
+}
+
+#[test]
+fn clusterings_label_every_vertex() {
+  let cover = CliqueCover {
+    cliques: vec![vec![2, 0], vec![1], vec![3, 4]],
+  };
+  assert_eq!(cover.labels(), vec![0, 1, 0, 2, 2]);
+  assert_eq!(
+    cover.to_cluster_csv(),
+    "vertex,cluster\n0,0\n1,1\n2,0\n3,2\n4,2\n"
+  );
+  assert!(CliqueCover { cliques: vec![] }.labels().is_empty());
+  let coloring = cover.to_dimacs_coloring();
+  assert!(coloring.ends_with("s col 3\nl 1 1\nl 2 2\nl 3 1\nl 4 3\nl 5 3\n"));
+  let gapped = CliqueCover {
+    cliques: vec![vec![2]],
+  };
+  assert!(gapped.to_dimacs_coloring().ends_with("s col 1\nl 3 1\n"));
+}
+
+#[test]
+fn dimacs_weights_are_read_and_written() {
+  let text = "p edge 3 2\nn 2 5\ne 1 2 7\ne 2 3\n";
+  let (g, weights) = Graph::parse_weighted(text, GraphFormat::Dimacs, Symmetrize::Error).unwrap();
+  assert_eq!(weights.vertex, vec![1, 5, 1]);
+  assert_eq!(weights.edge_weight(1, 0), 7);
+  assert_eq!(weights.edge_weight(1, 2), 1);
+  assert!(!weights.is_unit());
+  assert_eq!(g.to_weighted_dimacs(&weights), text);
+  assert_eq!(
+    g.to_format(GraphFormat::Dimacs),
+    "p edge 3 2\ne 1 2\ne 2 3\n"
+  );
+  let (_, unit) = Graph::parse_weighted("B?", GraphFormat::Graph6, Symmetrize::Error).unwrap();
+  assert_eq!(unit, Weights::unit(3));
+}
+
+#[test]
+fn arcs_follow_the_symmetrization() {
+  let text = "p edge 4 4\na 1 2 3\na 2 1 5\na 2 3\ne 3 4\n";
+  let read = |symmetrize| Graph::parse_weighted(text, GraphFormat::Dimacs, symmetrize);
+  assert!(read(Symmetrize::Error).is_err());
+  assert!(Graph::parse(text, GraphFormat::Dimacs).is_err());
+  let (union, weights) = read(Symmetrize::Union).unwrap();
+  assert_eq!(
+    union.to_weighted_dimacs(&weights),
+    "p edge 4 3\ne 1 2 5\ne 2 3\ne 3 4\n"
+  );
+  let (intersection, weights) = read(Symmetrize::Intersection).unwrap();
+  assert_eq!(
+    intersection.to_weighted_dimacs(&weights),
+    "p edge 4 2\ne 1 2 5\ne 3 4\n"
+  );
+  let arc = |line: &str| {
+    Graph::parse_weighted(
+      &format!("p edge 3 1\n{}", line),
+      GraphFormat::Dimacs,
+      Symmetrize::Union,
+    )
+  };
+  assert!(arc("a 1 1").is_err());
+  assert!(arc("a 1 4").is_err());
+  assert!(arc("a 1 2 x").is_err());
+  assert!("both".parse::<Symmetrize>().is_err());
+}
+
+#[test]
+fn streamed_edges_are_checked() {
+  assert_eq!(Graph::from_edge_iter(None, []).unwrap().size(), 0);
+  assert_eq!(Graph::from_edge_iter(Some(4), []).unwrap().size(), 4);
+  assert!(Graph::from_edge_iter(Some(3), [(0, 3)]).is_err());
+  assert!(Graph::from_edge_iter(None, [(0, 1), (2, 2)]).is_err());
+  let g = Graph::from_edge_iter(None, [(0, 1), (5, 1), (1, 0)]).unwrap();
+  assert_eq!(g.size(), 6);
+  assert_eq!(
+    g.to_format(GraphFormat::Dimacs),
+    "p edge 6 2\ne 1 2\ne 2 6\n"
+  );
+}
+
+#[test]
+fn third_party_covers_are_read_and_checked() {
+  let g = path(4);
+  let csv = parse_cover(
+    "vertex,cluster\n2,x\n3,x\n0,y\n1,y\n",
+    CoverFormat::ClusterCsv,
+  )
+  .unwrap();
+  let sol = parse_cover(
+    "s col 2\nl 1 1\nl 2 1\nl 3 2\nl 4 2\n",
+    CoverFormat::DimacsColoring,
+  )
+  .unwrap();
+  let json = parse_cover("{\"cliques\": [[0, 1], [2, 3]]}", CoverFormat::Json).unwrap();
+  for cover in [&csv, &sol, &json] {
+    assert!(g.cover_violations(cover).is_empty());
+    assert_eq!(cover.distance(&json), 0);
+  }
+  let labels = parse_cover("[0, 0, 0, null]", CoverFormat::Json).unwrap();
+  let violations = g.cover_violations(&labels);
+  assert_eq!(
+    violations,
+    vec![
+      Violation::NonAdjacent {
+        clique: 0,
+        u: 0,
+        v: 2
+      },
+      Violation::Uncovered { vertex: 3 },
+    ]
+  );
+  assert_eq!(
+    violations[0].named(1).to_string(),
+    "clique 0 holds non-adjacent vertices 1 and 3"
+  );
+  let twice = CliqueCover {
+    cliques: vec![vec![0, 1], vec![1, 2, 3], vec![], vec![7]],
+  };
+  assert_eq!(g.cover_violations(&twice).len(), 4);
+  assert!(parse_cover("0;1\n", CoverFormat::ClusterCsv).is_err());
+}
+
+#[test]
+fn stdio_paths_need_a_graph_format_but_take_csv_covers() {
+  assert!(GraphFormat::from_path("-").is_err());
+  assert_eq!(
+    "dimacs".parse::<GraphFormat>().unwrap(),
+    GraphFormat::Dimacs
+  );
+  assert_eq!(
+    "graph6".parse::<GraphFormat>().unwrap(),
+    GraphFormat::Graph6
+  );
+  assert!("g6".parse::<GraphFormat>().is_err());
+  assert_eq!(
+    CoverFormat::from_path("-").unwrap(),
+    CoverFormat::ClusterCsv
+  );
+}
+
+#[test]
+fn atomic_writes_replace_files_whole() {
+  let dir = std::env::temp_dir().join(format!("vcc-durable-{}", std::process::id()));
+  std::fs::create_dir_all(&dir).unwrap();
+  let path = dir.join("cover.csv");
+  write_atomic(&path, "0,0\n").unwrap();
+  write_atomic(&path, "0,0\n1,1\n").unwrap();
+  assert_eq!(std::fs::read_to_string(&path).unwrap(), "0,0\n1,1\n");
+  // Failed writes leave no temporary files behind.
+  assert!(write_atomic(dir.join("missing").join("cover.csv"), "").is_err());
+  assert!(write_atomic(&dir, "").is_err());
+  assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+  assert_eq!("full".parse::<Fsync>().unwrap(), Fsync::Full);
+  assert!("always".parse::<Fsync>().is_err());
+  std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn cached_instances_are_checked_against_their_checksums() {
+  let dir = std::env::temp_dir().join(format!("vcc-fetch-{}", std::process::id()));
+  std::fs::create_dir_all(&dir).unwrap();
+  std::fs::write(dir.join("tiny.col"), "p edge 2 1\ne 1 2\n").unwrap();
+  // Cached files aren't downloaded, so no URL is needed.
+  let mut cache = InstanceCache::open(&dir).unwrap();
+  assert_eq!(cache.fetch("tiny.col", "").unwrap(), dir.join("tiny.col"));
+  assert!(cache.fetch("../tiny.col", "").is_err());
+  std::fs::write(dir.join("tiny.col"), "p edge 2 0\n").unwrap();
+  let mut reopened = InstanceCache::open(&dir).unwrap();
+  assert!(reopened.fetch("tiny.col", "").is_err());
+  // Standard instances have to be their benchmark, checksum or not, in
+  // whatever edge order and with whatever comments.
+  let myciel3 = mycielski(3).to_format(GraphFormat::Dimacs);
+  let mut lines: Vec<&str> = myciel3.lines().collect();
+  lines[1..].reverse();
+  let reordered = format!("c from a mirror\n{}\n", lines.join("\n"));
+  std::fs::write(dir.join("myciel3.col"), &reordered).unwrap();
+  assert!(reopened.fetch("myciel3.col", "").is_ok());
+  std::fs::remove_file(dir.join("checksums.txt")).unwrap();
+  let truncated = myciel3.lines().take(10).collect::<Vec<_>>().join("\n");
+  std::fs::write(dir.join("myciel3.col"), truncated).unwrap();
+  let mut fresh = InstanceCache::open(&dir).unwrap();
+  assert!(fresh.fetch("myciel3.col", "").is_err());
+  let lookalike = "p edge 11 20\n".to_string()
+    + &(2..=11)
+      .flat_map(|v| [format!("e 1 {}", v), format!("e {} {}", v, v % 10 + 2)])
+      .collect::<Vec<_>>()
+      .join("\n");
+  std::fs::write(dir.join("myciel3.col"), lookalike).unwrap();
+  assert!(fresh.fetch("myciel3.col", "").is_err());
+  std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn failures_come_back_as_errors_of_their_kind() {
+  let Err(missing) = read_weighted_graph("no/such/graph.col", Symmetrize::Error) else {
+    panic!("read a missing file");
+  };
+  assert!(matches!(missing, VccError::Io(_)));
+  assert!(missing.to_string().starts_with("io error: "));
+  assert!(missing.source().is_some());
+  let Err(malformed) = Graph::parse("p edge 3 1\ne 1 x", GraphFormat::Dimacs) else {
+    panic!("parsed a malformed file");
+  };
+  assert!(matches!(malformed, VccError::Parse(_)));
+  assert!(malformed.source().is_none());
+  let mut g = path(3);
+  let out_of_range = g.add_edge(0, 3).unwrap_err();
+  assert!(matches!(out_of_range, VccError::InvalidGraph(_)));
+  assert_eq!(
+    out_of_range.to_string(),
+    "invalid graph: edge (0, 3) in a graph with 3 vertices"
+  );
+  let not_a_clique = CliqueCover {
+    cliques: vec![vec![0, 2], vec![1]],
+  };
+  assert!(matches!(
+    path(3).validate_cover(&not_a_clique),
+    Err(VccError::InvalidCover(_))
+  ));
+  let bad_params = SolveParams {
+    reverse_fraction: 2.0,
+    ..SolveParams::default()
+  };
+  assert!(matches!(
+    path(3).solve(&bad_params),
+    Err(VccError::InvalidParameter(_))
+  ));
+}
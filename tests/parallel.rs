@@ -0,0 +1,185 @@
+// Searches over several configurations, strategies or workers: racing,
+// portfolios, island models, shared bounds, thread pools and seeded runs
+// that don't depend on the thread count.
+
+use vcc::families::cycle;
+use vcc::memetic::{crossover, island_search, IslandParams};
+use vcc::multistart::multi_start;
+use vcc::portfolio::Portfolio;
+use vcc::race::{race, RaceParams};
+use vcc::rng::default_rng;
+use vcc::shared::SharedBounds;
+use vcc::strategy::IteratedGreedy;
+use vcc::tabu::Tabu;
+use vcc::threads::ThreadConfig;
+use vcc::{get_random_graph_with_k_cliques, SolveParams};
+
+#[test]
+fn races_stop_at_the_first_config_to_reach_its_target() {
+  let mut rng = default_rng(4);
+  let mut g = get_random_graph_with_k_cliques(30, 3, 0.3, &mut rng).unwrap();
+  g.seed(5);
+  let config = |target| SolveParams {
+    max_iterations: 2_000,
+    target,
+    ..SolveParams::default()
+  };
+  let race_params = RaceParams {
+    min_rounds: 2,
+    max_rounds: 10,
+    z: 2.0,
+  };
+  let outcome = race(&g, &[config(0), config(3)], &race_params).unwrap();
+  assert_eq!((outcome.winner, outcome.rounds), (1, 1));
+  assert!(outcome.reached_target && outcome.best_cover.len() <= 3);
+  g.validate_cover(&outcome.best_cover).unwrap();
+  // Out of reach: every round is run, and configs scoring alike all survive.
+  let outcome = race(&cycle(5), &[config(0), config(0)], &race_params).unwrap();
+  assert_eq!((outcome.winner, outcome.rounds), (0, 10));
+  assert!(!outcome.reached_target && outcome.eliminated.is_empty());
+  assert_eq!(outcome.best_cover.len(), 3);
+  assert!(race(&g, &[], &race_params).is_err());
+}
+
+#[test]
+fn portfolios_rotate_strategies_on_stagnation() {
+  let mut portfolio = Portfolio {
+    strategies: vec![
+      Box::new(IteratedGreedy {
+        reverse_fraction: 0.0,
+      }),
+      Box::new(Tabu::new(10)),
+    ],
+    stagnation_window: 5,
+  };
+  // C5's optimum is 3, so a target of 2 keeps the portfolio stagnating.
+  let mut g = cycle(5);
+  g.seed(1);
+  let result = portfolio.run(&mut g, 40, 2).unwrap();
+  assert!(!result.reached_target);
+  assert_eq!((result.best_cover.len(), result.steps), (3, 40));
+  let names: Vec<&str> = result.switches.iter().map(|&(_, name)| name).collect();
+  assert!(names.len() >= 2);
+  assert!(names.iter().step_by(2).all(|&name| name == "tabu"));
+  assert!(names
+    .iter()
+    .skip(1)
+    .step_by(2)
+    .all(|&name| name == "iterated greedy"));
+  for pair in result.switches.windows(2) {
+    assert_eq!(pair[1].0 - pair[0].0, 5);
+  }
+  // Handed a reachable target, it stops as soon as it gets there.
+  let mut g = cycle(5);
+  let result = portfolio.run(&mut g, 40, 3).unwrap();
+  assert!(result.reached_target && result.steps < 40);
+  assert!(Portfolio {
+    strategies: vec![],
+    stagnation_window: 5,
+  }
+  .run(&mut cycle(5), 40, 3)
+  .is_err());
+}
+
+#[test]
+fn seeded_islands_repeat_their_results() {
+  let mut rng = default_rng(6);
+  let g = get_random_graph_with_k_cliques(30, 3, 0.3, &mut rng).unwrap();
+  let mut island_params = IslandParams {
+    islands: 3,
+    population_size: 4,
+    generations: 20,
+    migration_interval: 5,
+    local_search_iterations: 50,
+    reverse_fraction: 0.0,
+    target: 0,
+    seed: 11,
+    min_distance: None,
+  };
+  let first = island_search(&g, &island_params).unwrap();
+  let second = island_search(&g, &island_params).unwrap();
+  assert!(!first.reached_target);
+  assert_eq!(first.generations, 20);
+  assert_eq!(first.island_bests, second.island_bests);
+  assert_eq!(first.best_cover.cliques, second.best_cover.cliques);
+  // More islands than threads still run, to the same result.
+  let single_thread = ThreadConfig {
+    threads: Some(1),
+    pin_cores: false,
+  };
+  let pooled = single_thread
+    .install(|| island_search(&g, &island_params).unwrap())
+    .unwrap();
+  assert_eq!(first.best_cover.cliques, pooled.best_cover.cliques);
+  g.validate_cover(&first.best_cover).unwrap();
+  assert_eq!(
+    first.island_bests.iter().min(),
+    Some(&first.best_cover.len())
+  );
+  // Children of two covers are covers.
+  let child = crossover(&first.best_cover, &g.cover(), g.size());
+  g.validate_cover(&child).unwrap();
+  island_params.population_size = 0;
+  assert!(island_search(&g, &island_params).is_err());
+}
+
+#[test]
+fn shared_bounds_keep_the_best_offers_from_every_thread() {
+  let bounds = SharedBounds::new();
+  assert!(!bounds.is_done(usize::MAX - 1));
+  std::thread::scope(|scope| {
+    for t in 0..8 {
+      let bounds = &bounds;
+      scope.spawn(move || {
+        for size in (10 + t..100).rev() {
+          bounds.offer_upper(size);
+          bounds.offer_lower(size / 10);
+        }
+      });
+    }
+  });
+  assert_eq!((bounds.upper(), bounds.lower()), (10, 9));
+  // Offers only ever tighten the bounds.
+  assert!(!bounds.offer_upper(10) && !bounds.offer_upper(50));
+  assert!(!bounds.offer_lower(3));
+  assert!(!bounds.is_done(9));
+  assert!(bounds.offer_lower(10) && bounds.is_done(9));
+  assert!(bounds.offer_upper(8) && bounds.is_done(8));
+}
+
+#[test]
+fn seeded_multi_starts_ignore_the_thread_count() {
+  let mut rng = default_rng(8);
+  let g = get_random_graph_with_k_cliques(40, 4, 0.3, &mut rng).unwrap();
+  // Out of reach, so no worker stops on another's progress.
+  let params = SolveParams {
+    max_iterations: 2_000,
+    target: 0,
+    max_restarts: 2,
+    ..SolveParams::default()
+  };
+  let run = |threads| {
+    let config = ThreadConfig {
+      threads: Some(threads),
+      pin_cores: false,
+    };
+    config
+      .install(|| multi_start(&g, &params, 4, Some(9)).unwrap())
+      .unwrap()
+  };
+  let single = run(1);
+  let pooled = run(4);
+  assert_eq!(single.best_cover.cliques, pooled.best_cover.cliques);
+  assert_eq!((single.restarts, pooled.restarts), (4 * 2, 4 * 2));
+  g.validate_cover(&single.best_cover).unwrap();
+}
+
+#[test]
+fn thread_configs_size_the_pool_their_drivers_run_in() {
+  let config = ThreadConfig {
+    threads: Some(3),
+    pin_cores: true,
+  };
+  assert_eq!(config.build_pool().unwrap().current_num_threads(), 3);
+  assert_eq!(config.install(rayon::current_num_threads).unwrap(), 3);
+}
@@ -0,0 +1,843 @@
+// Runs random sequences of public operations on random graphs and checks
+// the search state's invariants after every one of them.
+
+use proptest::prelude::*;
+use proptest::test_runner::TestCaseError;
+use vcc::anneal::{AnnealingSchedule, DonorPolicy};
+use vcc::bitset::{BitSet, Blocked, Words};
+use vcc::cadence::CheckCadence;
+use vcc::cover::CliqueCover;
+use vcc::decide::{Decide, Decision};
+use vcc::ejection::Ejection;
+use vcc::finish::ExactFinish;
+use vcc::forbidden::ForbiddenSet;
+use vcc::formats::GraphFormat;
+use vcc::freeze::Freeze;
+use vcc::links::LinkConstraints;
+use vcc::lns::Lns;
+use vcc::memetic::crossover;
+use vcc::multilevel::Multilevel;
+use vcc::order::CliqueOrder;
+use vcc::pareto::ParetoFront;
+use vcc::rng::default_rng;
+use vcc::solve::InitialCover;
+use vcc::starts::DistinctStarts;
+use vcc::strategy::SearchStrategy;
+use vcc::tabu::{Aspiration, Tabu, TenurePolicy};
+use vcc::{get_random_graph, get_random_graph_with_k_cliques, solve_batch, Graph, SolveParams};
+
+#[derive(Clone, Debug)]
+enum Op {
+  Greedy,
+  IteratedGreedy(f64),
+  OrderedGreedy(f64, bool, f64),
+  FrozenGreedy(usize, usize),
+  Shuffle,
+  Reverse,
+  Conform,
+  RunIterations(usize, f64),
+  // Iterations per restart, iterations before the first annealing, and
+  // vertices seeding each annealed clique.
+  Solve(usize, usize, usize, DonorPolicy),
+  // Steps, reactive tenure, aspiration.
+  Tabu(usize, bool, bool),
+  Lns(usize, usize),
+  Eject(usize),
+  // Residual vertex cap.
+  ExactFinish(usize),
+  Crossover,
+  Complement,
+  Quotient,
+}
+
+fn op() -> impl Strategy<Value = Op> {
+  prop_oneof![
+    Just(Op::Greedy),
+    (0.0..=1.0).prop_map(Op::IteratedGreedy),
+    (0.0..=1.0, any::<bool>(), 0.0..3.0).prop_map(|(r, l, n)| Op::OrderedGreedy(r, l, n)),
+    (0..5usize, 0..10usize).prop_map(|(k, n)| Op::FrozenGreedy(k, n)),
+    Just(Op::Shuffle),
+    Just(Op::Reverse),
+    Just(Op::Conform),
+    (0..50usize, 0.0..=1.0).prop_map(|(n, r)| Op::RunIterations(n, r)),
+    (0..200usize, 1..20usize, 1..5usize, donor_policy())
+      .prop_map(|(n, a, s, d)| Op::Solve(n, a, s, d)),
+    (0..50usize, any::<bool>(), any::<bool>()).prop_map(|(n, r, a)| Op::Tabu(n, r, a)),
+    (0..10usize, 0..5usize).prop_map(|(n, d)| Op::Lns(n, d)),
+    (0..5usize).prop_map(Op::Eject),
+    (0..30usize).prop_map(Op::ExactFinish),
+    Just(Op::Crossover),
+    Just(Op::Complement),
+    Just(Op::Quotient),
+  ]
+}
+
+fn donor_policy() -> impl Strategy<Value = DonorPolicy> {
+  prop_oneof![
+    Just(DonorPolicy::Random),
+    Just(DonorPolicy::Largest),
+    Just(DonorPolicy::MostConflicted),
+    Just(DonorPolicy::SizeWeighted),
+  ]
+}
+
+// (vertices, planted cliques, edge probability, seed)
+fn instance() -> impl Strategy<Value = (usize, usize, f64, u64)> {
+  (0..40usize, 0..8usize, 0.0..=1.0, any::<u64>())
+}
+
+fn graph((n, k, p, seed): (usize, usize, f64, u64)) -> Graph {
+  get_random_graph_with_k_cliques(n, k, p, &mut default_rng(seed)).unwrap()
+}
+
+// The cover's cliques in canonical order.
+fn canonical(cover: &CliqueCover) -> Vec<Vec<usize>> {
+  let mut cover = cover.clone();
+  cover.canonicalize();
+  cover.cliques
+}
+
+fn check(g: &Graph, op: &Op) -> Result<(), TestCaseError> {
+  g.check_invariants()
+    .and_then(|_| g.validate_cover(&g.cover()))
+    .map_err(|e| TestCaseError::fail(format!("after {:?}: {}", op, e)))?;
+  // Snapshots hold the same cover as the search state.
+  prop_assert_eq!(canonical(&g.snapshot().to_cover()), canonical(&g.cover()));
+  Ok(())
+}
+
+fn apply(g: &mut Graph, op: &Op) -> Result<(), TestCaseError> {
+  match *op {
+    Op::Greedy => g.vcc_greedy(),
+    Op::IteratedGreedy(reverse_fraction) => g.vcc_iterated_greedy(reverse_fraction),
+    Op::OrderedGreedy(reverse_fraction, largest_first, noise) => {
+      let order = if largest_first {
+        CliqueOrder::LargestFirst { noise }
+      } else {
+        CliqueOrder::SmallestFirst { noise }
+      };
+      let before = g.cliques_ct();
+      g.vcc_iterated_greedy_ordered(reverse_fraction, &order);
+      prop_assert!(g.cliques_ct() <= before);
+    }
+    Op::FrozenGreedy(k, passes) => {
+      // The same k cliques freeze_cliques picks: largest first, earlier
+      // cliques winning ties.
+      let mut frozen = g.cover().cliques;
+      frozen.sort_by_key(|c| std::cmp::Reverse(c.len()));
+      frozen.truncate(k);
+      g.freeze_cliques(&Freeze::Largest(k));
+      prop_assert_eq!(g.frozen_ct(), frozen.len());
+      for _ in 0..passes {
+        g.vcc_iterated_greedy(0.5);
+        check(g, op)?;
+      }
+      let cover = g.cover();
+      for members in &frozen {
+        prop_assert!(cover
+          .cliques
+          .iter()
+          .any(|c| members.iter().all(|v| c.contains(v))));
+      }
+      g.unfreeze_cliques();
+    }
+    Op::Shuffle => g.shuffle_active_cliques(),
+    Op::Reverse => g.reverse_active_cliques(),
+    Op::Conform => g.conform_cliques_to_vertices(),
+    Op::RunIterations(iterations, reverse_fraction) => {
+      g.vcc_run_iterations_to_target(iterations, 0, reverse_fraction);
+    }
+    Op::Solve(max_iterations, anneal_iterations, seed_size, donor) => {
+      let before = g.cliques_ct();
+      let result = g
+        .solve(&SolveParams {
+          max_iterations,
+          max_restarts: 2,
+          annealing: AnnealingSchedule {
+            initial_iterations: anneal_iterations,
+            growth: 0.5,
+            annealings_per_slowdown: 2,
+            seed_size,
+            donor,
+          },
+          ..SolveParams::default()
+        })
+        .unwrap();
+      prop_assert!(result.best_cover.len() <= before.max(1));
+      prop_assert!(g.validate_cover(&result.best_cover).is_ok());
+    }
+    Op::Tabu(steps, reactive, aspiration) => {
+      let mut tabu = Tabu::new(10);
+      if reactive {
+        tabu.tenure_policy = TenurePolicy::Reactive {
+          increase: 0.5,
+          decrease: 0.5,
+          window: 5,
+        };
+      }
+      if aspiration {
+        tabu.aspiration = Aspiration::NewBest;
+      }
+      tabu.start(g);
+      for _ in 0..steps {
+        tabu.step(g);
+        check(g, op)?;
+      }
+    }
+    Op::Lns(steps, destroy_ct) => {
+      let mut lns = Lns { destroy_ct };
+      for _ in 0..steps {
+        let before = g.cliques_ct();
+        lns.step(g);
+        check(g, op)?;
+        prop_assert!(g.cliques_ct() <= before);
+      }
+    }
+    Op::Eject(steps) => {
+      let mut ejection = Ejection;
+      for _ in 0..steps {
+        let before = g.cliques_ct();
+        let cliques = g.cover().cliques;
+        ejection.step(g);
+        check(g, op)?;
+        if g.cliques_ct() == before {
+          prop_assert_eq!(g.cover().cliques, cliques);
+        } else {
+          prop_assert_eq!(g.cliques_ct() + 1, before);
+        }
+      }
+    }
+    Op::ExactFinish(max_vertices) => {
+      let before = g.cliques_ct();
+      let improved = g.exact_finish(&ExactFinish {
+        max_vertices,
+        ..ExactFinish::default()
+      });
+      prop_assert_eq!(improved, g.cliques_ct() < before);
+      prop_assert!(g.cliques_ct() <= before);
+    }
+    Op::Crossover => {
+      let a = g.cover();
+      g.conform_cliques_to_vertices();
+      g.shuffle_active_cliques();
+      g.vcc_greedy();
+      let child = crossover(&a, &g.cover(), g.size());
+      g.load_cover(&child).unwrap();
+    }
+    Op::Complement => {
+      let mut complement = g.complement();
+      complement.conform_cliques_to_vertices();
+      check(&complement, op)?;
+      complement.vcc_greedy();
+      check(&complement, op)?;
+    }
+    Op::Quotient => {
+      let cover = g.cover();
+      let quotient = g.quotient(&cover).unwrap();
+      prop_assert_eq!(quotient.cliques.len(), cover.len());
+    }
+  }
+  check(g, op)
+}
+
+proptest! {
+  #[test]
+  fn invariants_hold_after_every_operation(
+    instance in instance(),
+    ops in prop::collection::vec(op(), 1..20),
+  ) {
+    let mut g = graph(instance);
+    check(&g, &Op::Conform)?;
+    for op in &ops {
+      apply(&mut g, op)?;
+    }
+  }
+
+  #[test]
+  fn dense_generators_draw_exactly_the_target_edges(instance in instance()) {
+    let (n, k, p, seed) = instance;
+    let pairs = n * n.saturating_sub(1) / 2;
+    let target = (pairs as f64 * p) as usize;
+    // Sparse graphs skip to their edges instead, and their counts are
+    // binomial about the target.
+    let dense = |edges: usize, candidates: usize| edges * 32 >= candidates;
+    let g = get_random_graph(n, p, &mut default_rng(seed)).unwrap();
+    if dense(target, pairs) {
+      prop_assert_eq!(g.edge_count(), target);
+    }
+    // Planted cliques count towards the target, and may exceed it.
+    let planted = graph(instance);
+    let reserved = match planted.planted() {
+      Some(cover) => cover.cliques.iter().map(|c| c.len() * c.len().saturating_sub(1) / 2).sum(),
+      None => 0,
+    };
+    prop_assert_eq!(planted.planted().map_or(0, |cover| cover.len()), k.min(n));
+    if dense(target.saturating_sub(reserved), pairs - reserved) {
+      prop_assert_eq!(planted.edge_count(), target.max(reserved));
+    } else {
+      prop_assert!(planted.edge_count() >= reserved);
+    }
+  }
+
+  #[test]
+  fn greedy_covers_are_valid(instance in instance(), seed in any::<u64>()) {
+    let mut g = graph(instance);
+    g.seed(seed);
+    g.vcc_greedy();
+    check(&g, &Op::Greedy)?;
+    for c in &g.cover().cliques {
+      prop_assert!(!c.is_empty());
+    }
+  }
+
+  #[test]
+  fn distance_separates_partitions(instance in instance(), seed in any::<u64>()) {
+    let mut g = graph(instance);
+    g.seed(seed);
+    g.vcc_greedy();
+    let a = g.cover();
+    let mut reordered = a.clone();
+    reordered.cliques.reverse();
+    for members in &mut reordered.cliques {
+      members.reverse();
+    }
+    prop_assert_eq!(a.distance(&reordered), 0);
+    g.vcc_iterated_greedy(1.0);
+    let b = g.cover();
+    let d = a.distance(&b);
+    prop_assert!(d <= g.size());
+    prop_assert_eq!(d == 0, canonical(&a) == canonical(&b));
+    // The diff moves exactly the vertices distance counts.
+    let diff = a.diff(&b);
+    prop_assert_eq!(diff.moved(), d);
+    for (i, run) in diff.runs.iter().enumerate() {
+      prop_assert!(i == 0 || diff.runs[i - 1].last < run.first);
+      for v in run.first..=run.last {
+        prop_assert!(a.cliques[run.from].contains(&v));
+        prop_assert!(b.cliques[run.to].contains(&v));
+      }
+    }
+  }
+
+  #[test]
+  fn replayed_restarts_match_the_log(
+    instance in instance(),
+    seed in any::<u64>(),
+    distinct in any::<bool>(),
+  ) {
+    let params = SolveParams {
+      max_iterations: 50,
+      reverse_fraction: 0.5,
+      target: 0,
+      max_restarts: 4,
+      annealing: AnnealingSchedule {
+        initial_iterations: 10,
+        ..AnnealingSchedule::default()
+      },
+      reverse_decay: None,
+      order: CliqueOrder::Shuffle,
+      freeze: None,
+      initial: InitialCover::Sequential,
+      finish: None,
+      // A small filter, so that replays see its false positives too.
+      distinct_starts: distinct.then_some(DistinctStarts {
+        bits: 64,
+        perturbations: 1,
+      }),
+      check: CheckCadence::default(),
+    };
+    let mut g = graph(instance);
+    g.seed(seed);
+    let log = g.solve(&params).unwrap().restart_log;
+    for record in &log {
+      let mut replay = graph(instance);
+      replay.seed(seed);
+      let replayed = replay.replay_restart(&params, None, record.restart).unwrap();
+      prop_assert_eq!(replayed.seed, record.seed);
+      prop_assert_eq!(replayed.cliques, record.cliques);
+      prop_assert_eq!(replayed.skipped, record.skipped);
+    }
+  }
+
+  #[test]
+  fn kernels_keep_the_optimum(
+    instance in (0..16usize, 0..5usize, 0.0..=1.0, any::<u64>()),
+  ) {
+    let g = graph(instance);
+    let kernel = g.kernelize();
+    let whole = g.exact_cover(1_000_000);
+    let reduced = kernel.graph.exact_cover(1_000_000);
+    prop_assert!(whole.optimal && reduced.optimal);
+    let lifted = kernel.lift(&g, &reduced.cover);
+    prop_assert!(g.validate_cover(&lifted).is_ok());
+    prop_assert_eq!(lifted.len(), whole.cover.len());
+    prop_assert_eq!(reduced.cover.len() + kernel.fixed_cliques, whole.cover.len());
+  }
+
+  // Constraints taken from an optimal cover, one of its cliques
+  // pre-assigned, keep it feasible, so the constrained optimum is the same.
+  #[test]
+  fn link_constraints_are_kept(
+    instance in (1..16usize, 0..5usize, 0.0..=1.0, any::<u64>()),
+    pairs in proptest::collection::vec((any::<usize>(), any::<usize>()), 0..10),
+    pin in any::<usize>(),
+  ) {
+    let g = graph(instance);
+    let whole = g.exact_cover(1_000_000);
+    let mut clique_of = vec![0; g.size()];
+    for (c, members) in whole.cover.cliques.iter().enumerate() {
+      for &v in members {
+        clique_of[v] = c;
+      }
+    }
+    let mut links = LinkConstraints::default();
+    for (u, v) in pairs {
+      let (u, v) = (u % g.size(), v % g.size());
+      if u == v {
+        continue;
+      }
+      if clique_of[u] == clique_of[v] {
+        links.must_link.push((u, v));
+      } else {
+        links.cannot_link.push((u, v));
+      }
+    }
+    let pinned = &whole.cover.cliques[pin % whole.cover.len()];
+    links.must_link.retain(|&(u, v)| pinned.contains(&u) == pinned.contains(&v));
+    links.pinned.push(pinned.clone());
+    let constrained = g.constrained(&links).unwrap();
+    let reduced = constrained.graph.exact_cover(1_000_000);
+    prop_assert!(whole.optimal && reduced.optimal);
+    let lifted = constrained.lift(&reduced.cover);
+    prop_assert!(g.validate_cover(&lifted).is_ok());
+    prop_assert!(links.check(&lifted).is_ok());
+    prop_assert_eq!(lifted.len(), whole.cover.len());
+  }
+
+  #[test]
+  fn partial_covers_stay_within_the_budget(instance in instance(), budget in 0..6usize) {
+    let params = SolveParams {
+      max_iterations: 50,
+      reverse_fraction: 0.5,
+      max_restarts: 2,
+      ..SolveParams::default()
+    };
+    let mut g = graph(instance);
+    let partial = g.partial_cover(budget, &params).unwrap();
+    prop_assert!(partial.cover.len() <= budget);
+    let mut whole = partial.cover.clone();
+    whole.cliques.extend(partial.uncovered.iter().map(|&v| vec![v]));
+    prop_assert!(g.validate_cover(&whole).is_ok());
+    if g.size() > 0 && budget >= g.size() {
+      prop_assert!(partial.uncovered.is_empty());
+    }
+  }
+
+  #[test]
+  fn forbidden_sets_are_never_broken(
+    instance in instance(),
+    sets in proptest::collection::vec(
+      (1..4usize, proptest::collection::vec(any::<usize>(), 2..6)),
+      0..4,
+    ),
+    seed in any::<u64>(),
+  ) {
+    let mut g = graph(instance);
+    if g.size() == 0 {
+      return Ok(());
+    }
+    let sets = sets
+      .into_iter()
+      .map(|(max_together, members)| {
+        let mut members: Vec<usize> = members.iter().map(|v| v % g.size()).collect();
+        members.sort_unstable();
+        members.dedup();
+        ForbiddenSet {
+          members,
+          max_together,
+        }
+      })
+      .collect();
+    g.set_forbidden(sets).unwrap();
+    g.seed(seed);
+    let params = SolveParams {
+      max_iterations: 100,
+      reverse_fraction: 0.5,
+      target: 0,
+      max_restarts: 2,
+      annealing: AnnealingSchedule {
+        initial_iterations: 10,
+        seed_size: 3,
+        ..AnnealingSchedule::default()
+      },
+      reverse_decay: None,
+      order: CliqueOrder::Shuffle,
+      freeze: None,
+      initial: InitialCover::Sequential,
+      finish: Some(ExactFinish::default()),
+      distinct_starts: None,
+      check: CheckCadence::default(),
+    };
+    let result = g.solve(&params).unwrap();
+    prop_assert!(g.validate_cover(&result.best_cover).is_ok());
+    prop_assert!(g.validate_cover(&g.cover()).is_ok());
+  }
+
+  // Labels taken from an optimal cover, each vertex allowed its own clique's
+  // label and maybe one more, can all be met.
+  #[test]
+  fn label_restrictions_are_kept(
+    instance in (1..14usize, 0..5usize, 0.0..=1.0, any::<u64>()),
+    extra in proptest::collection::vec(any::<usize>(), 14),
+  ) {
+    let g = graph(instance);
+    let whole = g.exact_cover(1_000_000);
+    let mut links = LinkConstraints {
+      labels: Some(whole.cover.len()),
+      ..LinkConstraints::default()
+    };
+    for (label, members) in whole.cover.cliques.iter().enumerate() {
+      for &v in members {
+        links.allowed.push((v, vec![label, extra[v] % whole.cover.len()]));
+      }
+    }
+    let constrained = g.constrained(&links).unwrap();
+    let reduced = constrained.graph.exact_cover(1_000_000);
+    prop_assert!(reduced.optimal);
+    prop_assert_eq!(reduced.cover.len(), whole.cover.len());
+    let labeled = constrained.lift_labeled(&reduced.cover).unwrap();
+    prop_assert!(links.check_labels(&labeled).is_ok());
+    prop_assert!(g.validate_cover(&constrained.lift(&reduced.cover)).is_ok());
+  }
+
+  // Every restart's cover is matched or beaten on both counts by a cover of
+  // the front, and no cover of the front beats another.
+  #[test]
+  fn pareto_fronts_hold_only_nondominated_covers(instance in instance()) {
+    let params = SolveParams {
+      max_iterations: 50,
+      reverse_fraction: 0.5,
+      max_restarts: 1,
+      ..SolveParams::default()
+    };
+    let mut g = graph(instance);
+    let mut front = ParetoFront::default();
+    let mut offered = vec![];
+    for _ in 0..6 {
+      g.vcc_run_restart(&params, None);
+      let cover = g.cover();
+      front.offer(&cover);
+      offered.push(cover);
+    }
+    let imbalance = |cover: &CliqueCover| {
+      let sizes = cover.cliques.iter().map(Vec::len);
+      sizes.clone().max().unwrap_or(0) - sizes.min().unwrap_or(0)
+    };
+    for cover in &offered {
+      prop_assert!(front
+        .points
+        .iter()
+        .any(|p| p.cliques <= cover.len() && p.imbalance <= imbalance(cover)));
+    }
+    for (i, a) in front.points.iter().enumerate() {
+      prop_assert_eq!(a.imbalance, imbalance(&a.cover));
+      for b in &front.points[i + 1..] {
+        prop_assert!(a.cliques < b.cliques && a.imbalance > b.imbalance);
+      }
+    }
+  }
+
+  // Every format reads back the graph it wrote, complemented or not.
+  #[test]
+  fn graph_formats_round_trip(instance in instance(), complemented in any::<bool>()) {
+    let mut g = graph(instance);
+    if complemented {
+      g = g.complement();
+    }
+    for format in [GraphFormat::Dimacs, GraphFormat::Graph6] {
+      let text = g.to_format(format);
+      let read = Graph::parse(&text, format).unwrap();
+      prop_assert_eq!(read.size(), g.size());
+      prop_assert_eq!(read.to_format(format), text);
+      prop_assert_eq!(
+        read.to_format(GraphFormat::Dimacs),
+        g.to_format(GraphFormat::Dimacs)
+      );
+    }
+  }
+
+  // Streamed edges build the same graph as edges added one by one; without
+  // a vertex count it ends at the largest vertex seen.
+  #[test]
+  fn streamed_graphs_match_built_ones(
+    edges in proptest::collection::vec((0..30usize, 0..30usize), 0..80),
+  ) {
+    let edges: Vec<_> = edges.into_iter().filter(|(u, v)| u != v).collect();
+    let mut built = Graph::new(30);
+    for &(u, v) in &edges {
+      built.add_edge(u, v).unwrap();
+    }
+    built.conform_cliques_to_vertices();
+    let known = Graph::from_edge_iter(Some(30), edges.iter().copied()).unwrap();
+    prop_assert_eq!(
+      known.to_format(GraphFormat::Dimacs),
+      built.to_format(GraphFormat::Dimacs)
+    );
+    let grown = Graph::from_edge_iter(None, edges.iter().copied()).unwrap();
+    let size = edges.iter().map(|&(u, v)| u.max(v) + 1).max().unwrap_or(0);
+    prop_assert_eq!(grown.size(), size);
+    prop_assert_eq!(
+      grown.to_format(GraphFormat::Graph6),
+      built.induced_subgraph(&(0..size).collect::<Vec<_>>()).to_format(GraphFormat::Graph6)
+    );
+    prop_assert!(grown.check_invariants().is_ok());
+  }
+
+  // Batched covers are valid covers of their own graphs, in input order,
+  // each found within a budget scaled to its graph.
+  #[test]
+  fn batched_covers_match_their_graphs(
+    instances in proptest::collection::vec(instance(), 0..6),
+  ) {
+    let graphs: Vec<Graph> = instances.into_iter().map(graph).collect();
+    let params: Vec<SolveParams> = graphs
+      .iter()
+      .map(|g| SolveParams {
+        max_iterations: 10 * g.size(),
+        reverse_fraction: 0.5,
+        max_restarts: 2,
+        ..SolveParams::default()
+      })
+      .collect();
+    let covers = solve_batch(&graphs, &params).unwrap();
+    prop_assert_eq!(covers.len(), graphs.len());
+    if !graphs.is_empty() {
+      prop_assert!(solve_batch(&graphs, &params[1..]).is_err());
+    }
+    for (g, cover) in graphs.iter().zip(&covers) {
+      prop_assert!(g.validate_cover(cover).is_ok());
+    }
+  }
+
+  // The word and blocked backends agree with a plain vector of bools, over
+  // lengths spanning several blocks.
+  #[test]
+  fn bitset_backends_agree(
+    len in 0..1600usize,
+    steps in proptest::collection::vec(
+      (0..6u8, proptest::collection::vec((any::<prop::sample::Index>(), any::<bool>()), 0..40)),
+      0..20,
+    ),
+  ) {
+    let mut model = vec![false; len];
+    let mut words = Words::zeros(len);
+    let mut blocked = Blocked::zeros(len);
+    for (kind, bits) in steps {
+      let mut other = vec![false; len];
+      let mut other_words = Words::zeros(len);
+      let mut other_blocked = Blocked::zeros(len);
+      for (index, value) in bits {
+        if len > 0 {
+          let i = index.index(len);
+          other[i] = value;
+          other_words.set(i, value);
+          other_blocked.set(i, value);
+        }
+      }
+      match kind {
+        0 => {
+          model = other;
+          words = other_words;
+          blocked = other_blocked;
+        }
+        1 => {
+          model.iter_mut().zip(&other).for_each(|(m, &o)| *m &= o);
+          words.and_inplace(&other_words);
+          blocked.and_inplace(&other_blocked);
+        }
+        2 => {
+          model.iter_mut().zip(&other).for_each(|(m, &o)| *m |= o);
+          words.or_inplace(&other_words);
+          blocked.or_inplace(&other_blocked);
+        }
+        3 => {
+          model.iter_mut().zip(&other).for_each(|(m, &o)| *m ^= o);
+          words.xor_inplace(&other_words);
+          blocked.xor_inplace(&other_blocked);
+        }
+        4 => {
+          model.fill(true);
+          words.set_all_true();
+          blocked.set_all_true();
+        }
+        _ => {
+          model.fill(false);
+          words.set_all_false();
+          blocked.set_all_false();
+        }
+      }
+      let ones = model.iter().filter(|&&m| m).count();
+      prop_assert_eq!(words.count_ones(), ones);
+      prop_assert_eq!(blocked.count_ones(), ones);
+      prop_assert_eq!(blocked.any(), ones > 0);
+      prop_assert!(blocked.and_cloned(&blocked) == blocked);
+      for (i, &m) in model.iter().enumerate() {
+        prop_assert_eq!(words.get_unchecked(i), m);
+        prop_assert_eq!(blocked.get_unchecked(i), m);
+      }
+    }
+  }
+
+  // Relabeling for locality permutes the vertices, and covers of the
+  // relabeled graph lift to covers of the original of the same size.
+  #[test]
+  fn relabeled_covers_lift_to_the_original(instance in instance()) {
+    let g = graph(instance);
+    let mut order = g.locality_order();
+    order.sort_unstable();
+    prop_assert_eq!(order, (0..g.size()).collect::<Vec<_>>());
+    let mut relabeling = g.relabel_for_locality();
+    relabeling.graph.vcc_greedy();
+    let cover = relabeling.graph.cover();
+    let lifted = relabeling.lift(&cover);
+    prop_assert!(g.validate_cover(&lifted).is_ok());
+    prop_assert_eq!(lifted.len(), cover.len());
+  }
+
+  // Permuting by a permutation and then by its inverse gives back the
+  // graph, and covers of the permuted graph relabel to covers of it.
+  #[test]
+  fn permuted_covers_relabel_to_the_original(
+    instance in instance(),
+    keys in prop::collection::vec(any::<u64>(), 40),
+  ) {
+    let g = graph(instance);
+    let mut perm: Vec<usize> = (0..g.size()).collect();
+    perm.sort_by_key(|&v| keys[v]);
+    let (mut permuted, inverse) = g.permute(&perm).unwrap();
+    for v in 0..g.size() {
+      prop_assert_eq!(inverse[perm[v]], v);
+      prop_assert_eq!(permuted.degree(perm[v]), g.degree(v));
+    }
+    let (back, identity) = permuted.permute(&inverse).unwrap();
+    prop_assert_eq!(identity, perm.clone());
+    prop_assert_eq!(back.to_format(GraphFormat::Dimacs), g.to_format(GraphFormat::Dimacs));
+    permuted.vcc_greedy();
+    let cover = permuted.cover().relabel(&inverse);
+    prop_assert!(g.validate_cover(&cover).is_ok());
+  }
+
+  // Covers expanded and refined up from the coarsest level are valid, and
+  // the levels shrink on the way down.
+  #[test]
+  fn multilevel_covers_are_valid(
+    instance in instance(),
+    coarsest in 0..12usize,
+    refine_iterations in 0..50usize,
+  ) {
+    let mut g = graph(instance);
+    let params = SolveParams {
+      max_iterations: 50,
+      reverse_fraction: 0.5,
+      max_restarts: 2,
+      ..SolveParams::default()
+    };
+    let multilevel = Multilevel { coarsest, refine_iterations };
+    let result = g.solve_multilevel(&params, &multilevel).unwrap();
+    prop_assert!(g.validate_cover(&result.cover).is_ok());
+    prop_assert!(g.check_invariants().is_ok());
+    prop_assert_eq!(g.cliques_ct(), result.cover.len());
+    prop_assert_eq!(result.levels[0], g.size());
+    prop_assert!(result.levels.windows(2).all(|w| w[1] < w[0]));
+  }
+
+  // Separators partition the vertices with no edge across, and covers
+  // solved side by side and repaired across them are valid.
+  #[test]
+  fn divided_covers_are_valid(instance in instance(), leaf_size in 0..10usize) {
+    let mut g = graph(instance);
+    if let Some(split) = g.bfs_separator() {
+      prop_assert!(!split.left.is_empty() && !split.right.is_empty());
+      let mut all: Vec<usize> = [&split.left[..], &split.separator, &split.right].concat();
+      all.sort_unstable();
+      prop_assert_eq!(all, (0..g.size()).collect::<Vec<_>>());
+      let across = g.induced_subgraph(&[&split.left[..], &split.right].concat());
+      let left = g.induced_subgraph(&split.left);
+      let right = g.induced_subgraph(&split.right);
+      prop_assert_eq!(
+        across.to_format(GraphFormat::Dimacs),
+        left.disjoint_sum(&right).to_format(GraphFormat::Dimacs)
+      );
+    }
+    let params = SolveParams {
+      max_iterations: 50,
+      reverse_fraction: 0.5,
+      max_restarts: 2,
+      ..SolveParams::default()
+    };
+    let cover = g.solve_divided(&params, leaf_size).unwrap();
+    prop_assert!(g.validate_cover(&cover).is_ok());
+    prop_assert!(g.check_invariants().is_ok());
+    prop_assert_eq!(g.cliques_ct(), cover.len());
+  }
+
+  // Decisions agree with the exact cover number, and a yes's cover is a
+  // valid cover within k.
+  #[test]
+  fn decisions_match_the_exact_cover_number(instance in instance(), slack in 0..3usize) {
+    let g = graph(instance);
+    let optimum = g.exact_cover(10_000_000).cover.len();
+    let params = SolveParams {
+      max_iterations: 50,
+      reverse_fraction: 0.5,
+      max_restarts: 2,
+      ..SolveParams::default()
+    };
+    for k in optimum.saturating_sub(slack)..=optimum + slack {
+      let decide = Decide { k, exact_size: 60, max_nodes: 1_000_000 };
+      match g.decide(&decide, &params).unwrap() {
+        Decision::Yes(cover) => {
+          prop_assert!(k >= optimum);
+          prop_assert!(cover.len() <= k);
+          prop_assert!(g.validate_cover(&cover).is_ok());
+        }
+        Decision::No { lower_bound } => {
+          prop_assert!(k < optimum);
+          prop_assert!(k < lower_bound && lower_bound <= optimum);
+        }
+        Decision::Unknown { .. } => prop_assert!(false, "small graphs are decided"),
+      }
+    }
+  }
+
+  // Spectral initial covers are valid covers, restarts run from them.
+  #[cfg(feature = "spectral")]
+  #[test]
+  fn spectral_covers_are_valid(instance in instance(), modularity in any::<bool>()) {
+    use vcc::spectral::SpectralMatrix;
+    let mut g = graph(instance);
+    let matrix = if modularity {
+      SpectralMatrix::Modularity
+    } else {
+      SpectralMatrix::Adjacency
+    };
+    let cover = g.spectral_cover(matrix);
+    prop_assert!(g.validate_cover(&cover).is_ok());
+    let initial = if modularity {
+      InitialCover::SpectralModularity
+    } else {
+      InitialCover::Spectral
+    };
+    let result = g
+      .solve(&SolveParams {
+        max_iterations: 50,
+        reverse_fraction: 0.5,
+        max_restarts: 2,
+        initial,
+        ..SolveParams::default()
+      })
+      .unwrap();
+    prop_assert!(g.validate_cover(&result.best_cover).is_ok());
+    prop_assert!(g.check_invariants().is_ok());
+  }
+}
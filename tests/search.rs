@@ -0,0 +1,411 @@
+// The search's machinery and reports: counters, snapshots, annealing,
+// distinct starts, profiles, estimates, throttling, check cadence, batch
+// manifests, pluggable generators and checkpoints.
+
+mod common;
+
+use common::{complete_graph, params};
+use rand_xoshiro::rand_core::{RngCore, SeedableRng};
+use rand_xoshiro::SplitMix64;
+use std::time::Duration;
+use vcc::anneal::{AnnealingSchedule, DonorPolicy};
+use vcc::cadence::CheckCadence;
+use vcc::checkpoint::Checkpoint;
+use vcc::eta::Estimate;
+use vcc::families::{complete_bipartite, disjoint_cliques, path};
+use vcc::manifest::{format_reports, Manifest};
+use vcc::profile::Profile;
+use vcc::rng::default_rng;
+use vcc::scratch::ScratchPool;
+use vcc::snapshot::{CoverSnapshot, SnapshotCell};
+use vcc::starts::DistinctStarts;
+use vcc::throttle::{ThreadSchedule, Throttle};
+use vcc::{get_random_graph_with_k_cliques, CliqueCover, Graph, SolveParams};
+
+#[test]
+fn counters_follow_the_search() {
+  // Repeated edges count once.
+  let g = Graph::from_edge_iter(Some(4), [(0, 1), (1, 0), (1, 2), (0, 1)]).unwrap();
+  assert_eq!(
+    (0..4).map(|v| g.degree(v)).collect::<Vec<_>>(),
+    [1, 2, 1, 0]
+  );
+  assert_eq!(g.union(&g).degree(1), 2);
+  let mut g = disjoint_cliques(3, 4);
+  g.vcc_greedy();
+  assert!(g.check_invariants().is_ok());
+  assert_eq!(g.cliques_ct(), 3);
+  for i in 0..3 {
+    assert_eq!(g.clique_size(i), 4);
+    assert_eq!(g.clique_neighbors_ct(i), 0);
+  }
+}
+
+#[test]
+fn separators_cut_paths_and_components() {
+  let mut g = path(9);
+  let split = g.bfs_separator().unwrap();
+  assert_eq!(split.separator.len(), 1);
+  assert_eq!(split.left.len() + split.right.len(), 8);
+  let cover = g.solve_divided(&params(0), 2).unwrap();
+  assert!(g.validate_cover(&cover).is_ok());
+  let split = disjoint_cliques(4, 3).bfs_separator().unwrap();
+  assert!(split.separator.is_empty());
+  assert_eq!((split.left.len(), split.right.len()), (6, 6));
+  assert!(Graph::new(2).bfs_separator().is_none());
+  assert!(complete_bipartite(1, 1).bfs_separator().is_none());
+}
+
+#[test]
+fn snapshots_are_read_while_solving() {
+  let mut g = disjoint_cliques(4, 5);
+  let cell = SnapshotCell::new();
+  assert!(cell.latest().is_none());
+  std::thread::scope(|scope| {
+    let reader = scope.spawn(|| {
+      let mut seen = usize::MAX;
+      while seen > 4 {
+        if let Some(snapshot) = cell.latest() {
+          assert!(snapshot.len() <= seen);
+          seen = snapshot.len();
+        }
+      }
+    });
+    let result = g.solve_with_snapshots(&params(4), &cell).unwrap();
+    assert_eq!(result.best_cover.len(), 4);
+    reader.join().unwrap();
+  });
+  let latest = cell.latest().unwrap();
+  assert!(g.validate_cover(&latest.to_cover()).is_ok());
+  assert_eq!(latest.clique_of(0), latest.clique_of(4));
+  assert!(!cell.offer(CoverSnapshot::from(&g.cover())));
+  let partial = CoverSnapshot::from(&CliqueCover {
+    cliques: vec![vec![2, 0]],
+  });
+  assert_eq!((partial.len(), partial.clique_of(1)), (2, 1));
+}
+
+#[test]
+fn clique_lookups_follow_moves_and_reorders() {
+  let mut g = disjoint_cliques(3, 3);
+  assert!((0..9).all(|v| g.clique_of(v) == v));
+  g.solve(&params(3)).unwrap();
+  assert!(g.same_clique(0, 2) && g.same_clique(6, 8));
+  assert!(!g.same_clique(2, 3));
+  let ids: Vec<usize> = (0..9).map(|v| g.clique_of(v)).collect();
+  g.shuffle_active_cliques();
+  assert_eq!((0..9).map(|v| g.clique_of(v)).collect::<Vec<_>>(), ids);
+  assert!(g.check_invariants().is_ok());
+}
+
+#[test]
+fn every_donor_policy_anneals_to_the_optimum() {
+  for donor in ["random", "largest", "most-conflicted", "size-weighted"] {
+    let donor: DonorPolicy = donor.parse().unwrap();
+    let params = SolveParams {
+      annealing: AnnealingSchedule {
+        initial_iterations: 5,
+        seed_size: 2,
+        donor,
+        ..AnnealingSchedule::default()
+      },
+      ..params(4)
+    };
+    for n in 0..3 {
+      let result = Graph::new(n).solve(&params).unwrap();
+      assert_eq!(result.best_cover.len(), n);
+    }
+    let mut g = disjoint_cliques(4, 5);
+    let result = g.solve(&params).unwrap();
+    assert_eq!(result.best_cover.len(), 4);
+    assert!(g.validate_cover(&result.best_cover).is_ok());
+  }
+  assert!("smallest".parse::<DonorPolicy>().is_err());
+}
+
+#[test]
+fn repeated_starts_are_skipped() {
+  // A complete graph has a single start: one clique.
+  let mut g = complete_graph(5);
+  let params = SolveParams {
+    max_restarts: 4,
+    distinct_starts: Some("on".parse().unwrap()),
+    ..params(0)
+  };
+  let result = g.solve(&params).unwrap();
+  assert_eq!(result.best_cover.len(), 1);
+  let skipped: Vec<bool> = result.restart_log.iter().map(|r| r.skipped).collect();
+  assert_eq!(skipped, [false, true, true, true]);
+  assert!("4096:2".parse::<DistinctStarts>().is_ok());
+  assert!("4096".parse::<DistinctStarts>().is_err());
+}
+
+#[test]
+fn merges_are_scored_in_parallel_with_own_buffers() {
+  let mut g = disjoint_cliques(3, 4);
+  let n = g.size();
+  let pairs: Vec<(usize, usize)> = (0..n)
+    .flat_map(|i| (0..n).filter(move |&j| j != i).map(move |j| (i, j)))
+    .collect();
+  let scored: usize = std::thread::scope(|scope| {
+    let workers: Vec<_> = pairs
+      .chunks(pairs.len() / 2)
+      .map(|chunk| {
+        let g = &g;
+        scope.spawn(move || {
+          let mut scratch = ScratchPool::new(n).take();
+          chunk
+            .iter()
+            .map(|&(i, j)| g.transferable_ct(i, j, &mut scratch))
+            .sum::<usize>()
+        })
+      })
+      .collect();
+    workers.into_iter().map(|w| w.join().unwrap()).sum()
+  });
+  // Each vertex fits with the 3 others of its clique.
+  assert_eq!(scored, n * 3);
+  let mut scratch = ScratchPool::new(n).take();
+  let (before, moving) = (g.clique_size(1), g.transferable_ct(0, 1, &mut scratch));
+  g.transfer_compatible_vertices_between(0, 1);
+  assert_eq!(g.clique_size(0), 1 + moving);
+  assert_eq!(g.clique_size(1), before - moving);
+}
+
+#[test]
+fn profiles_preset_valid_params() {
+  for name in ["dense", "sparse", "huge", "quick"] {
+    let profile: Profile = name.parse().unwrap();
+    let mut p = params(4);
+    profile.apply(&mut p);
+    p.validate().unwrap();
+    assert_eq!(p.target, 4);
+    assert_eq!(profile.multilevel().is_some(), name == "huge");
+  }
+  assert!("medium".parse::<Profile>().is_err());
+  // Quick solves a small instance in one restart.
+  let mut rng = default_rng(11);
+  let mut g = get_random_graph_with_k_cliques(30, 3, 0.3, &mut rng).unwrap();
+  let mut p = params(3);
+  Profile::Quick.apply(&mut p);
+  let result = g.solve(&p).unwrap();
+  g.validate_cover(&result.best_cover).unwrap();
+  assert_eq!(result.restarts, 1);
+}
+
+#[test]
+fn eta_estimates_follow_the_restart_results() {
+  let second = Duration::from_secs(1);
+  // One of four reached the target: a quarter per restart.
+  let estimate = Estimate::new(&[12, 10, 11, 12], 10, 4 * second);
+  assert_eq!(estimate.per_restart, Some(0.25));
+  assert!(!estimate.extrapolated);
+  assert_eq!(estimate.restarts_for(0.5), Some(3));
+  assert_eq!(estimate.time_for(0.5), Some(3 * second));
+  // None did: a quarter end at 11 and all at 12, so each clique fewer is
+  // four times rarer.
+  let estimate = Estimate::new(&[11, 12, 12, 12], 10, 4 * second);
+  assert!((estimate.per_restart.unwrap() - 0.0625).abs() < 1e-9);
+  assert!(estimate.extrapolated);
+  // Without two different results there is no tail to fit.
+  assert_eq!(Estimate::new(&[12, 12], 10, second).per_restart, None);
+  assert_eq!(Estimate::new(&[], 10, second).restarts_for(0.5), None);
+}
+
+#[test]
+fn thread_schedules_wrap_past_midnight() {
+  let schedule: ThreadSchedule = "22-7:2".parse().unwrap();
+  assert!(schedule.covers(23) && schedule.covers(0) && schedule.covers(6));
+  assert!(!schedule.covers(7) && !schedule.covers(21));
+  assert!(schedule.runs(1, 3) && !schedule.runs(2, 3) && schedule.runs(2, 12));
+  assert!("9-17:0".parse::<ThreadSchedule>().unwrap().covers(9));
+  assert!("22-24:2".parse::<ThreadSchedule>().is_err());
+  assert!("22-7".parse::<ThreadSchedule>().is_err());
+  let throttle = Throttle {
+    duty: 0.0,
+    ..Throttle::default()
+  };
+  assert!(throttle.validate().is_err());
+  assert!(Throttle::default().validate().is_ok() && Throttle::default().is_off());
+}
+
+#[test]
+fn run_summaries_report_unreached_targets() {
+  let mut rng = default_rng(7);
+  let mut g = get_random_graph_with_k_cliques(40, 5, 0.5, &mut rng).unwrap();
+  let result = g.solve(&params(1)).unwrap();
+  assert!(!result.reached_target && result.iterations > 0);
+  assert_eq!(result.iterations, g.iterations());
+  let summary = g.run_summary(&result.best_cover, 1, &result);
+  assert!(!summary.reached_target());
+  assert_eq!(summary.gap(), summary.best - summary.lower_bound);
+  let text = summary.to_string();
+  assert!(text.contains("target 1 not reached") && text.lines().count() == 5);
+}
+
+#[test]
+fn the_last_restart_is_traced_for_reports() {
+  let mut rng = default_rng(7);
+  let mut g = get_random_graph_with_k_cliques(40, 5, 0.5, &mut rng).unwrap();
+  let result = g.solve(&params(1)).unwrap();
+  let trace = g.last_restart();
+  // Short of the target, a restart runs every iteration.
+  assert_eq!(trace.iterations, params(1).max_iterations);
+  assert_eq!(result.iterations, result.restarts * trace.iterations);
+  assert!(trace.iterations_per_annealing >= AnnealingSchedule::default().initial_iterations);
+}
+
+#[test]
+fn timed_checks_leave_seeded_runs_unchanged() {
+  let cadence: CheckCadence = "250_000,30s".parse().unwrap();
+  assert_eq!(cadence.iterations, 250_000);
+  assert_eq!(cadence.interval, Some(Duration::from_secs(30)));
+  assert_eq!("2m".parse::<CheckCadence>().unwrap().iterations, 1_000_000);
+  assert!("often".parse::<CheckCadence>().is_err());
+  assert!("0".parse::<CheckCadence>().unwrap().validate().is_err());
+  let run = |check: CheckCadence| {
+    let mut rng = default_rng(11);
+    let mut g = get_random_graph_with_k_cliques(40, 5, 0.5, &mut rng).unwrap();
+    g.seed(3);
+    let params = SolveParams { check, ..params(1) };
+    let log = g.solve(&params).unwrap().restart_log;
+    log.iter().map(|record| record.cliques).collect::<Vec<_>>()
+  };
+  let timed = CheckCadence {
+    iterations: 100,
+    interval: Some(Duration::from_nanos(1)),
+  };
+  let untimed = CheckCadence {
+    interval: None,
+    ..timed
+  };
+  assert_eq!(run(timed), run(untimed));
+}
+
+#[test]
+fn manifests_report_every_job_in_order() {
+  let manifest = Manifest::from_toml_str(
+    "[[job]]\nname = \"easy\"\nvertices = 20\ncliques = 2\nedge_fraction = 0.3\n\
+     max_iterations = 10_000\nreverse_fraction = 0.0\nrestarts = 5\nseed = 1\n\
+     [[job]]\nvertices = 12\ncliques = 12\nedge_fraction = 0.5\n\
+     max_iterations = 100\nreverse_fraction = 0.0\nseed = 2\n",
+  )
+  .unwrap();
+  for parallel in [false, true] {
+    let reports = manifest.run(parallel).unwrap();
+    let names: Vec<&str> = reports.iter().map(|r| r.name.as_str()).collect();
+    assert_eq!(names, ["easy", "job1"]);
+    assert!(reports
+      .iter()
+      .all(|r| r.reached_target && r.best <= r.target));
+    assert_eq!(reports[0].target, 2);
+    let table = format_reports(&reports);
+    assert!(table.contains("\nreached target in 2 of 2 jobs, "));
+    assert_eq!(table.lines().count(), 1 + 2 + 1);
+  }
+  assert!(Manifest::from_toml_str("[[job]]\ncliques = 2\n").is_err());
+}
+
+// A generator that can't be seeded through SeedableRng: a Weyl sequence,
+// scrambled.
+#[derive(Clone)]
+struct Weyl(u64);
+
+impl RngCore for Weyl {
+  fn next_u32(&mut self) -> u32 {
+    self.next_u64() as u32
+  }
+
+  fn next_u64(&mut self) -> u64 {
+    self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let z = (self.0 ^ (self.0 >> 31)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z ^ (z >> 29)
+  }
+
+  fn fill_bytes(&mut self, dest: &mut [u8]) {
+    rand_xoshiro::rand_core::impls::fill_bytes_via_next(self, dest)
+  }
+
+  fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_xoshiro::rand_core::Error> {
+    self.fill_bytes(dest);
+    Ok(())
+  }
+}
+
+#[test]
+fn any_generator_drives_the_search() {
+  let mut rng = default_rng(12);
+  let g = get_random_graph_with_k_cliques(30, 3, 0.3, &mut rng).unwrap();
+  let solve = |mut g: Graph| {
+    let result = g.solve(&params(3)).unwrap();
+    (result.best_cover.cliques, g.default_rng_state())
+  };
+  let mut xoshiro = g.clone();
+  xoshiro.seed(5);
+  let mut splitmix = g.clone();
+  splitmix.set_rng(SplitMix64::seed_from_u64(5));
+  let mut weyl = g.clone();
+  weyl.set_rng(Weyl(5));
+  let (xoshiro_cover, xoshiro_state) = solve(xoshiro.clone());
+  let (splitmix_cover, splitmix_state) = solve(splitmix.clone());
+  let (weyl_cover, _) = solve(weyl.clone());
+  // The same generator and seed repeat the search; only the default
+  // generator's state can be saved in checkpoints.
+  assert_eq!(solve(xoshiro).0, xoshiro_cover);
+  assert_eq!(solve(splitmix).0, splitmix_cover);
+  assert_eq!(solve(weyl).0, weyl_cover);
+  assert!(splitmix_state.is_none());
+  // The solve drew its restart seeds from the generator, one draw, and
+  // left it otherwise as it was.
+  let mut drawn = default_rng(5);
+  drawn.next_u64();
+  assert!(xoshiro_state == Some(drawn));
+  for cliques in [splitmix_cover, weyl_cover] {
+    g.validate_cover(&CliqueCover { cliques }).unwrap();
+  }
+}
+
+#[test]
+fn resumed_checkpoints_finish_as_the_uninterrupted_run() {
+  let mut rng = default_rng(14);
+  let mut g = get_random_graph_with_k_cliques(40, 4, 0.3, &mut rng).unwrap();
+  g.seed(3);
+  // Out of reach, so every restart is run.
+  let params = SolveParams {
+    max_iterations: 1_000,
+    target: 0,
+    max_restarts: 6,
+    ..SolveParams::default()
+  };
+  let path = std::env::temp_dir().join(format!("vcc-checkpoint-{}.json", std::process::id()));
+  let path = path.to_str().unwrap();
+  let uninterrupted = g
+    .clone()
+    .resume_solve(&params, None, None, &mut |g, progress| {
+      if progress.restarts == 3 {
+        let checkpoint = Checkpoint::capture(g, &params, progress).unwrap();
+        checkpoint.save(path).unwrap();
+      }
+    })
+    .unwrap();
+  let checkpoint = Checkpoint::load(path).unwrap();
+  std::fs::remove_file(path).unwrap();
+  assert_eq!(checkpoint.restarts, 3);
+  let (mut h, progress) = checkpoint.restore().unwrap();
+  let resumed = h
+    .resume_solve(&checkpoint.params, None, Some(progress), &mut |_, _| {})
+    .unwrap();
+  assert_eq!(resumed.restarts, 6);
+  assert_eq!(resumed.best_cover.cliques, uninterrupted.best_cover.cliques);
+  // Only the default generator's state can be saved.
+  let mut foreign = g.clone();
+  foreign.set_rng(SplitMix64::seed_from_u64(3));
+  let mut captured = true;
+  foreign
+    .resume_solve(&params, None, None, &mut |g, progress| {
+      captured &= Checkpoint::capture(g, &params, progress).is_some();
+    })
+    .unwrap();
+  assert!(!captured);
+  assert!(Checkpoint::from_json_str("{}").is_err());
+}